@@ -46,6 +46,13 @@ pub struct BuildArgs {
     pub rustflags: Vec<String>,
     #[clap(long, action, help = "Do not activate the `default` feature")]
     pub no_default_features: bool,
+    #[clap(
+        long,
+        action,
+        help = "Compile atomic operations to instructions of the RISC-V atomic extension instead of \
+                lowering them to plain loads and stores"
+    )]
+    pub atomics: bool,
     #[clap(long, action, help = "Ignore `rust-version` specification in packages")]
     pub ignore_rust_version: bool,
     #[clap(long, action, help = "Assert that `Cargo.lock` will remain unchanged")]
@@ -84,6 +91,7 @@ impl Default for BuildArgs {
             output_directory: DEFAULT_OUTPUT_DIR.to_string(),
             locked: false,
             no_default_features: false,
+            atomics: false,
         }
     }
 }
@@ -2,9 +2,9 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use sp1_cli::{
     commands::{
-        build::BuildCmd, build_toolchain::BuildToolchainCmd,
-        install_toolchain::InstallToolchainCmd, new::NewCmd, prove::ProveCmd, trace::TraceCmd,
-        vkey::VkeyCmd,
+        build::BuildCmd, build_toolchain::BuildToolchainCmd, debug::DebugCmd,
+        install_toolchain::InstallToolchainCmd, new::NewCmd, prove::ProveCmd, replay::ReplayCmd,
+        trace::TraceCmd, vkey::VkeyCmd,
     },
     SP1_VERSION_MESSAGE,
 };
@@ -33,6 +33,8 @@ pub enum ProveCliCommands {
     BuildToolchain(BuildToolchainCmd),
     InstallToolchain(InstallToolchainCmd),
     Trace(TraceCmd),
+    Debug(DebugCmd),
+    Replay(ReplayCmd),
     Vkey(VkeyCmd),
 }
 
@@ -46,6 +48,8 @@ fn main() -> Result<()> {
         ProveCliCommands::BuildToolchain(cmd) => cmd.run(),
         ProveCliCommands::InstallToolchain(cmd) => cmd.run(),
         ProveCliCommands::Trace(cmd) => cmd.run(),
+        ProveCliCommands::Debug(cmd) => cmd.run(),
+        ProveCliCommands::Replay(cmd) => cmd.run(),
         ProveCliCommands::Vkey(cmd) => cmd.run(),
     }
 }
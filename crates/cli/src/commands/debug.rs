@@ -0,0 +1,74 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use sp1_core_executor::{
+    debugger::{serve_gdb, Debugger},
+    Executor, Program, ReplayLog, SP1Context,
+};
+use sp1_core_machine::{io::SP1Stdin, utils::setup_logger};
+use sp1_stark::SP1CoreOpts;
+
+use crate::commands::prove::Input;
+
+#[derive(Parser)]
+#[command(name = "debug", about = "Debug a program with GDB over the remote serial protocol.")]
+pub struct DebugCmd {
+    /// Path to the ELF.
+    #[arg(long, required = true)]
+    elf: String,
+
+    /// The input to the program, as a file path or hex string.
+    #[arg(long, value_parser)]
+    input: Option<Input>,
+
+    /// The port to listen on for a GDB connection.
+    #[arg(long, default_value = "3333")]
+    port: u16,
+
+    /// A replay log to serve the inputs and hook responses from, instead of `--input`.
+    #[arg(long, conflicts_with = "input")]
+    replay: Option<PathBuf>,
+
+    /// Functions to set breakpoints on before GDB connects.
+    #[arg(long = "break")]
+    breakpoints: Vec<String>,
+}
+
+impl DebugCmd {
+    pub fn run(&self) -> Result<()> {
+        setup_logger();
+
+        let elf = fs::read(&self.elf)?;
+        let program = Program::from(&elf).map_err(|e| anyhow::anyhow!("{e}"))?;
+
+        let mut stdin = SP1Stdin::new();
+        if let Some(ref input) = self.input {
+            input.write_to(&mut stdin)?;
+        }
+
+        let mut runtime =
+            Executor::with_context(program, SP1CoreOpts::default(), SP1Context::default());
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
+        if let Some(ref path) = self.replay {
+            runtime.replay_from(ReplayLog::load(path)?)?;
+        }
+
+        let mut debugger = Debugger::new(runtime);
+        for name in &self.breakpoints {
+            match debugger.add_function_breakpoint(name) {
+                Some(addr) => println!("Breakpoint at {name} ({addr:#x})"),
+                None => anyhow::bail!("no function named {name}"),
+            }
+        }
+
+        println!("Connect with: target remote localhost:{}", self.port);
+        match serve_gdb(debugger, ("127.0.0.1", self.port))? {
+            Some(exit_code) => println!("Program exited with code {exit_code}"),
+            None => println!("Debugging session ended"),
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 pub mod build;
 pub mod build_toolchain;
+pub mod debug;
 pub mod install_toolchain;
 pub mod new;
 pub mod prove;
+pub mod replay;
 pub mod trace;
 pub mod vkey;
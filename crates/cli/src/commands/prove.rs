@@ -12,7 +12,7 @@ use std::{env, fs::File, io::Read, path::PathBuf, str::FromStr, time::Instant};
 use crate::util::{elapsed, write_status};
 
 #[derive(Debug, Clone)]
-enum Input {
+pub(crate) enum Input {
     FilePath(PathBuf),
     HexBytes(Vec<u8>),
 }
@@ -49,6 +49,24 @@ impl FromStr for Input {
     }
 }
 
+impl Input {
+    /// Write the input to `stdin` as a single slice.
+    pub(crate) fn write_to(&self, stdin: &mut SP1Stdin) -> Result<()> {
+        match self {
+            Input::FilePath(ref path) => {
+                let mut file = File::open(path).expect("failed to open input file");
+                let mut bytes = Vec::new();
+                file.read_to_end(&mut bytes)?;
+                stdin.write_slice(&bytes);
+            }
+            Input::HexBytes(ref bytes) => {
+                stdin.write_slice(bytes);
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Parser)]
 #[command(name = "prove", about = "(default) Build and prove a program")]
 pub struct ProveCmd {
@@ -102,17 +120,7 @@ impl ProveCmd {
 
         let mut stdin = SP1Stdin::new();
         if let Some(ref input) = self.input {
-            match input {
-                Input::FilePath(ref path) => {
-                    let mut file = File::open(path).expect("failed to open input file");
-                    let mut bytes = Vec::new();
-                    file.read_to_end(&mut bytes)?;
-                    stdin.write_slice(&bytes);
-                }
-                Input::HexBytes(ref bytes) => {
-                    stdin.write_slice(bytes);
-                }
-            }
+            input.write_to(&mut stdin)?;
         }
 
         let start_time = Instant::now();
@@ -0,0 +1,60 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use sp1_core_executor::{Executor, Program, ReplayLog, SP1Context};
+use sp1_core_machine::{io::SP1Stdin, utils::setup_logger};
+use sp1_stark::SP1CoreOpts;
+
+use crate::commands::prove::Input;
+
+#[derive(Parser)]
+#[command(
+    name = "replay",
+    about = "Replay the inputs and hook responses recorded during an execution of a program."
+)]
+pub struct ReplayCmd {
+    /// Path to the ELF.
+    #[arg(long, required = true)]
+    elf: String,
+
+    /// Path to the replay log.
+    #[arg(long, required = true)]
+    log: PathBuf,
+
+    /// Execute the program on this input and record a new replay log, instead of replaying one.
+    #[arg(long, value_parser)]
+    record: Option<Input>,
+}
+
+impl ReplayCmd {
+    pub fn run(&self) -> Result<()> {
+        setup_logger();
+
+        let elf = fs::read(&self.elf)?;
+        let program = Program::from(&elf).map_err(|e| anyhow::anyhow!("{e}"))?;
+        let mut runtime =
+            Executor::with_context(program, SP1CoreOpts::default(), SP1Context::default());
+
+        if let Some(ref input) = self.record {
+            let mut stdin = SP1Stdin::new();
+            input.write_to(&mut stdin)?;
+            runtime.write_vecs(&stdin.buffer);
+            runtime.write_files(&stdin.files);
+            runtime.record_replay();
+        } else {
+            runtime.replay_from(ReplayLog::load(&self.log)?)?;
+        }
+
+        let result = runtime.run_fast();
+        if let Some(log) = runtime.take_replay_log() {
+            log.save(&self.log)?;
+            println!("Recorded {} events to {}", log.events.len(), self.log.display());
+        }
+        result?;
+
+        println!("Executed {} cycles", runtime.state.global_clk);
+        println!("Public values: 0x{}", hex::encode(&runtime.state.public_values_stream));
+        Ok(())
+    }
+}
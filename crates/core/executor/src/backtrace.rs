@@ -0,0 +1,216 @@
+use std::{
+    fmt::{self, Display},
+    ops::Deref,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Register, SymbolTable};
+
+/// The location in the source of a program where it panicked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanicLocation {
+    /// The source file.
+    pub file: String,
+    /// The line in the file.
+    pub line: u32,
+    /// The column in the line.
+    pub column: u32,
+}
+
+impl Display for PanicLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// A frame of the backtrace of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestFrame {
+    /// The program counter of the frame: the current instruction for the innermost frame, and the
+    /// call instruction for the others.
+    pub pc: u32,
+    /// The demangled name of the function containing `pc`, if the ELF has symbols for it.
+    pub function: Option<String>,
+}
+
+impl Display for GuestFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010x} in {}", self.pc, self.function.as_deref().unwrap_or("<unknown>"))
+    }
+}
+
+/// The maximum depth of a [`CallStack`].
+///
+/// Calls made beyond this depth are not tracked, which bounds the memory used by runaway recursion
+/// or by code which does not return through a link register.
+pub(crate) const MAX_STACK_DEPTH: usize = 1 << 12;
+
+/// How a jump moves through the calls of a program.
+///
+/// Following the RISC-V calling convention, both `ra` and `t0` are link registers: a jump which
+/// links to one is a call, and a jump through one which does not link is a return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Jump {
+    /// A call, which enters a new frame.
+    Call,
+    /// A return, which leaves the current frame.
+    Return,
+    /// Any other jump.
+    Other,
+}
+
+impl Jump {
+    /// Classify a jump which writes the return address to `rd`, jumping through `rs1` if it is a
+    /// `jalr`.
+    pub(crate) fn new(rd: u32, rs1: Option<u32>) -> Self {
+        let is_link =
+            |register: u32| register == Register::X1 as u32 || register == Register::X5 as u32;
+        if is_link(rd) {
+            Self::Call
+        } else if rd == Register::X0 as u32 && rs1.is_some_and(is_link) {
+            Self::Return
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// A stack of frames, tracked from the jumps of a program and bounded by [`MAX_STACK_DEPTH`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CallStack<T> {
+    /// The frames, from the outermost one.
+    frames: Vec<T>,
+}
+
+impl<T> Default for CallStack<T> {
+    fn default() -> Self {
+        Self { frames: Vec::new() }
+    }
+}
+
+impl<T> CallStack<T> {
+    /// Whether the stack is at [`MAX_STACK_DEPTH`], so that pushed frames are dropped.
+    pub(crate) fn is_full(&self) -> bool {
+        self.frames.len() >= MAX_STACK_DEPTH
+    }
+
+    /// Push a frame, unless the stack is full.
+    pub(crate) fn push(&mut self, frame: T) {
+        if !self.is_full() {
+            self.frames.push(frame);
+        }
+    }
+
+    /// Pop the innermost frame.
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        self.frames.pop()
+    }
+
+    /// Keep the `len` outermost frames.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.frames.truncate(len);
+    }
+}
+
+impl<T> Deref for CallStack<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.frames
+    }
+}
+
+/// A call the program is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Call {
+    /// The program counter of the call instruction.
+    pc: u32,
+    /// The address the call returns to.
+    return_address: u32,
+}
+
+impl CallStack<Call> {
+    /// Enter the call made by the instruction at `pc`, which returns to `return_address`.
+    pub(crate) fn call(&mut self, pc: u32, return_address: u32) {
+        self.push(Call { pc, return_address });
+    }
+
+    /// Return to `target`, leaving the innermost call returning there and the calls within it.
+    ///
+    /// Returns to addresses which no call returns to, such as those of hand-written assembly or of
+    /// calls past [`MAX_STACK_DEPTH`], are ignored.
+    pub(crate) fn ret(&mut self, target: u32) {
+        if let Some(idx) = self.iter().rposition(|call| call.return_address == target) {
+            self.truncate(idx);
+        }
+    }
+
+    /// The backtrace of the program at `pc`, from the innermost frame.
+    pub(crate) fn backtrace(&self, pc: u32, symbols: &SymbolTable) -> Vec<GuestFrame> {
+        std::iter::once(pc)
+            .chain(self.iter().rev().map(|call| call.pc))
+            .map(|pc| GuestFrame {
+                pc,
+                function: symbols
+                    .lookup_index(pc)
+                    .and_then(|index| symbols.demangled_name(index))
+                    .map(str::to_string),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CallStack, Jump, MAX_STACK_DEPTH};
+    use crate::{ElfSymbol, SymbolTable};
+
+    #[test]
+    fn test_backtrace() {
+        let symbols = SymbolTable::new(vec![
+            ElfSymbol { name: "main".into(), address: 0x100, size: 0x100 },
+            ElfSymbol { name: "helper".into(), address: 0x200, size: 0x100 },
+        ]);
+        let mut calls = CallStack::default();
+        calls.call(0x104, 0x108);
+        calls.call(0x210, 0x214);
+        calls.ret(0x214);
+        calls.call(0x220, 0x224);
+
+        let backtrace = calls.backtrace(0x500, &symbols);
+        let frames = backtrace.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            ["0x00000500 in <unknown>", "0x00000220 in helper", "0x00000104 in main"]
+        );
+
+        calls.ret(0x108);
+        assert_eq!(calls.backtrace(0x108, &symbols).len(), 1);
+    }
+
+    #[test]
+    fn test_jump() {
+        assert_eq!(Jump::new(1, None), Jump::Call);
+        assert_eq!(Jump::new(5, Some(10)), Jump::Call);
+        assert_eq!(Jump::new(0, Some(1)), Jump::Return);
+        assert_eq!(Jump::new(0, Some(5)), Jump::Return);
+        assert_eq!(Jump::new(0, Some(6)), Jump::Other);
+        assert_eq!(Jump::new(0, None), Jump::Other);
+    }
+
+    #[test]
+    fn test_max_stack_depth() {
+        let mut calls = CallStack::default();
+        for i in 0..=MAX_STACK_DEPTH as u32 {
+            calls.call(4 * i, 4 * i + 4);
+        }
+        assert_eq!(calls.len(), MAX_STACK_DEPTH);
+
+        // The return of the dropped call is ignored.
+        calls.ret(4 * MAX_STACK_DEPTH as u32 + 4);
+        assert_eq!(calls.len(), MAX_STACK_DEPTH);
+        calls.ret(4);
+        assert!(calls.is_empty());
+    }
+}
@@ -1,10 +1,11 @@
 use core::mem::take;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use hashbrown::HashMap;
 
 use crate::{
-    hook::{hookify, BoxedHook, HookEnv, HookRegistry},
+    hook::{hookify, BoxedHook, Hook, HookEnv, HookRegistry},
+    progress::{CancellationToken, ProgressCallback, ProofMonitor, ProofProgress},
     subproof::SubproofVerifier,
 };
 
@@ -21,6 +22,15 @@ pub struct SP1Context<'a> {
 
     /// The maximum number of cpu cycles to use for execution.
     pub max_cycles: Option<u64>,
+
+    /// The monitor receiving progress events and carrying the cancellation state of the proof.
+    pub monitor: ProofMonitor,
+
+    /// The environment variables served to the program through `sys_getenv`.
+    pub env: BTreeMap<String, String>,
+
+    /// The seed of the random number generator of the program, if it uses verifiable randomness.
+    pub rand_seed: Option<[u8; 32]>,
 }
 
 /// A builder for [`SP1Context`].
@@ -30,6 +40,10 @@ pub struct SP1ContextBuilder<'a> {
     hook_registry_entries: Vec<(u32, BoxedHook<'a>)>,
     subproof_verifier: Option<Arc<dyn SubproofVerifier + 'a>>,
     max_cycles: Option<u64>,
+    on_progress: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+    env: BTreeMap<String, String>,
+    rand_seed: Option<[u8; 32]>,
 }
 
 impl<'a> SP1Context<'a> {
@@ -40,6 +54,30 @@ impl<'a> SP1Context<'a> {
     }
 }
 
+impl SP1Context<'static> {
+    /// Narrow the lifetime of a context which only borrows `'static` data.
+    ///
+    /// Allows a context built on one thread to be moved to another (e.g. a blocking task) and
+    /// used there with provers borrowed for a shorter lifetime.
+    #[must_use]
+    pub fn scoped<'b>(self) -> SP1Context<'b> {
+        let SP1Context { hook_registry, subproof_verifier, max_cycles, monitor, env, rand_seed } =
+            self;
+        let hook_registry = hook_registry.map(|registry| HookRegistry {
+            table: registry
+                .table
+                .into_iter()
+                .map(|(fd, hook)| {
+                    let hook: BoxedHook<'b> =
+                        hookify(move |env, buf| hook.write().unwrap().invoke_hook(env, buf));
+                    (fd, hook)
+                })
+                .collect(),
+        });
+        SP1Context { hook_registry, subproof_verifier, max_cycles, monitor, env, rand_seed }
+    }
+}
+
 impl<'a> SP1ContextBuilder<'a> {
     /// Create a new [`SP1ContextBuilder`].
     ///
@@ -68,7 +106,20 @@ impl<'a> SP1ContextBuilder<'a> {
             });
         let subproof_verifier = take(&mut self.subproof_verifier);
         let cycle_limit = take(&mut self.max_cycles);
-        SP1Context { hook_registry, subproof_verifier, max_cycles: cycle_limit }
+        let monitor = ProofMonitor::new(
+            take(&mut self.on_progress),
+            take(&mut self.cancellation).unwrap_or_default(),
+        );
+        let env = take(&mut self.env);
+        let rand_seed = take(&mut self.rand_seed);
+        SP1Context {
+            hook_registry,
+            subproof_verifier,
+            max_cycles: cycle_limit,
+            monitor,
+            env,
+            rand_seed,
+        }
     }
 
     /// Add a runtime [Hook](super::Hook) into the context.
@@ -110,21 +161,62 @@ impl<'a> SP1ContextBuilder<'a> {
         self.max_cycles = Some(max_cycles);
         self
     }
+
+    /// Set a callback invoked with the [`ProofProgress`] events emitted while proving.
+    pub fn on_progress(&mut self, f: impl Fn(ProofProgress) + Send + Sync + 'static) -> &mut Self {
+        self.on_progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Set the token used to cancel proving.
+    pub fn cancellation(&mut self, token: CancellationToken) -> &mut Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Set an environment variable of the program.
+    ///
+    /// The program reads its environment with [`std::env::var`]. A program which does so appends
+    /// the [`environment_digest`](crate::syscalls::environment_digest) of its environment to its
+    /// public values when it halts.
+    pub fn env(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the seed of the random number generator of the program.
+    ///
+    /// The seed is only used by programs built with the `verifiable-rand` feature of `sp1-zkvm`,
+    /// which append it to their public values when they halt. Verifiers can then check that the
+    /// program used a seed they chose, such as a nonce.
+    pub fn rand_seed(&mut self, seed: [u8; 32]) -> &mut Self {
+        self.rand_seed = Some(seed);
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
 
-    use crate::{subproof::DefaultSubproofVerifier, SP1Context};
+    use crate::{subproof::DefaultSubproofVerifier, CancellationToken, ProofProgress, SP1Context};
 
     #[test]
     fn defaults() {
-        let SP1Context { hook_registry, subproof_verifier, max_cycles: cycle_limit } =
-            SP1Context::builder().build();
+        let SP1Context {
+            hook_registry,
+            subproof_verifier,
+            max_cycles: cycle_limit,
+            monitor,
+            env,
+            rand_seed,
+        } = SP1Context::builder().build();
         assert!(hook_registry.is_none());
         assert!(subproof_verifier.is_none());
         assert!(cycle_limit.is_none());
+        assert!(!monitor.is_cancelled());
+        assert!(env.is_empty());
+        assert!(rand_seed.is_none());
     }
 
     #[test]
@@ -155,4 +247,32 @@ mod tests {
             .build();
         assert!(subproof_verifier.is_some());
     }
+
+    #[test]
+    fn env() {
+        let SP1Context { env, .. } =
+            SP1Context::builder().env("NETWORK", "mainnet").env("NETWORK", "testnet").build();
+        assert_eq!(env.len(), 1);
+        assert_eq!(env["NETWORK"], "testnet");
+    }
+
+    #[test]
+    fn monitor() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let token = CancellationToken::new();
+        let SP1Context { monitor, .. } = SP1Context::builder()
+            .on_progress({
+                let events = events.clone();
+                move |event| events.lock().unwrap().push(event)
+            })
+            .cancellation(token.clone())
+            .build();
+
+        monitor.report(ProofProgress::ShardProven { shard: 1 });
+        assert_eq!(*events.lock().unwrap(), vec![ProofProgress::ShardProven { shard: 1 }]);
+
+        assert!(!monitor.is_cancelled());
+        token.cancel();
+        assert!(monitor.is_cancelled());
+    }
 }
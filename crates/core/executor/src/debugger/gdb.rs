@@ -0,0 +1,515 @@
+//! A server for the GDB remote serial protocol.
+//!
+//! Only the subset of the protocol needed for source-level debugging of a single-threaded RV32IM
+//! program is supported: reading and writing registers and memory, stepping, continuing,
+//! breakpoints and watchpoints.
+
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use super::{Debugger, StopReason, WatchKind, Watchpoint};
+use crate::{ExecutionError, Register};
+
+/// The byte sent by GDB to interrupt a running program.
+const INTERRUPT: u8 = 0x03;
+
+/// The number of the program counter in the GDB register numbering for RISC-V.
+const PC_REGISTER: usize = 32;
+
+/// The target description sent to GDB, describing the registers of the machine.
+const TARGET_XML: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE target SYSTEM "gdb-target.dtd">
+<target version="1.0">
+  <architecture>riscv:rv32</architecture>
+  <feature name="org.gnu.gdb.riscv.cpu">
+    <reg name="zero" bitsize="32" type="int" regnum="0"/>
+    <reg name="ra" bitsize="32" type="code_ptr"/>
+    <reg name="sp" bitsize="32" type="data_ptr"/>
+    <reg name="gp" bitsize="32" type="data_ptr"/>
+    <reg name="tp" bitsize="32" type="data_ptr"/>
+    <reg name="t0" bitsize="32" type="int"/>
+    <reg name="t1" bitsize="32" type="int"/>
+    <reg name="t2" bitsize="32" type="int"/>
+    <reg name="fp" bitsize="32" type="data_ptr"/>
+    <reg name="s1" bitsize="32" type="int"/>
+    <reg name="a0" bitsize="32" type="int"/>
+    <reg name="a1" bitsize="32" type="int"/>
+    <reg name="a2" bitsize="32" type="int"/>
+    <reg name="a3" bitsize="32" type="int"/>
+    <reg name="a4" bitsize="32" type="int"/>
+    <reg name="a5" bitsize="32" type="int"/>
+    <reg name="a6" bitsize="32" type="int"/>
+    <reg name="a7" bitsize="32" type="int"/>
+    <reg name="s2" bitsize="32" type="int"/>
+    <reg name="s3" bitsize="32" type="int"/>
+    <reg name="s4" bitsize="32" type="int"/>
+    <reg name="s5" bitsize="32" type="int"/>
+    <reg name="s6" bitsize="32" type="int"/>
+    <reg name="s7" bitsize="32" type="int"/>
+    <reg name="s8" bitsize="32" type="int"/>
+    <reg name="s9" bitsize="32" type="int"/>
+    <reg name="s10" bitsize="32" type="int"/>
+    <reg name="s11" bitsize="32" type="int"/>
+    <reg name="t3" bitsize="32" type="int"/>
+    <reg name="t4" bitsize="32" type="int"/>
+    <reg name="t5" bitsize="32" type="int"/>
+    <reg name="t6" bitsize="32" type="int"/>
+    <reg name="pc" bitsize="32" type="code_ptr"/>
+  </feature>
+</target>
+"#;
+
+/// A connection to a GDB client.
+pub trait GdbConnection: Read + Write {
+    /// Check, without blocking, whether the client has requested an interrupt.
+    fn poll_interrupt(&mut self) -> io::Result<bool>;
+}
+
+impl GdbConnection for TcpStream {
+    fn poll_interrupt(&mut self) -> io::Result<bool> {
+        self.set_nonblocking(true)?;
+        let mut byte = [0u8];
+        let result = match self.peek(&mut byte) {
+            // The client disconnected, so there is no point in continuing.
+            Ok(0) => Ok(true),
+            Ok(_) if byte[0] == INTERRUPT => self.read_exact(&mut byte).map(|()| true),
+            Ok(_) => Ok(false),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(false),
+            Err(e) => Err(e),
+        };
+        self.set_nonblocking(false)?;
+        result
+    }
+}
+
+/// Listen on `addr` for a single GDB client and serve it until it detaches or kills the program.
+///
+/// Returns the exit code of the program, if it ran to completion.
+pub fn serve_gdb(debugger: Debugger<'_>, addr: impl ToSocketAddrs) -> io::Result<Option<u32>> {
+    let listener = TcpListener::bind(addr)?;
+    tracing::info!("waiting for gdb to connect on {}", listener.local_addr()?);
+    let (stream, peer) = listener.accept()?;
+    stream.set_nodelay(true)?;
+    tracing::info!("gdb connected from {peer}");
+    GdbServer::new(debugger, stream).run()
+}
+
+/// A GDB remote serial protocol server for a [`Debugger`].
+pub struct GdbServer<'a, C> {
+    debugger: Debugger<'a>,
+    connection: C,
+    last_stop: String,
+    no_ack: bool,
+}
+
+impl<'a, C: GdbConnection> GdbServer<'a, C> {
+    /// Create a new [`GdbServer`] serving `debugger` over `connection`.
+    pub fn new(debugger: Debugger<'a>, connection: C) -> Self {
+        Self { debugger, connection, last_stop: "S05".to_string(), no_ack: false }
+    }
+
+    /// Consume the server, returning the underlying debugger.
+    pub fn into_debugger(self) -> Debugger<'a> {
+        self.debugger
+    }
+
+    /// Serve requests until the client detaches, kills the program or disconnects.
+    ///
+    /// Returns the exit code of the program, if it ran to completion.
+    pub fn run(&mut self) -> io::Result<Option<u32>> {
+        while let Some(packet) = self.read_packet()? {
+            let Some(response) = self.handle(&packet) else {
+                if packet.starts_with(b"D") {
+                    self.write_packet("OK")?;
+                }
+                break;
+            };
+            self.write_packet(&response)?;
+        }
+        Ok(self.debugger.exit_code())
+    }
+
+    /// Handle a single packet, returning the response or `None` if the session should end.
+    fn handle(&mut self, packet: &[u8]) -> Option<String> {
+        let packet = String::from_utf8_lossy(packet);
+        let (command, args) = packet.split_at(packet.len().min(1));
+        let response = match command {
+            "?" => self.last_stop.clone(),
+            "g" => {
+                let mut registers = self.debugger.registers().to_vec();
+                registers.push(self.debugger.pc());
+                registers.iter().map(|r| hex::encode(r.to_le_bytes())).collect()
+            }
+            "G" => match hex::decode(args) {
+                Ok(bytes) if bytes.len() >= 4 * (PC_REGISTER + 1) => {
+                    for (i, chunk) in bytes.chunks_exact(4).take(PC_REGISTER + 1).enumerate() {
+                        self.set_register(i, u32::from_le_bytes(chunk.try_into().unwrap()));
+                    }
+                    "OK".to_string()
+                }
+                _ => "E01".to_string(),
+            },
+            "p" => match usize::from_str_radix(args, 16) {
+                Ok(PC_REGISTER) => hex::encode(self.debugger.pc().to_le_bytes()),
+                Ok(i) if i < PC_REGISTER => hex::encode(self.debugger.registers()[i].to_le_bytes()),
+                _ => "E01".to_string(),
+            },
+            "P" => {
+                let parsed = args.split_once('=').and_then(|(register, value)| {
+                    let register = usize::from_str_radix(register, 16).ok()?;
+                    let value: [u8; 4] = hex::decode(value).ok()?.try_into().ok()?;
+                    (register <= PC_REGISTER).then_some((register, u32::from_le_bytes(value)))
+                });
+                match parsed {
+                    Some((register, value)) => {
+                        self.set_register(register, value);
+                        "OK".to_string()
+                    }
+                    None => "E01".to_string(),
+                }
+            }
+            "m" => match parse_addr_len(args) {
+                Some((addr, len)) => hex::encode(self.debugger.read_memory(addr, len)),
+                None => "E01".to_string(),
+            },
+            "M" => {
+                let parsed = args.split_once(':').and_then(|(range, data)| {
+                    let (addr, len) = parse_addr_len(range)?;
+                    let bytes = hex::decode(data).ok()?;
+                    (bytes.len() == len as usize).then_some((addr, bytes))
+                });
+                match parsed {
+                    Some((addr, bytes)) => {
+                        self.debugger.write_memory(addr, &bytes);
+                        "OK".to_string()
+                    }
+                    None => "E01".to_string(),
+                }
+            }
+            "c" | "s" => {
+                if let Ok(addr) = u32::from_str_radix(args, 16) {
+                    self.debugger.set_pc(addr);
+                }
+                let reason = if command == "s" {
+                    self.debugger.step()
+                } else {
+                    let connection = &mut self.connection;
+                    self.debugger.cont(|| connection.poll_interrupt().unwrap_or(true))
+                };
+                self.last_stop = stop_reply(&reason);
+                self.last_stop.clone()
+            }
+            "Z" | "z" => self.handle_breakpoint(command == "Z", args),
+            "H" | "T" => "OK".to_string(),
+            "k" | "D" => return None,
+            "q" | "Q" | "v" => return self.handle_query(&packet),
+            _ => String::new(),
+        };
+        Some(response)
+    }
+
+    /// Handle a `Z` or `z` packet, inserting or removing a breakpoint or watchpoint.
+    fn handle_breakpoint(&mut self, insert: bool, args: &str) -> String {
+        let Some((kind, addr, len)) = parse_breakpoint(args) else {
+            return "E01".to_string();
+        };
+        let kind = match kind {
+            0 | 1 => {
+                if insert {
+                    self.debugger.add_breakpoint(addr);
+                } else {
+                    self.debugger.remove_breakpoint(addr);
+                }
+                return "OK".to_string();
+            }
+            2 => WatchKind::Write,
+            3 => WatchKind::Read,
+            4 => WatchKind::Access,
+            _ => return String::new(),
+        };
+        let watchpoint = Watchpoint { addr, len, kind };
+        if insert {
+            self.debugger.add_watchpoint(watchpoint);
+        } else {
+            self.debugger.remove_watchpoint(&watchpoint);
+        }
+        "OK".to_string()
+    }
+
+    /// Handle a general query or multi-letter `v` packet.
+    fn handle_query(&mut self, packet: &str) -> Option<String> {
+        let response = if packet.starts_with("qSupported") {
+            "PacketSize=4000;qXfer:features:read+;swbreak+;hwbreak+;QStartNoAckMode+".to_string()
+        } else if let Some(args) = packet.strip_prefix("qXfer:features:read:target.xml:") {
+            match parse_addr_len(args) {
+                Some((offset, len)) => {
+                    let offset = (offset as usize).min(TARGET_XML.len());
+                    let end = (offset + len as usize).min(TARGET_XML.len());
+                    let marker = if end == TARGET_XML.len() { 'l' } else { 'm' };
+                    format!("{marker}{}", &TARGET_XML[offset..end])
+                }
+                None => "E01".to_string(),
+            }
+        } else if packet == "QStartNoAckMode" {
+            self.no_ack = true;
+            "OK".to_string()
+        } else if packet == "qAttached" {
+            "1".to_string()
+        } else if packet == "qC" {
+            "QC1".to_string()
+        } else if packet == "qfThreadInfo" {
+            "m1".to_string()
+        } else if packet == "qsThreadInfo" {
+            "l".to_string()
+        } else if let Some(command) = packet.strip_prefix("qRcmd,") {
+            let command = hex::decode(command).unwrap_or_default();
+            match command.as_slice() {
+                b"clk" => hex::encode(format!("{}\n", self.debugger.clk())),
+                _ => hex::encode("supported monitor commands: clk\n"),
+            }
+        } else if packet.starts_with("vKill") {
+            return None;
+        } else {
+            String::new()
+        };
+        Some(response)
+    }
+
+    /// Set a register by its GDB register number.
+    fn set_register(&mut self, register: usize, value: u32) {
+        if register == PC_REGISTER {
+            self.debugger.set_pc(value);
+        } else {
+            self.debugger.set_register(Register::from_u32(register as u32), value);
+        }
+    }
+
+    /// Read the next packet from the client, acknowledging it. Returns `None` on disconnect.
+    fn read_packet(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            // Skip acknowledgements and interrupts received while the program is stopped.
+            match self.read_byte()? {
+                Some(b'$') => {}
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+
+            let mut data = Vec::new();
+            let mut checksum = 0u8;
+            let mut escaped = false;
+            loop {
+                let Some(byte) = self.read_byte()? else { return Ok(None) };
+                if byte == b'#' && !escaped {
+                    break;
+                }
+                checksum = checksum.wrapping_add(byte);
+                if escaped {
+                    data.push(byte ^ 0x20);
+                    escaped = false;
+                } else if byte == b'}' {
+                    escaped = true;
+                } else {
+                    data.push(byte);
+                }
+            }
+
+            let mut expected = [0u8; 2];
+            self.connection.read_exact(&mut expected)?;
+            let expected = std::str::from_utf8(&expected)
+                .ok()
+                .and_then(|expected| u8::from_str_radix(expected, 16).ok());
+
+            if self.no_ack {
+                return Ok(Some(data));
+            }
+            if expected == Some(checksum) {
+                self.connection.write_all(b"+")?;
+                return Ok(Some(data));
+            }
+            self.connection.write_all(b"-")?;
+        }
+    }
+
+    /// Send a packet to the client, retransmitting it until it is acknowledged.
+    fn write_packet(&mut self, data: &str) -> io::Result<()> {
+        let mut packet = Vec::with_capacity(data.len() + 4);
+        packet.push(b'$');
+        for &byte in data.as_bytes() {
+            if matches!(byte, b'$' | b'#' | b'}' | b'*') {
+                packet.extend([b'}', byte ^ 0x20]);
+            } else {
+                packet.push(byte);
+            }
+        }
+        let checksum = packet[1..].iter().fold(0u8, |acc, &byte| acc.wrapping_add(byte));
+        packet.extend(format!("#{checksum:02x}").as_bytes());
+
+        loop {
+            self.connection.write_all(&packet)?;
+            self.connection.flush()?;
+            if self.no_ack {
+                return Ok(());
+            }
+            match self.read_byte()? {
+                Some(b'-') => continue,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Read a single byte from the client, returning `None` on disconnect.
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut byte = [0u8];
+        match self.connection.read_exact(&mut byte) {
+            Ok(()) => Ok(Some(byte[0])),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// The stop reply packet describing why the program stopped.
+fn stop_reply(reason: &StopReason) -> String {
+    match reason {
+        StopReason::Step => "S05".to_string(),
+        StopReason::Breakpoint(_) | StopReason::Ebreak(_) => "T05swbreak:;".to_string(),
+        StopReason::Watchpoint { watchpoint, addr } => {
+            let kind = match watchpoint.kind {
+                WatchKind::Write => "watch",
+                WatchKind::Read => "rwatch",
+                WatchKind::Access => "awatch",
+            };
+            format!("T05{kind}:{addr:x};")
+        }
+        StopReason::Interrupted => "S02".to_string(),
+        StopReason::Exited(code) => format!("W{:02x}", code & 0xff),
+        StopReason::Faulted(err) => {
+            tracing::error!("program faulted: {err}");
+            match err {
+                ExecutionError::InvalidMemoryAccess(..) => "S0b".to_string(),
+                ExecutionError::Unimplemented() | ExecutionError::UnsupportedSyscall(_) => {
+                    "S04".to_string()
+                }
+                _ => "S06".to_string(),
+            }
+        }
+    }
+}
+
+/// Parse an `addr,len` pair of hex numbers.
+fn parse_addr_len(args: &str) -> Option<(u32, u32)> {
+    let (addr, len) = args.split_once(',')?;
+    Some((u32::from_str_radix(addr, 16).ok()?, u32::from_str_radix(len, 16).ok()?))
+}
+
+/// Parse the `type,addr,kind` arguments of a `Z` or `z` packet.
+fn parse_breakpoint(args: &str) -> Option<(u8, u32, u32)> {
+    let (kind, range) = args.split_once(',')?;
+    let (addr, len) = parse_addr_len(range.split(';').next()?)?;
+    Some((kind.parse().ok()?, addr, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor, Read, Write};
+
+    use sp1_stark::SP1CoreOpts;
+
+    use super::{GdbConnection, GdbServer};
+    use crate::{debugger::Debugger, programs::tests::simple_program, Executor};
+
+    struct MockConnection {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl Read for MockConnection {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for MockConnection {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl GdbConnection for MockConnection {
+        fn poll_interrupt(&mut self) -> io::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    fn packet(data: &str) -> String {
+        let checksum = data.bytes().fold(0u8, u8::wrapping_add);
+        format!("${data}#{checksum:02x}+")
+    }
+
+    /// The acknowledgement of a packet followed by the reply `data`.
+    fn reply(data: &str) -> String {
+        let checksum = data.bytes().fold(0u8, u8::wrapping_add);
+        format!("+${data}#{checksum:02x}")
+    }
+
+    /// Run a session with the given packets, returning the server's output.
+    fn session(packets: &[&str]) -> String {
+        let input = packets.iter().map(|p| packet(p)).collect::<String>();
+        let connection =
+            MockConnection { input: Cursor::new(input.into_bytes()), output: Vec::new() };
+        let debugger = Debugger::new(Executor::new(simple_program(), SP1CoreOpts::default()));
+        let mut server = GdbServer::new(debugger, connection);
+        server.run().unwrap();
+        String::from_utf8(server.connection.output).unwrap()
+    }
+
+    #[test]
+    fn test_registers() {
+        let output = session(&["s", "p1d", "P1e=2a000000", "p1e", "p20"]);
+        let expected = ["S05", "05000000", "OK", "2a000000", "04000000"].map(reply).concat();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_memory() {
+        let output = session(&["M1000,2:abcd", "m1000,4"]);
+        assert_eq!(output, ["OK", "abcd0000"].map(reply).concat());
+    }
+
+    #[test]
+    fn test_monitor_clk() {
+        let output = session(&["s", "s", &format!("qRcmd,{}", hex::encode("clk"))]);
+        assert_eq!(
+            output,
+            ["S05".to_string(), "S05".to_string(), hex::encode("2\n")].map(|r| reply(&r)).concat()
+        );
+    }
+
+    #[test]
+    fn test_breakpoint_and_exit() {
+        let output = session(&["Z0,8,4", "c", "c", "k"]);
+        // The final `k` packet is acknowledged but not replied to.
+        assert_eq!(output, ["OK", "T05swbreak:;", "W00"].map(reply).concat() + "+");
+    }
+
+    #[test]
+    fn test_bad_checksum() {
+        let connection = MockConnection {
+            input: Cursor::new(b"$g#00".iter().copied().chain(packet("D").bytes()).collect()),
+            output: Vec::new(),
+        };
+        let debugger = Debugger::new(Executor::new(simple_program(), SP1CoreOpts::default()));
+        let mut server = GdbServer::new(debugger, connection);
+        server.run().unwrap();
+        assert_eq!(
+            String::from_utf8(server.connection.output).unwrap(),
+            format!("-{}", reply("OK"))
+        );
+    }
+}
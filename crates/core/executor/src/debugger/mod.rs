@@ -0,0 +1,402 @@
+//! An interactive debugger for the [`Executor`].
+//!
+//! The [`Debugger`] drives an [`Executor`] one instruction at a time, stopping at breakpoints and
+//! watchpoints. It can be used directly, or exposed to GDB over the remote serial protocol with
+//! [`serve_gdb`].
+
+mod gdb;
+
+pub use gdb::*;
+
+use hashbrown::HashSet;
+
+use crate::{
+    events::MemoryRecord, ExecutionError, Executor, ExecutorMode, Instruction, Opcode, Register,
+};
+
+/// The number of instructions executed between checks for an interrupt request while continuing.
+const INTERRUPT_CHECK_INTERVAL: u64 = 1 << 16;
+
+/// The kind of memory access a [`Watchpoint`] triggers on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchKind {
+    /// Trigger on writes to the watched memory.
+    Write,
+    /// Trigger on reads from the watched memory.
+    Read,
+    /// Trigger on both reads and writes to the watched memory.
+    Access,
+}
+
+impl WatchKind {
+    const fn matches(self, is_write: bool) -> bool {
+        match self {
+            WatchKind::Write => is_write,
+            WatchKind::Read => !is_write,
+            WatchKind::Access => true,
+        }
+    }
+}
+
+/// A watchpoint on the byte range `[addr, addr + len)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Watchpoint {
+    /// The first watched address.
+    pub addr: u32,
+    /// The number of watched bytes.
+    pub len: u32,
+    /// The kind of access which triggers the watchpoint.
+    pub kind: WatchKind,
+}
+
+impl Watchpoint {
+    const fn overlaps(&self, addr: u32, len: u32) -> bool {
+        addr < self.addr.saturating_add(self.len) && self.addr < addr.saturating_add(len)
+    }
+}
+
+/// The reason the [`Debugger`] stopped executing the program.
+#[derive(Debug)]
+pub enum StopReason {
+    /// A single instruction was executed.
+    Step,
+    /// The program counter reached a breakpoint.
+    Breakpoint(u32),
+    /// The instruction at the program counter is about to access watched memory.
+    Watchpoint {
+        /// The watchpoint that was triggered.
+        watchpoint: Watchpoint,
+        /// The address being accessed.
+        addr: u32,
+    },
+    /// The program executed an `ebreak` instruction.
+    Ebreak(u32),
+    /// Execution was interrupted by the user.
+    Interrupted,
+    /// The program halted with the given exit code.
+    Exited(u32),
+    /// The program failed with an error other than halting.
+    Faulted(ExecutionError),
+}
+
+/// A debugger for programs running inside the [`Executor`].
+///
+/// The executor is run in [`ExecutorMode::Simple`], so no events are traced while debugging.
+pub struct Debugger<'a> {
+    /// The executor being debugged.
+    pub runtime: Executor<'a>,
+    breakpoints: HashSet<u32>,
+    watchpoints: HashSet<Watchpoint>,
+    stopped_at_ebreak: bool,
+    exit_code: Option<u32>,
+}
+
+impl<'a> Debugger<'a> {
+    /// Create a new [`Debugger`] for an executor which has not started running yet.
+    ///
+    /// The program's memory image is loaded immediately so that memory can be inspected and
+    /// modified before the first instruction is executed.
+    #[must_use]
+    pub fn new(mut runtime: Executor<'a>) -> Self {
+        runtime.executor_mode = ExecutorMode::Simple;
+        if runtime.state.global_clk == 0 {
+            runtime.initialize();
+        }
+        Self {
+            runtime,
+            breakpoints: HashSet::new(),
+            watchpoints: HashSet::new(),
+            stopped_at_ebreak: false,
+            exit_code: None,
+        }
+    }
+
+    /// The current program counter.
+    #[must_use]
+    pub fn pc(&self) -> u32 {
+        self.runtime.state.pc
+    }
+
+    /// The number of instructions executed so far.
+    #[must_use]
+    pub fn clk(&self) -> u64 {
+        self.runtime.state.global_clk
+    }
+
+    /// The exit code of the program, if it has halted.
+    #[must_use]
+    pub fn exit_code(&self) -> Option<u32> {
+        self.exit_code
+    }
+
+    /// Get the current values of the registers.
+    #[must_use]
+    pub fn registers(&mut self) -> [u32; 32] {
+        self.runtime.registers()
+    }
+
+    /// Set the value of a register. Writes to `x0` are ignored.
+    pub fn set_register(&mut self, register: Register, value: u32) {
+        if register != Register::X0 {
+            self.write_word(register as u32, value);
+        }
+    }
+
+    /// Set the program counter.
+    pub fn set_pc(&mut self, pc: u32) {
+        self.runtime.state.pc = pc;
+        self.stopped_at_ebreak = false;
+    }
+
+    /// Read `len` bytes of memory starting at `addr`.
+    #[must_use]
+    pub fn read_memory(&mut self, addr: u32, len: u32) -> Vec<u8> {
+        (0..len).map(|i| self.runtime.byte(addr.wrapping_add(i))).collect()
+    }
+
+    /// Write `bytes` to memory starting at `addr`.
+    pub fn write_memory(&mut self, addr: u32, bytes: &[u8]) {
+        for (i, byte) in bytes.iter().enumerate() {
+            let addr = addr.wrapping_add(i as u32);
+            let aligned = addr - addr % 4;
+            let shift = (addr % 4) * 8;
+            let word = self.runtime.word(aligned);
+            self.write_word(aligned, (word & !(0xff << shift)) | (u32::from(*byte) << shift));
+        }
+    }
+
+    /// Overwrite a word of memory without creating an access record.
+    fn write_word(&mut self, addr: u32, value: u32) {
+        self.runtime
+            .state
+            .memory
+            .entry(addr)
+            .and_modify(|record| record.value = value)
+            .or_insert(MemoryRecord { value, shard: 0, timestamp: 0 });
+    }
+
+    /// Set a breakpoint at `addr`. Returns whether the breakpoint was newly inserted.
+    pub fn add_breakpoint(&mut self, addr: u32) -> bool {
+        self.breakpoints.insert(addr)
+    }
+
+    /// Set a breakpoint at the start of the function `name`, returning its address.
+    ///
+    /// Functions are resolved with the symbols of the program being debugged.
+    pub fn add_function_breakpoint(&mut self, name: &str) -> Option<u32> {
+        let addr = self.runtime.program.symbols.find(name)?.address;
+        self.breakpoints.insert(addr);
+        Some(addr)
+    }
+
+    /// Remove the breakpoint at `addr`. Returns whether a breakpoint was removed.
+    pub fn remove_breakpoint(&mut self, addr: u32) -> bool {
+        self.breakpoints.remove(&addr)
+    }
+
+    /// Add a watchpoint. Returns whether the watchpoint was newly inserted.
+    pub fn add_watchpoint(&mut self, watchpoint: Watchpoint) -> bool {
+        self.watchpoints.insert(watchpoint)
+    }
+
+    /// Remove a watchpoint. Returns whether a watchpoint was removed.
+    pub fn remove_watchpoint(&mut self, watchpoint: &Watchpoint) -> bool {
+        self.watchpoints.remove(watchpoint)
+    }
+
+    /// Execute a single instruction, ignoring breakpoints and watchpoints.
+    pub fn step(&mut self) -> StopReason {
+        self.resume();
+        self.execute_instruction().unwrap_or(StopReason::Step)
+    }
+
+    /// Execute instructions until a breakpoint or watchpoint is hit or the program stops.
+    ///
+    /// Breakpoints and watchpoints are not checked for the first instruction, so that continuing
+    /// from a stop makes progress. `interrupted` is polled periodically and execution stops with
+    /// [`StopReason::Interrupted`] once it returns `true`.
+    pub fn cont(&mut self, mut interrupted: impl FnMut() -> bool) -> StopReason {
+        self.resume();
+        if let Some(reason) = self.execute_instruction() {
+            return reason;
+        }
+        loop {
+            if self.clk() % INTERRUPT_CHECK_INTERVAL == 0 && interrupted() {
+                return StopReason::Interrupted;
+            }
+            if let Some(reason) = self.check_stop() {
+                return reason;
+            }
+            if let Some(reason) = self.execute_instruction() {
+                return reason;
+            }
+        }
+    }
+
+    /// Prepare to resume execution after a stop.
+    fn resume(&mut self) {
+        // The executor does not advance past an `ebreak`, so step over it explicitly.
+        if std::mem::take(&mut self.stopped_at_ebreak) {
+            let pc = self.runtime.state.pc;
+            let size = self.runtime.program.fetch(pc).map_or(4, Instruction::size);
+            self.runtime.state.pc = pc.wrapping_add(size);
+        }
+    }
+
+    /// Check whether the instruction at the current program counter hits a breakpoint or
+    /// watchpoint.
+    fn check_stop(&mut self) -> Option<StopReason> {
+        let pc = self.pc();
+        if self.breakpoints.contains(&pc) {
+            return Some(StopReason::Breakpoint(pc));
+        }
+        if self.watchpoints.is_empty() || self.exit_code.is_some() || !self.pc_is_valid() {
+            return None;
+        }
+        let instruction = self.runtime.fetch();
+        let (addr, len, is_write) = self.memory_access(&instruction)?;
+        self.watchpoints
+            .iter()
+            .find(|watchpoint| watchpoint.kind.matches(is_write) && watchpoint.overlaps(addr, len))
+            .map(|watchpoint| StopReason::Watchpoint { watchpoint: *watchpoint, addr })
+    }
+
+    /// Execute the instruction at the current program counter, returning a reason to stop if the
+    /// program can no longer make progress.
+    fn execute_instruction(&mut self) -> Option<StopReason> {
+        if let Some(exit_code) = self.exit_code {
+            return Some(StopReason::Exited(exit_code));
+        }
+        if !self.pc_is_valid() {
+            return Some(StopReason::Faulted(ExecutionError::InvalidMemoryAccess(
+                Opcode::UNIMP,
+                self.pc(),
+            )));
+        }
+
+        let result = self.runtime.execute_cycle();
+
+        // Records are not needed while debugging, so don't let them accumulate across shards.
+        self.runtime.records.clear();
+
+        match result {
+            Ok(false) => None,
+            Ok(true) => {
+                self.runtime.postprocess();
+                self.exit_code = Some(0);
+                Some(StopReason::Exited(0))
+            }
+            Err(ExecutionError::HaltWithNonZeroExitCode(exit_code)) => {
+                self.exit_code = Some(exit_code);
+                Some(StopReason::Exited(exit_code))
+            }
+            Err(ExecutionError::Breakpoint()) => {
+                self.stopped_at_ebreak = true;
+                Some(StopReason::Ebreak(self.pc()))
+            }
+            Err(err) => Some(StopReason::Faulted(err)),
+        }
+    }
+
+    /// Whether the program counter points to an instruction of the program.
+    fn pc_is_valid(&self) -> bool {
+        let pc = self.pc();
+        let program = &self.runtime.program;
+        program.instruction_index(pc).is_some_and(|index| program.instruction_pc(index) == pc)
+    }
+
+    /// The byte range and direction of the memory access made by `instruction`, if any.
+    fn memory_access(&mut self, instruction: &Instruction) -> Option<(u32, u32, bool)> {
+        let (len, is_write) = match instruction.opcode {
+            Opcode::LB | Opcode::LBU => (1, false),
+            Opcode::LH | Opcode::LHU => (2, false),
+            Opcode::LW | Opcode::LR => (4, false),
+            Opcode::SB => (1, true),
+            Opcode::SH => (2, true),
+            Opcode::SW => (4, true),
+            _ if instruction.is_atomic_instruction() => (4, true),
+            _ => return None,
+        };
+        let base = self.runtime.register(Register::from_u32(instruction.op_b));
+        // Atomic instructions have no offset, their third operand is a register.
+        if instruction.is_atomic_instruction() {
+            return Some((base, len, is_write));
+        }
+        Some((base.wrapping_add(instruction.op_c), len, is_write))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use super::{Debugger, StopReason, WatchKind, Watchpoint};
+    use crate::{
+        programs::tests::{fibonacci_program, simple_memory_program, simple_program},
+        Executor, Instruction, Opcode, Program, Register,
+    };
+
+    fn debugger(program: Program) -> Debugger<'static> {
+        Debugger::new(Executor::new(program, SP1CoreOpts::default()))
+    }
+
+    #[test]
+    fn test_step() {
+        let mut debugger = debugger(simple_program());
+        let pc = debugger.pc();
+        assert!(matches!(debugger.step(), StopReason::Step));
+        assert_eq!(debugger.pc(), pc + 4);
+        assert_eq!(debugger.clk(), 1);
+        assert_eq!(debugger.registers()[29], 5);
+    }
+
+    #[test]
+    fn test_breakpoint() {
+        let mut debugger = debugger(fibonacci_program());
+        let target = debugger.pc() + 8;
+        debugger.add_breakpoint(target);
+        assert!(matches!(debugger.cont(|| false), StopReason::Breakpoint(pc) if pc == target));
+        assert_eq!(debugger.pc(), target);
+
+        // Continuing from a breakpoint makes progress instead of stopping immediately.
+        debugger.remove_breakpoint(target);
+        assert!(matches!(debugger.cont(|| false), StopReason::Exited(0)));
+        assert_eq!(debugger.exit_code(), Some(0));
+    }
+
+    #[test]
+    fn test_watchpoint() {
+        let mut debugger = debugger(simple_memory_program());
+        debugger.add_watchpoint(Watchpoint { addr: 0x27654320, len: 4, kind: WatchKind::Write });
+        let StopReason::Watchpoint { addr, .. } = debugger.cont(|| false) else {
+            panic!("expected watchpoint");
+        };
+        assert_eq!(addr, 0x27654320);
+        assert_eq!(debugger.runtime.fetch().opcode, Opcode::SW);
+    }
+
+    #[test]
+    fn test_ebreak() {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 5, false, true),
+            Instruction::new(Opcode::EBREAK, 0, 0, 0, false, false),
+            Instruction::new(Opcode::ADD, 30, 0, 37, false, true),
+            Instruction::new(Opcode::ADD, 31, 0, 42, false, true),
+        ];
+        let mut debugger = debugger(Program::new(instructions, 0, 0));
+        assert!(matches!(debugger.cont(|| false), StopReason::Ebreak(4)));
+        assert!(matches!(debugger.step(), StopReason::Step));
+        assert_eq!(debugger.registers()[30], 37);
+    }
+
+    #[test]
+    fn test_modify_state() {
+        let mut debugger = debugger(simple_program());
+        debugger.set_register(Register::X5, 7);
+        debugger.set_register(Register::X0, 7);
+        debugger.write_memory(0x1001, &[0xaa, 0xbb]);
+        assert_eq!(debugger.registers()[5], 7);
+        assert_eq!(debugger.registers()[0], 0);
+        assert_eq!(debugger.read_memory(0x1000, 4), vec![0, 0xaa, 0xbb, 0]);
+    }
+}
@@ -179,6 +179,48 @@ pub fn emit_cpu_dependencies(executor: &mut Executor, event: &CpuEvent) {
         }
     }
 
+    if event.instruction.is_atomic_instruction() {
+        // The value stored by an AMO instruction is either the result of an ALU operation on the
+        // loaded value, or is selected by comparing the loaded value to `c`.
+        let prev_value = event.memory_record.unwrap().previous_record().value;
+        let value = event.memory_record.unwrap().value();
+        let (opcode, a) = match event.instruction.opcode {
+            Opcode::AMOADD | Opcode::AMOXOR | Opcode::AMOAND | Opcode::AMOOR => {
+                let opcode = match event.instruction.opcode {
+                    Opcode::AMOADD => Opcode::ADD,
+                    Opcode::AMOXOR => Opcode::XOR,
+                    Opcode::AMOAND => Opcode::AND,
+                    _ => Opcode::OR,
+                };
+                (Some(opcode), value)
+            }
+            Opcode::AMOMIN | Opcode::AMOMAX => {
+                (Some(Opcode::SLT), u32::from((prev_value as i32) < (event.c as i32)))
+            }
+            Opcode::AMOMINU | Opcode::AMOMAXU => {
+                (Some(Opcode::SLTU), u32::from(prev_value < event.c))
+            }
+            _ => (None, 0),
+        };
+        if let Some(opcode) = opcode {
+            let alu_event = AluEvent {
+                lookup_id: event.alu_lookup_id,
+                shard: event.shard,
+                clk: event.clk,
+                opcode,
+                a,
+                b: prev_value,
+                c: event.c,
+                sub_lookups: create_alu_lookups(),
+            };
+            match opcode {
+                Opcode::ADD => executor.record.add_events.push(alu_event),
+                Opcode::SLT | Opcode::SLTU => executor.record.lt_events.push(alu_event),
+                _ => executor.record.bitwise_events.push(alu_event),
+            }
+        }
+    }
+
     if event.instruction.is_branch_instruction() {
         let a_eq_b = event.a == event.b;
         let use_signed_comparison = matches!(event.instruction.opcode, Opcode::BLT | Opcode::BGE);
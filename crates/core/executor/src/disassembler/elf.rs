@@ -1,7 +1,7 @@
-use std::cmp::min;
+use std::{cmp::min, collections::BTreeMap, sync::OnceLock};
 
 use elf::{
-    abi::{EM_RISCV, ET_EXEC, PF_X, PT_LOAD},
+    abi::{EF_RISCV_RVC, EM_RISCV, ET_EXEC, PF_X, PT_LOAD, STT_FUNC},
     endian::LittleEndian,
     file::Class,
     ElfBytes,
@@ -9,25 +9,141 @@ use elf::{
 use hashbrown::HashMap;
 use sp1_primitives::consts::{MAXIMUM_MEMORY_SIZE, WORD_SIZE};
 
-/// RISC-V 32IM ELF (Executable and Linkable Format) File.
+use super::rvc::{expand, is_compressed};
+
+/// RISC-V 32IMAC ELF (Executable and Linkable Format) File.
 ///
 /// This file represents a binary in the ELF format, specifically the RISC-V 32IM architecture
 /// with the following extensions:
 ///
 /// - Base Integer Instruction Set (I)
 /// - Integer Multiplication and Division (M)
+/// - Atomic Instructions (A)
+/// - Compressed Instructions (C), optionally
 ///
 /// This format is commonly used in embedded systems and is supported by many compilers.
 #[derive(Debug, Clone)]
 pub(crate) struct Elf {
-    /// The instructions of the program encoded as 32-bits.
+    /// The instructions of the program encoded as 32-bits, or as 16-bits for compressed
+    /// instructions.
     pub(crate) instructions: Vec<u32>,
+    /// The address of each instruction, if the program contains compressed instructions.
+    pub(crate) instruction_pcs: Option<Vec<u32>>,
     /// The start address of the program.
     pub(crate) pc_start: u32,
     /// The base address of the program.
     pub(crate) pc_base: u32,
     /// The initial memory image, useful for global constants.
     pub(crate) memory_image: HashMap<u32, u32>,
+    /// The function symbols of the program, if the ELF was not stripped.
+    pub(crate) symbols: SymbolTable,
+}
+
+/// A function symbol loaded from the symbol table of an ELF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfSymbol {
+    /// The name of the function, as it appears in the ELF.
+    ///
+    /// Rust functions are mangled; use [`SymbolTable::demangled_name`] for a readable name.
+    pub name: String,
+    /// The address of the first instruction of the function.
+    pub address: u32,
+    /// The size of the function in bytes.
+    pub size: u32,
+}
+
+impl ElfSymbol {
+    /// Whether `pc` lies within the body of this function.
+    #[must_use]
+    pub const fn contains(&self, pc: u32) -> bool {
+        pc >= self.address && pc - self.address < self.size
+    }
+}
+
+/// The function symbols of an ELF, sorted by address.
+///
+/// Names are only demangled when they are first looked up, since most executions never report a
+/// function name.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: Vec<ElfSymbol>,
+    demangled: Vec<OnceLock<String>>,
+}
+
+impl SymbolTable {
+    /// Load the function symbols of a RISC-V ELF.
+    ///
+    /// # Errors
+    ///
+    /// This function may return an error if the ELF is not valid.
+    pub fn from_elf(input: &[u8]) -> eyre::Result<Self> {
+        Ok(Elf::decode(input)?.symbols)
+    }
+
+    /// Create a symbol table from a list of symbols.
+    #[must_use]
+    pub fn new(mut symbols: Vec<ElfSymbol>) -> Self {
+        symbols.sort_by_key(|symbol| symbol.address);
+        let demangled = symbols.iter().map(|_| OnceLock::new()).collect();
+        Self { symbols, demangled }
+    }
+
+    /// Find the function containing `pc`.
+    #[must_use]
+    pub fn lookup(&self, pc: u32) -> Option<&ElfSymbol> {
+        self.lookup_index(pc).map(|idx| &self.symbols[idx])
+    }
+
+    /// Find the index of the function containing `pc`.
+    #[must_use]
+    pub fn lookup_index(&self, pc: u32) -> Option<usize> {
+        let idx = self.symbols.partition_point(|symbol| symbol.address <= pc);
+        idx.checked_sub(1).filter(|&idx| self.symbols[idx].contains(pc))
+    }
+
+    /// Get the symbol at `index`, in order of increasing address.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&ElfSymbol> {
+        self.symbols.get(index)
+    }
+
+    /// The demangled name of the symbol at `index`.
+    #[must_use]
+    pub fn demangled_name(&self, index: usize) -> Option<&str> {
+        let symbol = self.symbols.get(index)?;
+        let name = self.demangled[index]
+            .get_or_init(|| rustc_demangle::demangle(&symbol.name).to_string());
+        Some(name.as_str())
+    }
+
+    /// Find a function by its demangled name, ignoring the trailing hash added by rustc.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<&ElfSymbol> {
+        (0..self.symbols.len())
+            .find(|&index| {
+                let demangled = self.demangled_name(index).unwrap_or_default();
+                demangled == name
+                    || demangled.rsplit_once("::h").is_some_and(|(prefix, _)| prefix == name)
+            })
+            .map(|index| &self.symbols[index])
+    }
+
+    /// Iterate over the symbols in order of increasing address.
+    pub fn iter(&self) -> impl Iterator<Item = &ElfSymbol> {
+        self.symbols.iter()
+    }
+
+    /// The number of symbols in the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the table contains no symbols.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
 }
 
 impl Elf {
@@ -35,11 +151,13 @@ impl Elf {
     #[must_use]
     pub(crate) const fn new(
         instructions: Vec<u32>,
+        instruction_pcs: Option<Vec<u32>>,
         pc_start: u32,
         pc_base: u32,
         memory_image: HashMap<u32, u32>,
+        symbols: SymbolTable,
     ) -> Self {
-        Self { instructions, pc_start, pc_base, memory_image }
+        Self { instructions, instruction_pcs, pc_start, pc_base, memory_image, symbols }
     }
 
     /// Parse the ELF file into a vector of 32-bit encoded instructions and the first memory
@@ -68,8 +186,12 @@ impl Elf {
         // Get the entrypoint of the ELF file as an u32.
         let entry: u32 = elf.ehdr.e_entry.try_into()?;
 
+        // Compressed instructions are only aligned to 2 bytes.
+        let compressed = elf.ehdr.e_flags & EF_RISCV_RVC != 0;
+        let alignment = if compressed { 2 } else { WORD_SIZE as u32 };
+
         // Make sure the entrypoint is valid.
-        if entry == MAXIMUM_MEMORY_SIZE || entry % WORD_SIZE as u32 != 0 {
+        if entry == MAXIMUM_MEMORY_SIZE || entry % alignment != 0 {
             eyre::bail!("invalid entrypoint");
         }
 
@@ -79,7 +201,24 @@ impl Elf {
             eyre::bail!("too many program headers");
         }
 
+        // Load the function symbols, which are only used for debugging and profiling, so a
+        // malformed symbol table or symbol is skipped rather than failing to load the program.
+        let mut symbols = Vec::new();
+        if let Ok(Some((symtab, strtab))) = elf.symbol_table() {
+            for symbol in symtab.iter().filter(|x| x.st_symtype() == STT_FUNC && x.st_size > 0) {
+                let (Ok(name), Ok(address), Ok(size)) = (
+                    strtab.get(symbol.st_name as usize),
+                    symbol.st_value.try_into(),
+                    symbol.st_size.try_into(),
+                ) else {
+                    continue;
+                };
+                symbols.push(ElfSymbol { name: name.to_string(), address, size });
+            }
+        }
+
         let mut instructions: Vec<u32> = Vec::new();
+        let mut instruction_pcs = compressed.then(Vec::new);
         let mut base_address = u32::MAX;
 
         // Only read segments that are executable instructions that are also PT_LOAD.
@@ -137,12 +276,134 @@ impl Elf {
                     word |= u32::from(*byte) << (j * 8);
                 }
                 image.insert(addr, word);
-                if (segment.p_flags & PF_X) != 0 {
+                if (segment.p_flags & PF_X) != 0 && !compressed {
                     instructions.push(word);
                 }
             }
+
+            // With compressed instructions, decode the segment as a stream of 16-bit and 32-bit
+            // instructions instead.
+            if let Some(instruction_pcs) = instruction_pcs.as_mut() {
+                if (segment.p_flags & PF_X) != 0 {
+                    let data = offset
+                        .checked_add(file_size)
+                        .and_then(|end| input.get(offset as usize..end as usize))
+                        .ok_or_else(|| eyre::eyre!("failed to read segment offset"))?;
+                    let anchors = std::iter::once(entry).chain(symbols.iter().map(|s| s.address));
+                    decode_compressed(data, vaddr, anchors, &mut instructions, instruction_pcs);
+                }
+            }
         }
 
-        Ok(Elf::new(instructions, entry, base_address, image))
+        Ok(Elf::new(
+            instructions,
+            instruction_pcs,
+            entry,
+            base_address,
+            image,
+            SymbolTable::new(symbols),
+        ))
+    }
+}
+
+/// Split `data`, loaded at `vaddr`, into 16-bit and 32-bit instructions.
+///
+/// Data embedded in the code can desynchronize a single linear sweep, so the code is swept from its
+/// start and again from every known instruction boundary: the `anchors`, such as the entrypoint
+/// and the start of each function, and the target of every direct jump or branch. A sweep stops at
+/// an instruction which was already decoded, since the sweeps agree from there on. A trailing
+/// partial instruction is ignored.
+fn decode_compressed(
+    data: &[u8],
+    vaddr: u32,
+    anchors: impl IntoIterator<Item = u32>,
+    instructions: &mut Vec<u32>,
+    instruction_pcs: &mut Vec<u32>,
+) {
+    let offset_of = |pc: u32| {
+        pc.checked_sub(vaddr)
+            .map(|offset| offset as usize)
+            .filter(|&offset| offset % 2 == 0 && offset < data.len())
+    };
+    let mut decoded = BTreeMap::new();
+    let mut pending =
+        std::iter::once(0).chain(anchors.into_iter().filter_map(offset_of)).collect::<Vec<_>>();
+    while let Some(mut i) = pending.pop() {
+        while i + 2 <= data.len() && !decoded.contains_key(&i) {
+            let mut word = u32::from(u16::from_le_bytes([data[i], data[i + 1]]));
+            let len = if is_compressed(word) { 2 } else { 4 };
+            if i + len > data.len() {
+                break;
+            }
+            if len == 4 {
+                word |= u32::from(u16::from_le_bytes([data[i + 2], data[i + 3]])) << 16;
+            }
+            decoded.insert(i, word);
+            let pc = vaddr + i as u32;
+            if let Some(target) = jump_offset(word).and_then(|o| offset_of(pc.wrapping_add(o))) {
+                pending.push(target);
+            }
+            i += len;
+        }
+    }
+
+    for (i, word) in decoded {
+        instruction_pcs.push(vaddr + i as u32);
+        instructions.push(word);
+    }
+}
+
+/// The offset of the target of a direct jump or branch from its address.
+fn jump_offset(word: u32) -> Option<u32> {
+    let word = if is_compressed(word) { expand(word as u16)? } else { word };
+    let bit = |n: u32| (word >> n) & 1;
+    let field = |hi: u32, lo: u32| (word >> lo) & ((1 << (hi - lo + 1)) - 1);
+    let sign_extend =
+        |value: u32, width: u32| ((value << (32 - width)) as i32 >> (32 - width)) as u32;
+    match word & 0x7f {
+        // JAL
+        0b110_1111 => Some(sign_extend(
+            bit(31) << 20 | field(19, 12) << 12 | bit(20) << 11 | field(30, 21) << 1,
+            21,
+        )),
+        // BRANCH
+        0b110_0011 => Some(sign_extend(
+            bit(31) << 12 | bit(7) << 11 | field(30, 25) << 5 | field(11, 8) << 1,
+            13,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_compressed, ElfSymbol, SymbolTable};
+
+    #[test]
+    fn test_symbols_are_demangled_on_lookup() {
+        let symbols = SymbolTable::new(vec![
+            ElfSymbol { name: "_ZN4main4main17h0123456789abcdefE".into(), address: 0, size: 8 },
+            ElfSymbol { name: "memcpy".into(), address: 8, size: 8 },
+        ]);
+        assert_eq!(symbols.demangled_name(0), Some("main::main::h0123456789abcdef"));
+        assert_eq!(symbols.demangled_name(1), Some("memcpy"));
+        assert_eq!(symbols.demangled_name(2), None);
+        assert_eq!(symbols.find("main::main").map(|symbol| symbol.address), Some(0));
+        assert_eq!(symbols.find("memcpy").map(|symbol| symbol.address), Some(8));
+    }
+
+    #[test]
+    fn test_decode_compressed_resyncs_at_anchors() {
+        // A halfword of data which looks like the start of a 32-bit instruction, followed by two
+        // `c.nop` instructions.
+        let data = [0xff, 0xff, 0x01, 0x00, 0x01, 0x00];
+        let (mut instructions, mut pcs) = (Vec::new(), Vec::new());
+        decode_compressed(&data, 0x100, [], &mut instructions, &mut pcs);
+        assert_eq!(pcs, [0x100, 0x104]);
+
+        let (mut instructions, mut pcs) = (Vec::new(), Vec::new());
+        decode_compressed(&data, 0x100, [0x102], &mut instructions, &mut pcs);
+        assert_eq!(pcs, [0x100, 0x102, 0x104]);
+        assert_eq!(instructions, [0x0001_ffff, 0x0001, 0x0001]);
     }
 }
@@ -2,6 +2,8 @@
 
 mod elf;
 mod rrs;
+mod rvc;
 
 pub(crate) use elf::*;
+pub use elf::{ElfSymbol, SymbolTable};
 pub(crate) use rrs::*;
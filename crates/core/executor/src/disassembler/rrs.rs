@@ -3,6 +3,7 @@ use rrs_lib::{
     process_instruction, InstructionProcessor,
 };
 
+use super::rvc::{expand, is_compressed};
 use crate::{Instruction, Opcode, Register};
 
 impl Instruction {
@@ -369,7 +370,8 @@ impl InstructionProcessor for InstructionTranspiler {
     }
 
     fn process_fence(&mut self, _: IType) -> Self::InstructionResult {
-        Instruction::unimp()
+        // With a single hart, memory accesses are never reordered, so a fence is a no-op.
+        Instruction::new(Opcode::ADD, Register::X0 as u32, 0, 0, true, true)
     }
 
     fn process_mret(&mut self) -> Self::InstructionResult {
@@ -381,7 +383,40 @@ impl InstructionProcessor for InstructionTranspiler {
     }
 }
 
-/// Transpile the [`Instruction`]s from the 32-bit encoded instructions.
+/// The major opcode of the atomic memory instructions, which are not decoded by `rrs`.
+const OPCODE_AMO: u32 = 0b010_1111;
+
+/// Transpile an instruction of the atomic ("A") extension.
+///
+/// The `aq` and `rl` ordering bits are ignored, since the zkVM executes a single hart. Returns
+/// `None` for encodings that are not 32-bit atomic memory instructions.
+fn transpile_atomic(instruction_u32: u32) -> Option<Instruction> {
+    let dec_insn = RType::new(instruction_u32);
+    if dec_insn.funct3 != 0b010 {
+        return None;
+    }
+    let opcode = match dec_insn.funct7 >> 2 {
+        0b00010 if dec_insn.rs2 == 0 => Opcode::LR,
+        0b00011 => Opcode::SC,
+        0b00001 => Opcode::AMOSWAP,
+        0b00000 => Opcode::AMOADD,
+        0b00100 => Opcode::AMOXOR,
+        0b01100 => Opcode::AMOAND,
+        0b01000 => Opcode::AMOOR,
+        0b10000 => Opcode::AMOMIN,
+        0b10100 => Opcode::AMOMAX,
+        0b11000 => Opcode::AMOMINU,
+        0b11100 => Opcode::AMOMAXU,
+        _ => return None,
+    };
+    Some(Instruction::from_r_type(opcode, &dec_insn))
+}
+
+/// Transpile the [`Instruction`]s from the encoded instructions.
+///
+/// Compressed instructions are expanded into the instructions they are a shorthand for, and the
+/// illegal ones are transpiled to [`Opcode::UNIMP`]. Atomic instructions are transpiled here, as
+/// they are not supported by `rrs`.
 ///
 /// # Panics
 ///
@@ -390,9 +425,58 @@ impl InstructionProcessor for InstructionTranspiler {
 pub(crate) fn transpile(instructions_u32: &[u32]) -> Vec<Instruction> {
     let mut instructions = Vec::new();
     let mut transpiler = InstructionTranspiler;
-    for instruction_u32 in instructions_u32 {
-        let instruction = process_instruction(&mut transpiler, *instruction_u32).unwrap();
+    for &instruction_u32 in instructions_u32 {
+        let instruction = if is_compressed(instruction_u32) {
+            let mut instruction = expand(instruction_u32 as u16)
+                .map_or_else(Instruction::unimp, |expanded| {
+                    process_instruction(&mut transpiler, expanded).unwrap()
+                });
+            instruction.compressed = true;
+            instruction
+        } else if instruction_u32 & 0x7f == OPCODE_AMO {
+            transpile_atomic(instruction_u32).unwrap_or_else(Instruction::unimp)
+        } else {
+            process_instruction(&mut transpiler, instruction_u32).unwrap()
+        };
         instructions.push(instruction);
     }
     instructions
 }
+
+#[cfg(test)]
+mod tests {
+    use super::transpile;
+    use crate::{Instruction, Opcode};
+
+    #[test]
+    fn test_transpile_atomic() {
+        let instructions = transpile(&[
+            0x1005_a52f, // lr.w a0, (a1)
+            0x18c5_a52f, // sc.w a0, a2, (a1)
+            0x08c5_a52f, // amoswap.w a0, a2, (a1)
+            0x00c5_a52f, // amoadd.w a0, a2, (a1)
+            0xe6c5_a52f, // amomaxu.w.aqrl a0, a2, (a1)
+            0x00c5_b52f, // amoadd.d a0, a2, (a1)
+            0x0ff0_000f, // fence
+        ]);
+        let decoded = instructions
+            .iter()
+            .map(|instruction| {
+                (instruction.opcode, instruction.op_a, instruction.op_b, instruction.op_c)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decoded,
+            vec![
+                (Opcode::LR, 10, 11, 0),
+                (Opcode::SC, 10, 11, 12),
+                (Opcode::AMOSWAP, 10, 11, 12),
+                (Opcode::AMOADD, 10, 11, 12),
+                (Opcode::AMOMAXU, 10, 11, 12),
+                (Opcode::UNIMP, 0, 0, 0),
+                (Opcode::ADD, 0, 0, 0),
+            ]
+        );
+        assert!(instructions.iter().take(5).all(Instruction::is_r_type));
+    }
+}
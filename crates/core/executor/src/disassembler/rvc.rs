@@ -0,0 +1,274 @@
+//! Expansion of RV32C compressed instructions into their 32-bit RV32I equivalents.
+
+/// Whether the encoded instruction `word` is a 16-bit compressed instruction.
+///
+/// All 32-bit instructions have their two lowest bits set.
+#[must_use]
+pub(crate) const fn is_compressed(word: u32) -> bool {
+    word & 0b11 != 0b11
+}
+
+/// Expand a 16-bit compressed instruction into the 32-bit instruction it is a shorthand for.
+///
+/// Returns `None` for illegal and reserved encodings, and for the floating point instructions,
+/// which the zkVM does not support.
+#[must_use]
+#[allow(clippy::too_many_lines)]
+pub(crate) fn expand(c: u16) -> Option<u32> {
+    let c = u32::from(c);
+    let funct3 = bits(c, 15, 13);
+
+    // The full and the compressed (3-bit, `x8..x15`) register fields.
+    let rd = bits(c, 11, 7);
+    let rs2 = bits(c, 6, 2);
+    let rd_c = bits(c, 4, 2) + 8;
+    let rs1_c = bits(c, 9, 7) + 8;
+
+    // The 6-bit immediate shared by most instructions of quadrant 1, sign extended.
+    let imm6 = sign_extend(bits(c, 12, 12) << 5 | bits(c, 6, 2), 6);
+
+    let word = match (c & 0b11, funct3) {
+        // C.ADDI4SPN
+        (0b00, 0b000) => {
+            let imm = bits(c, 12, 11) << 4
+                | bits(c, 10, 7) << 6
+                | bits(c, 6, 6) << 2
+                | bits(c, 5, 5) << 3;
+            if imm == 0 {
+                return None;
+            }
+            i_type(OP_IMM, rd_c, 0b000, 2, imm)
+        }
+        // C.LW
+        (0b00, 0b010) => i_type(LOAD, rd_c, 0b010, rs1_c, lw_sw_offset(c)),
+        // C.SW
+        (0b00, 0b110) => s_type(0b010, rs1_c, rd_c, lw_sw_offset(c)),
+        // C.ADDI, C.NOP
+        (0b01, 0b000) => i_type(OP_IMM, rd, 0b000, rd, imm6),
+        // C.JAL
+        (0b01, 0b001) => j_type(1, j_offset(c)),
+        // C.LI
+        (0b01, 0b010) => i_type(OP_IMM, rd, 0b000, 0, imm6),
+        // C.ADDI16SP
+        (0b01, 0b011) if rd == 2 => {
+            let imm = bits(c, 12, 12) << 9
+                | bits(c, 6, 6) << 4
+                | bits(c, 5, 5) << 6
+                | bits(c, 4, 3) << 7
+                | bits(c, 2, 2) << 5;
+            if imm == 0 {
+                return None;
+            }
+            i_type(OP_IMM, 2, 0b000, 2, sign_extend(imm, 10))
+        }
+        // C.LUI
+        (0b01, 0b011) => {
+            if imm6 == 0 {
+                return None;
+            }
+            (imm6 << 12) | rd << 7 | LUI
+        }
+        (0b01, 0b100) => match bits(c, 11, 10) {
+            // C.SRLI, C.SRAI
+            0b00 | 0b01 => {
+                if bits(c, 12, 12) != 0 {
+                    return None;
+                }
+                let funct7 = if bits(c, 11, 10) == 0b01 { 0b010_0000 } else { 0 };
+                i_type(OP_IMM, rs1_c, 0b101, rs1_c, funct7 << 5 | rs2)
+            }
+            // C.ANDI
+            0b10 => i_type(OP_IMM, rs1_c, 0b111, rs1_c, imm6),
+            // C.SUB, C.XOR, C.OR, C.AND
+            _ => {
+                if bits(c, 12, 12) != 0 {
+                    return None;
+                }
+                let (funct7, funct3) = match bits(c, 6, 5) {
+                    0b00 => (0b010_0000, 0b000),
+                    0b01 => (0, 0b100),
+                    0b10 => (0, 0b110),
+                    _ => (0, 0b111),
+                };
+                r_type(funct7, rs1_c, funct3, rs1_c, rd_c)
+            }
+        },
+        // C.J
+        (0b01, 0b101) => j_type(0, j_offset(c)),
+        // C.BEQZ, C.BNEZ
+        (0b01, 0b110 | 0b111) => {
+            let offset = sign_extend(
+                bits(c, 12, 12) << 8
+                    | bits(c, 11, 10) << 3
+                    | bits(c, 6, 5) << 6
+                    | bits(c, 4, 3) << 1
+                    | bits(c, 2, 2) << 5,
+                9,
+            );
+            b_type(funct3 & 1, rs1_c, offset)
+        }
+        // C.SLLI
+        (0b10, 0b000) => {
+            if bits(c, 12, 12) != 0 {
+                return None;
+            }
+            i_type(OP_IMM, rd, 0b001, rd, rs2)
+        }
+        // C.LWSP
+        (0b10, 0b010) => {
+            if rd == 0 {
+                return None;
+            }
+            let offset = bits(c, 12, 12) << 5 | bits(c, 6, 4) << 2 | bits(c, 3, 2) << 6;
+            i_type(LOAD, rd, 0b010, 2, offset)
+        }
+        (0b10, 0b100) => match (bits(c, 12, 12), rd, rs2) {
+            // C.JR
+            (0, 0, _) => return None,
+            (0, _, 0) => i_type(JALR, 0, 0b000, rd, 0),
+            // C.MV
+            (0, _, _) => r_type(0, rd, 0b000, 0, rs2),
+            // C.EBREAK
+            (1, 0, 0) => EBREAK,
+            // C.JALR
+            (1, _, 0) => i_type(JALR, 1, 0b000, rd, 0),
+            // C.ADD
+            _ => r_type(0, rd, 0b000, rd, rs2),
+        },
+        // C.SWSP
+        (0b10, 0b110) => {
+            let offset = bits(c, 12, 9) << 2 | bits(c, 8, 7) << 6;
+            s_type(0b010, 2, rs2, offset)
+        }
+        _ => return None,
+    };
+    Some(word)
+}
+
+const LOAD: u32 = 0b000_0011;
+const OP_IMM: u32 = 0b001_0011;
+const STORE: u32 = 0b010_0011;
+const OP: u32 = 0b011_0011;
+const LUI: u32 = 0b011_0111;
+const BRANCH: u32 = 0b110_0011;
+const JALR: u32 = 0b110_0111;
+const JAL: u32 = 0b110_1111;
+const EBREAK: u32 = 0x0010_0073;
+
+/// The bits `hi..=lo` of `word`, shifted down to bit 0.
+const fn bits(word: u32, hi: u32, lo: u32) -> u32 {
+    (word >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// Sign extend the lowest `width` bits of `value`.
+const fn sign_extend(value: u32, width: u32) -> u32 {
+    let shift = 32 - width;
+    (((value << shift) as i32) >> shift) as u32
+}
+
+/// The zero-extended offset of C.LW and C.SW.
+const fn lw_sw_offset(c: u32) -> u32 {
+    bits(c, 12, 10) << 3 | bits(c, 6, 6) << 2 | bits(c, 5, 5) << 6
+}
+
+/// The sign-extended offset of C.J and C.JAL.
+const fn j_offset(c: u32) -> u32 {
+    sign_extend(
+        bits(c, 12, 12) << 11
+            | bits(c, 11, 11) << 4
+            | bits(c, 10, 9) << 8
+            | bits(c, 8, 8) << 10
+            | bits(c, 7, 7) << 6
+            | bits(c, 6, 6) << 7
+            | bits(c, 5, 3) << 1
+            | bits(c, 2, 2) << 5,
+        12,
+    )
+}
+
+const fn r_type(funct7: u32, rd: u32, funct3: u32, rs1: u32, rs2: u32) -> u32 {
+    funct7 << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | OP
+}
+
+const fn i_type(opcode: u32, rd: u32, funct3: u32, rs1: u32, imm: u32) -> u32 {
+    (imm & 0xfff) << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+}
+
+const fn s_type(funct3: u32, rs1: u32, rs2: u32, imm: u32) -> u32 {
+    bits(imm, 11, 5) << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | bits(imm, 4, 0) << 7 | STORE
+}
+
+/// A BEQ (`funct3 = 0`) or BNE (`funct3 = 1`) comparing `rs1` to `x0`.
+const fn b_type(funct3: u32, rs1: u32, imm: u32) -> u32 {
+    bits(imm, 12, 12) << 31
+        | bits(imm, 10, 5) << 25
+        | rs1 << 15
+        | funct3 << 12
+        | bits(imm, 4, 1) << 8
+        | bits(imm, 11, 11) << 7
+        | BRANCH
+}
+
+const fn j_type(rd: u32, imm: u32) -> u32 {
+    bits(imm, 20, 20) << 31
+        | bits(imm, 10, 1) << 21
+        | bits(imm, 11, 11) << 20
+        | bits(imm, 19, 12) << 12
+        | rd << 7
+        | JAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, is_compressed};
+
+    #[test]
+    fn test_expand() {
+        // The encodings of each compressed instruction and of its expansion, as assembled by
+        // `llvm-mc -triple=riscv32 -mattr=+c`.
+        let cases = [
+            (0x0808, 0x01010513), // c.addi4spn a0, sp, 16
+            (0x414c, 0x00452583), // c.lw a1, 4(a0)
+            (0xc60c, 0x00b62423), // c.sw a1, 8(a2)
+            (0x0001, 0x00000013), // c.nop
+            (0x1575, 0xffd50513), // c.addi a0, -3
+            (0x2081, 0x040000ef), // c.jal 64
+            (0x57fd, 0xfff00793), // c.li a5, -1
+            (0x7139, 0xfc010113), // c.addi16sp sp, -64
+            (0x7685, 0xfffe16b7), // c.lui a3, 0xfffe1
+            (0x830d, 0x00375713), // c.srli a4, 3
+            (0x87fd, 0x41f7d793), // c.srai a5, 31
+            (0x9941, 0xff057513), // c.andi a0, -16
+            (0x8d0d, 0x40b50533), // c.sub a0, a1
+            (0x8e35, 0x00d64633), // c.xor a2, a3
+            (0x8f5d, 0x00f76733), // c.or a4, a5
+            (0x8c65, 0x00947433), // c.and s0, s1
+            (0xb001, 0x801ff06f), // c.j -2048
+            (0xd101, 0xf00500e3), // c.beqz a0, -256
+            (0xecfd, 0x0e049f63), // c.bnez s1, 254
+            (0x02c6, 0x01129293), // c.slli t0, 17
+            (0x50fe, 0x0fc12083), // c.lwsp ra, 252(sp)
+            (0x8082, 0x00008067), // c.jr ra
+            (0x851a, 0x00600533), // c.mv a0, t1
+            (0x9002, 0x00100073), // c.ebreak
+            (0x9382, 0x000380e7), // c.jalr t2
+            (0x994e, 0x01390933), // c.add s2, s3
+            (0xc152, 0x09412023), // c.swsp s4, 128(sp)
+        ];
+        for (compressed, expanded) in cases {
+            assert!(is_compressed(compressed as u32));
+            assert!(!is_compressed(expanded));
+            assert_eq!(expand(compressed), Some(expanded), "expanding {compressed:#06x}");
+        }
+    }
+
+    #[test]
+    fn test_expand_illegal() {
+        // The all-zero instruction is defined to be illegal.
+        assert_eq!(expand(0x0000), None);
+        // c.flw
+        assert_eq!(expand(0x6108), None);
+        // c.jr x0 is reserved.
+        assert_eq!(expand(0x8002), None);
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    LookupId, MemoryLocalEvent,
+};
+
+/// The number of rounds in the Blake2b compression function.
+pub const BLAKE2B_NUM_ROUNDS: usize = 12;
+
+/// The Blake2b initialization vector.
+pub const BLAKE2B_IV: [u64; 8] = [
+    0x6A09_E667_F3BC_C908,
+    0xBB67_AE85_84CA_A73B,
+    0x3C6E_F372_FE94_F82B,
+    0xA54F_F53A_5F1D_36F1,
+    0x510E_527F_ADE6_82D1,
+    0x9B05_688C_2B3E_6C1F,
+    0x1F83_D9AB_FB41_BD6B,
+    0x5BE0_CD19_137E_2179,
+];
+
+/// The message schedule of Blake2b. Round `r` uses the permutation `BLAKE2B_SIGMA[r % 10]`.
+pub const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The indices of the working vector words `a`, `b`, `c` and `d` mixed by each of the eight G
+/// function calls of a round. This is shared by Blake2b and BLAKE3.
+pub const BLAKE_G_STATE_INDICES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The number of steps of the finalization of the working vector, done after the last round. Step
+/// `i` accesses the same words as the G function call `i`. This is shared by Blake2b and BLAKE3.
+pub const BLAKE_NUM_FINALIZE_STEPS: usize = 4;
+
+/// Blake2b Compress Event.
+///
+/// This event is emitted when a Blake2b compression is performed. Each of the sixteen words of the
+/// state and of the message is a u64, stored as two u32 words.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Blake2bCompressEvent {
+    /// The lookup identifier.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the state, which holds the chaining value and the counter and final block
+    /// flag, and is used as the working vector.
+    pub state_ptr: u32,
+    /// The pointer to the message.
+    pub message_ptr: u32,
+    /// The memory writes of the low and high halves of `a`, `b`, `c` and `d`, for each G call and
+    /// then for each finalization step.
+    pub state_writes: Vec<[MemoryWriteRecord; 8]>,
+    /// The memory reads of the low and high halves of the two message words, for each G call.
+    pub message_reads: Vec<[MemoryReadRecord; 4]>,
+    /// The local memory accesses.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    LookupId, MemoryLocalEvent,
+};
+
+/// The number of rounds in the BLAKE3 compression function.
+pub const BLAKE3_NUM_ROUNDS: usize = 7;
+
+/// The BLAKE3 initialization vector, the first half of which initializes the working vector.
+pub const BLAKE3_IV: [u32; 8] = [
+    0x6A09_E667,
+    0xBB67_AE85,
+    0x3C6E_F372,
+    0xA54F_F53A,
+    0x510E_527F,
+    0x9B05_688C,
+    0x1F83_D9AB,
+    0x5BE0_CD19,
+];
+
+/// The message schedule of BLAKE3, i.e. the message permutation applied `r` times for round `r`.
+pub const BLAKE3_MSG_SCHEDULE: [[usize; 16]; BLAKE3_NUM_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+/// BLAKE3 Compress Event.
+///
+/// This event is emitted when a BLAKE3 compression is performed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Blake3CompressEvent {
+    /// The lookup identifier.
+    pub lookup_id: LookupId,
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the state, which holds the chaining value, the counter, the block length and
+    /// the flags, and is used as the working vector.
+    pub state_ptr: u32,
+    /// The pointer to the message.
+    pub message_ptr: u32,
+    /// The memory writes of `a`, `b`, `c` and `d`, for each G call and then for each finalization
+    /// step.
+    pub state_writes: Vec<[MemoryWriteRecord; 4]>,
+    /// The memory reads of the two message words, for each G call.
+    pub message_reads: Vec<[MemoryReadRecord; 2]>,
+    /// The local memory accesses.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
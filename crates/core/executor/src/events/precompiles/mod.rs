@@ -1,11 +1,16 @@
+mod blake2b_compress;
+mod blake3_compress;
 mod ec;
 mod edwards;
 mod fptower;
 mod keccak256_permute;
 mod sha256_compress;
 mod sha256_extend;
+mod u256x2048_mul;
 mod uint256;
 
+pub use blake2b_compress::*;
+pub use blake3_compress::*;
 pub use ec::*;
 pub use edwards::*;
 pub use fptower::*;
@@ -30,6 +35,10 @@ pub enum PrecompileEvent {
     ShaCompress(ShaCompressEvent),
     /// Keccak256 permute precompile event.
     KeccakPermute(KeccakPermuteEvent),
+    /// Blake2b compress precompile event.
+    Blake2bCompress(Blake2bCompressEvent),
+    /// Blake3 compress precompile event.
+    Blake3Compress(Blake3CompressEvent),
     /// Edwards curve add precompile event.
     EdAdd(EllipticCurveAddEvent),
     /// Edwards curve decompress precompile event.
@@ -40,6 +49,12 @@ pub enum PrecompileEvent {
     Secp256k1Double(EllipticCurveDoubleEvent),
     /// Secp256k1 curve decompress precompile event.
     Secp256k1Decompress(EllipticCurveDecompressEvent),
+    /// Secp256r1 curve add precompile event.
+    Secp256r1Add(EllipticCurveAddEvent),
+    /// Secp256r1 curve double precompile event.
+    Secp256r1Double(EllipticCurveDoubleEvent),
+    /// Secp256r1 curve decompress precompile event.
+    Secp256r1Decompress(EllipticCurveDecompressEvent),
     /// K256 curve decompress precompile event.
     K256Decompress(EllipticCurveDecompressEvent),
     /// Bn254 curve add precompile event.
@@ -66,6 +81,8 @@ pub enum PrecompileEvent {
     Bls12381Fp2Mul(Fp2MulEvent),
     /// Uint256 mul precompile event.
     Uint256Mul(Uint256MulEvent),
+    /// U256xU2048 mul precompile event.
+    U256xU2048Mul(U256xU2048MulEvent),
 }
 
 /// Trait to retrieve all the local memory events from a vec of precompile events.
@@ -89,21 +106,30 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::KeccakPermute(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Blake2bCompress(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
+                PrecompileEvent::Blake3Compress(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::EdDecompress(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
                 PrecompileEvent::Secp256k1Add(e)
+                | PrecompileEvent::Secp256r1Add(e)
                 | PrecompileEvent::EdAdd(e)
                 | PrecompileEvent::Bn254Add(e)
                 | PrecompileEvent::Bls12381Add(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
                 PrecompileEvent::Secp256k1Double(e)
+                | PrecompileEvent::Secp256r1Double(e)
                 | PrecompileEvent::Bn254Double(e)
                 | PrecompileEvent::Bls12381Double(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
                 PrecompileEvent::Secp256k1Decompress(e)
+                | PrecompileEvent::Secp256r1Decompress(e)
                 | PrecompileEvent::K256Decompress(e)
                 | PrecompileEvent::Bls12381Decompress(e) => {
                     iterators.push(e.local_mem_access.iter());
@@ -111,6 +137,9 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::Uint256Mul(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::U256xU2048Mul(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Bls12381Fp(e) | PrecompileEvent::Bn254Fp(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
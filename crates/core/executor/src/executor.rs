@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 use sp1_stark::SP1CoreOpts;
 use thiserror::Error;
 
+#[cfg(feature = "profiling")]
+use crate::profiler::Profiler;
 use crate::{
+    backtrace::{GuestFrame, Jump, PanicLocation},
     context::SP1Context,
     dependencies::{emit_cpu_dependencies, emit_divrem_dependencies},
     events::{
@@ -20,7 +23,8 @@ use crate::{
     hook::{HookEnv, HookRegistry},
     memory::{Entry, PagedMemory},
     record::{ExecutionRecord, MemoryAccessRecord},
-    report::ExecutionReport,
+    replay::ReplayMode,
+    report::{ExecutionReport, ShardEventCounts},
     state::{ExecutionState, ForkState},
     subproof::{DefaultSubproofVerifier, SubproofVerifier},
     syscalls::{default_syscall_map, Syscall, SyscallCode, SyscallContext},
@@ -99,6 +103,10 @@ pub struct Executor<'a> {
     /// A buffer for writing trace events to a file.
     pub trace_buf: Option<BufWriter<File>>,
 
+    /// The profiler recording the cycles spent in each function, if profiling is enabled.
+    #[cfg(feature = "profiling")]
+    pub profiler: Option<Profiler>,
+
     /// The state of the runtime when in unconstrained mode.
     pub unconstrained_state: ForkState,
 
@@ -113,6 +121,35 @@ pub struct Executor<'a> {
 
     /// The maximal shapes for the program.
     pub maximal_shapes: Option<Vec<HashMap<String, usize>>>,
+
+    /// The event counts of each shard, collected when set to `Some`.
+    ///
+    /// These allow estimating the trace heights of each shard without running in
+    /// [`ExecutorMode::Trace`]. The counts of the i-th shard correspond to the i-th record.
+    pub shard_event_counts: Option<Vec<ShardEventCounts>>,
+
+    /// Whether the interactions of the program are recorded or replayed.
+    pub replay: ReplayMode,
+
+    /// The size of the allocation that failed and the heap high-water mark, reported by the
+    /// program through [`FD_OUT_OF_MEMORY`](crate::syscalls::FD_OUT_OF_MEMORY) when it ran out of
+    /// memory.
+    pub out_of_memory: Option<(u32, u32)>,
+
+    /// The location and message of the panic reported by the program through
+    /// [`FD_PANIC`](crate::syscalls::FD_PANIC) when it panicked.
+    pub panic: Option<(PanicLocation, String)>,
+
+    /// Whether the calls of the program are tracked, to report its backtrace if it panics.
+    ///
+    /// [`Executor::execute_record`] turns this off, as it re-executes checkpoints whose execution
+    /// already reported any panic.
+    pub track_calls: bool,
+
+    /// The error raised by the syscall being executed, which stops the execution.
+    ///
+    /// Syscalls return the value of register a0, so they report errors through this field.
+    pub(crate) syscall_error: Option<ExecutionError>,
 }
 
 /// The different modes the executor can run in.
@@ -160,6 +197,43 @@ pub enum ExecutionError {
     /// The program ended in unconstrained mode.
     #[error("program ended in unconstrained mode")]
     EndInUnconstrained(),
+
+    /// The execution failed because the program panicked.
+    #[error("guest panicked at {location}: {message}")]
+    GuestPanic {
+        /// The panic message.
+        message: String,
+        /// The location in the source of the program where it panicked.
+        location: PanicLocation,
+        /// The backtrace of the program when it halted, from the innermost frame.
+        backtrace: Vec<GuestFrame>,
+    },
+
+    /// The execution failed because the program ran out of heap memory.
+    #[error(
+        "out of memory: failed to allocate {requested} bytes with the heap at {high_water_mark:#x}"
+    )]
+    OutOfMemory {
+        /// The size of the allocation that failed, in bytes.
+        requested: u32,
+        /// The highest address of the heap when the allocation failed.
+        high_water_mark: u32,
+    },
+    /// The execution jumped into the code of the program, but not to the start of an instruction.
+    #[error("jumped to {0:#x}, which is not the start of an instruction")]
+    InvalidPc(u32),
+
+    /// The execution diverged from the replay log it was replaying.
+    #[error("replay diverged: {0}")]
+    ReplayDiverged(String),
+
+    /// The execution failed because a syscall was called with invalid arguments.
+    #[error("invalid arguments {1:#x} and {2:#x} for syscall {0}")]
+    InvalidSyscallArguments(SyscallCode, u32, u32),
+
+    /// The program requested a randomness seed, but the host did not provide one.
+    #[error("the program requested a randomness seed, but none was provided")]
+    MissingRandSeed,
 }
 
 macro_rules! assert_valid_memory_access {
@@ -206,10 +280,14 @@ impl<'a> Executor<'a> {
             context.subproof_verifier.unwrap_or_else(|| Arc::new(DefaultSubproofVerifier::new()));
         let hook_registry = context.hook_registry.unwrap_or_default();
 
+        let mut state = ExecutionState::new(program.pc_start);
+        state.env = context.env;
+        state.rand_seed = context.rand_seed;
+
         Self {
             record,
             records: vec![],
-            state: ExecutionState::new(program.pc_start),
+            state,
             program,
             memory_accesses: MemoryAccessRecord::default(),
             shard_size: (opts.shard_size as u32) * 4,
@@ -217,6 +295,8 @@ impl<'a> Executor<'a> {
             cycle_tracker: HashMap::new(),
             io_buf: HashMap::new(),
             trace_buf,
+            #[cfg(feature = "profiling")]
+            profiler: None,
             unconstrained: false,
             unconstrained_state: ForkState::default(),
             syscall_map,
@@ -232,6 +312,12 @@ impl<'a> Executor<'a> {
             uninitialized_memory_checkpoint: PagedMemory::new_preallocated(),
             local_memory_access: HashMap::new(),
             maximal_shapes: None,
+            shard_event_counts: None,
+            replay: ReplayMode::Disabled,
+            out_of_memory: None,
+            panic: None,
+            track_calls: true,
+            syscall_error: None,
         }
     }
 
@@ -255,6 +341,35 @@ impl<'a> Executor<'a> {
         HookEnv { runtime: self }
     }
 
+    /// The backtrace of the program at the current program counter, from the innermost frame.
+    ///
+    /// The backtrace only contains the calls made while [`Executor::track_calls`] was on, and names
+    /// the functions of each frame if the ELF of the program has symbols.
+    #[must_use]
+    pub fn backtrace(&self) -> Vec<GuestFrame> {
+        self.state.call_stack.backtrace(self.state.pc, &self.program.symbols)
+    }
+
+    /// Track the call or return made by the jump at the current program counter, which writes the
+    /// return address `return_address` to `rd` and jumps to `next_pc` through `rs1` if it is a
+    /// `jalr`.
+    fn track_jump(
+        &mut self,
+        rd: Register,
+        rs1: Option<Register>,
+        return_address: u32,
+        next_pc: u32,
+    ) {
+        if !self.track_calls {
+            return;
+        }
+        match Jump::new(rd as u32, rs1.map(|rs1| rs1 as u32)) {
+            Jump::Call => self.state.call_stack.call(self.state.pc, return_address),
+            Jump::Return => self.state.call_stack.ret(next_pc),
+            Jump::Other => {}
+        }
+    }
+
     /// Recover runtime state from a program and existing execution state.
     #[must_use]
     pub fn recover(program: Program, state: ExecutionState, opts: SP1CoreOpts) -> Self {
@@ -768,6 +883,20 @@ impl<'a> Executor<'a> {
         (a, b, c, addr, memory_value)
     }
 
+    /// Fetch the input operand values for an atomic instruction, whose address must be aligned.
+    fn atomic_rr(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(Register, u32, u32), ExecutionError> {
+        let (rd, rs1, rs2) = instruction.r_type();
+        let c = self.rr(rs2, MemoryAccessPosition::C);
+        let b = self.rr(rs1, MemoryAccessPosition::B);
+        if b % 4 != 0 {
+            return Err(ExecutionError::InvalidMemoryAccess(instruction.opcode, b));
+        }
+        Ok((rd, b, c))
+    }
+
     /// Fetch the input operand values for a branch instruction.
     fn branch_rr(&mut self, instruction: &Instruction) -> (u32, u32, u32) {
         let (rs1, rs2, imm) = instruction.b_type();
@@ -779,9 +908,8 @@ impl<'a> Executor<'a> {
 
     /// Fetch the instruction at the current program counter.
     #[inline]
-    fn fetch(&self) -> Instruction {
-        let idx = ((self.state.pc - self.program.pc_base) / 4) as usize;
-        self.program.instructions[idx]
+    pub(crate) fn fetch(&self) -> Instruction {
+        *self.program.fetch(self.state.pc).unwrap()
     }
 
     /// Execute the given instruction over the current state of the runtime.
@@ -791,7 +919,7 @@ impl<'a> Executor<'a> {
         let mut clk = self.state.clk;
         let mut exit_code = 0u32;
 
-        let mut next_pc = self.state.pc.wrapping_add(4);
+        let mut next_pc = self.state.pc.wrapping_add(instruction.size());
 
         let rd: Register;
         let (a, b, c): (u32, u32, u32);
@@ -836,6 +964,12 @@ impl<'a> Executor<'a> {
                     self.report.event_counts[Opcode::ADD] += 2;
                     self.report.event_counts[Opcode::SLTU] += 1;
                 }
+                Opcode::AMOADD => self.report.event_counts[Opcode::ADD] += 1,
+                Opcode::AMOXOR => self.report.event_counts[Opcode::XOR] += 1,
+                Opcode::AMOAND => self.report.event_counts[Opcode::AND] += 1,
+                Opcode::AMOOR => self.report.event_counts[Opcode::OR] += 1,
+                Opcode::AMOMIN | Opcode::AMOMAX => self.report.event_counts[Opcode::SLT] += 1,
+                Opcode::AMOMINU | Opcode::AMOMAXU => self.report.event_counts[Opcode::SLTU] += 1,
                 _ => {}
             };
         }
@@ -1024,16 +1158,18 @@ impl<'a> Executor<'a> {
             Opcode::JAL => {
                 let (rd, imm) = instruction.j_type();
                 (b, c) = (imm, 0);
-                a = self.state.pc + 4;
+                a = self.state.pc + instruction.size();
                 self.rw(rd, a);
                 next_pc = self.state.pc.wrapping_add(imm);
+                self.track_jump(rd, None, a, next_pc);
             }
             Opcode::JALR => {
                 let (rd, rs1, imm) = instruction.i_type();
                 (b, c) = (self.rr(rs1, MemoryAccessPosition::B), imm);
-                a = self.state.pc + 4;
+                a = self.state.pc + instruction.size();
                 self.rw(rd, a);
                 next_pc = b.wrapping_add(c);
+                self.track_jump(rd, Some(rs1), a, next_pc);
             }
 
             // Upper immediate instructions.
@@ -1077,6 +1213,8 @@ impl<'a> Executor<'a> {
                     SyscallCode::KECCAK_PERMUTE => (self.opts.split_opts.keccak, 24),
                     SyscallCode::SHA_EXTEND => (self.opts.split_opts.sha_extend, 48),
                     SyscallCode::SHA_COMPRESS => (self.opts.split_opts.sha_compress, 80),
+                    SyscallCode::BLAKE2B_COMPRESS => (self.opts.split_opts.blake2b_compress, 100),
+                    SyscallCode::BLAKE3_COMPRESS => (self.opts.split_opts.blake3_compress, 60),
                     _ => (self.opts.split_opts.deferred, 1),
                 };
                 let nonce = (((*syscall_count as usize) % threshold) * multiplier) as u32;
@@ -1095,6 +1233,9 @@ impl<'a> Executor<'a> {
                         // register. If it returns None, we just keep the
                         // syscall_id in t0.
                         let res = syscall_impl.execute(&mut precompile_rt, syscall, b, c);
+                        if let Some(error) = precompile_rt.rt.syscall_error.take() {
+                            return Err(error);
+                        }
                         if let Some(val) = res {
                             a = val;
                         } else {
@@ -1103,6 +1244,21 @@ impl<'a> Executor<'a> {
 
                         // If the syscall is `HALT` and the exit code is non-zero, return an error.
                         if syscall == SyscallCode::HALT && precompile_rt.exit_code != 0 {
+                            if let Some((requested, high_water_mark)) =
+                                precompile_rt.rt.out_of_memory
+                            {
+                                return Err(ExecutionError::OutOfMemory {
+                                    requested,
+                                    high_water_mark,
+                                });
+                            }
+                            if let Some((location, message)) = precompile_rt.rt.panic.take() {
+                                return Err(ExecutionError::GuestPanic {
+                                    message,
+                                    location,
+                                    backtrace: precompile_rt.rt.backtrace(),
+                                });
+                            }
                             return Err(ExecutionError::HaltWithNonZeroExitCode(
                                 precompile_rt.exit_code,
                             ));
@@ -1188,6 +1344,50 @@ impl<'a> Executor<'a> {
                 self.alu_rw(instruction, rd, a, b, c, lookup_id);
             }
 
+            // Atomic instructions.
+            Opcode::LR => {
+                (rd, b, c) = self.atomic_rr(instruction)?;
+                a = self.mr_cpu(b, MemoryAccessPosition::Memory);
+                memory_store_value = Some(a);
+                self.rw(rd, a);
+            }
+            Opcode::SC => {
+                // With a single hart, nothing can invalidate the reservation made by `LR`, so the
+                // store conditional always succeeds.
+                (rd, b, c) = self.atomic_rr(instruction)?;
+                a = 0;
+                memory_store_value = Some(c);
+                self.mw_cpu(b, c, MemoryAccessPosition::Memory);
+                self.rw(rd, a);
+            }
+            Opcode::AMOSWAP
+            | Opcode::AMOADD
+            | Opcode::AMOXOR
+            | Opcode::AMOAND
+            | Opcode::AMOOR
+            | Opcode::AMOMIN
+            | Opcode::AMOMAX
+            | Opcode::AMOMINU
+            | Opcode::AMOMAXU => {
+                (rd, b, c) = self.atomic_rr(instruction)?;
+                a = self.word(b);
+                let value = match instruction.opcode {
+                    Opcode::AMOSWAP => c,
+                    Opcode::AMOADD => a.wrapping_add(c),
+                    Opcode::AMOXOR => a ^ c,
+                    Opcode::AMOAND => a & c,
+                    Opcode::AMOOR => a | c,
+                    Opcode::AMOMIN => (a as i32).min(c as i32) as u32,
+                    Opcode::AMOMAX => (a as i32).max(c as i32) as u32,
+                    Opcode::AMOMINU => a.min(c),
+                    Opcode::AMOMAXU => a.max(c),
+                    _ => unreachable!(),
+                };
+                memory_store_value = Some(value);
+                self.mw_cpu(b, value, MemoryAccessPosition::Memory);
+                self.rw(rd, a);
+            }
+
             // See https://github.com/riscv-non-isa/riscv-asm-manual/blob/master/riscv-asm.md#instruction-aliases
             Opcode::UNIMP => {
                 return Err(ExecutionError::Unimplemented());
@@ -1224,7 +1424,7 @@ impl<'a> Executor<'a> {
     /// Executes one cycle of the program, returning whether the program has finished.
     #[inline]
     #[allow(clippy::too_many_lines)]
-    fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
+    pub(crate) fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
         // Fetch the instruction at the current program counter.
         let instruction = self.fetch();
 
@@ -1232,9 +1432,28 @@ impl<'a> Executor<'a> {
         #[cfg(debug_assertions)]
         self.log(&instruction);
 
+        // Peek at the syscall being invoked, before the instruction overwrites `t0`.
+        #[cfg(feature = "profiling")]
+        let (pc, syscall) = (
+            self.state.pc,
+            (self.profiler.is_some() && instruction.opcode == Opcode::ECALL).then(|| {
+                let t0 = self.state.memory.get(Register::X5 as u32);
+                SyscallCode::from_u32(t0.map_or(0, |record| record.value))
+            }),
+        );
+
         // Execute the instruction.
         self.execute_instruction(&instruction)?;
 
+        // Charge the instruction, and any extra cycles of its syscall, to the profile.
+        #[cfg(feature = "profiling")]
+        if let (Some(profiler), false) = (&mut self.profiler, self.unconstrained) {
+            let extra_cycles = syscall
+                .and_then(|syscall| self.syscall_map.get(&syscall))
+                .map_or(0, |syscall| syscall.num_extra_cycles());
+            profiler.record(pc, &instruction, self.state.pc, 1 + u64::from(extra_cycles), syscall);
+        }
+
         // Increment the clock.
         self.state.global_clk += 1;
 
@@ -1364,6 +1583,7 @@ impl<'a> Executor<'a> {
             if cpu_exit || !shape_match_found {
                 self.state.current_shard += 1;
                 self.state.clk = 0;
+                self.record_shard_event_counts();
                 self.report.event_counts = Box::default();
                 self.bump_record();
             }
@@ -1376,9 +1596,10 @@ impl<'a> Executor<'a> {
             }
         }
 
-        let done = self.state.pc == 0
-            || self.state.pc.wrapping_sub(self.program.pc_base)
-                >= (self.program.instructions.len() * 4) as u32;
+        let done = self.state.pc == 0 || !self.program.contains_pc(self.state.pc);
+        if !done && self.program.instruction_index(self.state.pc).is_none() {
+            return Err(ExecutionError::InvalidPc(self.state.pc));
+        }
         if done && self.unconstrained {
             log::error!("program ended in unconstrained mode at clk {}", self.state.global_clk);
             return Err(ExecutionError::EndInUnconstrained());
@@ -1386,6 +1607,19 @@ impl<'a> Executor<'a> {
         Ok(done)
     }
 
+    /// Record the event counts of the current shard, if requested.
+    ///
+    /// This must be called before the counts are reset and the record is bumped, so that the
+    /// counts of the i-th shard correspond to the i-th record.
+    fn record_shard_event_counts(&mut self) {
+        if let Some(shard_event_counts) = &mut self.shard_event_counts {
+            shard_event_counts.push(ShardEventCounts {
+                global_clk: self.state.global_clk,
+                event_counts: self.report.event_counts.clone(),
+            });
+        }
+    }
+
     /// Bump the record.
     pub fn bump_record(&mut self) {
         // Copy all of the existing local memory accesses to the record's local_memory_access vec.
@@ -1409,6 +1643,7 @@ impl<'a> Executor<'a> {
     pub fn execute_record(&mut self) -> Result<(Vec<ExecutionRecord>, bool), ExecutionError> {
         self.executor_mode = ExecutorMode::Trace;
         self.print_report = true;
+        self.track_calls = false;
         let done = self.execute()?;
         Ok((std::mem::take(&mut self.records), done))
     }
@@ -1470,7 +1705,7 @@ impl<'a> Executor<'a> {
         Ok((checkpoint, done))
     }
 
-    fn initialize(&mut self) {
+    pub(crate) fn initialize(&mut self) {
         self.state.clk = 0;
 
         tracing::debug!("loading memory image");
@@ -1544,11 +1779,13 @@ impl<'a> Executor<'a> {
             self.postprocess();
 
             // Push the remaining execution record with memory initialize & finalize events.
+            self.record_shard_event_counts();
             self.bump_record();
         }
 
         // Push the remaining execution record, if there are any CPU events.
         if !self.record.cpu_events.is_empty() {
+            self.record_shard_event_counts();
             self.bump_record();
         }
 
@@ -1577,7 +1814,7 @@ impl<'a> Executor<'a> {
         Ok(done)
     }
 
-    fn postprocess(&mut self) {
+    pub(crate) fn postprocess(&mut self) {
         // Flush remaining stdout/stderr
         for (fd, buf) in &self.io_buf {
             if !buf.is_empty() {
@@ -1696,16 +1933,30 @@ fn log2_ceil_usize(n: usize) -> usize {
 #[cfg(test)]
 mod tests {
 
+    use num::BigUint;
+    use sha2::{Digest, Sha256};
+    use sp1_curves::{
+        weierstrass::{
+            secp256r1::{Secp256r1, Secp256r1Parameters},
+            WeierstrassParameters,
+        },
+        AffinePoint,
+    };
     use sp1_stark::SP1CoreOpts;
 
     use crate::programs::tests::{
-        fibonacci_program, panic_program, simple_memory_program, simple_program,
-        ssz_withdrawals_program,
+        atomic_program, blake2b_compress_program, blake3_compress_program, compressed_program,
+        fibonacci_program, fs_open_program, fs_read_program, getenv_program, guest_panic_program,
+        out_of_memory_program, panic_program, rand_seed_program, secp256r1_program,
+        simple_memory_program, simple_program, ssz_withdrawals_program, u256x2048_mul_program,
     };
 
-    use crate::Register;
+    use crate::{
+        syscalls::{encode_environment, SyscallCode},
+        OpenFile, Register, SP1Context,
+    };
 
-    use super::{Executor, Instruction, Opcode, Program};
+    use super::{ExecutionError, Executor, Instruction, Opcode, Program};
 
     fn _assert_send<T: Send>() {}
 
@@ -1722,6 +1973,145 @@ mod tests {
         assert_eq!(runtime.register(Register::X31), 42);
     }
 
+    #[test]
+    fn test_compressed_program_run() {
+        let program = compressed_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X31), 42);
+        assert_eq!(runtime.register(Register::X1), 10);
+        assert_eq!(runtime.register(Register::X28), 10);
+        assert_eq!(runtime.state.global_clk, 5);
+    }
+
+    #[test]
+    fn test_jump_into_instruction() {
+        // Jump from 8 into the middle of the 32-bit instruction at 2.
+        let mut program = compressed_program();
+        program.instructions[3] = Instruction::new(Opcode::JAL, 1, -4i32 as u32, 0, true, true);
+        program.instructions[3].compressed = true;
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        assert!(matches!(runtime.run(), Err(ExecutionError::InvalidPc(4))));
+    }
+
+    #[test]
+    fn test_atomic_program_run() {
+        let program = atomic_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X12), 5);
+        assert_eq!(runtime.register(Register::X14), 0);
+        assert_eq!(runtime.register(Register::X15), 7);
+        assert_eq!(runtime.register(Register::X17), 12);
+        assert_eq!(runtime.register(Register::X18), 0xffff_fff1);
+        assert_eq!(runtime.register(Register::X19), 1);
+        assert_eq!(runtime.register(Register::X20), 7);
+        assert_eq!(runtime.register(Register::X21), -3i32 as u32);
+        assert_eq!(runtime.register(Register::X22), 5);
+        assert_eq!(runtime.register(Register::X23), 5);
+        assert_eq!(runtime.register(Register::X24), -3i32 as u32);
+        assert_eq!(runtime.register(Register::X0), 0);
+        assert_eq!(runtime.register(Register::X25), 10);
+    }
+
+    #[test]
+    fn test_secp256r1_program_run() {
+        let program = secp256r1_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let (x, y) = Secp256r1Parameters::generator();
+        let generator = AffinePoint::<Secp256r1>::new(x, y);
+        let expected = generator.sw_double().sw_add(&generator);
+        let words = |addr: u32, runtime: &mut Executor| {
+            (0..8).map(|i| runtime.word(addr + 4 * i)).collect::<Vec<_>>()
+        };
+        let mut expected_x = expected.x.to_u32_digits();
+        expected_x.resize(8, 0);
+        let mut expected_y = expected.y.to_u32_digits();
+        expected_y.resize(8, 0);
+        assert_eq!(words(0x1000, &mut runtime), expected_x);
+        assert_eq!(words(0x1020, &mut runtime), expected_y);
+
+        let mut generator_y = generator.y.to_u32_digits();
+        generator_y.resize(8, 0);
+        assert_eq!(words(0x1080, &mut runtime), generator_y);
+    }
+
+    #[test]
+    fn test_u256x2048_mul_program_run() {
+        let program = u256x2048_mul_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let uint = |addr: u32, len: u32, runtime: &mut Executor| {
+            let words = (0..len).map(|i| runtime.word(addr + 4 * i)).collect::<Vec<_>>();
+            BigUint::from_slice(&words)
+        };
+        let a = uint(0x1000, 8, &mut runtime);
+        let b = uint(0x1100, 64, &mut runtime);
+        let lo = uint(0x1200, 64, &mut runtime);
+        let hi = uint(0x1300, 8, &mut runtime);
+        assert_eq!(a * b, lo + (hi << 2048));
+    }
+
+    #[test]
+    fn test_blake3_compress_program_run() {
+        let program = blake3_compress_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        // The BLAKE3 hash of `b"abc"` is the first half of the output.
+        let hash =
+            (0..8).flat_map(|i| runtime.word(0x1000 + 4 * i).to_le_bytes()).collect::<Vec<_>>();
+        assert_eq!(
+            hex::encode(hash),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
+
+    #[test]
+    fn test_blake2b_compress_program_run() {
+        let program = blake2b_compress_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        // The BLAKE2b hash of `b"abc"` is the finalized chaining value.
+        let hash =
+            (0..16).flat_map(|i| runtime.word(0x1000 + 4 * i).to_le_bytes()).collect::<Vec<_>>();
+        assert_eq!(
+            hex::encode(hash),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+    }
+
+    #[test]
+    fn test_blake3_compress_overlapping_arguments() {
+        let mut program = blake3_compress_program();
+        program.instructions[2] = Instruction::new(Opcode::ADD, 11, 0, 0x1020, false, true);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::InvalidSyscallArguments(
+                SyscallCode::BLAKE3_COMPRESS,
+                0x1000,
+                0x1020
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_blake2b_compress_wrapping_arguments() {
+        let mut program = blake2b_compress_program();
+        program.instructions[1] = Instruction::new(Opcode::ADD, 10, 0, 0xFFFF_FFC0, false, true);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::InvalidSyscallArguments(SyscallCode::BLAKE2B_COMPRESS, _, _))
+        ));
+    }
+
     #[test]
     fn test_fibonacci_program_run() {
         let program = fibonacci_program();
@@ -1744,6 +2134,134 @@ mod tests {
         runtime.run().unwrap();
     }
 
+    #[test]
+    fn test_guest_panic() {
+        let program = guest_panic_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        let Err(ExecutionError::GuestPanic { message, location, backtrace }) = runtime.run() else {
+            panic!("expected a guest panic");
+        };
+        assert_eq!(message, "boom");
+        assert_eq!(location.to_string(), "main.rs:7:5");
+        let frames = backtrace.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(frames, ["0x00000024 in panic", "0x00000000 in main"]);
+    }
+
+    #[test]
+    fn test_guest_panic_after_recover() {
+        let program = guest_panic_program();
+        let mut runtime = Executor::new(program.clone(), SP1CoreOpts::default());
+        runtime.initialize();
+        // Execute the call to `panic` before recovering the executor.
+        runtime.execute_cycle().unwrap();
+        let mut runtime = Executor::recover(program, runtime.state.clone(), SP1CoreOpts::default());
+        let Err(ExecutionError::GuestPanic { backtrace, .. }) = runtime.run() else {
+            panic!("expected a guest panic");
+        };
+        let frames = backtrace.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(frames, ["0x00000024 in panic", "0x00000000 in main"]);
+    }
+
+    #[test]
+    fn test_guest_panic_without_tracking_calls() {
+        let program = guest_panic_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.track_calls = false;
+        let Err(ExecutionError::GuestPanic { backtrace, .. }) = runtime.run() else {
+            panic!("expected a guest panic");
+        };
+        let frames = backtrace.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(frames, ["0x00000024 in panic"]);
+    }
+
+    #[test]
+    fn test_guest_panic_not_shadowed_by_hook() {
+        let program = guest_panic_program();
+        let context = SP1Context::builder().hook(crate::syscalls::FD_PANIC, |_, _| vec![]).build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        let Err(ExecutionError::GuestPanic { message, .. }) = runtime.run() else {
+            panic!("expected a guest panic");
+        };
+        assert_eq!(message, "boom");
+    }
+
+    #[test]
+    fn test_out_of_memory() {
+        let program = out_of_memory_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::OutOfMemory { requested: 0x100, high_water_mark: 0x7800_0000 })
+        ));
+    }
+
+    #[test]
+    fn test_getenv() {
+        let program = getenv_program();
+        let context = SP1Context::builder().env("CHAIN", "mainnet").env("DEBUG", "1").build();
+        let encoded = encode_environment(&context.env);
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X31), encoded.len() as u32);
+        assert_eq!(runtime.state.input_stream, vec![encoded]);
+    }
+
+    #[test]
+    fn test_rand_seed() {
+        let program = rand_seed_program();
+        let context = SP1Context::builder().rand_seed([7; 32]).build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X31), 32);
+        assert_eq!(runtime.state.input_stream, vec![vec![7; 32]]);
+    }
+
+    #[test]
+    fn test_rand_seed_missing() {
+        let program = rand_seed_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        assert!(matches!(runtime.run(), Err(ExecutionError::MissingRandSeed)));
+    }
+
+    #[test]
+    fn test_fs_open() {
+        let mut runtime = Executor::new(fs_open_program(), SP1CoreOpts::default());
+        runtime.write_files(&[("cfg".to_string(), b"abc".to_vec())].into());
+        runtime.run().unwrap();
+        let mut response = vec![1];
+        response.extend_from_slice(&3u32.to_le_bytes());
+        response.extend_from_slice(&3u64.to_le_bytes());
+        response.extend_from_slice(&Sha256::digest(b"abc"));
+        assert_eq!(runtime.register(Register::X31), response.len() as u32);
+        assert_eq!(runtime.state.input_stream, vec![response]);
+        assert_eq!(
+            runtime.state.open_files,
+            [(3, OpenFile { path: "cfg".to_string(), position: 0 })].into()
+        );
+
+        let mut runtime = Executor::new(fs_open_program(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X31), 1);
+        assert_eq!(runtime.state.input_stream, vec![vec![0]]);
+        assert!(runtime.state.open_files.is_empty());
+    }
+
+    #[test]
+    fn test_fs_read() {
+        let mut runtime = Executor::new(fs_read_program(), SP1CoreOpts::default());
+        runtime.write_files(&[("cfg".to_string(), b"abc".to_vec())].into());
+        runtime.run().unwrap();
+        assert_eq!(runtime.state.input_stream[0], b"bc");
+        assert!(runtime.state.open_files.is_empty());
+
+        // Without the file, the program seeks a file descriptor which is not open.
+        let mut runtime = Executor::new(fs_read_program(), SP1CoreOpts::default());
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::InvalidSyscallArguments(SyscallCode::FS_SEEK, 3, 1))
+        ));
+    }
+
     #[test]
     fn test_add() {
         // main:
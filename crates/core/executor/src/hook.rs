@@ -3,6 +3,7 @@ use core::fmt::Debug;
 use std::sync::{Arc, RwLock, RwLockWriteGuard};
 
 use hashbrown::HashMap;
+use num::{BigUint, Integer, Zero};
 use sp1_curves::k256::{Invert, RecoveryId, Signature, VerifyingKey};
 
 use crate::Executor;
@@ -10,16 +11,9 @@ use crate::Executor;
 /// A runtime hook, wrapped in a smart pointer.
 pub type BoxedHook<'a> = Arc<RwLock<dyn Hook + Send + Sync + 'a>>;
 
-/// The file descriptor through which to access `hook_ecrecover`.
-pub const FD_ECRECOVER_HOOK: u32 = 5;
-
-// Note: we skip 6 because we have an eddsa hook in dev.
-
-/// The file descriptor through which to access `hook_ecrecover_2`.
-pub const FD_ECRECOVER_HOOK_2: u32 = 7;
-
-/// The file descriptor through which to access `hook_ed_decompress`.
-pub const FD_EDDECOMPRESS: u32 = 8;
+pub use sp1_primitives::consts::fd::{
+    FD_BIGINT_DIVREM, FD_ECRECOVER_HOOK, FD_ECRECOVER_HOOK_2, FD_EDDECOMPRESS,
+};
 
 /// A runtime hook. May be called during execution by writing to a specified file descriptor,
 /// accepting and returning arbitrary data.
@@ -86,6 +80,7 @@ impl<'a> Default for HookRegistry<'a> {
             (FD_ECRECOVER_HOOK, hookify(hook_ecrecover)),
             (FD_ECRECOVER_HOOK_2, hookify(hook_ecrecover_v2)),
             (FD_EDDECOMPRESS, hookify(hook_ed_decompress)),
+            (FD_BIGINT_DIVREM, hookify(hook_bigint_divrem)),
         ]);
 
         Self { table }
@@ -224,14 +219,62 @@ pub fn hook_ed_decompress(_: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
     }
 }
 
+/// Divides a wide integer by a modulus using the num crate.
+///
+/// # Arguments
+/// * `env` - The environment in which the hook is invoked.
+/// * `buf` - The buffer containing the dividend and the modulus.
+///    - The dividend and the modulus are little-endian.
+///    - The dividend is twice as long as the modulus, which is non-zero.
+///
+/// The result is the quotient, padded to the length of the dividend, and the remainder, padded to
+/// the length of the modulus.
+///
+/// WARNING: This function merely hints at the quotient and remainder. These values must be
+/// constrained by the zkVM for correctness.
+#[must_use]
+pub fn hook_bigint_divrem(_: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+    assert_eq!(buf.len() % 3, 0, "bigint divrem input should have length 2n + n, this is a bug.");
+    let (dividend, modulus) = buf.split_at(buf.len() / 3 * 2);
+    let (dividend_len, modulus_len) = (dividend.len(), modulus.len());
+
+    let modulus = BigUint::from_bytes_le(modulus);
+    assert!(!modulus.is_zero(), "bigint divrem modulus should be non-zero, this is a bug.");
+    let (quotient, remainder) = BigUint::from_bytes_le(dividend).div_rem(&modulus);
+
+    let mut quotient = quotient.to_bytes_le();
+    quotient.resize(dividend_len, 0);
+    let mut remainder = remainder.to_bytes_le();
+    remainder.resize(modulus_len, 0);
+
+    vec![quotient, remainder]
+}
+
 #[cfg(test)]
 pub mod tests {
+    use sp1_stark::SP1CoreOpts;
+
     use super::*;
+    use crate::programs::tests::simple_program;
 
     #[test]
     pub fn hook_fds_match() {
         use sp1_zkvm::lib::io;
         assert_eq!(FD_ECRECOVER_HOOK, io::FD_ECRECOVER_HOOK);
+        assert_eq!(FD_BIGINT_DIVREM, io::FD_BIGINT_DIVREM);
+    }
+
+    #[test]
+    pub fn hook_bigint_divrem_pads_results() {
+        let runtime = Executor::new(simple_program(), SP1CoreOpts::default());
+        let env = HookEnv { runtime: &runtime };
+
+        // 1000 divided by 7, with a dividend of four bytes and a modulus of two bytes.
+        let mut buf = 1000u32.to_le_bytes().to_vec();
+        buf.extend_from_slice(&7u16.to_le_bytes());
+        let result = hook_bigint_divrem(env, &buf);
+
+        assert_eq!(result, vec![142u32.to_le_bytes().to_vec(), 6u16.to_le_bytes().to_vec()]);
     }
 
     #[test]
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::opcode::Opcode;
 
-/// RISC-V 32IM Instruction.
+/// RISC-V 32IMAC Instruction.
 ///
 /// The structure of the instruction differs from the RISC-V ISA. We do not encode the instructions
 /// as 32-bit words, but instead use a custom encoding that is more friendly to decode in the
@@ -24,6 +24,9 @@ pub struct Instruction {
     pub imm_b: bool,
     /// Whether the third operand is an immediate value.
     pub imm_c: bool,
+    /// Whether the instruction was expanded from a 16-bit compressed instruction, in which case
+    /// the next instruction is at `pc + 2` instead of `pc + 4`.
+    pub compressed: bool,
 }
 
 impl Instruction {
@@ -37,7 +40,17 @@ impl Instruction {
         imm_b: bool,
         imm_c: bool,
     ) -> Self {
-        Self { opcode, op_a, op_b, op_c, imm_b, imm_c }
+        Self { opcode, op_a, op_b, op_c, imm_b, imm_c, compressed: false }
+    }
+
+    /// The size of the encoded instruction in bytes, which is the offset of the next instruction.
+    #[must_use]
+    pub const fn size(&self) -> u32 {
+        if self.compressed {
+            2
+        } else {
+            4
+        }
     }
 
     /// Returns if the instruction is an ALU instruction.
@@ -88,6 +101,25 @@ impl Instruction {
         )
     }
 
+    /// Returns if the instruction is an atomic memory instruction.
+    #[must_use]
+    pub const fn is_atomic_instruction(&self) -> bool {
+        matches!(
+            self.opcode,
+            Opcode::LR
+                | Opcode::SC
+                | Opcode::AMOSWAP
+                | Opcode::AMOADD
+                | Opcode::AMOXOR
+                | Opcode::AMOAND
+                | Opcode::AMOOR
+                | Opcode::AMOMIN
+                | Opcode::AMOMAX
+                | Opcode::AMOMINU
+                | Opcode::AMOMAXU
+        )
+    }
+
     /// Returns if the instruction is a branch instruction.
     #[must_use]
     pub const fn is_branch_instruction(&self) -> bool {
@@ -1,4 +1,4 @@
-use std::io::Read;
+use std::{collections::BTreeMap, io::Read};
 
 use serde::{de::DeserializeOwned, Serialize};
 use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, StarkVerifyingKey};
@@ -33,6 +33,13 @@ impl<'a> Executor<'a> {
         }
     }
 
+    /// Add files to the read-only file system of the program.
+    pub fn write_files(&mut self, files: &BTreeMap<String, Vec<u8>>) {
+        self.state
+            .files
+            .extend(files.iter().map(|(path, contents)| (path.clone(), contents.clone())));
+    }
+
     /// Write a proof and verifying key to the proof stream.
     pub fn write_proof(
         &mut self,
@@ -19,7 +19,9 @@
 #![allow(clippy::explicit_iter_loop)]
 #![warn(missing_docs)]
 
+mod backtrace;
 mod context;
+pub mod debugger;
 mod dependencies;
 mod disassembler;
 pub mod events;
@@ -29,29 +31,39 @@ mod instruction;
 mod io;
 mod memory;
 mod opcode;
+#[cfg(feature = "profiling")]
+pub mod profiler;
 mod program;
 #[cfg(any(test, feature = "programs"))]
 pub mod programs;
+mod progress;
 mod record;
 mod reduce;
 mod register;
+mod replay;
 mod report;
 mod shape;
+mod snapshot;
 mod state;
 pub mod subproof;
 pub mod syscalls;
 mod utils;
 
+pub use backtrace::{GuestFrame, PanicLocation};
 pub use context::*;
+pub use disassembler::{ElfSymbol, SymbolTable};
 pub use executor::*;
 pub use hook::*;
 pub use instruction::*;
 pub use opcode::*;
 pub use program::*;
+pub use progress::*;
 pub use record::*;
 pub use reduce::*;
 pub use register::*;
+pub use replay::*;
 pub use report::*;
 pub use shape::*;
+pub use snapshot::*;
 pub use state::*;
 pub use utils::*;
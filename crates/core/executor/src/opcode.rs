@@ -101,6 +101,28 @@ pub enum Opcode {
     REMU = 37,
     /// Unimplemented instruction.
     UNIMP = 39,
+    /// rd ← m32(rs1), reserve m32(rs1), pc ← pc + 4
+    LR = 40,
+    /// m32(rs1) ← rs2, rd ← 0, pc ← pc + 4
+    SC = 41,
+    /// rd ← m32(rs1), m32(rs1) ← rs2, pc ← pc + 4
+    AMOSWAP = 42,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) + rs2, pc ← pc + 4
+    AMOADD = 43,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) ^ rs2, pc ← pc + 4
+    AMOXOR = 44,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) & rs2, pc ← pc + 4
+    AMOAND = 45,
+    /// rd ← m32(rs1), m32(rs1) ← m32(rs1) | rs2, pc ← pc + 4
+    AMOOR = 46,
+    /// rd ← m32(rs1), m32(rs1) ← min(m32(rs1), rs2) (signed), pc ← pc + 4
+    AMOMIN = 47,
+    /// rd ← m32(rs1), m32(rs1) ← max(m32(rs1), rs2) (signed), pc ← pc + 4
+    AMOMAX = 48,
+    /// rd ← m32(rs1), m32(rs1) ← min(m32(rs1), rs2) (unsigned), pc ← pc + 4
+    AMOMINU = 49,
+    /// rd ← m32(rs1), m32(rs1) ← max(m32(rs1), rs2) (unsigned), pc ← pc + 4
+    AMOMAXU = 50,
 }
 
 /// Byte Opcode.
@@ -174,6 +196,17 @@ impl Opcode {
             Opcode::REM => "rem",
             Opcode::REMU => "remu",
             Opcode::UNIMP => "unimp",
+            Opcode::LR => "lr.w",
+            Opcode::SC => "sc.w",
+            Opcode::AMOSWAP => "amoswap.w",
+            Opcode::AMOADD => "amoadd.w",
+            Opcode::AMOXOR => "amoxor.w",
+            Opcode::AMOAND => "amoand.w",
+            Opcode::AMOOR => "amoor.w",
+            Opcode::AMOMIN => "amomin.w",
+            Opcode::AMOMAX => "amomax.w",
+            Opcode::AMOMINU => "amominu.w",
+            Opcode::AMOMAXU => "amomaxu.w",
         }
     }
 
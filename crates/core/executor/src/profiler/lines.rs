@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use elf::{endian::LittleEndian, ElfBytes};
+use gimli::EndianArcSlice;
+
+/// A location in the source code of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLocation {
+    /// The path of the source file.
+    pub file: String,
+    /// The line number within the file.
+    pub line: u32,
+}
+
+/// A mapping from program counters to source lines, loaded from the DWARF debug info of an ELF.
+pub struct LineTable {
+    context: addr2line::Context<EndianArcSlice<gimli::LittleEndian>>,
+}
+
+impl LineTable {
+    /// Load the line table of a RISC-V ELF.
+    ///
+    /// An ELF without debug info produces an empty table.
+    ///
+    /// # Errors
+    ///
+    /// This function may return an error if the ELF or its debug info is not valid.
+    pub fn from_elf(input: &[u8]) -> eyre::Result<Self> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(input)?;
+        let dwarf = gimli::Dwarf::load(|id| -> Result<_, gimli::Error> {
+            let data = match elf.section_header_by_name(id.name()) {
+                Ok(Some(header)) => elf.section_data(&header).map_or(&[][..], |(data, _)| data),
+                _ => &[],
+            };
+            Ok(EndianArcSlice::new(Arc::from(data), gimli::LittleEndian))
+        })?;
+        let context = addr2line::Context::from_dwarf(dwarf)?;
+        Ok(Self { context })
+    }
+
+    /// Find the source line which generated the instruction at `pc`.
+    #[must_use]
+    pub fn location(&self, pc: u32) -> Option<SourceLocation> {
+        let location = self.context.find_location(u64::from(pc)).ok()??;
+        Some(SourceLocation { file: location.file?.to_string(), line: location.line? })
+    }
+}
+
+impl std::fmt::Debug for LineTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LineTable").finish_non_exhaustive()
+    }
+}
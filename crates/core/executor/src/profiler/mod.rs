@@ -0,0 +1,459 @@
+//! A cycle profiler for programs running inside the [`crate::Executor`].
+//!
+//! The profiler reconstructs the call stack of the program from the jumps it executes, using the
+//! function symbols of the ELF to name each frame. Every executed instruction is charged one
+//! cycle, plus the extra cycles of the syscall it invokes, to the stack it was executed in. The
+//! collected profile can be summarized per function, per syscall and per source line, or exported
+//! for use with external viewers (see [`Profiler::write_folded`], [`Profiler::write_speedscope`]
+//! and [`Profiler::write_gecko`]).
+
+mod lines;
+mod output;
+
+pub use lines::*;
+
+use hashbrown::HashMap;
+
+use crate::{
+    backtrace::{CallStack, Jump},
+    syscalls::SyscallCode,
+    Instruction, Opcode, Register, SymbolTable,
+};
+
+/// The index of the root node of the call tree.
+const ROOT: usize = 0;
+
+/// A node of the call tree, representing a unique call stack.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    /// The node of the calling stack.
+    parent: usize,
+    /// The function executing at the top of the stack.
+    function: usize,
+    /// The cycles spent in the function itself while at this stack.
+    self_cycles: u64,
+}
+
+/// The cycles spent in a single function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCycles {
+    /// The name of the function.
+    pub name: String,
+    /// The cycles spent in the function itself.
+    pub self_cycles: u64,
+    /// The cycles spent in the function and everything it called.
+    pub total_cycles: u64,
+}
+
+/// The cycles spent in a syscall invoked from a single function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyscallCycles {
+    /// The name of the function invoking the syscall.
+    pub function: String,
+    /// The syscall invoked.
+    pub syscall: SyscallCode,
+    /// The number of times the syscall was invoked.
+    pub count: u64,
+    /// The cycles spent in the invocations.
+    pub cycles: u64,
+}
+
+/// The cycles spent on a single source line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineCycles {
+    /// The source file, or `??` if it is unknown.
+    pub file: String,
+    /// The line number, or `0` if it is unknown.
+    pub line: u32,
+    /// The cycles spent executing instructions generated from the line.
+    pub cycles: u64,
+}
+
+/// A cycle profiler for the [`crate::Executor`].
+///
+/// Attach a profiler to an executor by setting [`crate::Executor::profiler`] before running it.
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    symbols: SymbolTable,
+    nodes: Vec<Node>,
+    children: HashMap<(usize, usize), usize>,
+    stack: CallStack<usize>,
+    pc_cycles: HashMap<u32, u64>,
+    syscalls: HashMap<(usize, SyscallCode), (u64, u64)>,
+    total_cycles: u64,
+    total_instructions: u64,
+}
+
+impl Profiler {
+    /// Create a profiler which names stack frames after the given function symbols.
+    #[must_use]
+    pub fn new(symbols: SymbolTable) -> Self {
+        Self {
+            symbols,
+            nodes: vec![Node { parent: ROOT, function: usize::MAX, self_cycles: 0 }],
+            children: HashMap::new(),
+            stack: CallStack::default(),
+            pc_cycles: HashMap::new(),
+            syscalls: HashMap::new(),
+            total_cycles: 0,
+            total_instructions: 0,
+        }
+    }
+
+    /// Create a profiler for a RISC-V ELF, using its symbol table.
+    ///
+    /// # Errors
+    ///
+    /// This function may return an error if the ELF is not valid.
+    pub fn from_elf(input: &[u8]) -> eyre::Result<Self> {
+        Ok(Self::new(SymbolTable::from_elf(input)?))
+    }
+
+    /// The total number of cycles recorded.
+    #[must_use]
+    pub fn total_cycles(&self) -> u64 {
+        self.total_cycles
+    }
+
+    /// The total number of instructions recorded.
+    #[must_use]
+    pub fn total_instructions(&self) -> u64 {
+        self.total_instructions
+    }
+
+    /// Record the execution of `instruction` at `pc`, which took `cycles` cycles, invoked
+    /// `syscall` if it is an `ecall`, and transferred control to `next_pc`.
+    pub fn record(
+        &mut self,
+        pc: u32,
+        instruction: &Instruction,
+        next_pc: u32,
+        cycles: u64,
+        syscall: Option<SyscallCode>,
+    ) {
+        let function = self.function_at(pc);
+        if self.current_function() != Some(function) {
+            self.enter(function);
+        }
+
+        let node = self.current_node();
+        self.nodes[node].self_cycles += cycles;
+        *self.pc_cycles.entry(pc).or_default() += cycles;
+        if let Some(syscall) = syscall {
+            let (count, syscall_cycles) = self.syscalls.entry((function, syscall)).or_default();
+            *count += 1;
+            *syscall_cycles += cycles;
+        }
+        self.total_cycles += cycles;
+        self.total_instructions += 1;
+
+        // Update the call stack based on the control flow of the instruction.
+        let jump = match instruction.opcode {
+            Opcode::JAL => Jump::new(instruction.op_a, None),
+            Opcode::JALR => Jump::new(instruction.op_a, Some(instruction.op_b)),
+            _ => return,
+        };
+        match jump {
+            Jump::Call => {
+                // A call, which pushes a new frame for the callee.
+                let callee = self.function_at(next_pc);
+                self.push(callee);
+            }
+            Jump::Return => {
+                // A return, which pops the frame of the current function.
+                self.stack.pop();
+            }
+            Jump::Other
+                if instruction.op_a == Register::X0 as u32
+                    && self
+                        .symbols
+                        .get(self.function_at(next_pc))
+                        .is_some_and(|f| f.address == next_pc) =>
+            {
+                // A tail call, which replaces the frame of the current function.
+                self.stack.pop();
+                let callee = self.function_at(next_pc);
+                self.push(callee);
+            }
+            Jump::Other => {}
+        }
+    }
+
+    /// The cycles spent in each function, sorted by decreasing total cycles.
+    #[must_use]
+    pub fn functions(&self) -> Vec<FunctionCycles> {
+        let mut self_cycles: HashMap<usize, u64> = HashMap::new();
+        let mut total_cycles: HashMap<usize, u64> = HashMap::new();
+        let mut seen = Vec::new();
+        for node in self.nodes.iter().skip(1).filter(|node| node.self_cycles > 0) {
+            *self_cycles.entry(node.function).or_default() += node.self_cycles;
+
+            // Charge every function on the stack once, so that recursion is not double counted.
+            seen.clear();
+            let mut current = node;
+            loop {
+                if !seen.contains(&current.function) {
+                    seen.push(current.function);
+                    *total_cycles.entry(current.function).or_default() += node.self_cycles;
+                }
+                if current.parent == ROOT {
+                    break;
+                }
+                current = &self.nodes[current.parent];
+            }
+        }
+
+        let mut functions = total_cycles
+            .into_iter()
+            .map(|(function, total_cycles)| FunctionCycles {
+                name: self.function_name(function).to_string(),
+                self_cycles: self_cycles.get(&function).copied().unwrap_or_default(),
+                total_cycles,
+            })
+            .collect::<Vec<_>>();
+        functions.sort_by(|a, b| b.total_cycles.cmp(&a.total_cycles).then(a.name.cmp(&b.name)));
+        functions
+    }
+
+    /// The cycles spent in syscalls, grouped by the invoking function and sorted by decreasing
+    /// cycles.
+    #[must_use]
+    pub fn syscalls(&self) -> Vec<SyscallCycles> {
+        let mut syscalls = self
+            .syscalls
+            .iter()
+            .map(|(&(function, syscall), &(count, cycles))| SyscallCycles {
+                function: self.function_name(function).to_string(),
+                syscall,
+                count,
+                cycles,
+            })
+            .collect::<Vec<_>>();
+        syscalls.sort_by(|a, b| {
+            b.cycles
+                .cmp(&a.cycles)
+                .then(a.function.cmp(&b.function))
+                .then(a.syscall.cmp(&b.syscall))
+        });
+        syscalls
+    }
+
+    /// The cycles spent on each source line, sorted by decreasing cycles.
+    #[must_use]
+    pub fn lines(&self, table: &LineTable) -> Vec<LineCycles> {
+        let mut cycles: HashMap<(String, u32), u64> = HashMap::new();
+        for (&pc, &pc_cycles) in &self.pc_cycles {
+            let key = match table.location(pc) {
+                Some(location) => (location.file, location.line),
+                None => ("??".to_string(), 0),
+            };
+            *cycles.entry(key).or_default() += pc_cycles;
+        }
+
+        let mut lines = cycles
+            .into_iter()
+            .map(|((file, line), cycles)| LineCycles { file, line, cycles })
+            .collect::<Vec<_>>();
+        lines.sort_by(|a, b| {
+            b.cycles.cmp(&a.cycles).then(a.file.cmp(&b.file)).then(a.line.cmp(&b.line))
+        });
+        lines
+    }
+
+    /// The unique call stacks, from the outermost to the innermost function, along with the
+    /// cycles spent at the top of each stack.
+    pub fn stacks(&self) -> impl Iterator<Item = (Vec<&str>, u64)> + '_ {
+        self.sampled_nodes().map(|node| {
+            let mut stack =
+                self.node_functions(node).map(|f| self.function_name(f)).collect::<Vec<_>>();
+            stack.reverse();
+            (stack, self.nodes[node].self_cycles)
+        })
+    }
+
+    /// The nodes of the call tree which have cycles of their own.
+    fn sampled_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        (1..self.nodes.len()).filter(|&node| self.nodes[node].self_cycles > 0)
+    }
+
+    /// The functions on the stack of `node`, from the innermost to the outermost.
+    fn node_functions(&self, mut node: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::from_fn(move || {
+            (node != ROOT).then(|| {
+                let function = self.nodes[node].function;
+                node = self.nodes[node].parent;
+                function
+            })
+        })
+    }
+
+    /// The name of a function, without the hash added by rustc.
+    fn function_name(&self, function: usize) -> &str {
+        match self.symbols.demangled_name(function) {
+            Some(name) => strip_hash(name),
+            None => "[unknown]",
+        }
+    }
+
+    /// The function containing `pc`, or an index past the end of the symbol table if unknown.
+    fn function_at(&self, pc: u32) -> usize {
+        self.symbols.lookup_index(pc).unwrap_or(self.symbols.len())
+    }
+
+    fn current_node(&self) -> usize {
+        self.stack.last().copied().unwrap_or(ROOT)
+    }
+
+    fn current_function(&self) -> Option<usize> {
+        self.stack.last().map(|&node| self.nodes[node].function)
+    }
+
+    /// Resynchronize the stack after control reached `function` without a matching call or
+    /// return, by unwinding to its most recent frame or pushing a new one.
+    fn enter(&mut self, function: usize) {
+        match self.stack.iter().rposition(|&node| self.nodes[node].function == function) {
+            Some(depth) => self.stack.truncate(depth + 1),
+            None => self.push(function),
+        }
+    }
+
+    /// Push a frame for `function`, or charge it to the deepest frame if the stack is full.
+    fn push(&mut self, function: usize) {
+        // Code without a symbol can't be meaningfully attributed, so don't nest functions in it.
+        if self.current_function() == Some(self.symbols.len()) {
+            self.stack.pop();
+        }
+        if self.stack.is_full() {
+            return;
+        }
+        let parent = self.current_node();
+        let next = self.nodes.len();
+        let node = *self.children.entry((parent, function)).or_insert(next);
+        if node == next {
+            self.nodes.push(Node { parent, function, self_cycles: 0 });
+        }
+        self.stack.push(node);
+    }
+}
+
+/// Strip the trailing `::h<hash>` added by rustc from a demangled symbol name.
+fn strip_hash(name: &str) -> &str {
+    match name.rsplit_once("::h") {
+        Some((prefix, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            prefix
+        }
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use super::{FunctionCycles, LineTable, Profiler};
+    use crate::{
+        programs::tests::FIBONACCI_ELF, ElfSymbol, Executor, Instruction, Opcode, Program,
+        SymbolTable,
+    };
+
+    fn symbols() -> SymbolTable {
+        SymbolTable::new(vec![
+            ElfSymbol { name: "main::h0123456789abcdef".to_string(), address: 0, size: 16 },
+            ElfSymbol { name: "callee".to_string(), address: 16, size: 8 },
+            ElfSymbol { name: "tail".to_string(), address: 24, size: 8 },
+        ])
+    }
+
+    fn nop() -> Instruction {
+        Instruction::new(Opcode::ADD, 0, 0, 0, true, true)
+    }
+
+    #[test]
+    fn test_call_and_return() {
+        let mut profiler = Profiler::new(symbols());
+        // main: call callee, which tail calls tail, which returns to main.
+        profiler.record(0, &nop(), 4, 1, None);
+        profiler.record(4, &Instruction::new(Opcode::JAL, 1, 12, 0, true, true), 16, 1, None);
+        profiler.record(16, &nop(), 20, 1, None);
+        profiler.record(20, &Instruction::new(Opcode::JAL, 0, 4, 0, true, true), 24, 1, None);
+        profiler.record(24, &nop(), 28, 5, None);
+        profiler.record(28, &Instruction::new(Opcode::JALR, 0, 1, 0, false, true), 8, 1, None);
+        profiler.record(8, &nop(), 12, 1, None);
+
+        assert_eq!(profiler.total_cycles(), 11);
+        assert_eq!(profiler.total_instructions(), 7);
+        assert_eq!(
+            profiler.functions(),
+            vec![
+                FunctionCycles { name: "main".to_string(), self_cycles: 3, total_cycles: 11 },
+                FunctionCycles { name: "tail".to_string(), self_cycles: 6, total_cycles: 6 },
+                FunctionCycles { name: "callee".to_string(), self_cycles: 2, total_cycles: 2 },
+            ]
+        );
+
+        let mut stacks = profiler.stacks().collect::<Vec<_>>();
+        stacks.sort();
+        assert_eq!(
+            stacks,
+            vec![(vec!["main"], 3), (vec!["main", "callee"], 2), (vec!["main", "tail"], 6),]
+        );
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut profiler = Profiler::new(symbols());
+        let call = Instruction::new(Opcode::JAL, 1, 0, 0, true, true);
+        profiler.record(16, &call, 16, 1, None);
+        profiler.record(16, &call, 16, 1, None);
+        profiler.record(16, &nop(), 20, 1, None);
+
+        let functions = profiler.functions();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].total_cycles, 3);
+        assert_eq!(profiler.stacks().map(|(stack, _)| stack.len()).max(), Some(3));
+    }
+
+    #[test]
+    fn test_unknown_function() {
+        let mut profiler = Profiler::new(symbols());
+        profiler.record(100, &nop(), 104, 2, None);
+        assert_eq!(profiler.functions()[0].name, "[unknown]");
+    }
+
+    #[test]
+    fn test_profile_program() {
+        let program = Program::from(FIBONACCI_ELF).unwrap();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.profiler = Some(Profiler::from_elf(FIBONACCI_ELF).unwrap());
+        runtime.run_fast().unwrap();
+        let profiler = runtime.profiler.take().unwrap();
+
+        assert_eq!(profiler.total_instructions(), runtime.state.global_clk);
+        assert!(profiler.total_cycles() >= profiler.total_instructions());
+        let functions = profiler.functions();
+        assert!(functions.iter().any(|function| function.name == "main"));
+        assert_eq!(functions.iter().map(|function| function.self_cycles).sum::<u64>(), {
+            profiler.total_cycles()
+        });
+
+        let lines = profiler.lines(&LineTable::from_elf(FIBONACCI_ELF).unwrap());
+        assert_eq!(lines.iter().map(|line| line.cycles).sum::<u64>(), profiler.total_cycles());
+
+        let mut folded = Vec::new();
+        profiler.write_folded(&mut folded).unwrap();
+        let folded_cycles = String::from_utf8(folded)
+            .unwrap()
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(folded_cycles, profiler.total_cycles());
+
+        let mut speedscope = Vec::new();
+        profiler.write_speedscope(&mut speedscope, "fibonacci").unwrap();
+        serde_json::from_slice::<serde_json::Value>(&speedscope).unwrap();
+
+        let mut gecko = Vec::new();
+        profiler.write_gecko(&mut gecko, "fibonacci").unwrap();
+        serde_json::from_slice::<serde_json::Value>(&gecko).unwrap();
+    }
+}
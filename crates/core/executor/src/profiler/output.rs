@@ -0,0 +1,156 @@
+use std::io::{self, Write};
+
+use hashbrown::HashMap;
+use serde_json::{json, Value};
+
+use super::{Profiler, ROOT};
+
+impl Profiler {
+    /// Write the profile as folded stacks, one `outer;inner cycles` line per unique stack.
+    ///
+    /// This is the input format of `flamegraph.pl`, `inferno` and most other flame graph tools.
+    pub fn write_folded(&self, mut writer: impl Write) -> io::Result<()> {
+        for (stack, cycles) in self.stacks() {
+            let stack = stack.iter().map(|name| name.replace(';', ",")).collect::<Vec<_>>();
+            writeln!(writer, "{} {cycles}", stack.join(";"))?;
+        }
+        Ok(())
+    }
+
+    /// Write the profile in the [speedscope](https://www.speedscope.app) file format.
+    pub fn write_speedscope(&self, writer: impl Write, name: &str) -> io::Result<()> {
+        let (frames, frame_of) = self.frames();
+        let mut samples = Vec::new();
+        let mut weights = Vec::new();
+        for node in self.sampled_nodes() {
+            let mut stack =
+                self.node_functions(node).map(|function| frame_of[&function]).collect::<Vec<_>>();
+            stack.reverse();
+            samples.push(stack);
+            weights.push(self.nodes[node].self_cycles);
+        }
+
+        let profile = json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "name": name,
+            "exporter": "sp1-core-executor",
+            "activeProfileIndex": 0,
+            "shared": {
+                "frames": frames.iter().map(|name| json!({ "name": name })).collect::<Vec<_>>(),
+            },
+            "profiles": [{
+                "type": "sampled",
+                "name": name,
+                "unit": "none",
+                "startValue": 0,
+                "endValue": self.total_cycles,
+                "samples": samples,
+                "weights": weights,
+            }],
+        });
+        serde_json::to_writer(writer, &profile).map_err(io::Error::from)
+    }
+
+    /// Write the profile in the Gecko profile format, which can be loaded into the
+    /// [Firefox Profiler](https://profiler.firefox.com).
+    ///
+    /// Each unique stack is written as a single sample weighted by its cycles.
+    pub fn write_gecko(&self, writer: impl Write, name: &str) -> io::Result<()> {
+        let (frames, frame_of) = self.frames();
+
+        // The stack table mirrors the call tree, without its root.
+        let stack_table = self.nodes[1..]
+            .iter()
+            .map(|node| {
+                let prefix = if node.parent == ROOT { Value::Null } else { json!(node.parent - 1) };
+                json!([prefix, frame_of.get(&node.function)])
+            })
+            .collect::<Vec<_>>();
+
+        let mut time = 0;
+        let samples = self
+            .sampled_nodes()
+            .map(|node| {
+                let sample = json!([node - 1, time, self.nodes[node].self_cycles]);
+                time += self.nodes[node].self_cycles;
+                sample
+            })
+            .collect::<Vec<_>>();
+
+        let frame_table = (0..frames.len())
+            .map(|frame| json!([frame, false, 0, null, null, null, 0, 0]))
+            .collect::<Vec<_>>();
+
+        let profile = json!({
+            "meta": {
+                "version": 24,
+                "interval": 1,
+                "startTime": 0,
+                "processType": 0,
+                "product": name,
+                "stackwalk": 0,
+                "debug": false,
+                "gcpoison": false,
+                "asyncstack": false,
+                "categories": [{ "name": "Other", "color": "grey", "subcategories": ["Other"] }],
+                "markerSchema": [],
+            },
+            "libs": [],
+            "pausedRanges": [],
+            "processes": [],
+            "threads": [{
+                "name": name,
+                "processType": "default",
+                "processName": name,
+                "pid": 0,
+                "tid": 0,
+                "registerTime": 0,
+                "unregisterTime": null,
+                "markers": {
+                    "schema": {
+                        "name": 0, "startTime": 1, "endTime": 2, "phase": 3, "category": 4, "data": 5
+                    },
+                    "data": [],
+                },
+                "samples": {
+                    "schema": { "stack": 0, "time": 1, "weight": 2 },
+                    "weightType": "samples",
+                    "data": samples,
+                },
+                "frameTable": {
+                    "schema": {
+                        "location": 0,
+                        "relevantForJS": 1,
+                        "innerWindowID": 2,
+                        "implementation": 3,
+                        "line": 4,
+                        "column": 5,
+                        "category": 6,
+                        "subcategory": 7,
+                    },
+                    "data": frame_table,
+                },
+                "stackTable": {
+                    "schema": { "prefix": 0, "frame": 1 },
+                    "data": stack_table,
+                },
+                "stringTable": frames,
+            }],
+        });
+        serde_json::to_writer(writer, &profile).map_err(io::Error::from)
+    }
+
+    /// The names of the functions appearing in the profile, and the index of each function in
+    /// that list.
+    fn frames(&self) -> (Vec<&str>, HashMap<usize, usize>) {
+        let mut frames = Vec::new();
+        let mut frame_of = HashMap::new();
+        for node in &self.nodes[1..] {
+            frame_of.entry(node.function).or_insert_with(|| {
+                frames.push(self.function_name(node.function));
+                frames.len() - 1
+            });
+        }
+        (frames, frame_of)
+    }
+}
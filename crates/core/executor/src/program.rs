@@ -10,7 +10,7 @@ use sp1_stark::air::{MachineAir, MachineProgram};
 use crate::{
     disassembler::{transpile, Elf},
     instruction::Instruction,
-    CoreShape,
+    CoreShape, SymbolTable,
 };
 
 /// A program that can be executed by the SP1 zkVM.
@@ -29,6 +29,16 @@ pub struct Program {
     pub memory_image: HashMap<u32, u32>,
     /// The shape for the preprocessed tables.
     pub preprocessed_shape: Option<CoreShape>,
+    /// The address of each instruction, in increasing order, if the program contains compressed
+    /// instructions.
+    ///
+    /// Otherwise, the instruction at index `i` is at address `pc_base + 4 * i`.
+    pub instruction_pcs: Option<Vec<u32>>,
+    /// The function symbols of the program, used to symbolicate backtraces.
+    ///
+    /// Not serialized, so that programs keep their serialized form.
+    #[serde(skip)]
+    pub symbols: SymbolTable,
 }
 
 impl Program {
@@ -41,10 +51,12 @@ impl Program {
             pc_base,
             memory_image: HashMap::new(),
             preprocessed_shape: None,
+            instruction_pcs: None,
+            symbols: SymbolTable::default(),
         }
     }
 
-    /// Disassemble a RV32IM ELF to a program that be executed by the VM.
+    /// Disassemble a RV32IM or RV32IMC ELF to a program that be executed by the VM.
     ///
     /// # Errors
     ///
@@ -63,6 +75,8 @@ impl Program {
             pc_base: elf.pc_base,
             memory_image: elf.memory_image,
             preprocessed_shape: None,
+            instruction_pcs: elf.instruction_pcs,
+            symbols: elf.symbols,
         })
     }
 
@@ -77,6 +91,51 @@ impl Program {
         Program::from(&elf_code)
     }
 
+    /// The address of the instruction at `index`.
+    #[must_use]
+    pub fn instruction_pc(&self, index: usize) -> u32 {
+        match &self.instruction_pcs {
+            Some(pcs) => pcs[index],
+            None => self.pc_base + (index as u32 * 4),
+        }
+    }
+
+    /// The index of the instruction at address `pc`, if there is one.
+    #[must_use]
+    #[inline]
+    pub fn instruction_index(&self, pc: u32) -> Option<usize> {
+        match &self.instruction_pcs {
+            Some(pcs) => pcs.binary_search(&pc).ok(),
+            None => {
+                let index = (pc.wrapping_sub(self.pc_base) / 4) as usize;
+                (index < self.instructions.len()).then_some(index)
+            }
+        }
+    }
+
+    /// Whether `pc` lies within the code of the program, from its base address to the end of its
+    /// last instruction.
+    ///
+    /// The execution ends when the program jumps outside of its code.
+    #[must_use]
+    pub fn contains_pc(&self, pc: u32) -> bool {
+        let code_size = match &self.instruction_pcs {
+            Some(pcs) => pcs
+                .last()
+                .zip(self.instructions.last())
+                .map_or(0, |(&last, instruction)| last + instruction.size() - self.pc_base),
+            None => (self.instructions.len() * 4) as u32,
+        };
+        pc.wrapping_sub(self.pc_base) < code_size
+    }
+
+    /// The instruction at address `pc`, if there is one.
+    #[must_use]
+    #[inline]
+    pub fn fetch(&self, pc: u32) -> Option<&Instruction> {
+        self.instruction_index(pc).map(|index| &self.instructions[index])
+    }
+
     /// Custom logic for padding the trace to a power of two according to the proof shape.
     pub fn fixed_log2_rows<F: Field, A: MachineAir<F>>(&self, air: &A) -> Option<usize> {
         self.preprocessed_shape
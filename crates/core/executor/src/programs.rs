@@ -3,7 +3,13 @@
 #[allow(dead_code)]
 #[allow(missing_docs)]
 pub mod tests {
-    use crate::{Instruction, Opcode, Program};
+    use sp1_curves::weierstrass::{secp256r1::Secp256r1Parameters, WeierstrassParameters};
+
+    use crate::{
+        events::{BLAKE2B_IV, BLAKE3_IV},
+        syscalls::SyscallCode,
+        ElfSymbol, Instruction, Opcode, Program, SymbolTable,
+    };
 
     pub const CHESS_ELF: &[u8] =
         include_bytes!("../../../../examples/chess/program/elf/riscv32im-succinct-zkvm-elf");
@@ -130,6 +136,185 @@ pub mod tests {
         Program::new(instructions, 0, 0)
     }
 
+    /// Get a program mixing compressed and uncompressed instructions.
+    ///
+    /// It computes `x31 = 5 + 37` and links the return address of a compressed `jal` into `x28`.
+    #[must_use]
+    pub fn compressed_program() -> Program {
+        let compressed = |mut instruction: Instruction| {
+            instruction.compressed = true;
+            instruction
+        };
+        let instructions = vec![
+            compressed(Instruction::new(Opcode::ADD, 29, 0, 5, false, true)),
+            Instruction::new(Opcode::ADD, 30, 0, 37, false, true),
+            compressed(Instruction::new(Opcode::ADD, 31, 30, 29, false, false)),
+            compressed(Instruction::new(Opcode::JAL, 1, 4, 0, true, true)),
+            compressed(Instruction::new(Opcode::ADD, 28, 0, 1, false, true)),
+            compressed(Instruction::new(Opcode::ADD, 28, 1, 0, false, false)),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.instruction_pcs = Some(vec![0, 2, 6, 8, 10, 12]);
+        program
+    }
+
+    /// Get a program running each atomic memory instruction on the same address.
+    #[must_use]
+    pub fn atomic_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 10, 0, 0x27654320, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 5, false, true),
+            Instruction::new(Opcode::ADD, 13, 0, 7, false, true),
+            Instruction::new(Opcode::ADD, 16, 0, -3i32 as u32, false, true),
+            Instruction::new(Opcode::SW, 11, 10, 0, false, true),
+            Instruction::new(Opcode::LR, 12, 10, 0, false, false),
+            Instruction::new(Opcode::SC, 14, 10, 13, false, false),
+            Instruction::new(Opcode::AMOADD, 15, 10, 11, false, false),
+            Instruction::new(Opcode::AMOXOR, 17, 10, 16, false, false),
+            Instruction::new(Opcode::AMOAND, 18, 10, 11, false, false),
+            Instruction::new(Opcode::AMOOR, 19, 10, 13, false, false),
+            Instruction::new(Opcode::AMOMIN, 20, 10, 16, false, false),
+            Instruction::new(Opcode::AMOMAX, 21, 10, 11, false, false),
+            Instruction::new(Opcode::AMOMINU, 22, 10, 16, false, false),
+            Instruction::new(Opcode::AMOMAXU, 23, 10, 16, false, false),
+            Instruction::new(Opcode::AMOSWAP, 24, 10, 11, false, false),
+            Instruction::new(Opcode::AMOADD, 0, 10, 11, false, false),
+            Instruction::new(Opcode::LW, 25, 10, 0, false, true),
+        ];
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Get a program exercising the secp256r1 precompiles.
+    ///
+    /// It doubles the generator stored at `0x1000`, adds the generator stored at `0x1040` to the
+    /// result and decompresses the generator from its x coordinate stored at `0x10A0`, writing the
+    /// y coordinate to `0x1080`.
+    #[must_use]
+    pub fn secp256r1_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::SECP256R1_DOUBLE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::SECP256R1_ADD as u32, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1040, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::SECP256R1_DECOMPRESS as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1080, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+
+        let (x, y) = Secp256r1Parameters::generator();
+        let mut x_words = x.to_u32_digits();
+        x_words.resize(8, 0);
+        let mut y_words = y.to_u32_digits();
+        y_words.resize(8, 0);
+        for (i, word) in x_words.iter().chain(y_words.iter()).enumerate() {
+            let offset = 4 * i as u32;
+            program.memory_image.insert(0x1000 + offset, *word);
+            program.memory_image.insert(0x1040 + offset, *word);
+        }
+        for (i, word) in x_words.iter().enumerate() {
+            program.memory_image.insert(0x10A0 + 4 * i as u32, *word);
+        }
+        program
+    }
+
+    /// Get a program exercising the u256x2048 mul precompile.
+    ///
+    /// It multiplies the uint256 stored at `0x1000` by the uint2048 stored at `0x1100`, writing
+    /// the low 2048 bits of the product to `0x1200` and the high 256 bits to `0x1300`.
+    #[must_use]
+    pub fn u256x2048_mul_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::U256XU2048_MUL as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1100, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, 0x1200, false, true),
+            Instruction::new(Opcode::ADD, 13, 0, 0x1300, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+
+        // Fill the inputs with words whose products carry across limbs.
+        for i in 0..8 {
+            program.memory_image.insert(0x1000 + 4 * i, 0xFFFF_FFFF - i);
+        }
+        for i in 0..64 {
+            program.memory_image.insert(0x1100 + 4 * i, 0x9E37_79B9u32.wrapping_mul(i + 1));
+        }
+        program
+    }
+
+    /// Get a program exercising the BLAKE3 compress precompile.
+    ///
+    /// It compresses the single block `b"abc"`, as done when hashing it, with the state stored at
+    /// `0x1000` and the message stored at `0x1100`.
+    #[must_use]
+    pub fn blake3_compress_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::BLAKE3_COMPRESS as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1100, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+
+        // The chaining value, the counter, the block length and the CHUNK_START | CHUNK_END | ROOT
+        // flags.
+        let mut state = [0u32; 16];
+        state[..8].copy_from_slice(&BLAKE3_IV);
+        state[14] = 3;
+        state[15] = 0b1011;
+        let mut message = [0u32; 16];
+        message[0] = u32::from_le_bytes(*b"abc\0");
+        for i in 0..16 {
+            program.memory_image.insert(0x1000 + 4 * i as u32, state[i]);
+            program.memory_image.insert(0x1100 + 4 * i as u32, message[i]);
+        }
+        program
+    }
+
+    /// Get a program exercising the Blake2b compress precompile.
+    ///
+    /// It compresses the single block `b"abc"`, as done when hashing it to 64 bytes, with the state
+    /// stored at `0x1000` and the message stored at `0x1100`.
+    #[must_use]
+    pub fn blake2b_compress_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::BLAKE2B_COMPRESS as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1100, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+
+        // The parameter-mixed chaining value, the byte counter and the final block flag.
+        let mut state = [0u64; 16];
+        state[..8].copy_from_slice(&BLAKE2B_IV);
+        state[0] ^= 0x0101_0040;
+        state[12] = 3;
+        state[14] = u64::MAX;
+        let mut message = [0u64; 16];
+        message[0] = u64::from_le_bytes(*b"abc\0\0\0\0\0");
+        for i in 0..16 {
+            for (j, value) in [(0x1000, state[i]), (0x1100, message[i])] {
+                program.memory_image.insert(j + 8 * i as u32, value as u32);
+                program.memory_image.insert(j + 8 * i as u32 + 4, (value >> 32) as u32);
+            }
+        }
+        program
+    }
+
     /// Get the fibonacci program.
     ///
     /// # Panics
@@ -160,6 +345,129 @@ pub mod tests {
         Program::from(PANIC_ELF).unwrap()
     }
 
+    /// Get a program reporting that it ran out of memory.
+    ///
+    /// It writes a failed allocation of `0x100` bytes with the heap at `0x7800_0000` to
+    /// [`FD_OUT_OF_MEMORY`](crate::syscalls::FD_OUT_OF_MEMORY), and halts with exit code 1.
+    #[must_use]
+    pub fn out_of_memory_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, crate::syscalls::FD_OUT_OF_MEMORY, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, 8, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HALT as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image.insert(0x1000, 0x100);
+        program.memory_image.insert(0x1004, 0x7800_0000);
+        program
+    }
+
+    /// Get a program reporting that it panicked.
+    ///
+    /// It calls a function which writes a panic with the message `boom` at `main.rs:7:5` to
+    /// [`FD_PANIC`](crate::syscalls::FD_PANIC), and halts with exit code 1.
+    #[must_use]
+    pub fn guest_panic_program() -> Program {
+        let mut report = Vec::new();
+        report.extend_from_slice(&7u32.to_le_bytes());
+        report.extend_from_slice(&5u32.to_le_bytes());
+        report.extend_from_slice(&7u32.to_le_bytes());
+        report.extend_from_slice(b"main.rs");
+        report.extend_from_slice(b"boom");
+        let instructions = vec![
+            Instruction::new(Opcode::JAL, 1, 8, 0, true, true),
+            Instruction::new(Opcode::ADD, 0, 0, 0, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, crate::syscalls::FD_PANIC, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, report.len() as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HALT as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        for (i, chunk) in report.chunks(4).enumerate() {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            program.memory_image.insert(0x1000 + 4 * i as u32, u32::from_le_bytes(word));
+        }
+        program.symbols = SymbolTable::new(vec![
+            ElfSymbol { name: "main".into(), address: 0, size: 8 },
+            ElfSymbol { name: "panic".into(), address: 8, size: 32 },
+        ]);
+        program
+    }
+
+    #[must_use]
+    pub fn getenv_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::GETENV as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 31, 5, 0, false, true),
+        ];
+        Program::new(instructions, 0, 0)
+    }
+
+    #[must_use]
+    pub fn rand_seed_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::RAND_SEED as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 31, 5, 0, false, true),
+        ];
+        Program::new(instructions, 0, 0)
+    }
+
+    #[must_use]
+    pub fn fs_open_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_OPEN as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 3, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 31, 5, 0, false, true),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image.insert(0x1000, u32::from_le_bytes(*b"cfg\0"));
+        program
+    }
+
+    /// Get a program opening the file at `cfg`, reading up to 5 bytes of it from its second byte
+    /// and closing it, assuming it was opened at file descriptor 3.
+    #[must_use]
+    pub fn fs_read_program() -> Program {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_OPEN as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 3, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_SEEK as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 3, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_READ as u32, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 5, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_CLOSE as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image.insert(0x1000, u32::from_le_bytes(*b"cfg\0"));
+        program
+    }
+
     #[must_use]
     #[allow(clippy::unreadable_literal)]
     pub fn simple_memory_program() -> Program {
@@ -0,0 +1,144 @@
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A progress event emitted while generating a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofProgress {
+    /// The program finished executing and was split into `num_shards` shards.
+    ShardsExecuted {
+        /// The total number of shards.
+        num_shards: u32,
+    },
+    /// A core shard proof was generated.
+    ShardProven {
+        /// The index of the shard, starting at 1.
+        shard: u32,
+    },
+    /// The core proof is complete.
+    CoreProven,
+    /// The recursion prover started proving layer `layer` of the reduction tree.
+    RecursionLayer {
+        /// The layer being proven, starting at 1.
+        layer: usize,
+        /// The total number of layers.
+        num_layers: usize,
+    },
+    /// The compressed proof is being shrunk.
+    ShrinkStarted,
+    /// The shrunk proof is being wrapped into a SNARK-friendly field.
+    WrapStarted,
+}
+
+/// A handle used to cancel a proof in progress.
+///
+/// Cancellation is cooperative: the prover stops scheduling new work, waits for the work already in
+/// flight to drain, and returns a cancellation error.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new [`CancellationToken`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request that the proof using this token be cancelled.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether cancellation has been requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Create a guard which cancels this token when dropped, unless it is disarmed first.
+    ///
+    /// Used to cancel a proof running in the background when the future awaiting it is dropped.
+    #[must_use]
+    pub fn drop_guard(&self) -> CancelOnDrop {
+        CancelOnDrop { token: Some(self.clone()) }
+    }
+}
+
+impl Debug for CancellationToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("CancellationToken").field("cancelled", &self.is_cancelled()).finish()
+    }
+}
+
+/// Cancels a [`CancellationToken`] when dropped. See [`CancellationToken::drop_guard`].
+#[derive(Debug)]
+pub struct CancelOnDrop {
+    token: Option<CancellationToken>,
+}
+
+impl CancelOnDrop {
+    /// Disarm the guard, so that dropping it no longer cancels the token.
+    pub fn disarm(mut self) {
+        self.token = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(token) = self.token.take() {
+            token.cancel();
+        }
+    }
+}
+
+/// The callback invoked with every [`ProofProgress`] event.
+pub type ProgressCallback = Arc<dyn Fn(ProofProgress) + Send + Sync>;
+
+/// Observes and controls a proof while it is being generated.
+#[derive(Clone, Default)]
+pub struct ProofMonitor {
+    on_progress: Option<ProgressCallback>,
+    cancellation: CancellationToken,
+}
+
+impl ProofMonitor {
+    /// Create a new [`ProofMonitor`].
+    #[must_use]
+    pub fn new(on_progress: Option<ProgressCallback>, cancellation: CancellationToken) -> Self {
+        Self { on_progress, cancellation }
+    }
+
+    /// Report a progress event.
+    pub fn report(&self, event: ProofProgress) {
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(event);
+        }
+    }
+
+    /// Whether the proof has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// The cancellation token of the proof.
+    #[must_use]
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+}
+
+impl Debug for ProofMonitor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ProofMonitor")
+            .field("on_progress", &self.on_progress.is_some())
+            .field("cancellation", &self.cancellation)
+            .finish()
+    }
+}
@@ -141,6 +141,8 @@ impl ExecutionRecord {
                 SyscallCode::KECCAK_PERMUTE => opts.keccak,
                 SyscallCode::SHA_EXTEND => opts.sha_extend,
                 SyscallCode::SHA_COMPRESS => opts.sha_compress,
+                SyscallCode::BLAKE2B_COMPRESS => opts.blake2b_compress,
+                SyscallCode::BLAKE3_COMPRESS => opts.blake3_compress,
                 _ => opts.deferred,
             };
 
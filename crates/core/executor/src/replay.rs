@@ -0,0 +1,424 @@
+//! Recording and replaying the inputs of an execution.
+//!
+//! While recording, the executor logs every `WRITE`, `HINT_LEN` and `HINT_READ` syscall, every
+//! syscall which serves host data to the program (`GETENV`, `RAND_SEED`, `FS_OPEN` and `FS_READ`)
+//! and every hook invocation, along with the data returned to the program. Replaying a log serves
+//! the hints, host data and hook responses back from it, so that an execution can be reproduced
+//! without the original input, context or hooks, and checks that the program performs the same
+//! writes.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{snapshot::program_digest, syscalls::SyscallCode, ExecutionError, Executor, Program};
+
+/// The magic bytes at the start of every replay log file.
+pub const REPLAY_MAGIC: [u8; 8] = *b"SP1RPLY\0";
+
+/// The version of the replay log format written by this executor.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Errors that can occur while saving or loading a replay log.
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    /// Reading or writing the replay log file failed.
+    #[error("replay log io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The replay log could not be serialized or deserialized.
+    #[error("replay log serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+
+    /// The file is not a replay log.
+    #[error("not a replay log")]
+    InvalidMagic,
+
+    /// The replay log was written with an unsupported version of the format.
+    #[error("unsupported replay log version {0}, expected {REPLAY_VERSION}")]
+    UnsupportedVersion(u32),
+
+    /// The replay log was recorded while executing a different program.
+    #[error("replay log was recorded while executing a different program")]
+    ProgramMismatch,
+}
+
+/// An interaction of the program with the outside world, recorded in a [`ReplayLog`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayEvent {
+    /// A write to a file descriptor without a hook.
+    Write {
+        /// The file descriptor.
+        fd: u32,
+        /// The bytes written.
+        bytes: Vec<u8>,
+    },
+    /// A `HINT_LEN` syscall.
+    HintLen {
+        /// The length returned to the program.
+        len: u32,
+    },
+    /// A `HINT_READ` syscall.
+    HintRead {
+        /// The bytes written into the memory of the program.
+        bytes: Vec<u8>,
+    },
+    /// A syscall which adds data from the host to the input stream, such as `GETENV`.
+    HostInput {
+        /// The syscall.
+        syscall: SyscallCode,
+        /// The data added to the input stream.
+        bytes: Vec<u8>,
+    },
+    /// A write to a file descriptor with a hook.
+    Hook {
+        /// The file descriptor.
+        fd: u32,
+        /// The bytes written, passed to the hook.
+        input: Vec<u8>,
+        /// The response of the hook, added to the input stream.
+        output: Vec<Vec<u8>>,
+    },
+}
+
+/// The recorded interactions of an execution with the outside world.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayLog {
+    /// The digest of the program the log was recorded for.
+    pub program_digest: [u8; 32],
+    /// The events, in the order they happened.
+    pub events: Vec<ReplayEvent>,
+}
+
+impl ReplayLog {
+    /// Create an empty [`ReplayLog`] for executing `program`.
+    #[must_use]
+    pub fn new(program: &Program) -> Self {
+        Self { program_digest: program_digest(program), events: Vec::new() }
+    }
+
+    /// Save the replay log to the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&REPLAY_MAGIC)?;
+        writer.write_all(&REPLAY_VERSION.to_le_bytes())?;
+        bincode::serialize_into(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Load a replay log from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is not a valid replay log.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0; REPLAY_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != REPLAY_MAGIC {
+            return Err(ReplayError::InvalidMagic);
+        }
+        let mut version = [0; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+        Ok(bincode::deserialize_from(reader)?)
+    }
+}
+
+/// Whether the executor records or replays the interactions of the program.
+#[derive(Debug, Clone, Default)]
+pub enum ReplayMode {
+    /// Neither record nor replay.
+    #[default]
+    Disabled,
+    /// Record the interactions into the log.
+    Record(ReplayLog),
+    /// Serve the interactions from the log.
+    Replay {
+        /// The log being replayed.
+        log: ReplayLog,
+        /// The index of the next event to replay.
+        position: usize,
+    },
+}
+
+impl ReplayMode {
+    /// Append the event to the log, if recording.
+    ///
+    /// The event is only built when it is recorded, to avoid copying the data otherwise.
+    pub(crate) fn record(&mut self, event: impl FnOnce() -> ReplayEvent) {
+        if let ReplayMode::Record(log) = self {
+            log.events.push(event());
+        }
+    }
+
+    /// Take the next event of the log, if replaying.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all the events of the log were already replayed.
+    pub(crate) fn next_event(&mut self) -> Result<Option<ReplayEvent>, ExecutionError> {
+        let ReplayMode::Replay { log, position } = self else {
+            return Ok(None);
+        };
+        let Some(event) = log.events.get(*position) else {
+            return Err(ExecutionError::ReplayDiverged(format!(
+                "the log ended after {position} events"
+            )));
+        };
+        *position += 1;
+        Ok(Some(event.clone()))
+    }
+
+    /// Whether the next event of the log is a hook invocation on `fd`, if replaying.
+    fn next_is_hook(&self, fd: u32) -> bool {
+        matches!(
+            self,
+            ReplayMode::Replay { log, position }
+                if matches!(log.events.get(*position), Some(ReplayEvent::Hook { fd: f, .. }) if *f == fd)
+        )
+    }
+}
+
+/// The error describing how the execution diverged from the replayed event.
+pub(crate) fn replay_diverged(event: &ReplayEvent, actual: &str) -> ExecutionError {
+    ExecutionError::ReplayDiverged(format!(
+        "expected {event:?}, but the program performed {actual}"
+    ))
+}
+
+impl<'a> Executor<'a> {
+    /// Start recording the interactions of the program into a new [`ReplayLog`].
+    pub fn record_replay(&mut self) {
+        self.replay = ReplayMode::Record(ReplayLog::new(&self.program));
+    }
+
+    /// Replay the interactions of the program from `log`, instead of reading the input stream and
+    /// invoking hooks.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the log was recorded for a different program.
+    pub fn replay_from(&mut self, log: ReplayLog) -> Result<(), ReplayError> {
+        if log.program_digest != program_digest(&self.program) {
+            return Err(ReplayError::ProgramMismatch);
+        }
+        self.replay = ReplayMode::Replay { log, position: 0 };
+        Ok(())
+    }
+
+    /// Take the recorded [`ReplayLog`], if recording, and stop recording.
+    pub fn take_replay_log(&mut self) -> Option<ReplayLog> {
+        match std::mem::take(&mut self.replay) {
+            ReplayMode::Record(log) => Some(log),
+            other => {
+                self.replay = other;
+                None
+            }
+        }
+    }
+
+    /// Invoke the hook registered for `fd`, or serve its response from the replay log.
+    ///
+    /// Returns `None` if there is no hook for `fd`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call diverges from the replay log.
+    pub(crate) fn invoke_hook(
+        &mut self,
+        fd: u32,
+        input: &[u8],
+    ) -> Result<Option<Vec<Vec<u8>>>, ExecutionError> {
+        if let ReplayMode::Replay { .. } = self.replay {
+            if !self.replay.next_is_hook(fd) {
+                return Ok(None);
+            }
+            let Some(ReplayEvent::Hook { input: expected, output, .. }) =
+                self.replay.next_event()?
+            else {
+                unreachable!()
+            };
+            if expected != input {
+                let event = ReplayEvent::Hook { fd, input: expected, output };
+                return Err(replay_diverged(
+                    &event,
+                    &format!("a hook call on fd {fd} with other data"),
+                ));
+            }
+            return Ok(Some(output));
+        }
+
+        let Some(hook) = self.hook_registry.get(fd) else {
+            return Ok(None);
+        };
+        let output = hook.invoke_hook(self.hook_env(), input);
+        self.replay.record(|| ReplayEvent::Hook {
+            fd,
+            input: input.to_vec(),
+            output: output.clone(),
+        });
+        Ok(Some(output))
+    }
+
+    /// Record a write of `bytes` to `fd`, or check it against the replay log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write diverges from the replay log.
+    pub(crate) fn replay_write(&mut self, fd: u32, bytes: &[u8]) -> Result<(), ExecutionError> {
+        match self.replay.next_event()? {
+            Some(ReplayEvent::Write { fd: expected_fd, bytes: expected })
+                if expected_fd == fd && expected == bytes => {}
+            Some(event) => return Err(replay_diverged(&event, &format!("a write to fd {fd}"))),
+            None => self.replay.record(|| ReplayEvent::Write { fd, bytes: bytes.to_vec() }),
+        }
+        Ok(())
+    }
+
+    /// Add the data served by `syscall` from the host to the beginning of the input stream, where
+    /// the program reads it with `HINT_LEN` and `HINT_READ`.
+    ///
+    /// The data is recorded, or served from the replay log instead of calling `serve`, so that
+    /// replays don't depend on the context of the original execution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the syscall diverges from the replay log, or if `serve` fails.
+    pub(crate) fn serve_host_input(
+        &mut self,
+        syscall: SyscallCode,
+        serve: impl FnOnce(&Self) -> Result<Vec<u8>, ExecutionError>,
+    ) -> Result<(), ExecutionError> {
+        let bytes = match self.replay.next_event()? {
+            Some(ReplayEvent::HostInput { syscall: expected, bytes }) if expected == syscall => {
+                bytes
+            }
+            Some(event) => return Err(replay_diverged(&event, &format!("a {syscall:?} syscall"))),
+            None => {
+                let bytes = serve(self)?;
+                self.replay.record(|| ReplayEvent::HostInput { syscall, bytes: bytes.clone() });
+                bytes
+            }
+        };
+        let ptr = self.state.input_stream_ptr;
+        self.state.input_stream.insert(ptr, bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use crate::{
+        programs::tests::{fibonacci_program, rand_seed_program, simple_program},
+        syscalls::SyscallCode,
+        ExecutionError, Executor, Program, Register, SP1Context,
+    };
+
+    use super::{ReplayError, ReplayEvent, ReplayLog};
+
+    const HINT_IO_ELF: &[u8] =
+        include_bytes!("../../../../tests/hint-io/elf/riscv32im-succinct-zkvm-elf");
+
+    #[test]
+    fn test_replay_serves_hints_without_input() {
+        let program = Program::from(HINT_IO_ELF).unwrap();
+        let data = vec![7u8; 100];
+        let mut recorded = Executor::new(program.clone(), SP1CoreOpts::default());
+        recorded.write_vecs(&[bincode::serialize(&data).unwrap(), data]);
+        recorded.record_replay();
+        recorded.run_fast().unwrap();
+        let log = recorded.take_replay_log().unwrap();
+        assert!(log.events.iter().any(|event| matches!(event, ReplayEvent::HintRead { .. })));
+
+        let path = std::env::temp_dir().join("sp1-replay-test.bin");
+        log.save(&path).unwrap();
+        let loaded = ReplayLog::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.events, log.events);
+
+        let mut replayed = Executor::new(program, SP1CoreOpts::default());
+        replayed.replay_from(loaded).unwrap();
+        replayed.run_fast().unwrap();
+        assert_eq!(replayed.state.global_clk, recorded.state.global_clk);
+        assert_eq!(replayed.state.public_values_stream, recorded.state.public_values_stream);
+    }
+
+    #[test]
+    fn test_replay_serves_hooks_without_calling_them() {
+        let context =
+            SP1Context::builder().hook(30, |_, input| vec![input.to_vec(), vec![1]]).build();
+        let mut recorded =
+            Executor::with_context(simple_program(), SP1CoreOpts::default(), context);
+        recorded.record_replay();
+        let output = recorded.invoke_hook(30, b"input").unwrap().unwrap();
+        let log = recorded.take_replay_log().unwrap();
+
+        let context = SP1Context::builder().without_default_hooks().build();
+        let mut replayed =
+            Executor::with_context(simple_program(), SP1CoreOpts::default(), context);
+        replayed.replay_from(log).unwrap();
+        assert_eq!(replayed.invoke_hook(31, b"input").unwrap(), None);
+        assert_eq!(replayed.invoke_hook(30, b"input").unwrap(), Some(output));
+    }
+
+    #[test]
+    fn test_replay_detects_divergence() {
+        let mut recorded = Executor::new(simple_program(), SP1CoreOpts::default());
+        recorded.record_replay();
+        recorded.replay_write(1, b"hello").unwrap();
+        let log = recorded.take_replay_log().unwrap();
+
+        let mut replayed = Executor::new(simple_program(), SP1CoreOpts::default());
+        replayed.replay_from(log).unwrap();
+        assert!(matches!(
+            replayed.replay_write(1, b"world"),
+            Err(ExecutionError::ReplayDiverged(_))
+        ));
+        assert!(matches!(
+            replayed.replay_write(1, b"hello"),
+            Err(ExecutionError::ReplayDiverged(_))
+        ));
+    }
+
+    #[test]
+    fn test_replay_serves_host_inputs_without_context() {
+        let program = rand_seed_program();
+        let context = SP1Context::builder().rand_seed([7; 32]).build();
+        let mut recorded = Executor::with_context(program.clone(), SP1CoreOpts::default(), context);
+        recorded.record_replay();
+        recorded.run_fast().unwrap();
+        let log = recorded.take_replay_log().unwrap();
+        assert!(log.events.iter().any(|event| matches!(
+            event,
+            ReplayEvent::HostInput { syscall: SyscallCode::RAND_SEED, .. }
+        )));
+
+        let mut replayed = Executor::new(program, SP1CoreOpts::default());
+        replayed.replay_from(log).unwrap();
+        replayed.run_fast().unwrap();
+        assert_eq!(replayed.register(Register::X31), 32);
+    }
+
+    #[test]
+    fn test_replay_rejects_other_program() {
+        let log = ReplayLog::new(&fibonacci_program());
+        let mut runtime = Executor::new(simple_program(), SP1CoreOpts::default());
+        assert!(matches!(runtime.replay_from(log), Err(ReplayError::ProgramMismatch)));
+    }
+}
@@ -5,11 +5,12 @@ use std::{
 
 use enum_map::{EnumArray, EnumMap};
 use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
 use crate::{events::sorted_table_lines, syscalls::SyscallCode, Opcode};
 
 /// An execution report.
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutionReport {
     /// The event counts.
     pub event_counts: Box<EnumMap<Opcode, u64>>,
@@ -37,6 +38,18 @@ impl ExecutionReport {
     }
 }
 
+/// The number of events emitted by a single shard, counted without emitting them.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ShardEventCounts {
+    /// The global clock at the end of the shard.
+    ///
+    /// The number of instructions executed in a shard is the difference between its global clock
+    /// and the one of the previous shard.
+    pub global_clk: u64,
+    /// The event counts, including the events each instruction depends on.
+    pub event_counts: Box<EnumMap<Opcode, u64>>,
+}
+
 /// Combines two `HashMap`s together. If a key is in both maps, the values are added together.
 fn counts_add_assign<K, V>(lhs: &mut EnumMap<K, V>, rhs: EnumMap<K, V>)
 where
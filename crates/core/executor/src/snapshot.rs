@@ -0,0 +1,256 @@
+//! Snapshots of an [`Executor`] in the middle of an execution.
+//!
+//! A snapshot captures everything needed to continue executing a program later, possibly in
+//! another process: the memory (including the registers), the environment, randomness seed, files
+//! and open files, the input, proof and public values streams, the public values digests, the
+//! execution report and the cycle tracker.
+//!
+//! Events which were already emitted into [`Executor::records`] are not part of a snapshot, so
+//! snapshots are meant for executors running in [`ExecutorMode::Simple`](crate::ExecutorMode).
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use sp1_stark::air::PublicValues;
+use thiserror::Error;
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::{report::ExecutionReport, state::ExecutionState, Executor, Program};
+
+/// The magic bytes at the start of every snapshot file.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"SP1SNAP\0";
+
+/// The version of the snapshot format written by this executor.
+///
+/// Must be bumped whenever the layout of [`ExecutionState`] or of the snapshot itself changes.
+pub const SNAPSHOT_VERSION: u32 = 6;
+
+/// Errors that can occur while saving or restoring an execution snapshot.
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    /// Reading or writing the snapshot file failed.
+    #[error("snapshot io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The snapshot could not be serialized or deserialized.
+    #[error("snapshot serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+
+    /// The file is not an execution snapshot.
+    #[error("not an execution snapshot")]
+    InvalidMagic,
+
+    /// The snapshot was written with an unsupported version of the format.
+    #[error("unsupported snapshot version {0}, expected {SNAPSHOT_VERSION}")]
+    UnsupportedVersion(u32),
+
+    /// The snapshot was taken while executing a different program.
+    #[error("snapshot was taken while executing a different program")]
+    ProgramMismatch,
+
+    /// The snapshot was taken while a hook was registered that is missing from the executor.
+    #[error("snapshot requires a hook registered for file descriptor {0}")]
+    MissingHook(u32),
+
+    /// Snapshots cannot be taken while the executor is in unconstrained mode.
+    #[error("cannot take a snapshot in unconstrained mode")]
+    Unconstrained,
+}
+
+/// The contents of a snapshot file, following the magic bytes and the version.
+#[derive(Serialize, Deserialize)]
+struct Snapshot<'a> {
+    program_digest: [u8; 32],
+    state: Cow<'a, ExecutionState>,
+    public_values: PublicValues<u32, u32>,
+    report: Cow<'a, ExecutionReport>,
+    cycle_tracker: Cow<'a, HashMap<String, (u64, u32)>>,
+    io_buf: Cow<'a, HashMap<u32, String>>,
+    hooks: Vec<u32>,
+}
+
+impl<'a> Executor<'a> {
+    /// Save a snapshot of the execution to the file at `path`.
+    ///
+    /// The execution can later be continued from the snapshot with [`Executor::resume`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the executor is in unconstrained mode or if the
+    /// file cannot be written.
+    pub fn snapshot(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_snapshot(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write a snapshot of the execution to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the executor is in unconstrained mode or if the
+    /// snapshot cannot be written.
+    pub fn write_snapshot(&self, mut writer: impl Write) -> Result<(), SnapshotError> {
+        if self.unconstrained {
+            return Err(SnapshotError::Unconstrained);
+        }
+
+        let mut hooks = self.hook_registry.table.keys().copied().collect::<Vec<_>>();
+        hooks.sort_unstable();
+        let snapshot = Snapshot {
+            program_digest: program_digest(&self.program),
+            state: Cow::Borrowed(&self.state),
+            public_values: self.record.public_values,
+            report: Cow::Borrowed(&self.report),
+            cycle_tracker: Cow::Borrowed(&self.cycle_tracker),
+            io_buf: Cow::Borrowed(&self.io_buf),
+            hooks,
+        };
+
+        writer.write_all(&SNAPSHOT_MAGIC)?;
+        writer.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        bincode::serialize_into(writer, &snapshot)?;
+        Ok(())
+    }
+
+    /// Restore the execution from the snapshot file at `path`.
+    ///
+    /// The executor must have been created for the same program the snapshot was taken from,
+    /// with the same hooks registered. Hooks themselves are not part of the snapshot: any state
+    /// they hold is the responsibility of the caller.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is not a valid snapshot, if it was taken
+    /// while executing a different program, or if a hook is missing.
+    pub fn resume(&mut self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        self.read_snapshot(BufReader::new(File::open(path)?))
+    }
+
+    /// Restore the execution from a snapshot read from `reader`.
+    ///
+    /// See [`Executor::resume`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the snapshot is not valid, if it was taken while
+    /// executing a different program, or if a hook is missing.
+    pub fn read_snapshot(&mut self, mut reader: impl Read) -> Result<(), SnapshotError> {
+        let mut magic = [0; SNAPSHOT_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let mut version = [0; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let snapshot: Snapshot = bincode::deserialize_from(reader)?;
+        if snapshot.program_digest != program_digest(&self.program) {
+            return Err(SnapshotError::ProgramMismatch);
+        }
+        if let Some(&fd) =
+            snapshot.hooks.iter().find(|&&fd| !self.hook_registry.table.contains_key(&fd))
+        {
+            return Err(SnapshotError::MissingHook(fd));
+        }
+
+        self.state = snapshot.state.into_owned();
+        self.record.public_values = snapshot.public_values;
+        self.report = snapshot.report.into_owned();
+        self.cycle_tracker = snapshot.cycle_tracker.into_owned();
+        self.io_buf = snapshot.io_buf.into_owned();
+        self.unconstrained = false;
+        Ok(())
+    }
+}
+
+/// A digest of the instructions, entrypoint and initial memory of a program.
+pub(crate) fn program_digest(program: &Program) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(&program.pc_start.to_le_bytes());
+    hasher.update(&program.pc_base.to_le_bytes());
+    hasher.update(&(program.instructions.len() as u64).to_le_bytes());
+    for instruction in &program.instructions {
+        hasher.update(&bincode::serialize(instruction).unwrap());
+    }
+    if let Some(pcs) = &program.instruction_pcs {
+        for pc in pcs {
+            hasher.update(&pc.to_le_bytes());
+        }
+    }
+    let mut memory_image = program.memory_image.iter().collect::<Vec<_>>();
+    memory_image.sort_unstable();
+    for (addr, value) in memory_image {
+        hasher.update(&addr.to_le_bytes());
+        hasher.update(&value.to_le_bytes());
+    }
+    let mut digest = [0; 32];
+    hasher.finalize(&mut digest);
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use crate::{
+        programs::tests::{fibonacci_program, simple_program, ssz_withdrawals_program},
+        ExecutionError, Executor,
+    };
+
+    use super::SnapshotError;
+
+    #[test]
+    fn test_snapshot_resume_matches_uninterrupted_run() {
+        let program = ssz_withdrawals_program();
+        let mut expected = Executor::new(program.clone(), SP1CoreOpts::default());
+        expected.run_fast().unwrap();
+
+        let mut paused = Executor::new(program.clone(), SP1CoreOpts::default());
+        paused.max_cycles = Some(expected.state.global_clk / 2);
+        assert!(matches!(paused.run_fast(), Err(ExecutionError::ExceededCycleLimit(_))));
+        let mut snapshot = Vec::new();
+        paused.write_snapshot(&mut snapshot).unwrap();
+
+        let mut resumed = Executor::new(program, SP1CoreOpts::default());
+        resumed.read_snapshot(snapshot.as_slice()).unwrap();
+        assert_eq!(resumed.state.global_clk, expected.state.global_clk / 2);
+        resumed.run_fast().unwrap();
+
+        assert_eq!(resumed.state.global_clk, expected.state.global_clk);
+        assert_eq!(resumed.state.public_values_stream, expected.state.public_values_stream);
+        assert_eq!(resumed.registers(), expected.registers());
+        assert_eq!(resumed.report.opcode_counts, expected.report.opcode_counts);
+        assert_eq!(
+            resumed.record.public_values.committed_value_digest,
+            expected.record.public_values.committed_value_digest
+        );
+    }
+
+    #[test]
+    fn test_snapshot_rejects_other_program() {
+        let mut runtime = Executor::new(simple_program(), SP1CoreOpts::default());
+        runtime.run_fast().unwrap();
+        let mut snapshot = Vec::new();
+        runtime.write_snapshot(&mut snapshot).unwrap();
+
+        let mut other = Executor::new(fibonacci_program(), SP1CoreOpts::default());
+        let err = other.read_snapshot(snapshot.as_slice()).unwrap_err();
+        assert!(matches!(err, SnapshotError::ProgramMismatch));
+
+        snapshot[0] ^= 1;
+        let err = runtime.read_snapshot(snapshot.as_slice()).unwrap_err();
+        assert!(matches!(err, SnapshotError::InvalidMagic));
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Seek, Write},
 };
@@ -8,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, StarkVerifyingKey};
 
 use crate::{
+    backtrace::{Call, CallStack},
     events::MemoryRecord,
     memory::PagedMemory,
     record::{ExecutionRecord, MemoryAccessRecord},
@@ -46,6 +48,19 @@ pub struct ExecutionState {
     /// A ptr to the current position in the input stream incremented by `HINT_READ` opcode.
     pub input_stream_ptr: usize,
 
+    /// The environment variables served to the program by the `GETENV` syscall.
+    pub env: BTreeMap<String, String>,
+
+    /// The randomness seed served to the program by the `RAND_SEED` syscall.
+    pub rand_seed: Option<[u8; 32]>,
+
+    /// The files served to the program by the `FS_OPEN` and `FS_READ` syscalls, by path.
+    pub files: BTreeMap<String, Vec<u8>>,
+
+    /// The files the program opened with the `FS_OPEN` syscall and did not close yet, by file
+    /// descriptor.
+    pub open_files: BTreeMap<u32, OpenFile>,
+
     /// A stream of proofs (reduce vk, proof, verifying key) inputted to the program.
     pub proof_stream:
         Vec<(SP1ReduceProof<BabyBearPoseidon2>, StarkVerifyingKey<BabyBearPoseidon2>)>,
@@ -62,6 +77,9 @@ pub struct ExecutionState {
 
     /// Keeps track of how many times a certain syscall has been called.
     pub syscall_counts: HashMap<SyscallCode, u64>,
+
+    /// The calls the program is in, used to build its backtrace.
+    pub(crate) call_stack: CallStack<Call>,
 }
 
 impl ExecutionState {
@@ -78,15 +96,29 @@ impl ExecutionState {
             uninitialized_memory: PagedMemory::default(),
             input_stream: Vec::new(),
             input_stream_ptr: 0,
+            env: BTreeMap::new(),
+            rand_seed: None,
+            files: BTreeMap::new(),
+            open_files: BTreeMap::new(),
             public_values_stream: Vec::new(),
             public_values_stream_ptr: 0,
             proof_stream: Vec::new(),
             proof_stream_ptr: 0,
             syscall_counts: HashMap::new(),
+            call_stack: CallStack::default(),
         }
     }
 }
 
+/// A file opened by the program with the `FS_OPEN` syscall.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFile {
+    /// The path the file was opened at.
+    pub path: String,
+    /// The position of the next byte served by the `FS_READ` syscall.
+    pub position: u64,
+}
+
 /// Holds data to track changes made to the runtime since a fork point.
 #[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
@@ -105,6 +137,8 @@ pub struct ForkState {
     pub record: ExecutionRecord,
     /// Whether `emit_events` was enabled at the fork point.
     pub executor_mode: ExecutorMode,
+    /// The calls the program was in at the fork point.
+    pub(crate) call_stack: CallStack<Call>,
 }
 
 impl ExecutionState {
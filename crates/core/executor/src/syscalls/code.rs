@@ -84,6 +84,24 @@ pub enum SyscallCode {
     /// Executes the `HINT_READ` precompile.
     HINT_READ = 0x00_00_00_F1,
 
+    /// Executes the `GETENV` precompile.
+    GETENV = 0x00_00_00_F2,
+
+    /// Executes the `RAND_SEED` precompile.
+    RAND_SEED = 0x00_00_00_F3,
+
+    /// Executes the `FS_OPEN` precompile.
+    FS_OPEN = 0x00_00_00_F4,
+
+    /// Executes the `FS_READ` precompile.
+    FS_READ = 0x00_00_00_F5,
+
+    /// Executes the `FS_SEEK` precompile.
+    FS_SEEK = 0x00_00_00_F6,
+
+    /// Executes the `FS_CLOSE` precompile.
+    FS_CLOSE = 0x00_00_00_F7,
+
     /// Executes the `UINT256_MUL` precompile.
     UINT256_MUL = 0x00_01_01_1D,
 
@@ -128,6 +146,24 @@ pub enum SyscallCode {
 
     /// Executes the `BN254_FP2_MUL` precompile.
     BN254_FP2_MUL = 0x00_01_01_2B,
+
+    /// Executes the `SECP256R1_ADD` precompile.
+    SECP256R1_ADD = 0x00_01_01_2C,
+
+    /// Executes the `SECP256R1_DOUBLE` precompile.
+    SECP256R1_DOUBLE = 0x00_00_01_2D,
+
+    /// Executes the `SECP256R1_DECOMPRESS` precompile.
+    SECP256R1_DECOMPRESS = 0x00_00_01_2E,
+
+    /// Executes the `BLAKE2B_COMPRESS` precompile.
+    BLAKE2B_COMPRESS = 0x00_60_01_2F,
+
+    /// Executes the `BLAKE3_COMPRESS` precompile.
+    BLAKE3_COMPRESS = 0x00_38_01_30,
+
+    /// Executes the `U256XU2048_MUL` precompile.
+    U256XU2048_MUL = 0x00_01_01_31,
 }
 
 impl SyscallCode {
@@ -147,6 +183,11 @@ impl SyscallCode {
             0x00_01_01_0A => SyscallCode::SECP256K1_ADD,
             0x00_00_01_0B => SyscallCode::SECP256K1_DOUBLE,
             0x00_00_01_0C => SyscallCode::SECP256K1_DECOMPRESS,
+            0x00_01_01_2C => SyscallCode::SECP256R1_ADD,
+            0x00_00_01_2D => SyscallCode::SECP256R1_DOUBLE,
+            0x00_00_01_2E => SyscallCode::SECP256R1_DECOMPRESS,
+            0x00_60_01_2F => SyscallCode::BLAKE2B_COMPRESS,
+            0x00_38_01_30 => SyscallCode::BLAKE3_COMPRESS,
             0x00_01_01_0E => SyscallCode::BN254_ADD,
             0x00_00_01_0F => SyscallCode::BN254_DOUBLE,
             0x00_01_01_1E => SyscallCode::BLS12381_ADD,
@@ -156,7 +197,14 @@ impl SyscallCode {
             0x00_00_00_1B => SyscallCode::VERIFY_SP1_PROOF,
             0x00_00_00_F0 => SyscallCode::HINT_LEN,
             0x00_00_00_F1 => SyscallCode::HINT_READ,
+            0x00_00_00_F2 => SyscallCode::GETENV,
+            0x00_00_00_F3 => SyscallCode::RAND_SEED,
+            0x00_00_00_F4 => SyscallCode::FS_OPEN,
+            0x00_00_00_F5 => SyscallCode::FS_READ,
+            0x00_00_00_F6 => SyscallCode::FS_SEEK,
+            0x00_00_00_F7 => SyscallCode::FS_CLOSE,
             0x00_01_01_1D => SyscallCode::UINT256_MUL,
+            0x00_01_01_31 => SyscallCode::U256XU2048_MUL,
             0x00_01_01_20 => SyscallCode::BLS12381_FP_ADD,
             0x00_01_01_21 => SyscallCode::BLS12381_FP_SUB,
             0x00_01_01_22 => SyscallCode::BLS12381_FP_MUL,
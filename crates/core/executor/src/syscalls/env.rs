@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+
+use super::{Syscall, SyscallCode, SyscallContext};
+
+pub(crate) struct GetEnvSyscall;
+
+impl Syscall for GetEnvSyscall {
+    /// Adds the environment of the program, encoded with [`encode_environment`], to the beginning
+    /// of the input stream, where it can be read with `HINT_LEN` and `HINT_READ`.
+    fn execute(
+        &self,
+        ctx: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        _: u32,
+        _: u32,
+    ) -> Option<u32> {
+        let served =
+            ctx.rt.serve_host_input(syscall_code, |rt| Ok(encode_environment(&rt.state.env)));
+        ctx.rt.syscall_error = served.err();
+        None
+    }
+}
+
+/// Encodes the environment of a program the way it is served by the `GETENV` syscall.
+///
+/// The variables are encoded in order of their names, each one as the little-endian `u32` length
+/// of its name, the name, the little-endian `u32` length of its value and the value.
+#[must_use]
+pub fn encode_environment(env: &BTreeMap<String, String>) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    for (key, value) in env {
+        for bytes in [key.as_bytes(), value.as_bytes()] {
+            encoded.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            encoded.extend_from_slice(bytes);
+        }
+    }
+    encoded
+}
+
+/// The digest of the environment of a program.
+///
+/// A program which reads its environment appends this digest to its public values when it halts,
+/// so that verifiers can check which environment the program saw. See
+/// [`FD_PUBLIC_VALUES`](sp1_primitives::consts::fd::FD_PUBLIC_VALUES) for where the digest goes.
+#[must_use]
+pub fn environment_digest(env: &BTreeMap<String, String>) -> [u8; 32] {
+    Sha256::digest(encode_environment(env)).into()
+}
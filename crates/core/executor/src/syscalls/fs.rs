@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+use sp1_primitives::fs::{file_chunk_hashes, file_digest};
+
+use super::{Syscall, SyscallCode, SyscallContext};
+use crate::{ExecutionError, OpenFile};
+
+/// The first file descriptor of the files opened by the program.
+///
+/// File descriptors are allocated like POSIX ones, as the lowest one which is not open, above those
+/// of the standard streams.
+const FIRST_FILE_FD: u32 = 3;
+
+pub(crate) struct FsOpenSyscall;
+
+impl Syscall for FsOpenSyscall {
+    /// Opens the file at the path `path_len` bytes long at `path_ptr`, and adds the response to the
+    /// beginning of the input stream, where it can be read with `HINT_LEN` and `HINT_READ`.
+    ///
+    /// The response is a `1` byte followed by the little-endian `u32` file descriptor of the file,
+    /// its little-endian `u64` length and the sha256 hashes of its chunks, or a single `0` byte if
+    /// there is no file at the path.
+    fn execute(
+        &self,
+        ctx: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        path_ptr: u32,
+        path_len: u32,
+    ) -> Option<u32> {
+        let rt = &mut ctx.rt;
+        let path = (0..path_len).map(|i| rt.byte(path_ptr + i)).collect::<Vec<u8>>();
+        let path = String::from_utf8(path).ok();
+        let fd = (FIRST_FILE_FD..).find(|fd| !rt.state.open_files.contains_key(fd)).unwrap();
+        let served = rt.serve_host_input(syscall_code, |rt| {
+            let file = path.as_ref().and_then(|path| rt.state.files.get(path));
+            Ok(match file {
+                Some(contents) => {
+                    let mut response = vec![1];
+                    response.extend_from_slice(&fd.to_le_bytes());
+                    response.extend_from_slice(&(contents.len() as u64).to_le_bytes());
+                    response.extend_from_slice(&file_chunk_hashes(contents));
+                    response
+                }
+                None => vec![0],
+            })
+        });
+        if let Err(error) = served {
+            rt.syscall_error = Some(error);
+            return None;
+        }
+
+        // The response may come from a replay log, which tells whether the file was found.
+        if let (Some(path), [1, ..]) =
+            (path, rt.state.input_stream[rt.state.input_stream_ptr].as_slice())
+        {
+            rt.state.open_files.insert(fd, OpenFile { path, position: 0 });
+        }
+        None
+    }
+}
+
+pub(crate) struct FsReadSyscall;
+
+impl Syscall for FsReadSyscall {
+    /// Reads up to `len` bytes of the file open at `fd` from its position, and adds them to the
+    /// beginning of the input stream, where they can be read with `HINT_LEN` and `HINT_READ`.
+    ///
+    /// Fewer bytes are read if the file ends first, advancing the position past the bytes read.
+    fn execute(
+        &self,
+        ctx: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        fd: u32,
+        len: u32,
+    ) -> Option<u32> {
+        let rt = &mut ctx.rt;
+        let Some(OpenFile { path, position }) = rt.state.open_files.get(&fd).cloned() else {
+            rt.syscall_error = Some(ExecutionError::InvalidSyscallArguments(syscall_code, fd, len));
+            return None;
+        };
+        let served = rt.serve_host_input(syscall_code, |rt| {
+            let contents = rt.state.files.get(&path).map_or(&[][..], Vec::as_slice);
+            let start = usize::try_from(position).unwrap_or(usize::MAX).min(contents.len());
+            let end = start.saturating_add(len as usize).min(contents.len());
+            Ok(contents[start..end].to_vec())
+        });
+        if let Err(error) = served {
+            rt.syscall_error = Some(error);
+            return None;
+        }
+
+        let read = rt.state.input_stream[rt.state.input_stream_ptr].len() as u64;
+        rt.state.open_files.get_mut(&fd).unwrap().position = position + read;
+        None
+    }
+}
+
+pub(crate) struct FsSeekSyscall;
+
+impl Syscall for FsSeekSyscall {
+    /// Moves the position of the file open at `fd` to `position` bytes from its start.
+    ///
+    /// The position may be past the end of the file, where reads return no bytes.
+    fn execute(
+        &self,
+        ctx: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        fd: u32,
+        position: u32,
+    ) -> Option<u32> {
+        match ctx.rt.state.open_files.get_mut(&fd) {
+            Some(file) => file.position = u64::from(position),
+            None => {
+                ctx.rt.syscall_error =
+                    Some(ExecutionError::InvalidSyscallArguments(syscall_code, fd, position));
+            }
+        }
+        None
+    }
+}
+
+pub(crate) struct FsCloseSyscall;
+
+impl Syscall for FsCloseSyscall {
+    /// Closes the file open at `fd`, freeing the file descriptor.
+    fn execute(
+        &self,
+        ctx: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        fd: u32,
+        arg2: u32,
+    ) -> Option<u32> {
+        if ctx.rt.state.open_files.remove(&fd).is_none() {
+            ctx.rt.syscall_error =
+                Some(ExecutionError::InvalidSyscallArguments(syscall_code, fd, arg2));
+        }
+        None
+    }
+}
+
+/// The digest of the files opened by a program.
+///
+/// `files` maps every path the program opened to the contents of the file, or to `None` if there
+/// was no file at the path. A program which opens files appends this digest to its public values
+/// when it halts, so that verifiers can check which files the program read. See
+/// [`FD_PUBLIC_VALUES`](sp1_primitives::consts::fd::FD_PUBLIC_VALUES) for where the digest goes.
+///
+/// The digest is the sha256 hash of the paths in order, each one as the little-endian `u32` length
+/// of the path, the path and either a `1` byte followed by the
+/// [`file_digest`](sp1_primitives::fs::file_digest) of the contents or a `0` byte.
+#[must_use]
+pub fn file_system_digest(files: &BTreeMap<String, Option<Vec<u8>>>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for (path, contents) in files {
+        hasher.update((path.len() as u32).to_le_bytes());
+        hasher.update(path.as_bytes());
+        match contents {
+            Some(contents) => {
+                hasher.update([1]);
+                hasher.update(file_digest(contents));
+            }
+            None => hasher.update([0]),
+        }
+    }
+    hasher.finalize().into()
+}
@@ -1,4 +1,7 @@
+use std::borrow::Cow;
+
 use super::{Syscall, SyscallCode, SyscallContext};
+use crate::replay::{replay_diverged, ReplayEvent};
 
 pub(crate) struct HintLenSyscall;
 
@@ -10,14 +13,29 @@ impl Syscall for HintLenSyscall {
         _arg1: u32,
         _arg2: u32,
     ) -> Option<u32> {
-        if ctx.rt.state.input_stream_ptr >= ctx.rt.state.input_stream.len() {
-            panic!(
-                "failed reading stdin due to insufficient input data: input_stream_ptr={}, input_stream_len={}",
-                ctx.rt.state.input_stream_ptr,
-                ctx.rt.state.input_stream.len()
-            );
-        }
-        Some(ctx.rt.state.input_stream[ctx.rt.state.input_stream_ptr].len() as u32)
+        let len = match ctx.rt.replay.next_event() {
+            Ok(Some(ReplayEvent::HintLen { len })) => len,
+            Ok(Some(event)) => {
+                ctx.rt.syscall_error = Some(replay_diverged(&event, "a hint length read"));
+                return None;
+            }
+            Err(error) => {
+                ctx.rt.syscall_error = Some(error);
+                return None;
+            }
+            Ok(None) => {
+                if ctx.rt.state.input_stream_ptr >= ctx.rt.state.input_stream.len() {
+                    panic!(
+                        "failed reading stdin due to insufficient input data: input_stream_ptr={}, input_stream_len={}",
+                        ctx.rt.state.input_stream_ptr,
+                        ctx.rt.state.input_stream.len()
+                    );
+                }
+                ctx.rt.state.input_stream[ctx.rt.state.input_stream_ptr].len() as u32
+            }
+        };
+        ctx.rt.replay.record(|| ReplayEvent::HintLen { len });
+        Some(len)
     }
 }
 
@@ -25,15 +43,29 @@ pub(crate) struct HintReadSyscall;
 
 impl Syscall for HintReadSyscall {
     fn execute(&self, ctx: &mut SyscallContext, _: SyscallCode, ptr: u32, len: u32) -> Option<u32> {
-        if ctx.rt.state.input_stream_ptr >= ctx.rt.state.input_stream.len() {
-            panic!(
-                "failed reading stdin due to insufficient input data: input_stream_ptr={}, input_stream_len={}",
-                ctx.rt.state.input_stream_ptr,
-                ctx.rt.state.input_stream.len()
-            );
-        }
-        let vec = &ctx.rt.state.input_stream[ctx.rt.state.input_stream_ptr];
-        ctx.rt.state.input_stream_ptr += 1;
+        let vec = match ctx.rt.replay.next_event() {
+            Ok(Some(ReplayEvent::HintRead { bytes })) => Cow::Owned(bytes),
+            Ok(Some(event)) => {
+                ctx.rt.syscall_error = Some(replay_diverged(&event, "a hint read"));
+                return None;
+            }
+            Err(error) => {
+                ctx.rt.syscall_error = Some(error);
+                return None;
+            }
+            Ok(None) => {
+                if ctx.rt.state.input_stream_ptr >= ctx.rt.state.input_stream.len() {
+                    panic!(
+                        "failed reading stdin due to insufficient input data: input_stream_ptr={}, input_stream_len={}",
+                        ctx.rt.state.input_stream_ptr,
+                        ctx.rt.state.input_stream.len()
+                    );
+                }
+                ctx.rt.state.input_stream_ptr += 1;
+                Cow::Borrowed(&ctx.rt.state.input_stream[ctx.rt.state.input_stream_ptr - 1])
+            }
+        };
+        ctx.rt.replay.record(|| ReplayEvent::HintRead { bytes: vec.to_vec() });
         assert!(!ctx.rt.unconstrained, "hint read should not be used in a unconstrained block");
         assert_eq!(vec.len() as u32, len, "hint input stream read length mismatch");
         assert_eq!(ptr % 4, 0, "hint read address not aligned to 4 bytes");
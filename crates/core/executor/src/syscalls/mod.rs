@@ -4,9 +4,12 @@ mod code;
 mod commit;
 mod context;
 mod deferred;
+mod env;
+mod fs;
 mod halt;
 mod hint;
 mod precompiles;
+mod rand_seed;
 mod unconstrained;
 mod verify;
 mod write;
@@ -15,6 +18,10 @@ use std::sync::Arc;
 
 use commit::CommitSyscall;
 use deferred::CommitDeferredSyscall;
+use env::GetEnvSyscall;
+pub use env::{encode_environment, environment_digest};
+pub use fs::file_system_digest;
+use fs::{FsCloseSyscall, FsOpenSyscall, FsReadSyscall, FsSeekSyscall};
 use halt::HaltSyscall;
 use hashbrown::HashMap;
 
@@ -22,10 +29,13 @@ pub use code::*;
 pub use context::*;
 use hint::{HintLenSyscall, HintReadSyscall};
 use precompiles::{
+    blake2b::compress::Blake2bCompressSyscall,
+    blake3::compress::Blake3CompressSyscall,
     edwards::{add::EdwardsAddAssignSyscall, decompress::EdwardsDecompressSyscall},
     fptower::{Fp2AddSubSyscall, Fp2MulSyscall, FpOpSyscall},
     keccak256::permute::Keccak256PermuteSyscall,
     sha256::{compress::Sha256CompressSyscall, extend::Sha256ExtendSyscall},
+    u256x2048_mul::U256xU2048MulSyscall,
     uint256::Uint256MulSyscall,
     weierstrass::{
         add::WeierstrassAddAssignSyscall, decompress::WeierstrassDecompressSyscall,
@@ -33,17 +43,20 @@ use precompiles::{
     },
 };
 
+use rand_seed::RandSeedSyscall;
 use sp1_curves::{
     edwards::ed25519::{Ed25519, Ed25519Parameters},
     weierstrass::{
         bls12_381::{Bls12381, Bls12381BaseField},
         bn254::{Bn254, Bn254BaseField},
         secp256k1::Secp256k1,
+        secp256r1::Secp256r1,
     },
 };
 use unconstrained::{EnterUnconstrainedSyscall, ExitUnconstrainedSyscall};
 use verify::VerifySyscall;
 use write::WriteSyscall;
+pub use write::{FD_OUT_OF_MEMORY, FD_PANIC};
 
 use crate::events::FieldOperation;
 
@@ -94,6 +107,10 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::KECCAK_PERMUTE, Arc::new(Keccak256PermuteSyscall));
 
+    syscall_map.insert(SyscallCode::BLAKE2B_COMPRESS, Arc::new(Blake2bCompressSyscall));
+
+    syscall_map.insert(SyscallCode::BLAKE3_COMPRESS, Arc::new(Blake3CompressSyscall));
+
     syscall_map.insert(
         SyscallCode::SECP256K1_ADD,
         Arc::new(WeierstrassAddAssignSyscall::<Secp256k1>::new()),
@@ -109,6 +126,21 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDecompressSyscall::<Secp256k1>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::SECP256R1_ADD,
+        Arc::new(WeierstrassAddAssignSyscall::<Secp256r1>::new()),
+    );
+
+    syscall_map.insert(
+        SyscallCode::SECP256R1_DOUBLE,
+        Arc::new(WeierstrassDoubleAssignSyscall::<Secp256r1>::new()),
+    );
+
+    syscall_map.insert(
+        SyscallCode::SECP256R1_DECOMPRESS,
+        Arc::new(WeierstrassDecompressSyscall::<Secp256r1>::new()),
+    );
+
     syscall_map
         .insert(SyscallCode::BN254_ADD, Arc::new(WeierstrassAddAssignSyscall::<Bn254>::new()));
 
@@ -129,6 +161,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::UINT256_MUL, Arc::new(Uint256MulSyscall));
 
+    syscall_map.insert(SyscallCode::U256XU2048_MUL, Arc::new(U256xU2048MulSyscall));
+
     syscall_map.insert(
         SyscallCode::BLS12381_FP_ADD,
         Arc::new(FpOpSyscall::<Bls12381BaseField>::new(FieldOperation::Add)),
@@ -201,6 +235,18 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::HINT_READ, Arc::new(HintReadSyscall));
 
+    syscall_map.insert(SyscallCode::GETENV, Arc::new(GetEnvSyscall));
+
+    syscall_map.insert(SyscallCode::RAND_SEED, Arc::new(RandSeedSyscall));
+
+    syscall_map.insert(SyscallCode::FS_OPEN, Arc::new(FsOpenSyscall));
+
+    syscall_map.insert(SyscallCode::FS_READ, Arc::new(FsReadSyscall));
+
+    syscall_map.insert(SyscallCode::FS_SEEK, Arc::new(FsSeekSyscall));
+
+    syscall_map.insert(SyscallCode::FS_CLOSE, Arc::new(FsCloseSyscall));
+
     syscall_map.insert(
         SyscallCode::BLS12381_DECOMPRESS,
         Arc::new(WeierstrassDecompressSyscall::<Bls12381>::new()),
@@ -0,0 +1,136 @@
+use crate::{
+    events::{
+        Blake2bCompressEvent, MemoryWriteRecord, PrecompileEvent, BLAKE2B_IV, BLAKE2B_NUM_ROUNDS,
+        BLAKE2B_SIGMA, BLAKE_G_STATE_INDICES, BLAKE_NUM_FINALIZE_STEPS,
+    },
+    syscalls::{precompiles::are_disjoint_words, Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+/// The number of u32 words in the state and in the message, each being 16 u64's.
+const NUM_WORDS: u32 = 32;
+
+pub(crate) struct Blake2bCompressSyscall;
+
+impl Syscall for Blake2bCompressSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        (BLAKE2B_NUM_ROUNDS * 8 + BLAKE_NUM_FINALIZE_STEPS) as u32
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Option<u32> {
+        let clk_init = rt.clk;
+        let state_ptr = arg1;
+        let message_ptr = arg2;
+        if !are_disjoint_words(state_ptr, message_ptr, NUM_WORDS) {
+            rt.rt.syscall_error =
+                Some(ExecutionError::InvalidSyscallArguments(syscall_code, arg1, arg2));
+            return None;
+        }
+
+        // The state holds the chaining value `h`, followed by the words which are XORed with the
+        // IV to initialize the second half of the working vector: the counter `t` and the final
+        // block flag `f` in words 12 to 14, and zeros otherwise.
+        let mut state = rt
+            .slice_unsafe(state_ptr, NUM_WORDS as usize)
+            .chunks_exact(2)
+            .map(|limbs| limbs[0] as u64 | (limbs[1] as u64) << 32)
+            .collect::<Vec<_>>();
+        let h: [u64; 8] = core::array::from_fn(|i| state[i]);
+        for (word, iv) in state[8..].iter_mut().zip(BLAKE2B_IV) {
+            *word ^= iv;
+        }
+
+        let mut state_writes = Vec::new();
+        let mut message_reads = Vec::new();
+        for round in 0..BLAKE2B_NUM_ROUNDS {
+            let sigma = &BLAKE2B_SIGMA[round % 10];
+            for (i, indices) in BLAKE_G_STATE_INDICES.iter().enumerate() {
+                // Read the low and high halves of the two message words.
+                let mut records = Vec::with_capacity(4);
+                let message = [2 * i, 2 * i + 1].map(|j| {
+                    let addr = message_ptr + sigma[j] as u32 * 8;
+                    let (lo_record, lo) = rt.mr(addr);
+                    let (hi_record, hi) = rt.mr(addr + 4);
+                    records.extend([lo_record, hi_record]);
+                    lo as u64 | (hi as u64) << 32
+                });
+                message_reads.push(records.try_into().unwrap());
+
+                // Mix the four words of the working vector.
+                let mixed = g(indices.map(|index| state[index]), message);
+                for (index, value) in indices.iter().zip(mixed) {
+                    state[*index] = value;
+                }
+
+                // Write the low and high halves of `a`, `b`, `c` and `d`.
+                state_writes.push(write_words(rt, state_ptr, &state, indices));
+                rt.clk += 1;
+            }
+        }
+
+        // Finalize the chaining value, `h[i] ^= v[i] ^ v[i + 8]`, leaving the second half of the
+        // working vector in the state.
+        for (i, indices) in BLAKE_G_STATE_INDICES[..BLAKE_NUM_FINALIZE_STEPS].iter().enumerate() {
+            state[i] ^= h[i] ^ state[i + 8];
+            state[i + 4] ^= h[i + 4] ^ state[i + 12];
+            state_writes.push(write_words(rt, state_ptr, &state, indices));
+            rt.clk += 1;
+        }
+
+        // Push the Blake2b compress event.
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Blake2bCompress(Blake2bCompressEvent {
+            lookup_id,
+            shard,
+            clk: clk_init,
+            state_ptr,
+            message_ptr,
+            state_writes,
+            message_reads,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(clk_init, syscall_code.syscall_id(), arg1, arg2, lookup_id);
+        rt.record_mut().add_precompile_event(syscall_code, syscall_event, event);
+
+        None
+    }
+}
+
+/// Writes the low and high halves of the words of `state` at `indices`.
+fn write_words(
+    rt: &mut SyscallContext,
+    state_ptr: u32,
+    state: &[u64],
+    indices: &[usize; 4],
+) -> [MemoryWriteRecord; 8] {
+    let mut records = Vec::with_capacity(8);
+    for index in indices {
+        let addr = state_ptr + *index as u32 * 8;
+        records.push(rt.mw(addr, state[*index] as u32));
+        records.push(rt.mw(addr + 4, (state[*index] >> 32) as u32));
+    }
+    records.try_into().unwrap()
+}
+
+/// The Blake2b G function, which mixes the words `a`, `b`, `c` and `d` with the message words `x`
+/// and `y`.
+#[allow(clippy::many_single_char_names)]
+fn g([mut a, mut b, mut c, mut d]: [u64; 4], [x, y]: [u64; 2]) -> [u64; 4] {
+    a = a.wrapping_add(b).wrapping_add(x);
+    d = (d ^ a).rotate_right(32);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(24);
+    a = a.wrapping_add(b).wrapping_add(y);
+    d = (d ^ a).rotate_right(16);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(63);
+    [a, b, c, d]
+}
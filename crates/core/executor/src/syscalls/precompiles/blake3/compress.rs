@@ -0,0 +1,111 @@
+use crate::{
+    events::{
+        Blake3CompressEvent, PrecompileEvent, BLAKE3_IV, BLAKE3_MSG_SCHEDULE, BLAKE3_NUM_ROUNDS,
+        BLAKE_G_STATE_INDICES, BLAKE_NUM_FINALIZE_STEPS,
+    },
+    syscalls::{precompiles::are_disjoint_words, Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+/// The number of u32 words in the state and in the message.
+const NUM_WORDS: u32 = 16;
+
+pub(crate) struct Blake3CompressSyscall;
+
+impl Syscall for Blake3CompressSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        (BLAKE3_NUM_ROUNDS * 8 + BLAKE_NUM_FINALIZE_STEPS) as u32
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Option<u32> {
+        let clk_init = rt.clk;
+        let state_ptr = arg1;
+        let message_ptr = arg2;
+        if !are_disjoint_words(state_ptr, message_ptr, NUM_WORDS) {
+            rt.rt.syscall_error =
+                Some(ExecutionError::InvalidSyscallArguments(syscall_code, arg1, arg2));
+            return None;
+        }
+
+        // The state holds the chaining value, followed by the words which are XORed with the
+        // first half of the IV (zeros), the counter, the block length and the flags.
+        let mut state = rt.slice_unsafe(state_ptr, NUM_WORDS as usize);
+        let cv: [u32; 8] = core::array::from_fn(|i| state[i]);
+        for (word, iv) in state[8..12].iter_mut().zip(BLAKE3_IV) {
+            *word ^= iv;
+        }
+
+        let mut state_writes = Vec::new();
+        let mut message_reads = Vec::new();
+        for round in 0..BLAKE3_NUM_ROUNDS {
+            for (i, indices) in BLAKE_G_STATE_INDICES.iter().enumerate() {
+                // Read the two message words.
+                let message = [2 * i, 2 * i + 1]
+                    .map(|j| rt.mr(message_ptr + BLAKE3_MSG_SCHEDULE[round][j] as u32 * 4));
+                message_reads.push(message.map(|(record, _)| record));
+
+                // Mix the four words of the working vector.
+                let mixed = g(indices.map(|index| state[index]), message.map(|(_, value)| value));
+                for (index, value) in indices.iter().zip(mixed) {
+                    state[*index] = value;
+                }
+
+                // Write `a`, `b`, `c` and `d`.
+                state_writes
+                    .push(indices.map(|index| rt.mw(state_ptr + index as u32 * 4, state[index])));
+                rt.clk += 1;
+            }
+        }
+
+        // Finalize the output, `v[i] ^= v[i + 8]` and `v[i + 8] ^= cv[i]`.
+        for (i, indices) in BLAKE_G_STATE_INDICES[..BLAKE_NUM_FINALIZE_STEPS].iter().enumerate() {
+            for j in [i, i + 4] {
+                state[j] ^= state[j + 8];
+                state[j + 8] ^= cv[j];
+            }
+            state_writes
+                .push(indices.map(|index| rt.mw(state_ptr + index as u32 * 4, state[index])));
+            rt.clk += 1;
+        }
+
+        // Push the BLAKE3 compress event.
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Blake3Compress(Blake3CompressEvent {
+            lookup_id,
+            shard,
+            clk: clk_init,
+            state_ptr,
+            message_ptr,
+            state_writes,
+            message_reads,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(clk_init, syscall_code.syscall_id(), arg1, arg2, lookup_id);
+        rt.record_mut().add_precompile_event(syscall_code, syscall_event, event);
+
+        None
+    }
+}
+
+/// The BLAKE3 G function, which mixes the words `a`, `b`, `c` and `d` with the message words `x`
+/// and `y`.
+#[allow(clippy::many_single_char_names)]
+fn g([mut a, mut b, mut c, mut d]: [u32; 4], [x, y]: [u32; 2]) -> [u32; 4] {
+    a = a.wrapping_add(b).wrapping_add(x);
+    d = (d ^ a).rotate_right(16);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(12);
+    a = a.wrapping_add(b).wrapping_add(y);
+    d = (d ^ a).rotate_right(8);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(7);
+    [a, b, c, d]
+}
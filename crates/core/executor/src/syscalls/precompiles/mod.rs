@@ -1,6 +1,18 @@
+pub mod blake2b;
+pub mod blake3;
 pub mod edwards;
 pub mod fptower;
 pub mod keccak256;
 pub mod sha256;
+pub mod u256x2048_mul;
 pub mod uint256;
 pub mod weierstrass;
+
+/// Whether the ranges of `len` words at `a` and at `b` are word-aligned, fit in the address space
+/// and do not overlap.
+pub(crate) fn are_disjoint_words(a: u32, b: u32, len: u32) -> bool {
+    let (Some(a_end), Some(b_end)) = (a.checked_add(4 * len), b.checked_add(4 * len)) else {
+        return false;
+    };
+    a % 4 == 0 && b % 4 == 0 && (a_end <= b || b_end <= a)
+}
@@ -0,0 +1,93 @@
+use num::BigUint;
+use sp1_primitives::consts::{bytes_to_words_le, words_to_bytes_le_vec, WORD_SIZE};
+
+use crate::{
+    events::{PrecompileEvent, U256xU2048MulEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    Register,
+};
+
+/// The number of words in a uint256.
+const U256_NUM_WORDS: usize = 8;
+
+/// The number of words in a uint2048.
+const U2048_NUM_WORDS: usize = 64;
+
+/// The number of bytes in a uint256.
+const U256_NUM_BYTES: usize = U256_NUM_WORDS * WORD_SIZE;
+
+/// The number of bytes in a uint2048.
+const U2048_NUM_BYTES: usize = U2048_NUM_WORDS * WORD_SIZE;
+
+pub(crate) struct U256xU2048MulSyscall;
+
+impl Syscall for U256xU2048MulSyscall {
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Option<u32> {
+        let clk = rt.clk;
+
+        let a_ptr = arg1;
+        let b_ptr = arg2;
+        assert!(a_ptr % 4 == 0 && b_ptr % 4 == 0, "a and b must be word aligned");
+
+        // The pointers to the lo and hi outputs are passed in the registers `a2` and `a3`.
+        let (lo_ptr_memory, lo_ptr) = rt.mr(Register::X12 as u32);
+        let (hi_ptr_memory, hi_ptr) = rt.mr(Register::X13 as u32);
+        assert!(lo_ptr % 4 == 0 && hi_ptr % 4 == 0, "lo and hi must be word aligned");
+
+        // Read the a and b values.
+        let (a_memory_records, a) = rt.mr_slice(a_ptr, U256_NUM_WORDS);
+        let (b_memory_records, b) = rt.mr_slice(b_ptr, U2048_NUM_WORDS);
+        let uint256_a = BigUint::from_bytes_le(&words_to_bytes_le_vec(&a));
+        let uint2048_b = BigUint::from_bytes_le(&words_to_bytes_le_vec(&b));
+
+        // Split the 2304-bit product into its low 2048 bits and its high 256 bits.
+        let mut result_bytes = (uint256_a * uint2048_b).to_bytes_le();
+        result_bytes.resize(U2048_NUM_BYTES + U256_NUM_BYTES, 0u8);
+        let lo = bytes_to_words_le::<U2048_NUM_WORDS>(&result_bytes[..U2048_NUM_BYTES]);
+        let hi = bytes_to_words_le::<U256_NUM_WORDS>(&result_bytes[U2048_NUM_BYTES..]);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        // Write the result to lo and hi and keep track of the memory records.
+        let lo_memory_records = rt.mw_slice(lo_ptr, &lo);
+        let hi_memory_records = rt.mw_slice(hi_ptr, &hi);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::U256xU2048Mul(U256xU2048MulEvent {
+            lookup_id,
+            shard,
+            clk,
+            a_ptr,
+            a,
+            b_ptr,
+            b,
+            lo_ptr,
+            lo_ptr_memory,
+            lo: lo.to_vec(),
+            hi_ptr,
+            hi_ptr_memory,
+            hi: hi.to_vec(),
+            a_memory_records,
+            b_memory_records,
+            lo_memory_records,
+            hi_memory_records,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(clk, syscall_code.syscall_id(), arg1, arg2, lookup_id);
+        rt.record_mut().add_precompile_event(syscall_code, syscall_event, event);
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
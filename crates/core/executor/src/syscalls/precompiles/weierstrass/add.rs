@@ -35,6 +35,11 @@ impl<E: EllipticCurve> Syscall for WeierstrassAddAssignSyscall<E> {
                 syscall_event,
                 PrecompileEvent::Secp256k1Add(event),
             ),
+            CurveType::Secp256r1 => rt.record_mut().add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Secp256r1Add(event),
+            ),
             CurveType::Bn254 => {
                 rt.record_mut().add_precompile_event(
                     syscall_code,
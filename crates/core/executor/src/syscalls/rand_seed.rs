@@ -0,0 +1,22 @@
+use super::{Syscall, SyscallCode, SyscallContext};
+use crate::ExecutionError;
+
+pub(crate) struct RandSeedSyscall;
+
+impl Syscall for RandSeedSyscall {
+    /// Adds the randomness seed of the program to the beginning of the input stream, where it can
+    /// be read with `HINT_LEN` and `HINT_READ`.
+    fn execute(
+        &self,
+        ctx: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        _: u32,
+        _: u32,
+    ) -> Option<u32> {
+        let served = ctx.rt.serve_host_input(syscall_code, |rt| {
+            rt.state.rand_seed.map(|seed| seed.to_vec()).ok_or(ExecutionError::MissingRandSeed)
+        });
+        ctx.rt.syscall_error = served.err();
+        None
+    }
+}
@@ -20,6 +20,7 @@ impl Syscall for EnterUnconstrainedSyscall {
             record: std::mem::take(&mut ctx.rt.record),
             op_record: std::mem::take(&mut ctx.rt.memory_accesses),
             executor_mode: ctx.rt.executor_mode,
+            call_stack: ctx.rt.state.call_stack.clone(),
         };
         ctx.rt.executor_mode = ExecutorMode::Simple;
         Some(1)
@@ -49,6 +50,7 @@ impl Syscall for ExitUnconstrainedSyscall {
             ctx.rt.record = std::mem::take(&mut ctx.rt.unconstrained_state.record);
             ctx.rt.memory_accesses = std::mem::take(&mut ctx.rt.unconstrained_state.op_record);
             ctx.rt.executor_mode = ctx.rt.unconstrained_state.executor_mode;
+            ctx.rt.state.call_stack = std::mem::take(&mut ctx.rt.unconstrained_state.call_stack);
             ctx.rt.unconstrained = false;
         }
         ctx.rt.unconstrained_state = ForkState::default();
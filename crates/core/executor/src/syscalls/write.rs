@@ -1,9 +1,14 @@
-use sp1_primitives::consts::num_to_comma_separated;
+use sp1_primitives::consts::{
+    fd::{FD_HINT, FD_PUBLIC_VALUES},
+    num_to_comma_separated,
+};
 
-use crate::{Executor, Register};
+use crate::{Executor, PanicLocation, Register};
 
 use super::{Syscall, SyscallCode, SyscallContext};
 
+pub use sp1_primitives::consts::fd::{FD_OUT_OF_MEMORY, FD_PANIC};
+
 pub(crate) struct WriteSyscall;
 
 impl Syscall for WriteSyscall {
@@ -23,9 +28,18 @@ impl Syscall for WriteSyscall {
     /// If fd = 4:
     /// - Update the input stream.
     ///
-    /// If the fd matches a hook in the hook registry, invoke the hook.
+    /// If fd = 9:
+    /// - Record the failed allocation reported by the program when it runs out of memory.
+    ///
+    /// If fd = 10:
+    /// - Record the location and message of the panic reported by the program.
+    ///
+    /// Else, if the fd matches a hook in the hook registry, invoke the hook.
     ///
     /// Else, log a warning.
+    ///
+    /// Writes and hook responses are recorded or replayed according to the executor's
+    /// [`ReplayMode`](crate::ReplayMode).
     #[allow(clippy::pedantic)]
     fn execute(
         &self,
@@ -42,6 +56,27 @@ impl Syscall for WriteSyscall {
         // Read nbytes from memory starting at write_buf.
         let bytes = (0..nbytes).map(|i| rt.byte(write_buf + i)).collect::<Vec<u8>>();
         let slice = bytes.as_slice();
+        // The file descriptors served by the executor itself can't be shadowed by hooks.
+        let reserved = (1..=4).contains(&fd) || fd == FD_OUT_OF_MEMORY || fd == FD_PANIC;
+        if !reserved {
+            match rt.invoke_hook(fd, slice) {
+                Ok(Some(res)) => {
+                    // Add result vectors to the beginning of the stream.
+                    let ptr = rt.state.input_stream_ptr;
+                    rt.state.input_stream.splice(ptr..ptr, res);
+                    return None;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    rt.syscall_error = Some(error);
+                    return None;
+                }
+            }
+        }
+        if let Err(error) = rt.replay_write(fd, slice) {
+            rt.syscall_error = Some(error);
+            return None;
+        }
         if fd == 1 {
             let s = core::str::from_utf8(slice).unwrap();
             match parse_cycle_tracker_command(s) {
@@ -60,15 +95,16 @@ impl Syscall for WriteSyscall {
             if !flush_s.is_empty() {
                 flush_s.into_iter().for_each(|line| println!("stderr: {}", line));
             }
-        } else if fd == 3 {
+        } else if fd == FD_PUBLIC_VALUES {
             rt.state.public_values_stream.extend_from_slice(slice);
-        } else if fd == 4 {
+        } else if fd == FD_HINT {
             rt.state.input_stream.push(slice.to_vec());
-        } else if let Some(mut hook) = rt.hook_registry.get(fd) {
-            let res = hook.invoke_hook(rt.hook_env(), slice);
-            // Add result vectors to the beginning of the stream.
-            let ptr = rt.state.input_stream_ptr;
-            rt.state.input_stream.splice(ptr..ptr, res);
+        } else if fd == FD_OUT_OF_MEMORY && slice.len() == 8 {
+            let requested = u32::from_le_bytes(slice[0..4].try_into().unwrap());
+            let high_water_mark = u32::from_le_bytes(slice[4..8].try_into().unwrap());
+            rt.out_of_memory = Some((requested, high_water_mark));
+        } else if fd == FD_PANIC && slice.len() >= 12 {
+            rt.panic = parse_panic(slice);
         } else {
             tracing::warn!("tried to write to unknown file descriptor {fd}");
         }
@@ -76,6 +112,16 @@ impl Syscall for WriteSyscall {
     }
 }
 
+/// Parse the location and message of a panic reported through [`FD_PANIC`].
+fn parse_panic(slice: &[u8]) -> Option<(PanicLocation, String)> {
+    let line = u32::from_le_bytes(slice[0..4].try_into().unwrap());
+    let column = u32::from_le_bytes(slice[4..8].try_into().unwrap());
+    let file_len = u32::from_le_bytes(slice[8..12].try_into().unwrap()) as usize;
+    let (file, message) = slice[12..].split_at_checked(file_len)?;
+    let location = PanicLocation { file: String::from_utf8_lossy(file).into_owned(), line, column };
+    Some((location, String::from_utf8_lossy(message).into_owned()))
+}
+
 /// An enum representing the different cycle tracker commands.
 #[derive(Clone)]
 enum CycleTrackerCommand {
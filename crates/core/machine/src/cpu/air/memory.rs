@@ -12,6 +12,7 @@ use crate::{
     operations::BabyBearWordRangeChecker,
 };
 use sp1_core_executor::{events::MemoryAccessPosition, Opcode};
+use sp1_primitives::consts::WORD_SIZE;
 
 impl CpuChip {
     /// Computes whether the opcode is a memory instruction.
@@ -27,6 +28,41 @@ impl CpuChip {
             + opcode_selectors.is_sb
             + opcode_selectors.is_sh
             + opcode_selectors.is_sw
+            + self.is_atomic_instruction::<AB>(opcode_selectors)
+    }
+
+    /// Computes whether the opcode is an atomic memory instruction.
+    pub(crate) fn is_atomic_instruction<AB: SP1AirBuilder>(
+        &self,
+        opcode_selectors: &OpcodeSelectorCols<AB::Var>,
+    ) -> AB::Expr {
+        opcode_selectors.is_lr
+            + opcode_selectors.is_sc
+            + opcode_selectors.is_amoswap
+            + self.is_amo_alu_instruction::<AB>(opcode_selectors)
+            + self.is_amo_cmp_instruction::<AB>(opcode_selectors)
+    }
+
+    /// Computes whether the opcode is an AMO instruction storing the result of an ALU operation.
+    pub(crate) fn is_amo_alu_instruction<AB: SP1AirBuilder>(
+        &self,
+        opcode_selectors: &OpcodeSelectorCols<AB::Var>,
+    ) -> AB::Expr {
+        opcode_selectors.is_amoadd
+            + opcode_selectors.is_amoxor
+            + opcode_selectors.is_amoand
+            + opcode_selectors.is_amoor
+    }
+
+    /// Computes whether the opcode is an AMO instruction storing the minimum or the maximum.
+    pub(crate) fn is_amo_cmp_instruction<AB: SP1AirBuilder>(
+        &self,
+        opcode_selectors: &OpcodeSelectorCols<AB::Var>,
+    ) -> AB::Expr {
+        opcode_selectors.is_amomin
+            + opcode_selectors.is_amomax
+            + opcode_selectors.is_amominu
+            + opcode_selectors.is_amomaxu
     }
 
     /// Computes whether the opcode is a load instruction.
@@ -65,7 +101,8 @@ impl CpuChip {
         // Get the memory specific columns.
         let memory_columns = local.opcode_specific_columns.memory();
 
-        // Send to the ALU table to verify correct calculation of addr_word.
+        // Send to the ALU table to verify correct calculation of addr_word.  Atomic instructions
+        // have no offset, so their address is checked in `eval_memory_atomic`.
         builder.send_alu(
             AB::Expr::from_canonical_u32(Opcode::ADD as u32),
             memory_columns.addr_word,
@@ -73,7 +110,8 @@ impl CpuChip {
             local.op_c_val(),
             local.shard,
             memory_columns.addr_word_nonce,
-            is_memory_instruction.clone(),
+            self.is_load_instruction::<AB>(&local.selectors)
+                + self.is_store_instruction::<AB>(&local.selectors),
         );
 
         // Range check the addr_word to be a valid babybear word.
@@ -261,6 +299,94 @@ impl CpuChip {
             .assert_word_eq(mem_val.map(|x| x.into()), a_val.map(|x| x.into()));
     }
 
+    /// Evaluates constraints related to the atomic memory instructions.
+    ///
+    /// The zkVM executes a single hart, so SC always succeeds.
+    pub(crate) fn eval_memory_atomic<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &CpuCols<AB::Var>,
+    ) {
+        let memory_columns = local.opcode_specific_columns.memory();
+        let is_atomic = self.is_atomic_instruction::<AB>(&local.selectors);
+        let is_amo_alu = self.is_amo_alu_instruction::<AB>(&local.selectors);
+        let is_amo_cmp = self.is_amo_cmp_instruction::<AB>(&local.selectors);
+
+        let mem_val = *memory_columns.memory_access.value();
+        let prev_mem_val = *memory_columns.memory_access.prev_value();
+
+        // The address is op_b without any offset, and it must be aligned.
+        builder.when(is_atomic.clone()).assert_word_eq(memory_columns.addr_word, local.op_b_val());
+        builder.when(is_atomic.clone()).assert_zero(memory_columns.addr_offset);
+
+        // When not writing to x0, LR and the AMO instructions write the loaded value to op_a.
+        builder
+            .when(is_atomic - local.selectors.is_sc)
+            .when(AB::Expr::one() - local.instruction.op_a_0)
+            .assert_word_eq(local.op_a_val(), prev_mem_val);
+
+        // LR does not change the memory value.
+        builder.when(local.selectors.is_lr).assert_word_eq(mem_val, prev_mem_val);
+
+        // SC writes zero to op_a to signal its success.
+        builder.when(local.selectors.is_sc).assert_word_zero(local.op_a_val());
+
+        // SC and AMOSWAP store op_c.
+        builder
+            .when(local.selectors.is_sc + local.selectors.is_amoswap)
+            .assert_word_eq(mem_val, local.op_c_val());
+
+        // AMOADD, AMOXOR, AMOAND and AMOOR store the result of the ALU operation on the loaded
+        // value and op_c.
+        let alu_opcode = local.selectors.is_amoadd * Opcode::ADD.as_field::<AB::F>()
+            + local.selectors.is_amoxor * Opcode::XOR.as_field::<AB::F>()
+            + local.selectors.is_amoand * Opcode::AND.as_field::<AB::F>()
+            + local.selectors.is_amoor * Opcode::OR.as_field::<AB::F>();
+        builder.send_alu(
+            alu_opcode,
+            mem_val,
+            prev_mem_val,
+            local.op_c_val(),
+            local.shard,
+            local.nonce,
+            is_amo_alu,
+        );
+
+        // The other AMO instructions compare the loaded value to op_c, and store the minimum or
+        // the maximum of the two.
+        let cmp_opcode = (local.selectors.is_amomin + local.selectors.is_amomax)
+            * Opcode::SLT.as_field::<AB::F>()
+            + (local.selectors.is_amominu + local.selectors.is_amomaxu)
+                * Opcode::SLTU.as_field::<AB::F>();
+        builder.send_alu(
+            cmp_opcode,
+            Word([
+                memory_columns.amo_lt.into(),
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+            ]),
+            prev_mem_val,
+            local.op_c_val(),
+            local.shard,
+            local.nonce,
+            is_amo_cmp,
+        );
+
+        let is_min = local.selectors.is_amomin + local.selectors.is_amominu;
+        let is_max = local.selectors.is_amomax + local.selectors.is_amomaxu;
+        let lt = memory_columns.amo_lt;
+        let op_c_val = local.op_c_val();
+        for i in 0..WORD_SIZE {
+            builder
+                .when(is_min.clone())
+                .assert_eq(mem_val[i], lt * prev_mem_val[i] + (AB::Expr::one() - lt) * op_c_val[i]);
+            builder
+                .when(is_max.clone())
+                .assert_eq(mem_val[i], lt * op_c_val[i] + (AB::Expr::one() - lt) * prev_mem_val[i]);
+        }
+    }
+
     /// This function is used to evaluate the unsigned memory value for the load memory
     /// instructions.
     pub(crate) fn eval_unsigned_mem_value<AB: SP1AirBuilder>(
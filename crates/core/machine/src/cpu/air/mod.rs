@@ -62,6 +62,7 @@ where
         self.eval_memory_address_and_access::<AB>(builder, local, is_memory_instruction.clone());
         self.eval_memory_load::<AB>(builder, local);
         self.eval_memory_store::<AB>(builder, local);
+        self.eval_memory_atomic::<AB>(builder, local);
 
         // ALU instructions.
         builder.send_alu(
@@ -143,14 +144,15 @@ impl CpuChip {
 
         let is_jump_instruction = local.selectors.is_jal + local.selectors.is_jalr;
 
-        // Verify that the local.pc + 4 is saved in op_a for both jump instructions.
+        // Verify that the address of the next instruction is saved in op_a for both jump
+        // instructions.
         // When op_a is set to register X0, the RISC-V spec states that the jump instruction will
         // not have a return destination address (it is effectively a GOTO command).  In this case,
         // we shouldn't verify the return address.
-        builder
-            .when(is_jump_instruction.clone())
-            .when_not(local.instruction.op_a_0)
-            .assert_eq(local.op_a_val().reduce::<AB>(), local.pc + AB::F::from_canonical_u8(4));
+        builder.when(is_jump_instruction.clone()).when_not(local.instruction.op_a_0).assert_eq(
+            local.op_a_val().reduce::<AB>(),
+            local.pc + self.instruction_size::<AB>(local),
+        );
 
         // Verify that the word form of local.pc is correct for JAL instructions.
         builder.when(local.selectors.is_jal).assert_eq(jump_columns.pc.reduce::<AB>(), local.pc);
@@ -312,21 +314,34 @@ impl CpuChip {
                     + is_halt),
         );
 
-        // Verify that the pc increments by 4 for all instructions except branch, jump and halt
-        // instructions. The other case is handled by eval_jump, eval_branch and eval_ecall
-        // (for halt).
+        // Verify that the pc increments by the instruction size for all instructions except branch,
+        // jump and halt instructions. The other case is handled by eval_jump, eval_branch and
+        // eval_ecall (for halt).
         builder
             .when_transition()
             .when(next.is_real)
             .when(local.is_sequential_instr)
-            .assert_eq(local.pc + AB::Expr::from_canonical_u8(4), next.pc);
+            .assert_eq(local.pc + self.instruction_size::<AB>(local), next.pc);
 
         // When the last row is real and it's a sequential instruction, assert that local.next_pc
-        // <==> local.pc + 4
+        // <==> local.pc + instruction size
         builder
             .when(local.is_real)
             .when(local.is_sequential_instr)
-            .assert_eq(local.pc + AB::Expr::from_canonical_u8(4), local.next_pc);
+            .assert_eq(local.pc + self.instruction_size::<AB>(local), local.next_pc);
+    }
+
+    /// The size in bytes of the instruction, which is 2 for compressed instructions and 4
+    /// otherwise.
+    ///
+    /// Without the `rvc` feature, every instruction is 4 bytes, as in the released circuit.
+    #[allow(unused_variables)]
+    pub(crate) fn instruction_size<AB: SP1AirBuilder>(&self, local: &CpuCols<AB::Var>) -> AB::Expr {
+        #[cfg(feature = "rvc")]
+        return AB::Expr::from_canonical_u8(4)
+            - AB::Expr::from_canonical_u8(2) * local.instruction.is_compressed;
+        #[cfg(not(feature = "rvc"))]
+        AB::Expr::from_canonical_u8(4)
     }
 
     /// Constraints related to the public values.
@@ -24,6 +24,11 @@ pub struct InstructionCols<T> {
 
     /// Flags to indicate if op_a is register 0.
     pub op_a_0: T,
+
+    /// Whether the instruction was expanded from a compressed instruction, so that the next
+    /// instruction is at pc + 2 instead of pc + 4.
+    #[cfg(feature = "rvc")]
+    pub is_compressed: T,
 }
 
 impl<F: PrimeField> InstructionCols<F> {
@@ -34,6 +39,10 @@ impl<F: PrimeField> InstructionCols<F> {
         self.op_c = instruction.op_c.into();
 
         self.op_a_0 = F::from_bool(instruction.op_a == Register::X0 as u32);
+        #[cfg(feature = "rvc")]
+        {
+            self.is_compressed = F::from_bool(instruction.compressed);
+        }
     }
 }
 
@@ -42,12 +51,13 @@ impl<T> IntoIterator for InstructionCols<T> {
     type IntoIter = IntoIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        once(self.opcode)
+        let cols = once(self.opcode)
             .chain(self.op_a)
             .chain(self.op_b)
             .chain(self.op_c)
-            .chain(once(self.op_a_0))
-            .collect::<Vec<_>>()
-            .into_iter()
+            .chain(once(self.op_a_0));
+        #[cfg(feature = "rvc")]
+        let cols = cols.chain(once(self.is_compressed));
+        cols.collect::<Vec<_>>().into_iter()
     }
 }
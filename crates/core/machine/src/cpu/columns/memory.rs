@@ -34,6 +34,10 @@ pub struct MemoryColumns<T> {
     // determine the sign for that value (used for LB and LH).
     pub most_sig_byte_decomp: [T; 8],
 
+    // Whether the loaded value is less than op_c.  This is used to select the value stored by the
+    // AMOMIN, AMOMAX, AMOMINU and AMOMAXU instructions.
+    pub amo_lt: T,
+
     pub addr_word_nonce: T,
     pub unsigned_mem_val_nonce: T,
 }
@@ -45,6 +45,19 @@ pub struct OpcodeSelectorCols<T> {
     pub is_sh: T,
     pub is_sw: T,
 
+    /// Atomic Memory Instructions.
+    pub is_lr: T,
+    pub is_sc: T,
+    pub is_amoswap: T,
+    pub is_amoadd: T,
+    pub is_amoxor: T,
+    pub is_amoand: T,
+    pub is_amoor: T,
+    pub is_amomin: T,
+    pub is_amomax: T,
+    pub is_amominu: T,
+    pub is_amomaxu: T,
+
     /// Branch Instructions.
     pub is_beq: T,
     pub is_bne: T,
@@ -83,6 +96,21 @@ impl<F: PrimeField> OpcodeSelectorCols<F> {
                 Opcode::SW => self.is_sw = F::one(),
                 _ => unreachable!(),
             }
+        } else if instruction.is_atomic_instruction() {
+            match instruction.opcode {
+                Opcode::LR => self.is_lr = F::one(),
+                Opcode::SC => self.is_sc = F::one(),
+                Opcode::AMOSWAP => self.is_amoswap = F::one(),
+                Opcode::AMOADD => self.is_amoadd = F::one(),
+                Opcode::AMOXOR => self.is_amoxor = F::one(),
+                Opcode::AMOAND => self.is_amoand = F::one(),
+                Opcode::AMOOR => self.is_amoor = F::one(),
+                Opcode::AMOMIN => self.is_amomin = F::one(),
+                Opcode::AMOMAX => self.is_amomax = F::one(),
+                Opcode::AMOMINU => self.is_amominu = F::one(),
+                Opcode::AMOMAXU => self.is_amomaxu = F::one(),
+                _ => unreachable!(),
+            }
         } else if instruction.is_branch_instruction() {
             match instruction.opcode {
                 Opcode::BEQ => self.is_beq = F::one(),
@@ -123,6 +151,17 @@ impl<T> IntoIterator for OpcodeSelectorCols<T> {
             self.is_sb,
             self.is_sh,
             self.is_sw,
+            self.is_lr,
+            self.is_sc,
+            self.is_amoswap,
+            self.is_amoadd,
+            self.is_amoxor,
+            self.is_amoand,
+            self.is_amoor,
+            self.is_amomin,
+            self.is_amomax,
+            self.is_amominu,
+            self.is_amomaxu,
             self.is_beq,
             self.is_bne,
             self.is_blt,
@@ -234,23 +234,14 @@ impl CpuChip {
         blu_events: &mut impl ByteRecord,
         nonce_lookup: &HashMap<LookupId, u32>,
     ) {
-        if !matches!(
-            event.instruction.opcode,
-            Opcode::LB
-                | Opcode::LH
-                | Opcode::LW
-                | Opcode::LBU
-                | Opcode::LHU
-                | Opcode::SB
-                | Opcode::SH
-                | Opcode::SW
-        ) {
+        let is_atomic = event.instruction.is_atomic_instruction();
+        if !event.instruction.is_memory_instruction() && !is_atomic {
             return;
         }
 
-        // Populate addr_word and addr_aligned columns.
+        // Populate addr_word and addr_aligned columns.  Atomic instructions have no offset.
         let memory_columns = cols.opcode_specific_columns.memory_mut();
-        let memory_addr = event.b.wrapping_add(event.c);
+        let memory_addr = if is_atomic { event.b } else { event.b.wrapping_add(event.c) };
         let aligned_addr = memory_addr - memory_addr % WORD_SIZE as u32;
         memory_columns.addr_word = memory_addr.into();
         memory_columns.addr_word_range_checker.populate(memory_addr);
@@ -272,6 +263,18 @@ impl CpuChip {
         memory_columns.offset_is_two = F::from_bool(addr_offset == 2);
         memory_columns.offset_is_three = F::from_bool(addr_offset == 3);
 
+        // For the AMO instructions storing a minimum or a maximum, set the comparison column.
+        let prev_mem_value = event.memory_record.unwrap().previous_record().value;
+        match event.instruction.opcode {
+            Opcode::AMOMIN | Opcode::AMOMAX => {
+                memory_columns.amo_lt = F::from_bool((prev_mem_value as i32) < (event.c as i32));
+            }
+            Opcode::AMOMINU | Opcode::AMOMAXU => {
+                memory_columns.amo_lt = F::from_bool(prev_mem_value < event.c);
+            }
+            _ => {}
+        }
+
         // If it is a load instruction, set the unsigned_mem_val column.
         let mem_value = event.memory_record.unwrap().value();
         if matches!(
@@ -1,26 +1,141 @@
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+use serde::{
+    de::{self, DeserializeOwned, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 use sp1_core_executor::SP1ReduceProof;
 use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, StarkVerifyingKey};
 
+/// Marks a binary encoded [`SP1Stdin`] as versioned, where an unversioned one starts with the
+/// length of its buffer instead.
+const STDIN_VERSION_MARKER: u64 = u64::MAX;
+
+/// The version of the binary encoding of [`SP1Stdin`] with files.
+///
+/// A stdin without files is encoded like before files existed, so that older versions can still
+/// read it.
+pub const STDIN_VERSION: u32 = 1;
+
 /// Standard input for the prover.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+///
+/// In binary formats such as bincode, which cannot skip missing fields, a stdin without files is
+/// encoded as its buffer, pointer and proofs, like before files existed. A stdin with files is
+/// encoded as [`STDIN_VERSION_MARKER`] and [`STDIN_VERSION`] followed by its buffer, pointer,
+/// proofs and files. Both encodings are read.
+#[derive(Debug, Clone, Default)]
 pub struct SP1Stdin {
     /// Input stored as a vec of vec of bytes. It's stored this way because the read syscall reads
     /// a vec of bytes at a time.
     pub buffer: Vec<Vec<u8>>,
     pub ptr: usize,
     pub proofs: Vec<(SP1ReduceProof<BabyBearPoseidon2>, StarkVerifyingKey<BabyBearPoseidon2>)>,
+    /// The files of the read-only file system of the program, by path.
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+/// The fields of a [`SP1Stdin`] in human readable formats, where missing files can be defaulted.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "SP1Stdin")]
+struct SP1StdinFields<Buffer, Proofs, Files> {
+    buffer: Buffer,
+    ptr: usize,
+    proofs: Proofs,
+    #[serde(default)]
+    files: Files,
+}
+
+impl Serialize for SP1Stdin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return SP1StdinFields {
+                buffer: &self.buffer,
+                ptr: self.ptr,
+                proofs: &self.proofs,
+                files: &self.files,
+            }
+            .serialize(serializer);
+        }
+
+        if self.files.is_empty() {
+            let mut tuple = serializer.serialize_tuple(3)?;
+            tuple.serialize_element(&self.buffer)?;
+            tuple.serialize_element(&self.ptr)?;
+            tuple.serialize_element(&self.proofs)?;
+            return tuple.end();
+        }
+        let mut tuple = serializer.serialize_tuple(6)?;
+        tuple.serialize_element(&STDIN_VERSION_MARKER)?;
+        tuple.serialize_element(&STDIN_VERSION)?;
+        tuple.serialize_element(&self.buffer)?;
+        tuple.serialize_element(&self.ptr)?;
+        tuple.serialize_element(&self.proofs)?;
+        tuple.serialize_element(&self.files)?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SP1Stdin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let SP1StdinFields { buffer, ptr, proofs, files } =
+                SP1StdinFields::deserialize(deserializer)?;
+            return Ok(Self { buffer, ptr, proofs, files });
+        }
+
+        // The elements read depend on the first one, so the tuple is as long as the longest
+        // encoding.
+        deserializer.deserialize_tuple(usize::MAX, SP1StdinVisitor)
+    }
+}
+
+/// Reads either binary encoding of a [`SP1Stdin`].
+struct SP1StdinVisitor;
+
+impl<'de> Visitor<'de> for SP1StdinVisitor {
+    type Value = SP1Stdin;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a binary encoded SP1Stdin")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<SP1Stdin, A::Error> {
+        fn next<'de, T: Deserialize<'de>, A: SeqAccess<'de>>(seq: &mut A) -> Result<T, A::Error> {
+            seq.next_element()?.ok_or_else(|| de::Error::custom("truncated SP1Stdin"))
+        }
+
+        let head: u64 = next(&mut seq)?;
+        if head != STDIN_VERSION_MARKER {
+            // An unversioned stdin, whose buffer is `head` elements long.
+            let buffer = (0..head).map(|_| next(&mut seq)).collect::<Result<_, _>>()?;
+            let ptr = next(&mut seq)?;
+            let proofs = next(&mut seq)?;
+            return Ok(SP1Stdin { buffer, ptr, proofs, files: BTreeMap::new() });
+        }
+
+        let version: u32 = next(&mut seq)?;
+        if version != STDIN_VERSION {
+            return Err(de::Error::custom(format!("unsupported SP1Stdin version {version}")));
+        }
+        Ok(SP1Stdin {
+            buffer: next(&mut seq)?,
+            ptr: next(&mut seq)?,
+            proofs: next(&mut seq)?,
+            files: next(&mut seq)?,
+        })
+    }
 }
 
 impl SP1Stdin {
     /// Create a new `SP1Stdin`.
     pub const fn new() -> Self {
-        Self { buffer: Vec::new(), ptr: 0, proofs: Vec::new() }
+        Self { buffer: Vec::new(), ptr: 0, proofs: Vec::new(), files: BTreeMap::new() }
     }
 
     /// Create a `SP1Stdin` from a slice of bytes.
     pub fn from(data: &[u8]) -> Self {
-        Self { buffer: vec![data.to_vec()], ptr: 0, proofs: Vec::new() }
+        Self { buffer: vec![data.to_vec()], ptr: 0, proofs: Vec::new(), files: BTreeMap::new() }
     }
 
     /// Read a value from the buffer.
@@ -60,6 +175,13 @@ impl SP1Stdin {
     ) {
         self.proofs.push((proof, vk));
     }
+
+    /// Add a file to the read-only file system of the program.
+    ///
+    /// The program opens the file at `path` with `sp1_zkvm::fs`.
+    pub fn write_file(&mut self, path: impl Into<String>, contents: Vec<u8>) {
+        self.files.insert(path.into(), contents);
+    }
 }
 
 pub mod proof_serde {
@@ -98,3 +220,45 @@ pub mod proof_serde {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use sp1_core_executor::SP1ReduceProof;
+    use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, StarkVerifyingKey};
+
+    use super::SP1Stdin;
+
+    /// The layout of [`SP1Stdin`] before files existed.
+    #[derive(Serialize, Deserialize)]
+    struct LegacyStdin {
+        buffer: Vec<Vec<u8>>,
+        ptr: usize,
+        proofs: Vec<(SP1ReduceProof<BabyBearPoseidon2>, StarkVerifyingKey<BabyBearPoseidon2>)>,
+    }
+
+    #[test]
+    fn test_stdin_reads_and_writes_legacy_encoding() {
+        let legacy = LegacyStdin { buffer: vec![vec![1, 2, 3], vec![]], ptr: 1, proofs: vec![] };
+        let bytes = bincode::serialize(&legacy).unwrap();
+
+        let stdin: SP1Stdin = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(stdin.buffer, legacy.buffer);
+        assert_eq!(stdin.ptr, 1);
+        assert!(stdin.files.is_empty());
+
+        // Without files, older versions can read the stdin.
+        assert_eq!(bincode::serialize(&stdin).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_stdin_round_trips_files() {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&7u32);
+        stdin.write_file("config.toml", b"a = 1".to_vec());
+
+        let decoded: SP1Stdin = bincode::deserialize(&bincode::serialize(&stdin).unwrap()).unwrap();
+        assert_eq!(decoded.buffer, stdin.buffer);
+        assert_eq!(decoded.files, stdin.files);
+    }
+}
@@ -30,6 +30,9 @@ pub mod utils;
 /// This string should be updated whenever any step in verifying an SP1 proof changes, including
 /// core, recursion, and plonk-bn254. This string is used to download SP1 artifacts and the gnark
 /// docker image.
+///
+/// The `rvc` feature changes the core circuit, so proofs made with it are not covered by the
+/// artifacts of this version.
 pub const SP1_CIRCUIT_VERSION: &str = "v3.0.0";
 
 // Re-export the `SP1ReduceProof` struct from sp1_core_machine.
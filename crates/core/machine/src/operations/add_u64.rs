@@ -0,0 +1,84 @@
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, Field};
+use sp1_core_executor::events::ByteRecord;
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::SP1AirBuilder;
+
+use crate::air::WordAirBuilder;
+
+/// The number of bytes in a 64-bit integer.
+pub const U64_SIZE: usize = 8;
+
+/// A set of columns needed to compute the wrapping add of two 64-bit integers, given as their
+/// little-endian bytes.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct AddU64Operation<T> {
+    /// The result of `a + b`.
+    pub value: [T; U64_SIZE],
+
+    /// The carry out of each byte but the last.
+    pub carry: [T; U64_SIZE - 1],
+}
+
+impl<F: Field> AddU64Operation<F> {
+    pub fn populate(&mut self, record: &mut impl ByteRecord, shard: u32, a: u64, b: u64) -> u64 {
+        let expected = a.wrapping_add(b);
+        let a_bytes = a.to_le_bytes();
+        let b_bytes = b.to_le_bytes();
+        let expected_bytes = expected.to_le_bytes();
+
+        let mut carry = 0u32;
+        for i in 0..U64_SIZE {
+            let sum = a_bytes[i] as u32 + b_bytes[i] as u32 + carry;
+            self.value[i] = F::from_canonical_u8(expected_bytes[i]);
+            carry = sum >> 8;
+            if i < U64_SIZE - 1 {
+                self.carry[i] = F::from_canonical_u32(carry);
+            }
+        }
+
+        // Range check.
+        {
+            record.add_u8_range_checks(shard, &a_bytes);
+            record.add_u8_range_checks(shard, &b_bytes);
+            record.add_u8_range_checks(shard, &expected_bytes);
+        }
+
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        a: [AB::Var; U64_SIZE],
+        b: [AB::Var; U64_SIZE],
+        cols: AddU64Operation<AB::Var>,
+        is_real: AB::Var,
+    ) {
+        let base = AB::F::from_canonical_u32(256);
+        let mut builder_is_real = builder.when(is_real);
+
+        // For each byte, assert that the sum of the inputs and the incoming carry is equal to the
+        // output byte plus the outgoing carry. The carry out of the last byte is discarded.
+        let mut carry_in = AB::Expr::zero();
+        for i in 0..U64_SIZE {
+            let sum = a[i] + b[i] + carry_in.clone();
+            if i < U64_SIZE - 1 {
+                builder_is_real.assert_eq(sum, cols.value[i] + cols.carry[i] * base);
+                builder_is_real.assert_bool(cols.carry[i]);
+                carry_in = cols.carry[i].into();
+            } else {
+                let overflow = sum - cols.value[i];
+                builder_is_real.assert_zero(overflow.clone() * (overflow - base));
+            }
+        }
+        builder_is_real.assert_bool(is_real);
+
+        // Range check each byte.
+        {
+            builder.slice_range_check_u8(&a, is_real);
+            builder.slice_range_check_u8(&b, is_real);
+            builder.slice_range_check_u8(&cols.value, is_real);
+        }
+    }
+}
@@ -183,6 +183,66 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
     ) -> BigUint {
         self.populate_with_modulus(record, shard, a, b, &P::modulus(), op)
     }
+
+    /// Populate these columns for the operation `(a * b + c) % modulus`, returning the result and
+    /// the carry `(a * b + c) / modulus`. This is useful to chain wide multiplications, where the
+    /// carry of one limb is added to the product of the next one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn populate_mul_and_carry(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        a: &BigUint,
+        b: &BigUint,
+        c: &BigUint,
+        modulus: &BigUint,
+    ) -> (BigUint, BigUint) {
+        let p_a: Polynomial<F> = P::to_limbs_field::<F, _>(a).into();
+        let p_b: Polynomial<F> = P::to_limbs_field::<F, _>(b).into();
+        let p_c: Polynomial<F> = P::to_limbs_field::<F, _>(c).into();
+
+        let mul_add = a * b + c;
+        let result = &mul_add % modulus;
+        let carry = (&mul_add - &result) / modulus;
+        debug_assert!(&result < modulus);
+        debug_assert!(&carry < modulus);
+        debug_assert_eq!(&carry * modulus, mul_add - &result);
+
+        // As in `populate_carry_and_witness`, the modulus may have one more limb than the field.
+        let p_modulus_limbs =
+            modulus.to_bytes_le().iter().map(|x| F::from_canonical_u8(*x)).collect::<Vec<F>>();
+        let p_modulus: Polynomial<F> = p_modulus_limbs.iter().into();
+        let p_result: Polynomial<F> = P::to_limbs_field::<F, _>(&result).into();
+        let p_carry: Polynomial<F> = P::to_limbs_field::<F, _>(&carry).into();
+
+        // Compute the vanishing polynomial.
+        let p_op = &p_a * &p_b + &p_c;
+        let p_vanishing: Polynomial<F> = &p_op - &p_result - &p_carry * &p_modulus;
+
+        let p_witness = compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB as u32,
+            P::NB_WITNESS_LIMBS,
+        );
+        let (mut p_witness_low, mut p_witness_high) = split_u16_limbs_to_u8_limbs(&p_witness);
+
+        self.result = p_result.into();
+        self.carry = p_carry.into();
+
+        p_witness_low.resize(P::Witness::USIZE, F::zero());
+        p_witness_high.resize(P::Witness::USIZE, F::zero());
+        self.witness_low = Limbs(p_witness_low.try_into().unwrap());
+        self.witness_high = Limbs(p_witness_high.try_into().unwrap());
+
+        // Range checks
+        record.add_u8_range_checks_field(shard, &self.result.0);
+        record.add_u8_range_checks_field(shard, &self.carry.0);
+        record.add_u8_range_checks_field(shard, &self.witness_low.0);
+        record.add_u8_range_checks_field(shard, &self.witness_high.0);
+
+        (result, carry)
+    }
 }
 
 impl<V: Copy, P: FieldParameters> FieldOpCols<V, P> {
@@ -251,6 +311,31 @@ impl<V: Copy, P: FieldParameters> FieldOpCols<V, P> {
         self.eval_with_polynomials(builder, p_op, modulus.clone(), p_result, is_real);
     }
 
+    /// Evaluates the constraints of `result = (a * b + c) % modulus`, where the carry columns hold
+    /// `(a * b + c) / modulus`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn eval_mul_and_carry<AB: SP1AirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        a: &(impl Into<Polynomial<AB::Expr>> + Clone),
+        b: &(impl Into<Polynomial<AB::Expr>> + Clone),
+        c: &(impl Into<Polynomial<AB::Expr>> + Clone),
+        modulus: &(impl Into<Polynomial<AB::Expr>> + Clone),
+        is_real: impl Into<AB::Expr> + Clone,
+    ) where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        let p_a: Polynomial<AB::Expr> = (a).clone().into();
+        let p_b: Polynomial<AB::Expr> = (b).clone().into();
+        let p_c: Polynomial<AB::Expr> = (c).clone().into();
+
+        let p_result: Polynomial<AB::Expr> = self.result.into();
+        let p_op = p_a * p_b + p_c;
+
+        self.eval_with_polynomials(builder, p_op, modulus.clone(), p_result, is_real);
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn eval_with_polynomials<AB: SP1AirBuilder<Var = V>>(
         &self,
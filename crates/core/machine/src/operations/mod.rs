@@ -7,11 +7,13 @@
 mod add;
 mod add4;
 mod add5;
+mod add_u64;
 mod and;
 mod baby_bear_range;
 mod baby_bear_word;
 pub mod field;
 mod fixed_rotate_right;
+mod fixed_rotate_right_u64;
 mod fixed_shift_right;
 mod is_equal_word;
 mod is_zero;
@@ -24,10 +26,12 @@ mod xor;
 pub use add::*;
 pub use add4::*;
 pub use add5::*;
+pub use add_u64::*;
 pub use and::*;
 pub use baby_bear_range::*;
 pub use baby_bear_word::*;
 pub use fixed_rotate_right::*;
+pub use fixed_rotate_right_u64::*;
 pub use fixed_shift_right::*;
 pub use is_equal_word::*;
 pub use is_zero::*;
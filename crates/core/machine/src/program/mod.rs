@@ -70,7 +70,7 @@ impl<F: PrimeField> MachineAir<F> for ProgramChip {
             .iter()
             .enumerate()
             .map(|(i, &instruction)| {
-                let pc = program.pc_base + (i as u32 * 4);
+                let pc = program.instruction_pc(i);
                 let mut row = [F::zero(); NUM_PROGRAM_PREPROCESSED_COLS];
                 let cols: &mut ProgramPreprocessedCols<F> = row.as_mut_slice().borrow_mut();
                 cols.pc = F::from_canonical_u32(pc);
@@ -123,7 +123,7 @@ impl<F: PrimeField> MachineAir<F> for ProgramChip {
             .into_iter()
             .enumerate()
             .map(|(i, _)| {
-                let pc = input.program.pc_base + (i as u32 * 4);
+                let pc = input.program.instruction_pc(i);
                 let mut row = [F::zero(); NUM_PROGRAM_MULT_COLS];
                 let cols: &mut ProgramMultiplicityCols<F> = row.as_mut_slice().borrow_mut();
                 cols.shard = F::from_canonical_u32(input.public_values.execution_shard);
@@ -210,6 +210,8 @@ mod tests {
                 pc_base: 0,
                 memory_image: HashMap::new(),
                 preprocessed_shape: None,
+                instruction_pcs: None,
+                symbols: Default::default(),
             }),
             ..Default::default()
         };
@@ -64,10 +64,34 @@ impl CostEstimator for ExecutionReport {
             (secp256k1_double_events as u64) * costs[&RiscvAirDiscriminants::Secp256k1Double];
         total_chips += 1;
 
+        let p256_decompress_events = self.syscall_counts[SyscallCode::SECP256R1_DECOMPRESS];
+        total_area +=
+            (p256_decompress_events as u64) * costs[&RiscvAirDiscriminants::P256Decompress];
+        total_chips += 1;
+
+        let secp256r1_add_events = self.syscall_counts[SyscallCode::SECP256R1_ADD];
+        total_area += (secp256r1_add_events as u64) * costs[&RiscvAirDiscriminants::Secp256r1Add];
+        total_chips += 1;
+
+        let secp256r1_double_events = self.syscall_counts[SyscallCode::SECP256R1_DOUBLE];
+        total_area +=
+            (secp256r1_double_events as u64) * costs[&RiscvAirDiscriminants::Secp256r1Double];
+        total_chips += 1;
+
         let keccak256_permute_events = self.syscall_counts[SyscallCode::KECCAK_PERMUTE];
         total_area += (keccak256_permute_events as u64) * costs[&RiscvAirDiscriminants::KeccakP];
         total_chips += 1;
 
+        let blake2b_compress_events = self.syscall_counts[SyscallCode::BLAKE2B_COMPRESS];
+        total_area +=
+            (blake2b_compress_events as u64) * costs[&RiscvAirDiscriminants::Blake2bCompress];
+        total_chips += 1;
+
+        let blake3_compress_events = self.syscall_counts[SyscallCode::BLAKE3_COMPRESS];
+        total_area +=
+            (blake3_compress_events as u64) * costs[&RiscvAirDiscriminants::Blake3Compress];
+        total_chips += 1;
+
         let bn254_add_events = self.syscall_counts[SyscallCode::BN254_ADD];
         total_area += (bn254_add_events as u64) * costs[&RiscvAirDiscriminants::Bn254Add];
         total_chips += 1;
@@ -89,6 +113,10 @@ impl CostEstimator for ExecutionReport {
         total_area += (uint256_mul_events as u64) * costs[&RiscvAirDiscriminants::Uint256Mul];
         total_chips += 1;
 
+        let u256x2048_mul_events = self.syscall_counts[SyscallCode::U256XU2048_MUL];
+        total_area += (u256x2048_mul_events as u64) * costs[&RiscvAirDiscriminants::U256x2048Mul];
+        total_chips += 1;
+
         let bls12381_fp_events = self.syscall_counts[SyscallCode::BLS12381_FP_ADD]
             + self.syscall_counts[SyscallCode::BLS12381_FP_SUB]
             + self.syscall_counts[SyscallCode::BLS12381_FP_MUL];
@@ -0,0 +1,445 @@
+use hashbrown::HashMap;
+use p3_field::PrimeField32;
+use sp1_core_executor::{
+    CoreShape, ExecutionError, ExecutionRecord, ExecutionReport, Executor, ExecutorMode, Opcode,
+    Program, SP1Context, ShardEventCounts,
+};
+use sp1_stark::{air::MachineAir, MachineRecord, SP1CoreOpts};
+use thiserror::Error;
+
+use crate::{
+    io::SP1Stdin,
+    memory::{MemoryLocalChip, NUM_LOCAL_MEMORY_ENTRIES_PER_ROW},
+    riscv::MemoryChipType::{Finalize, Initialize},
+    utils::next_power_of_two,
+};
+
+use super::{
+    AddSubChip, BitwiseChip, CoreShapeConfig, CoreShapeError, CpuChip, DivRemChip, LtChip,
+    MemoryGlobalChip, MulChip, RiscvAir, ShiftLeft, ShiftRightChip, SyscallChip,
+};
+
+#[derive(Error, Debug)]
+pub enum EstimateError {
+    #[error("failed to execute program: {0}")]
+    ExecutionError(ExecutionError),
+    #[error("failed to fix shape: {0}")]
+    ShapeError(CoreShapeError),
+}
+
+/// The kind of events a shard proves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShardKind {
+    /// A shard containing cpu events.
+    Core,
+    /// A shard containing the deferred events of a single precompile.
+    Precompile,
+    /// A shard containing global memory initialize and finalize events.
+    Memory,
+}
+
+/// The predicted trace heights of a single shard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardEstimate {
+    /// The kind of events the shard proves.
+    pub kind: ShardKind,
+    /// The number of rows of each chip included in the shard, before padding.
+    pub heights: HashMap<String, usize>,
+    /// The number of rows of each chip included in the shard, after padding.
+    pub padded_heights: HashMap<String, usize>,
+    /// The number of cells in the padded traces of the shard.
+    pub cells: u64,
+}
+
+/// A prediction of the shards needed to prove an execution with the core prover.
+#[derive(Debug, Clone)]
+pub struct CoreEstimate {
+    /// The predicted shards, in the order they would be proven.
+    pub shards: Vec<ShardEstimate>,
+    /// The report of the execution.
+    pub report: ExecutionReport,
+}
+
+impl CoreEstimate {
+    /// The number of shards of the core proof.
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// The number of cells in the padded traces of all shards.
+    pub fn total_cells(&self) -> u64 {
+        self.shards.iter().map(|shard| shard.cells).sum()
+    }
+
+    /// The largest padded height of each chip across all shards.
+    pub fn max_padded_heights(&self) -> HashMap<String, usize> {
+        let mut max_heights = HashMap::<String, usize>::new();
+        for (chip, &height) in self.shards.iter().flat_map(|shard| &shard.padded_heights) {
+            let max_height = max_heights.entry(chip.clone()).or_default();
+            *max_height = (*max_height).max(height);
+        }
+        max_heights
+    }
+}
+
+/// Estimate the shards needed to prove the execution of a program, without generating any traces.
+///
+/// The program is executed in [`ExecutorMode::Checkpoint`] and the trace height of each chip is
+/// derived from the number of events each shard would emit. If a shape config is given, the
+/// heights are padded to the shapes the prover would use, so `program` should already have its
+/// preprocessed shape fixed.
+pub fn estimate_with_context<F: PrimeField32>(
+    program: Program,
+    stdin: &SP1Stdin,
+    opts: SP1CoreOpts,
+    context: SP1Context,
+    shape_config: Option<&CoreShapeConfig<F>>,
+) -> Result<CoreEstimate, EstimateError> {
+    let estimator = ShardEstimator::new(&program, shape_config)?;
+
+    // Setup the runtime.
+    let mut runtime = Executor::with_context(program.clone(), opts, context);
+    runtime.maximal_shapes = shape_config.map(|shape_config| {
+        shape_config.maximal_core_shapes().into_iter().map(|shape| shape.inner).collect()
+    });
+    runtime.write_vecs(&stdin.buffer);
+    runtime.write_files(&stdin.files);
+    for proof in stdin.proofs.iter() {
+        let (proof, vk) = proof.clone();
+        runtime.write_proof(proof, vk);
+    }
+    runtime.executor_mode = ExecutorMode::Checkpoint;
+    runtime.print_report = true;
+    runtime.shard_event_counts = Some(Vec::new());
+
+    let mut shards = Vec::new();
+    let mut global_clk = 0;
+    let mut deferred = ExecutionRecord::new(program.clone().into());
+    loop {
+        // Checkpoints are never saved, so there is no need to keep track of the touched memory.
+        runtime.memory_checkpoint.clear();
+        runtime.uninitialized_memory_checkpoint.clear();
+        let done = runtime.execute().map_err(EstimateError::ExecutionError)?;
+
+        let records = std::mem::take(&mut runtime.records);
+        let counts = runtime.shard_event_counts.replace(Vec::new()).unwrap_or_default();
+        for (mut record, counts) in records.into_iter().zip(counts) {
+            deferred.append(&mut record.defer());
+            let instructions = counts.global_clk - global_clk;
+            global_clk = counts.global_clk;
+            if instructions > 0 {
+                shards.push(estimator.core_shard(instructions, &counts, &record)?);
+            }
+        }
+
+        // Estimate the precompile shards which are ready to be committed to.
+        for record in deferred.split(done, opts.split_opts) {
+            shards.push(estimator.precompile_shard(&record)?);
+        }
+
+        if done {
+            break;
+        }
+    }
+
+    // Estimate the global memory shards, which are only emitted in trace mode.
+    let memory_image = &runtime.program.memory_image;
+    let (mut num_init, mut num_finalize) = (1usize, 1usize);
+    for addr in runtime.state.memory.keys().filter(|&addr| addr != 0) {
+        num_finalize += 1;
+        if !memory_image.contains_key(&addr) {
+            num_init += 1;
+        }
+    }
+    let chunk_size = opts.split_opts.memory;
+    let num_memory_shards = num_init.div_ceil(chunk_size).max(num_finalize.div_ceil(chunk_size));
+    for i in 0..num_memory_shards {
+        let num_init = num_init.saturating_sub(i * chunk_size).min(chunk_size);
+        let num_finalize = num_finalize.saturating_sub(i * chunk_size).min(chunk_size);
+        shards.push(estimator.memory_shard(num_init, num_finalize)?);
+    }
+
+    Ok(CoreEstimate { shards, report: runtime.report })
+}
+
+/// Pads the estimated heights of each shard and computes its area.
+struct ShardEstimator<'a, F: PrimeField32> {
+    shape_config: Option<&'a CoreShapeConfig<F>>,
+    preprocessed_heights: Vec<(RiscvAir<F>, usize)>,
+    preprocessed_shape: Option<CoreShape>,
+    costs: HashMap<String, u64>,
+}
+
+impl<'a, F: PrimeField32> ShardEstimator<'a, F> {
+    fn new(
+        program: &Program,
+        shape_config: Option<&'a CoreShapeConfig<F>>,
+    ) -> Result<Self, EstimateError> {
+        // With a shape config, the shape of every shard includes the preprocessed shape.
+        let preprocessed_heights = RiscvAir::<F>::preprocessed_heights(program);
+        let preprocessed_shape = match (shape_config, &program.preprocessed_shape) {
+            (Some(_), Some(shape)) => Some(shape.clone()),
+            (Some(shape_config), None) => Some(
+                shape_config
+                    .find_preprocessed_shape(program)
+                    .ok_or(EstimateError::ShapeError(CoreShapeError::PreprocessedShapeError))?,
+            ),
+            (None, _) => None,
+        };
+
+        let costs = RiscvAir::<F>::chips()
+            .into_iter()
+            .map(|chip| (chip.name(), chip.cost() + chip.preprocessed_width() as u64))
+            .collect();
+
+        Ok(Self { shape_config, preprocessed_heights, preprocessed_shape, costs })
+    }
+
+    fn core_shard(
+        &self,
+        instructions: u64,
+        counts: &ShardEventCounts,
+        record: &ExecutionRecord,
+    ) -> Result<ShardEstimate, EstimateError> {
+        let heights = estimated_core_heights(instructions, counts, record);
+        let shape = match self.shape_config {
+            Some(shape_config) => Some(
+                shape_config
+                    .find_core_shape(&heights)
+                    .ok_or_else(|| EstimateError::ShapeError(shape_error(&heights)))?,
+            ),
+            None => None,
+        };
+        Ok(self.shard(ShardKind::Core, heights, shape))
+    }
+
+    fn precompile_shard(&self, record: &ExecutionRecord) -> Result<ShardEstimate, EstimateError> {
+        let (air, height, mem_events) = RiscvAir::<F>::get_all_precompile_airs()
+            .into_iter()
+            .find_map(|(air, _)| {
+                let (height, mem_events) = air.get_precompile_heights(record)?;
+                Some((air, height, mem_events))
+            })
+            .expect("deferred record without precompile events");
+        let syscall_events = height / air.rows_per_event();
+
+        let shape = match self.shape_config {
+            Some(shape_config) => {
+                Some(shape_config.find_precompile_shape(&air, height, mem_events).ok_or_else(
+                    || EstimateError::ShapeError(CoreShapeError::ShapeError(record.stats())),
+                )?)
+            }
+            None => None,
+        };
+        let heights = vec![
+            (air, height),
+            (RiscvAir::SyscallPrecompile(SyscallChip::precompile()), syscall_events),
+            (
+                RiscvAir::MemoryLocal(MemoryLocalChip::new()),
+                mem_events.div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW),
+            ),
+        ];
+        Ok(self.shard(ShardKind::Precompile, heights, shape))
+    }
+
+    fn memory_shard(
+        &self,
+        num_init: usize,
+        num_finalize: usize,
+    ) -> Result<ShardEstimate, EstimateError> {
+        let heights = vec![
+            (RiscvAir::MemoryGlobalInit(MemoryGlobalChip::new(Initialize)), num_init),
+            (RiscvAir::MemoryGlobalFinal(MemoryGlobalChip::new(Finalize)), num_finalize),
+        ];
+        let shape = match self.shape_config {
+            Some(shape_config) => Some(
+                shape_config
+                    .find_memory_shape(&heights)
+                    .ok_or_else(|| EstimateError::ShapeError(shape_error(&heights)))?,
+            ),
+            None => None,
+        };
+        Ok(self.shard(ShardKind::Memory, heights, shape))
+    }
+
+    /// Pad the heights of the chips of a shard, either to the given shape or to the next power of
+    /// two, and compute the area of the padded traces.
+    fn shard(
+        &self,
+        kind: ShardKind,
+        heights: Vec<(RiscvAir<F>, usize)>,
+        shape: Option<CoreShape>,
+    ) -> ShardEstimate {
+        let shape = shape.map(|mut shape| {
+            shape.extend(self.preprocessed_shape.clone().unwrap_or_default());
+            shape
+        });
+
+        let mut estimate = ShardEstimate {
+            kind,
+            heights: HashMap::new(),
+            padded_heights: HashMap::new(),
+            cells: 0,
+        };
+        for (air, height) in self.preprocessed_heights.iter().chain(&heights) {
+            let padded_height = match &shape {
+                Some(shape) => match shape.inner.get(&air.name()) {
+                    Some(log_height) => 1 << log_height,
+                    None => continue,
+                },
+                None if *height == 0 => continue,
+                None => next_power_of_two(*height, None),
+            };
+            estimate.cells += padded_height as u64 * self.costs[&air.name()];
+            estimate.heights.insert(air.name(), *height);
+            estimate.padded_heights.insert(air.name(), padded_height);
+        }
+        estimate
+    }
+}
+
+/// Estimate the heights of the core chips of a shard from its event counts, in the order of
+/// [`RiscvAir::core_heights`].
+fn estimated_core_heights<F: PrimeField32>(
+    instructions: u64,
+    counts: &ShardEventCounts,
+    record: &ExecutionRecord,
+) -> Vec<(RiscvAir<F>, usize)> {
+    let count = |opcodes: &[Opcode]| -> usize {
+        opcodes.iter().map(|&opcode| counts.event_counts[opcode] as usize).sum()
+    };
+    vec![
+        (RiscvAir::Cpu(CpuChip::default()), instructions as usize),
+        (
+            RiscvAir::DivRem(DivRemChip::default()),
+            count(&[Opcode::DIV, Opcode::DIVU, Opcode::REM, Opcode::REMU]),
+        ),
+        // The executor doesn't count the address computation of stores as an ADD event, since
+        // the shape of each shard is chosen from those counts, but the trace does emit one.
+        (
+            RiscvAir::Add(AddSubChip::default()),
+            count(&[Opcode::ADD, Opcode::SUB, Opcode::SB, Opcode::SH, Opcode::SW]),
+        ),
+        (RiscvAir::Bitwise(BitwiseChip::default()), count(&[Opcode::XOR, Opcode::OR, Opcode::AND])),
+        (
+            RiscvAir::Mul(MulChip::default()),
+            count(&[Opcode::MUL, Opcode::MULH, Opcode::MULHU, Opcode::MULHSU]),
+        ),
+        (RiscvAir::ShiftRight(ShiftRightChip::default()), count(&[Opcode::SRL, Opcode::SRA])),
+        (RiscvAir::ShiftLeft(ShiftLeft::default()), count(&[Opcode::SLL])),
+        (RiscvAir::Lt(LtChip::default()), count(&[Opcode::SLT, Opcode::SLTU])),
+        (
+            RiscvAir::MemoryLocal(MemoryLocalChip::new()),
+            record.cpu_local_memory_access.len().div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW),
+        ),
+        (RiscvAir::SyscallCore(SyscallChip::core()), record.syscall_events.len()),
+    ]
+}
+
+fn shape_error<F: PrimeField32>(heights: &[(RiscvAir<F>, usize)]) -> CoreShapeError {
+    CoreShapeError::ShapeError(heights.iter().map(|(air, height)| (air.name(), *height)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use sp1_core_executor::{
+        programs::tests::{fibonacci_program, simple_memory_program, ssz_withdrawals_program},
+        Executor,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_estimate_matches_trace() {
+        let program = ssz_withdrawals_program();
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1 << 12;
+        let estimate = estimate_with_context::<BabyBear>(
+            program.clone(),
+            &SP1Stdin::new(),
+            opts,
+            SP1Context::default(),
+            None,
+        )
+        .unwrap();
+
+        // Generate the records the prover would commit to.
+        let mut runtime = Executor::new(program.clone(), opts);
+        runtime.run().unwrap();
+        let mut records = std::mem::take(&mut runtime.records);
+        let mut deferred = ExecutionRecord::new(program.into());
+        for record in records.iter_mut() {
+            deferred.append(&mut record.defer());
+        }
+        records.retain(|record| record.contains_cpu());
+        let deferred = deferred.split(true, opts.split_opts);
+
+        // The cpu and memory heights are exact, while the ALU heights are upper bounds.
+        let core_shards = estimate.shards.iter().filter(|shard| shard.kind == ShardKind::Core);
+        assert_eq!(core_shards.clone().count(), records.len());
+        for (shard, record) in core_shards.zip(&records) {
+            for (air, height) in RiscvAir::<BabyBear>::core_heights(record) {
+                let estimated_height = shard.heights.get(&air.name()).copied().unwrap_or_default();
+                match air {
+                    RiscvAir::Cpu(_) | RiscvAir::MemoryLocal(_) | RiscvAir::SyscallCore(_) => {
+                        assert_eq!(estimated_height, height, "{}", air.name())
+                    }
+                    _ => assert!(estimated_height >= height, "{}", air.name()),
+                }
+            }
+        }
+
+        let deferred_shards = estimate.shards.iter().filter(|shard| shard.kind != ShardKind::Core);
+        assert_eq!(deferred_shards.count(), deferred.len());
+    }
+
+    #[test]
+    fn test_estimate_counts_store_addresses() {
+        let program = simple_memory_program();
+        let opts = SP1CoreOpts::default();
+        let estimate = estimate_with_context::<BabyBear>(
+            program.clone(),
+            &SP1Stdin::new(),
+            opts,
+            SP1Context::default(),
+            None,
+        )
+        .unwrap();
+
+        let mut runtime = Executor::new(program, opts);
+        runtime.run().unwrap();
+        let add_events =
+            runtime.records.iter().map(|record| record.add_events.len()).sum::<usize>();
+
+        // Each store computes its address with an ADD event, which the estimate accounts for even
+        // though the executor's own counts, used to choose the shard shapes, don't.
+        let add = RiscvAir::<BabyBear>::Add(AddSubChip::default()).name();
+        assert!(estimate.shards[0].heights[&add] >= add_events);
+    }
+
+    #[test]
+    fn test_estimate_with_shapes() {
+        let mut program = fibonacci_program();
+        let shape_config = CoreShapeConfig::<BabyBear>::default();
+        shape_config.fix_preprocessed_shape(&mut program).unwrap();
+        let estimate = estimate_with_context(
+            program,
+            &SP1Stdin::new(),
+            SP1CoreOpts::default(),
+            SP1Context::default(),
+            Some(&shape_config),
+        )
+        .unwrap();
+
+        let kinds = estimate.shards.iter().map(|shard| shard.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, [ShardKind::Core, ShardKind::Memory]);
+        for shard in &estimate.shards {
+            for (chip, &padded_height) in &shard.padded_heights {
+                assert!(padded_height.is_power_of_two());
+                assert!(padded_height >= shard.heights[chip]);
+            }
+        }
+        assert!(estimate.total_cells() > 0);
+    }
+}
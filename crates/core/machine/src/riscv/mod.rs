@@ -1,4 +1,5 @@
 pub mod cost;
+pub mod estimate;
 
 mod shape;
 
@@ -40,9 +41,12 @@ pub(crate) mod riscv_chips {
         syscall::{
             chip::SyscallChip,
             precompiles::{
+                blake2b::Blake2bCompressChip,
+                blake3::Blake3CompressChip,
                 edwards::{EdAddAssignChip, EdDecompressChip},
                 keccak256::KeccakPermuteChip,
                 sha256::{ShaCompressChip, ShaExtendChip},
+                u256x2048_mul::U256x2048MulChip,
                 uint256::Uint256MulChip,
                 weierstrass::{
                     WeierstrassAddAssignChip, WeierstrassDecompressChip,
@@ -55,7 +59,7 @@ pub(crate) mod riscv_chips {
         edwards::{ed25519::Ed25519Parameters, EdwardsCurve},
         weierstrass::{
             bls12_381::Bls12381Parameters, bn254::Bn254Parameters, secp256k1::Secp256k1Parameters,
-            SwCurve,
+            secp256r1::Secp256r1Parameters, SwCurve,
         },
     };
 }
@@ -114,8 +118,18 @@ pub enum RiscvAir<F: PrimeField32> {
     Secp256k1Add(WeierstrassAddAssignChip<SwCurve<Secp256k1Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve secp256k1.
     Secp256k1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256k1Parameters>>),
+    /// A precompile for decompressing a point on the P256 curve.
+    P256Decompress(WeierstrassDecompressChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for addition on the Elliptic curve secp256r1.
+    Secp256r1Add(WeierstrassAddAssignChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for doubling a point on the Elliptic curve secp256r1.
+    Secp256r1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256r1Parameters>>),
     /// A precompile for the Keccak permutation.
     KeccakP(KeccakPermuteChip),
+    /// A precompile for the BLAKE2b compression rounds.
+    Blake2bCompress(Blake2bCompressChip),
+    /// A precompile for the BLAKE3 compression rounds.
+    Blake3Compress(Blake3CompressChip),
     /// A precompile for addition on the Elliptic curve bn254.
     Bn254Add(WeierstrassAddAssignChip<SwCurve<Bn254Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve bn254.
@@ -126,6 +140,8 @@ pub enum RiscvAir<F: PrimeField32> {
     Bls12381Double(WeierstrassDoubleAssignChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for uint256 mul.
     Uint256Mul(Uint256MulChip),
+    /// A precompile for the multiplication of a uint256 by a uint2048.
+    U256x2048Mul(U256x2048MulChip),
     /// A precompile for decompressing a point on the BLS12-381 curve.
     Bls12381Decompress(WeierstrassDecompressChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for BLS12-381 fp operation.
@@ -218,10 +234,37 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Secp256k1Double, secp256k1_double_assign.cost());
         chips.push(secp256k1_double_assign);
 
+        let p256_decompress = Chip::new(RiscvAir::P256Decompress(WeierstrassDecompressChip::<
+            SwCurve<Secp256r1Parameters>,
+        >::with_lsb_rule()));
+        costs.insert(RiscvAirDiscriminants::P256Decompress, p256_decompress.cost());
+        chips.push(p256_decompress);
+
+        let secp256r1_add_assign = Chip::new(RiscvAir::Secp256r1Add(WeierstrassAddAssignChip::<
+            SwCurve<Secp256r1Parameters>,
+        >::new()));
+        costs.insert(RiscvAirDiscriminants::Secp256r1Add, secp256r1_add_assign.cost());
+        chips.push(secp256r1_add_assign);
+
+        let secp256r1_double_assign =
+            Chip::new(RiscvAir::Secp256r1Double(WeierstrassDoubleAssignChip::<
+                SwCurve<Secp256r1Parameters>,
+            >::new()));
+        costs.insert(RiscvAirDiscriminants::Secp256r1Double, secp256r1_double_assign.cost());
+        chips.push(secp256r1_double_assign);
+
         let keccak_permute = Chip::new(RiscvAir::KeccakP(KeccakPermuteChip::new()));
         costs.insert(RiscvAirDiscriminants::KeccakP, 24 * keccak_permute.cost());
         chips.push(keccak_permute);
 
+        let blake2b_compress = Chip::new(RiscvAir::Blake2bCompress(Blake2bCompressChip::new()));
+        costs.insert(RiscvAirDiscriminants::Blake2bCompress, 96 * blake2b_compress.cost());
+        chips.push(blake2b_compress);
+
+        let blake3_compress = Chip::new(RiscvAir::Blake3Compress(Blake3CompressChip::new()));
+        costs.insert(RiscvAirDiscriminants::Blake3Compress, 56 * blake3_compress.cost());
+        chips.push(blake3_compress);
+
         let bn254_add_assign = Chip::new(RiscvAir::Bn254Add(WeierstrassAddAssignChip::<
             SwCurve<Bn254Parameters>,
         >::new()));
@@ -250,6 +293,10 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Uint256Mul, uint256_mul.cost());
         chips.push(uint256_mul);
 
+        let u256x2048_mul = Chip::new(RiscvAir::U256x2048Mul(U256x2048MulChip::default()));
+        costs.insert(RiscvAirDiscriminants::U256x2048Mul, u256x2048_mul.cost());
+        chips.push(u256x2048_mul);
+
         let bls12381_fp = Chip::new(RiscvAir::Bls12381Fp(FpOpChip::<Bls12381BaseField>::new()));
         costs.insert(RiscvAirDiscriminants::Bls12381Fp, bls12381_fp.cost());
         chips.push(bls12381_fp);
@@ -455,6 +502,8 @@ impl<F: PrimeField32> RiscvAir<F> {
             Self::Sha256Compress(_) => 80,
             Self::Sha256Extend(_) => 48,
             Self::KeccakP(_) => 24,
+            Self::Blake2bCompress(_) => 96,
+            Self::Blake3Compress(_) => 56,
             _ => 1,
         }
     }
@@ -470,13 +519,19 @@ impl<F: PrimeField32> RiscvAir<F> {
             Self::Ed25519Add(_) => SyscallCode::ED_ADD,
             Self::Ed25519Decompress(_) => SyscallCode::ED_DECOMPRESS,
             Self::KeccakP(_) => SyscallCode::KECCAK_PERMUTE,
+            Self::Blake2bCompress(_) => SyscallCode::BLAKE2B_COMPRESS,
+            Self::Blake3Compress(_) => SyscallCode::BLAKE3_COMPRESS,
             Self::Secp256k1Add(_) => SyscallCode::SECP256K1_ADD,
             Self::Secp256k1Double(_) => SyscallCode::SECP256K1_DOUBLE,
+            Self::Secp256r1Add(_) => SyscallCode::SECP256R1_ADD,
+            Self::Secp256r1Double(_) => SyscallCode::SECP256R1_DOUBLE,
             Self::Sha256Compress(_) => SyscallCode::SHA_COMPRESS,
             Self::Sha256Extend(_) => SyscallCode::SHA_EXTEND,
             Self::Uint256Mul(_) => SyscallCode::UINT256_MUL,
+            Self::U256x2048Mul(_) => SyscallCode::U256XU2048_MUL,
             Self::Bls12381Decompress(_) => SyscallCode::BLS12381_DECOMPRESS,
             Self::K256Decompress(_) => SyscallCode::SECP256K1_DECOMPRESS,
+            Self::P256Decompress(_) => SyscallCode::SECP256R1_DECOMPRESS,
             Self::Bls12381Double(_) => SyscallCode::BLS12381_DOUBLE,
             Self::Bls12381Fp(_) => SyscallCode::BLS12381_FP_ADD,
             Self::Bls12381Fp2Mul(_) => SyscallCode::BLS12381_FP2_MUL,
@@ -549,7 +604,8 @@ pub mod tests {
 
     use sp1_core_executor::{
         programs::tests::{
-            fibonacci_program, simple_memory_program, simple_program, ssz_withdrawals_program,
+            atomic_program, compressed_program, fibonacci_program, secp256r1_program,
+            simple_memory_program, simple_program, ssz_withdrawals_program,
         },
         Instruction, Opcode, Program,
     };
@@ -565,6 +621,43 @@ pub mod tests {
         run_test::<CpuProver<_, _>>(program).unwrap();
     }
 
+    #[test]
+    #[cfg(feature = "rvc")]
+    fn test_compressed_prove() {
+        utils::setup_logger();
+        let program = compressed_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(feature = "rvc"))]
+    fn test_compressed_prove_requires_rvc() {
+        utils::setup_logger();
+        let program = compressed_program();
+        let result = prove::<_, CpuProver<_, _>>(
+            program,
+            &SP1Stdin::new(),
+            BabyBearPoseidon2::new(),
+            SP1CoreOpts::default(),
+            None,
+        );
+        assert!(matches!(result, Err(utils::SP1CoreProverError::CompressedInstructions)));
+    }
+
+    #[test]
+    fn test_atomic_prove() {
+        utils::setup_logger();
+        let program = atomic_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_secp256r1_prove() {
+        utils::setup_logger();
+        let program = secp256r1_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
     #[test]
     fn test_shift_prove() {
         utils::setup_logger();
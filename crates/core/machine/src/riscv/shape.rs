@@ -65,15 +65,49 @@ impl<F: PrimeField32> CoreShapeConfig<F> {
             return Err(CoreShapeError::PreprocessedShapeAlreadyFixed);
         }
 
-        let heights = RiscvAir::<F>::preprocessed_heights(program);
         let prep_shape =
-            Self::find_shape_from_allowed_heights(&heights, &self.allowed_preprocessed_log_heights)
-                .ok_or(CoreShapeError::PreprocessedShapeError)?;
+            self.find_preprocessed_shape(program).ok_or(CoreShapeError::PreprocessedShapeError)?;
 
         program.preprocessed_shape = Some(prep_shape);
         Ok(())
     }
 
+    /// Find the shape of the preprocessed chips of a program.
+    pub(crate) fn find_preprocessed_shape(&self, program: &Program) -> Option<CoreShape> {
+        let heights = RiscvAir::<F>::preprocessed_heights(program);
+        Self::find_shape_from_allowed_heights(&heights, &self.allowed_preprocessed_log_heights)
+    }
+
+    /// Find the shape of a shard containing cpu events, given the heights of its core chips.
+    pub(crate) fn find_core_shape(&self, heights: &[(RiscvAir<F>, usize)]) -> Option<CoreShape> {
+        self.allowed_core_log_heights.iter().find_map(|allowed_log_heights| {
+            Self::find_shape_from_allowed_heights(heights, allowed_log_heights)
+        })
+    }
+
+    /// Find the shape of a global memory shard, given the heights of its memory chips.
+    pub(crate) fn find_memory_shape(&self, heights: &[(RiscvAir<F>, usize)]) -> Option<CoreShape> {
+        Self::find_shape_from_allowed_heights(heights, &self.memory_allowed_log_heights)
+    }
+
+    /// Find the shape of a precompile shard, given the number of rows of the precompile chip and
+    /// the number of local memory events of its precompile events.
+    pub(crate) fn find_precompile_shape(
+        &self,
+        air: &RiscvAir<F>,
+        height: usize,
+        mem_events: usize,
+    ) -> Option<CoreShape> {
+        let (mem_events_per_row, allowed_log_heights) =
+            self.precompile_allowed_log_heights.get(air)?;
+        let allowed_log_height =
+            allowed_log_heights.iter().find(|&&log_height| height <= (1 << log_height))?;
+        self.get_precompile_shapes(air, *mem_events_per_row, *allowed_log_height)
+            .into_iter()
+            .find(|shape| mem_events <= (1 << shape[2].1) * NUM_LOCAL_MEMORY_ENTRIES_PER_ROW)
+            .map(|shape| shape.into_iter().collect())
+    }
+
     #[inline]
     fn find_shape_from_allowed_heights(
         heights: &[(RiscvAir<F>, usize)],
@@ -156,9 +190,9 @@ impl<F: PrimeField32> CoreShapeConfig<F> {
             || !record.global_memory_finalize_events.is_empty()
         {
             let heights = RiscvAir::<F>::get_memory_init_final_heights(record);
-            let shape =
-                Self::find_shape_from_allowed_heights(&heights, &self.memory_allowed_log_heights)
-                    .ok_or(CoreShapeError::ShapeError(record.stats()))?;
+            let shape = self
+                .find_memory_shape(&heights)
+                .ok_or(CoreShapeError::ShapeError(record.stats()))?;
             record.shape.as_mut().unwrap().extend(shape);
             return Ok(());
         }
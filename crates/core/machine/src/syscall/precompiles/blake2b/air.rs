@@ -0,0 +1,302 @@
+use core::borrow::Borrow;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::{
+    events::{BLAKE2B_IV, BLAKE2B_NUM_ROUNDS, BLAKE_G_STATE_INDICES, BLAKE_NUM_FINALIZE_STEPS},
+    syscalls::SyscallCode,
+    ByteOpcode,
+};
+use sp1_stark::{
+    air::{InteractionScope, SP1AirBuilder},
+    Word,
+};
+
+use super::{Blake2bCompressChip, Blake2bCompressCols, NUM_BLAKE2B_COMPRESS_COLS};
+use crate::{
+    air::MemoryAirBuilder,
+    memory::MemoryCols,
+    operations::{AddU64Operation, FixedRotateRightU64Operation, XorOperation, U64_SIZE},
+};
+
+impl<F> BaseAir<F> for Blake2bCompressChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE2B_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake2bCompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        // Initialize columns.
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake2bCompressCols<AB::Var> = (*local).borrow();
+        let next: &Blake2bCompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Evaluate the control flags.
+        self.eval_flags(builder);
+
+        // Whether the current row is the last of a 100-row cycle.
+        let is_last = local.round[BLAKE2B_NUM_ROUNDS] * local.g_index[BLAKE_NUM_FINALIZE_STEPS - 1];
+
+        // Copy over the inputs and the chaining value until the end of the compression (every 100
+        // rows).
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.state_ptr, next.state_ptr);
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.message_ptr, next.message_ptr);
+        for (local_h, next_h) in local.h.iter().flatten().zip(next.h.iter().flatten()) {
+            builder
+                .when_transition()
+                .when(AB::Expr::one() - is_last.clone())
+                .assert_eq(*local_h, *next_h);
+        }
+
+        // Select the words `h[i]` and `h[i + 4]` of the chaining value for the index `i < 4` of the
+        // G function call or finalization step.
+        for j in 0..4 {
+            for i in 0..U64_SIZE {
+                builder.when(local.g_index[j]).assert_eq(local.h_lo[i], local.h[j][i]);
+                builder.when(local.g_index[j]).assert_eq(local.h_hi[i], local.h[j + 4][i]);
+            }
+        }
+
+        // The G function call or finalization step of the current row is executed at
+        // `clk + 8 * round + g_index`.
+        let step = (0..=BLAKE2B_NUM_ROUNDS)
+            .map(|r| local.round[r] * AB::F::from_canonical_usize(8 * r))
+            .chain((0..8).map(|j| local.g_index[j] * AB::F::from_canonical_usize(j)))
+            .sum::<AB::Expr>();
+        let clk = local.clk + step;
+
+        // Access the low and high halves of the words `a`, `b`, `c` and `d` of the working vector.
+        for (k, halves) in local.state.chunks_exact(2).enumerate() {
+            let state_index = (0..8)
+                .map(|j| {
+                    local.g_index[j] * AB::F::from_canonical_usize(BLAKE_G_STATE_INDICES[j][k])
+                })
+                .sum::<AB::Expr>();
+            let addr = local.state_ptr + state_index * AB::F::from_canonical_u32(8);
+            for (h, state) in halves.iter().enumerate() {
+                builder.eval_memory_access(
+                    local.shard,
+                    clk.clone(),
+                    addr.clone() + AB::F::from_canonical_usize(4 * h),
+                    state,
+                    local.is_real,
+                );
+            }
+        }
+
+        // Read the low and high halves of the message words `x` and `y`.
+        for (halves, index) in local.message.chunks_exact(2).zip(local.message_indices) {
+            let addr = local.message_ptr + index * AB::F::from_canonical_u32(8);
+            for (h, message) in halves.iter().enumerate() {
+                builder.eval_memory_access(
+                    local.shard,
+                    clk.clone(),
+                    addr.clone() + AB::F::from_canonical_usize(4 * h),
+                    message,
+                    local.is_mix,
+                );
+            }
+        }
+
+        let [a, b, c, d] = core::array::from_fn(|k| {
+            u64_bytes(*local.state[2 * k].prev_value(), *local.state[2 * k + 1].prev_value())
+        });
+        let [x, y] = core::array::from_fn(|k| {
+            u64_bytes(*local.message[2 * k].value(), *local.message[2 * k + 1].value())
+        });
+
+        // The first four G function calls read the chaining value as `a` and `b`, and initialize
+        // `c` and `d` by XORing them with the IV. The other rows use the words as they are.
+        for i in 0..U64_SIZE {
+            builder.when(local.is_init).assert_eq(a[i], local.h_lo[i]);
+            builder.when(local.is_init).assert_eq(b[i], local.h_hi[i]);
+        }
+        let iv_bytes = |offset: usize, i: usize| {
+            (0..4)
+                .map(|j| {
+                    let byte = BLAKE2B_IV[offset + j].to_le_bytes()[i];
+                    local.g_index[j] * AB::F::from_canonical_u8(byte)
+                })
+                .sum::<AB::Expr>()
+        };
+        for i in 0..U64_SIZE {
+            for (input, word, offset) in [(local.c, c, 0), (local.d, d, 4)] {
+                builder.send_byte(
+                    AB::F::from_canonical_u32(ByteOpcode::XOR as u32),
+                    input[i],
+                    word[i],
+                    iv_bytes(offset, i),
+                    local.is_init,
+                );
+                builder.when_not(local.is_init).assert_eq(input[i], word[i]);
+            }
+        }
+
+        // a' := a + b + x.
+        AddU64Operation::<AB::F>::eval(builder, a, b, local.a_plus_b, local.is_mix);
+        AddU64Operation::<AB::F>::eval(builder, local.a_plus_b.value, x, local.a_1, local.is_mix);
+
+        // d' := (d ^ a') rightrotate 32.
+        let d_xor_a_1 = eval_xor(builder, local.d, local.a_1.value, local.d_xor_a_1, local.is_mix);
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            d_xor_a_1,
+            32,
+            local.d_1,
+            local.is_mix,
+        );
+
+        // c' := c + d'.
+        AddU64Operation::<AB::F>::eval(builder, local.c, local.d_1.value, local.c_1, local.is_mix);
+
+        // b' := (b ^ c') rightrotate 24.
+        let b_xor_c_1 = eval_xor(builder, b, local.c_1.value, local.b_xor_c_1, local.is_mix);
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            b_xor_c_1,
+            24,
+            local.b_1,
+            local.is_mix,
+        );
+
+        // a'' := a' + b' + y.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.a_1.value,
+            local.b_1.value,
+            local.a_1_plus_b_1,
+            local.is_mix,
+        );
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.a_1_plus_b_1.value,
+            y,
+            local.a_2,
+            local.is_mix,
+        );
+
+        // d'' := (d' ^ a'') rightrotate 16.
+        let d_1_xor_a_2 =
+            eval_xor(builder, local.d_1.value, local.a_2.value, local.d_1_xor_a_2, local.is_mix);
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            d_1_xor_a_2,
+            16,
+            local.d_2,
+            local.is_mix,
+        );
+
+        // c'' := c' + d''.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.c_1.value,
+            local.d_2.value,
+            local.c_2,
+            local.is_mix,
+        );
+
+        // b'' := (b' ^ c'') rightrotate 63.
+        let b_1_xor_c_2 =
+            eval_xor(builder, local.b_1.value, local.c_2.value, local.b_1_xor_c_2, local.is_mix);
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            b_1_xor_c_2,
+            63,
+            local.b_2,
+            local.is_mix,
+        );
+
+        // Write `a''`, `b''`, `c''` and `d''` back to the working vector.
+        let results = [local.a_2.value, local.b_2.value, local.c_2.value, local.d_2.value];
+        for (k, result) in results.iter().enumerate() {
+            let value = u64_bytes(*local.state[2 * k].value(), *local.state[2 * k + 1].value());
+            for i in 0..U64_SIZE {
+                builder.when(local.is_mix).assert_eq(value[i], result[i]);
+            }
+        }
+
+        // In the finalization step `i`, write `h[i] ^ v[i] ^ v[i + 8]` and
+        // `h[i + 4] ^ v[i + 4] ^ v[i + 12]`, and keep `v[i + 8]` and `v[i + 12]`.
+        let a_xor_c = eval_xor(builder, a, c, local.a_xor_c, local.is_finalize);
+        let h_lo =
+            eval_xor(builder, local.h_lo, a_xor_c, local.h_lo_xor_a_xor_c, local.is_finalize);
+        let b_xor_d = eval_xor(builder, b, d, local.b_xor_d, local.is_finalize);
+        let h_hi =
+            eval_xor(builder, local.h_hi, b_xor_d, local.h_hi_xor_b_xor_d, local.is_finalize);
+        for (k, result) in [h_lo, h_hi, c, d].iter().enumerate() {
+            let value = u64_bytes(*local.state[2 * k].value(), *local.state[2 * k + 1].value());
+            for i in 0..U64_SIZE {
+                builder.when(local.is_finalize).assert_eq(value[i], result[i]);
+            }
+        }
+
+        // Receive syscall event in first row of 100-cycle.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE2B_COMPRESS.syscall_id()),
+            local.state_ptr,
+            local.message_ptr,
+            local.is_first,
+            InteractionScope::Local,
+        );
+
+        // Assert that is_real is a bool.
+        builder.assert_bool(local.is_real);
+
+        // Ensure that all rows in a 100 row cycle has the same `is_real` values.
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last)
+            .assert_eq(local.is_real, next.is_real);
+
+        // Assert that the table ends in nonreal columns. Since each compress ecall is 100 cycles and
+        // the table is padded to a power of 2, the last row of the table should always be padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+}
+
+/// The little-endian bytes of the u64 whose low and high halves are `lo` and `hi`.
+fn u64_bytes<T: Copy>(lo: Word<T>, hi: Word<T>) -> [T; U64_SIZE] {
+    core::array::from_fn(|i| if i < 4 { lo[i] } else { hi[i - 4] })
+}
+
+/// Constrains the xor of two u64's given as their bytes, returning the bytes of the result.
+fn eval_xor<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    a: [AB::Var; U64_SIZE],
+    b: [AB::Var; U64_SIZE],
+    cols: [XorOperation<AB::Var>; 2],
+    is_real: AB::Var,
+) -> [AB::Var; U64_SIZE] {
+    for (h, cols) in cols.iter().enumerate() {
+        let a = Word(core::array::from_fn(|i| a[4 * h + i]));
+        let b = Word(core::array::from_fn(|i| b[4 * h + i]));
+        XorOperation::<AB::F>::eval(builder, a, b, *cols, is_real);
+    }
+    u64_bytes(cols[0].value, cols[1].value)
+}
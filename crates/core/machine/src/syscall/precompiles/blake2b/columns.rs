@@ -0,0 +1,104 @@
+use std::mem::size_of;
+
+use sp1_core_executor::events::BLAKE2B_NUM_ROUNDS;
+use sp1_derive::AlignedBorrow;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{AddU64Operation, FixedRotateRightU64Operation, XorOperation, U64_SIZE},
+};
+
+pub const NUM_BLAKE2B_COMPRESS_COLS: usize = size_of::<Blake2bCompressCols<u8>>();
+
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake2bCompressCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub nonce: T,
+    pub clk: T,
+    pub state_ptr: T,
+    pub message_ptr: T,
+
+    /// One-hot flags for the index of the G function call within the round.
+    pub g_index: [T; 8],
+
+    /// One-hot flags for the round, the last flag being for the finalization steps.
+    pub round: [T; BLAKE2B_NUM_ROUNDS + 1],
+
+    /// The indices of the two message words mixed in by the G function call.
+    pub message_indices: [T; 2],
+
+    /// Whether the current row is the first of a 100-row cycle and is real.
+    pub is_first: T,
+
+    /// Whether the current row is one of the first four G function calls, which initialize the
+    /// words `c` and `d` of the working vector by XORing them with the IV, and is real.
+    pub is_init: T,
+
+    /// Whether the current row is a G function call and is real.
+    pub is_mix: T,
+
+    /// Whether the current row is a finalization step and is real.
+    pub is_finalize: T,
+
+    /// The chaining value, copied over the whole compression.
+    pub h: [[T; U64_SIZE]; 8],
+
+    /// The words `h[i]` and `h[i + 4]` of the chaining value, where `i < 4` is the index of the G
+    /// function call or of the finalization step.
+    pub h_lo: [T; U64_SIZE],
+    pub h_hi: [T; U64_SIZE],
+
+    /// The memory accesses of the low and high halves of the working vector words `a`, `b`, `c`
+    /// and `d`.
+    pub state: [MemoryWriteCols<T>; 8],
+
+    /// The memory reads of the low and high halves of the message words `x` and `y`.
+    pub message: [MemoryReadCols<T>; 4],
+
+    /// The inputs `c` and `d` of the G function call, XORed with the IV in the first four calls.
+    pub c: [T; U64_SIZE],
+    pub d: [T; U64_SIZE],
+
+    /// `a' := a + b + x`.
+    pub a_plus_b: AddU64Operation<T>,
+    pub a_1: AddU64Operation<T>,
+
+    /// `d' := (d ^ a') rightrotate 32`.
+    pub d_xor_a_1: [XorOperation<T>; 2],
+    pub d_1: FixedRotateRightU64Operation<T>,
+
+    /// `c' := c + d'`.
+    pub c_1: AddU64Operation<T>,
+
+    /// `b' := (b ^ c') rightrotate 24`.
+    pub b_xor_c_1: [XorOperation<T>; 2],
+    pub b_1: FixedRotateRightU64Operation<T>,
+
+    /// `a'' := a' + b' + y`.
+    pub a_1_plus_b_1: AddU64Operation<T>,
+    pub a_2: AddU64Operation<T>,
+
+    /// `d'' := (d' ^ a'') rightrotate 16`.
+    pub d_1_xor_a_2: [XorOperation<T>; 2],
+    pub d_2: FixedRotateRightU64Operation<T>,
+
+    /// `c'' := c' + d''`.
+    pub c_2: AddU64Operation<T>,
+
+    /// `b'' := (b' ^ c'') rightrotate 63`.
+    pub b_1_xor_c_2: [XorOperation<T>; 2],
+    pub b_2: FixedRotateRightU64Operation<T>,
+
+    /// `h[i] ^ v[i] ^ v[i + 8]`, in the finalization step `i`.
+    pub a_xor_c: [XorOperation<T>; 2],
+    pub h_lo_xor_a_xor_c: [XorOperation<T>; 2],
+
+    /// `h[i + 4] ^ v[i + 4] ^ v[i + 12]`, in the finalization step `i`.
+    pub b_xor_d: [XorOperation<T>; 2],
+    pub h_hi_xor_b_xor_d: [XorOperation<T>; 2],
+
+    /// Selector.
+    pub is_real: T,
+}
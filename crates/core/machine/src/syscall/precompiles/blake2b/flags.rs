@@ -0,0 +1,108 @@
+use core::borrow::Borrow;
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, Field};
+use p3_matrix::Matrix;
+use sp1_core_executor::events::{BLAKE2B_NUM_ROUNDS, BLAKE2B_SIGMA, BLAKE_NUM_FINALIZE_STEPS};
+use sp1_stark::air::SP1AirBuilder;
+
+use super::{Blake2bCompressChip, Blake2bCompressCols};
+
+impl<F: Field> Blake2bCompressCols<F> {
+    pub fn populate_flags(&mut self, i: usize) {
+        // Populate the flags of the G function call or finalization step and the round of the
+        // current row.
+        let step = i % (BLAKE2B_NUM_ROUNDS * 8 + BLAKE_NUM_FINALIZE_STEPS);
+        let (round, g_index) = (step / 8, step % 8);
+        self.g_index = core::array::from_fn(|j| F::from_bool(j == g_index));
+        self.round = core::array::from_fn(|r| F::from_bool(r == round));
+
+        // Populate the indices of the message words mixed in by the G function call.
+        if round < BLAKE2B_NUM_ROUNDS {
+            self.message_indices = core::array::from_fn(|j| {
+                F::from_canonical_usize(BLAKE2B_SIGMA[round % 10][2 * g_index + j])
+            });
+        }
+
+        self.is_first = F::from_bool(step == 0) * self.is_real;
+        self.is_init = F::from_bool(round == 0 && g_index < 4) * self.is_real;
+        self.is_finalize = F::from_bool(round == BLAKE2B_NUM_ROUNDS) * self.is_real;
+        self.is_mix = self.is_real - self.is_finalize;
+    }
+}
+
+impl Blake2bCompressChip {
+    pub fn eval_flags<AB: SP1AirBuilder>(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake2bCompressCols<AB::Var> = (*local).borrow();
+        let next: &Blake2bCompressCols<AB::Var> = (*next).borrow();
+
+        let one = AB::Expr::one();
+
+        // The flags are one-hot.
+        for &flag in local.g_index.iter().chain(local.round.iter()) {
+            builder.assert_bool(flag);
+        }
+        builder.assert_one(local.g_index.iter().map(|&flag| flag.into()).sum::<AB::Expr>());
+        builder.assert_one(local.round.iter().map(|&flag| flag.into()).sum::<AB::Expr>());
+
+        // The first row of the table is the first G function call of the first round.
+        builder.when_first_row().assert_one(local.g_index[0]);
+        builder.when_first_row().assert_one(local.round[0]);
+
+        // Whether the current row is the last finalization step, after which the next compression
+        // starts.
+        let is_last = local.round[BLAKE2B_NUM_ROUNDS] * local.g_index[BLAKE_NUM_FINALIZE_STEPS - 1];
+
+        // Move on to the next G function call or finalization step in every row, and back to the
+        // first G function call after the last finalization step.
+        for j in 0..8 {
+            builder
+                .when_transition()
+                .when(one.clone() - is_last.clone())
+                .assert_eq(local.g_index[j], next.g_index[(j + 1) % 8]);
+        }
+        builder.when_transition().when(is_last.clone()).assert_one(next.g_index[0]);
+
+        // Move on to the next round, or to the finalization, after the last G function call of a
+        // round, and back to the first round after the last finalization step. Otherwise, keep the
+        // round the same.
+        for r in 0..=BLAKE2B_NUM_ROUNDS {
+            builder
+                .when_transition()
+                .when(local.g_index[7])
+                .assert_eq(local.round[r], next.round[(r + 1) % (BLAKE2B_NUM_ROUNDS + 1)]);
+            builder
+                .when_transition()
+                .when(one.clone() - local.g_index[7] - is_last.clone())
+                .assert_eq(local.round[r], next.round[r]);
+        }
+        builder.when_transition().when(is_last).assert_one(next.round[0]);
+
+        // Constrain the message indices according to the message schedule, which repeats after ten
+        // rounds.
+        for j in 0..2 {
+            let mut message_index = AB::Expr::zero();
+            for r in 0..BLAKE2B_NUM_ROUNDS {
+                let schedule = &BLAKE2B_SIGMA[r % 10];
+                for g_index in 0..8 {
+                    message_index += local.round[r]
+                        * local.g_index[g_index]
+                        * AB::F::from_canonical_usize(schedule[2 * g_index + j]);
+                }
+            }
+            builder.assert_eq(local.message_indices[j], message_index);
+        }
+
+        // is_first == first G function call AND first round AND is_real.
+        builder.assert_eq(local.g_index[0] * local.round[0] * local.is_real, local.is_first);
+
+        // is_init == one of the first four G function calls AND first round AND is_real.
+        let is_first_four = (0..4).map(|j| local.g_index[j].into()).sum::<AB::Expr>();
+        builder.assert_eq(is_first_four * local.round[0] * local.is_real, local.is_init);
+
+        // is_finalize == finalization AND is_real, and is_mix == NOT finalization AND is_real.
+        builder.assert_eq(local.round[BLAKE2B_NUM_ROUNDS] * local.is_real, local.is_finalize);
+        builder.assert_eq(local.is_real - local.is_finalize, local.is_mix);
+    }
+}
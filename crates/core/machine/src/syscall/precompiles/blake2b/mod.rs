@@ -0,0 +1,46 @@
+mod air;
+mod columns;
+mod flags;
+mod trace;
+
+pub use columns::*;
+
+/// Implements the BLAKE2b compression function, which applies the G function eight times per round
+/// to the working vector `v` of 16 u64's, mixing in the message `m` of 16 u64's. The inputs to the
+/// syscall are the 4byte-aligned pointers to the state and to `m`.
+///
+/// The state is used as the working vector. It holds the chaining value `h` followed by the words
+/// which are XORed with the IV to initialize the second half of the working vector, i.e. zeros
+/// except for the counter `t` in words 12 and 13 and the final block flag `f` in word 14. The first
+/// four G function calls apply this initialization on the fly. After the last round, four
+/// finalization steps write `h[i] ^ v[i] ^ v[i + 8]` to the first half of the state, which is the
+/// new chaining value, and leave `v[i + 8]` in the second half.
+///
+/// As the AIR operates on 32-bit words, each u64 is handled as its eight little-endian bytes.
+///
+/// In the AIR, each BLAKE2b compress syscall takes up 100 rows, where each of the first 96 rows
+/// corresponds to a single call of the G function and each of the last 4 rows to a finalization
+/// step.
+#[derive(Default)]
+pub struct Blake2bCompressChip;
+
+impl Blake2bCompressChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+    use sp1_core_executor::programs::tests::blake2b_compress_program;
+    use sp1_stark::CpuProver;
+
+    use crate::utils::{self, run_test};
+
+    #[test]
+    fn test_blake2b_compress_prove() {
+        utils::setup_logger();
+        let program = blake2b_compress_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
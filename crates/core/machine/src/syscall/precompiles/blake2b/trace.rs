@@ -0,0 +1,263 @@
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{
+        Blake2bCompressEvent, ByteLookupEvent, ByteRecord, MemoryWriteRecord, PrecompileEvent,
+        BLAKE2B_IV,
+    },
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_stark::air::MachineAir;
+use std::borrow::BorrowMut;
+
+use crate::operations::XorOperation;
+
+use super::{Blake2bCompressChip, Blake2bCompressCols, NUM_BLAKE2B_COMPRESS_COLS};
+
+impl<F: PrimeField32> MachineAir<F> for Blake2bCompressChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Blake2bCompress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+        let mut wrapped_rows = Some(rows);
+        for (_, event) in input.get_precompile_events(SyscallCode::BLAKE2B_COMPRESS).iter() {
+            let event = if let PrecompileEvent::Blake2bCompress(event) = event {
+                event
+            } else {
+                unreachable!()
+            };
+            self.event_to_rows(event, &mut wrapped_rows, &mut new_byte_lookup_events);
+        }
+
+        let mut rows = wrapped_rows.unwrap();
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = nb_rows.next_power_of_two();
+        if padded_nb_rows == 2 || padded_nb_rows == 1 {
+            padded_nb_rows = 4;
+        }
+        for i in nb_rows..padded_nb_rows {
+            let mut row = [F::zero(); NUM_BLAKE2B_COMPRESS_COLS];
+            let cols: &mut Blake2bCompressCols<F> = row.as_mut_slice().borrow_mut();
+            cols.populate_flags(i);
+            rows.push(row);
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE2B_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Blake2bCompressCols<F> = trace.values
+                [i * NUM_BLAKE2B_COMPRESS_COLS..(i + 1) * NUM_BLAKE2B_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let events = input.get_precompile_events(SyscallCode::BLAKE2B_COMPRESS);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    let event = if let PrecompileEvent::Blake2bCompress(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    };
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::BLAKE2B_COMPRESS).is_empty()
+        }
+    }
+}
+
+impl Blake2bCompressChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Blake2bCompressEvent,
+        rows: &mut Option<Vec<[F; NUM_BLAKE2B_COMPRESS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let shard = event.shard;
+
+        // The chaining value is read as `a` and `b` by the first four G function calls.
+        let prev_word = |writes: &[MemoryWriteRecord; 8], k: usize| {
+            u64_from_halves(writes[2 * k].prev_value, writes[2 * k + 1].prev_value)
+        };
+        let mut h = [0u64; 8];
+        for (i, state_writes) in event.state_writes[..4].iter().enumerate() {
+            h[i] = prev_word(state_writes, 0);
+            h[i + 4] = prev_word(state_writes, 1);
+        }
+
+        for (j, state_writes) in event.state_writes.iter().enumerate() {
+            let mut row = [F::zero(); NUM_BLAKE2B_COMPRESS_COLS];
+            let cols: &mut Blake2bCompressCols<F> = row.as_mut_slice().borrow_mut();
+            cols.is_real = F::one();
+            cols.populate_flags(j);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+            cols.message_ptr = F::from_canonical_u32(event.message_ptr);
+
+            for (state, record) in cols.state.iter_mut().zip(state_writes) {
+                state.populate(*record, blu);
+            }
+
+            cols.h = h.map(u64::to_le_bytes).map(|bytes| bytes.map(F::from_canonical_u8));
+            let g_index = j % 8;
+            if g_index < 4 {
+                cols.h_lo = cols.h[g_index];
+                cols.h_hi = cols.h[g_index + 4];
+            }
+
+            let [a, b, c, d] = core::array::from_fn(|k| prev_word(state_writes, k));
+            let [c_in, d_in] = if cols.is_init == F::one() {
+                let c_in = populate_xor_bytes(blu, shard, c, BLAKE2B_IV[g_index]);
+                let d_in = populate_xor_bytes(blu, shard, d, BLAKE2B_IV[g_index + 4]);
+                [c_in, d_in]
+            } else {
+                [c, d]
+            };
+            cols.c = c_in.to_le_bytes().map(F::from_canonical_u8);
+            cols.d = d_in.to_le_bytes().map(F::from_canonical_u8);
+
+            if cols.is_finalize == F::one() {
+                // `h[i] ^ v[i] ^ v[i + 8]` and `h[i + 4] ^ v[i + 4] ^ v[i + 12]`.
+                let a_xor_c = populate_xor(&mut cols.a_xor_c, blu, shard, a, c);
+                let h_lo =
+                    populate_xor(&mut cols.h_lo_xor_a_xor_c, blu, shard, h[g_index], a_xor_c);
+                let b_xor_d = populate_xor(&mut cols.b_xor_d, blu, shard, b, d);
+                let h_hi =
+                    populate_xor(&mut cols.h_hi_xor_b_xor_d, blu, shard, h[g_index + 4], b_xor_d);
+
+                debug_assert_eq!(
+                    [h_lo, h_hi, c, d],
+                    core::array::from_fn(|k| {
+                        u64_from_halves(state_writes[2 * k].value, state_writes[2 * k + 1].value)
+                    }),
+                    "blake2b finalization mismatch"
+                );
+            } else {
+                let message_reads = &event.message_reads[j];
+                for (message, record) in cols.message.iter_mut().zip(message_reads) {
+                    message.populate(*record, blu);
+                }
+                let [x, y] = core::array::from_fn(|k| {
+                    u64_from_halves(message_reads[2 * k].value, message_reads[2 * k + 1].value)
+                });
+
+                // `a' := a + b + x`.
+                let a_plus_b = cols.a_plus_b.populate(blu, shard, a, b);
+                let a_1 = cols.a_1.populate(blu, shard, a_plus_b, x);
+
+                // `d' := (d ^ a') rightrotate 32`.
+                let d_xor_a_1 = populate_xor(&mut cols.d_xor_a_1, blu, shard, d_in, a_1);
+                let d_1 = cols.d_1.populate(blu, shard, d_xor_a_1, 32);
+
+                // `c' := c + d'`.
+                let c_1 = cols.c_1.populate(blu, shard, c_in, d_1);
+
+                // `b' := (b ^ c') rightrotate 24`.
+                let b_xor_c_1 = populate_xor(&mut cols.b_xor_c_1, blu, shard, b, c_1);
+                let b_1 = cols.b_1.populate(blu, shard, b_xor_c_1, 24);
+
+                // `a'' := a' + b' + y`.
+                let a_1_plus_b_1 = cols.a_1_plus_b_1.populate(blu, shard, a_1, b_1);
+                let a_2 = cols.a_2.populate(blu, shard, a_1_plus_b_1, y);
+
+                // `d'' := (d' ^ a'') rightrotate 16`.
+                let d_1_xor_a_2 = populate_xor(&mut cols.d_1_xor_a_2, blu, shard, d_1, a_2);
+                let d_2 = cols.d_2.populate(blu, shard, d_1_xor_a_2, 16);
+
+                // `c'' := c' + d''`.
+                let c_2 = cols.c_2.populate(blu, shard, c_1, d_2);
+
+                // `b'' := (b' ^ c'') rightrotate 63`.
+                let b_1_xor_c_2 = populate_xor(&mut cols.b_1_xor_c_2, blu, shard, b_1, c_2);
+                let b_2 = cols.b_2.populate(blu, shard, b_1_xor_c_2, 63);
+
+                debug_assert_eq!(
+                    [a_2, b_2, c_2, d_2],
+                    core::array::from_fn(|k| {
+                        u64_from_halves(state_writes[2 * k].value, state_writes[2 * k + 1].value)
+                    }),
+                    "blake2b G function mismatch"
+                );
+            }
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+    }
+}
+
+/// The u64 whose low and high halves are `lo` and `hi`.
+fn u64_from_halves(lo: u32, hi: u32) -> u64 {
+    lo as u64 | (hi as u64) << 32
+}
+
+/// Populates the xor of two u64's, computed on their low and high halves.
+fn populate_xor<F: PrimeField32>(
+    cols: &mut [XorOperation<F>; 2],
+    blu: &mut impl ByteRecord,
+    shard: u32,
+    x: u64,
+    y: u64,
+) -> u64 {
+    let lo = cols[0].populate(blu, shard, x as u32, y as u32);
+    let hi = cols[1].populate(blu, shard, (x >> 32) as u32, (y >> 32) as u32);
+    u64_from_halves(lo, hi)
+}
+
+/// Records the byte lookups of the xor of a u64 with a constant, returning the result.
+fn populate_xor_bytes(blu: &mut impl ByteRecord, shard: u32, x: u64, y: u64) -> u64 {
+    for (x_byte, y_byte) in x.to_le_bytes().into_iter().zip(y.to_le_bytes()) {
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            shard,
+            opcode: ByteOpcode::XOR,
+            a1: (x_byte ^ y_byte) as u16,
+            a2: 0,
+            b: x_byte,
+            c: y_byte,
+        });
+    }
+    x ^ y
+}
@@ -0,0 +1,280 @@
+use core::borrow::Borrow;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::{
+    events::{BLAKE3_IV, BLAKE3_NUM_ROUNDS, BLAKE_G_STATE_INDICES, BLAKE_NUM_FINALIZE_STEPS},
+    syscalls::SyscallCode,
+    ByteOpcode,
+};
+use sp1_stark::air::{InteractionScope, SP1AirBuilder};
+
+use super::{Blake3CompressChip, Blake3CompressCols, NUM_BLAKE3_COMPRESS_COLS};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+};
+
+impl<F> BaseAir<F> for Blake3CompressChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE3_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake3CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        // Initialize columns.
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake3CompressCols<AB::Var> = (*local).borrow();
+        let next: &Blake3CompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Evaluate the control flags.
+        self.eval_flags(builder);
+
+        // Whether the current row is the last of a 60-row cycle.
+        let is_last = local.round[BLAKE3_NUM_ROUNDS] * local.g_index[BLAKE_NUM_FINALIZE_STEPS - 1];
+
+        // Copy over the inputs and the chaining value until the end of the compression (every 60
+        // rows).
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.state_ptr, next.state_ptr);
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last.clone())
+            .assert_eq(local.message_ptr, next.message_ptr);
+        for (local_cv, next_cv) in local.cv.iter().zip(next.cv.iter()) {
+            builder
+                .when_transition()
+                .when(AB::Expr::one() - is_last.clone())
+                .assert_word_eq(*local_cv, *next_cv);
+        }
+
+        // Select the words `cv[i]` and `cv[i + 4]` of the chaining value for the index `i < 4` of
+        // the G function call or finalization step.
+        for j in 0..4 {
+            builder.when(local.g_index[j]).assert_word_eq(local.cv_lo, local.cv[j]);
+            builder.when(local.g_index[j]).assert_word_eq(local.cv_hi, local.cv[j + 4]);
+        }
+
+        // The G function call or finalization step of the current row is executed at
+        // `clk + 8 * round + g_index`.
+        let step = (0..=BLAKE3_NUM_ROUNDS)
+            .map(|r| local.round[r] * AB::F::from_canonical_usize(8 * r))
+            .chain((0..8).map(|j| local.g_index[j] * AB::F::from_canonical_usize(j)))
+            .sum::<AB::Expr>();
+        let clk = local.clk + step;
+
+        // Access the words `a`, `b`, `c` and `d` of the working vector.
+        for (k, state) in local.state.iter().enumerate() {
+            let state_index = (0..8)
+                .map(|j| {
+                    local.g_index[j] * AB::F::from_canonical_usize(BLAKE_G_STATE_INDICES[j][k])
+                })
+                .sum::<AB::Expr>();
+            builder.eval_memory_access(
+                local.shard,
+                clk.clone(),
+                local.state_ptr + state_index * AB::F::from_canonical_u32(4),
+                state,
+                local.is_real,
+            );
+        }
+
+        // Read the message words `x` and `y`.
+        for (message, index) in local.message.iter().zip(local.message_indices) {
+            builder.eval_memory_access(
+                local.shard,
+                clk.clone(),
+                local.message_ptr + index * AB::F::from_canonical_u32(4),
+                message,
+                local.is_mix,
+            );
+        }
+
+        let [a, b, c, d] = local.state.map(|state| *state.prev_value());
+        let [x, y] = local.message.map(|message| *message.value());
+
+        // The first four G function calls read the chaining value as `a` and `b`, and initialize
+        // `c` by XORing it with the IV. The other rows use the word as it is.
+        builder.when(local.is_init).assert_word_eq(a, local.cv_lo);
+        builder.when(local.is_init).assert_word_eq(b, local.cv_hi);
+        for i in 0..4 {
+            let iv_byte = (0..4)
+                .map(|j| {
+                    let byte = BLAKE3_IV[j].to_le_bytes()[i];
+                    local.g_index[j] * AB::F::from_canonical_u8(byte)
+                })
+                .sum::<AB::Expr>();
+            builder.send_byte(
+                AB::F::from_canonical_u32(ByteOpcode::XOR as u32),
+                local.c[i],
+                c[i],
+                iv_byte,
+                local.is_init,
+            );
+        }
+        builder.when_not(local.is_init).assert_word_eq(local.c, c);
+
+        // a' := a + b + x.
+        AddOperation::<AB::F>::eval(builder, a, b, local.a_plus_b, local.is_mix.into());
+        AddOperation::<AB::F>::eval(
+            builder,
+            local.a_plus_b.value,
+            x,
+            local.a_1,
+            local.is_mix.into(),
+        );
+
+        // d' := (d ^ a') rightrotate 16.
+        XorOperation::<AB::F>::eval(builder, d, local.a_1.value, local.d_xor_a_1, local.is_mix);
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            local.d_xor_a_1.value,
+            16,
+            local.d_1,
+            local.is_mix,
+        );
+
+        // c' := c + d'.
+        AddOperation::<AB::F>::eval(
+            builder,
+            local.c,
+            local.d_1.value,
+            local.c_1,
+            local.is_mix.into(),
+        );
+
+        // b' := (b ^ c') rightrotate 12.
+        XorOperation::<AB::F>::eval(builder, b, local.c_1.value, local.b_xor_c_1, local.is_mix);
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            local.b_xor_c_1.value,
+            12,
+            local.b_1,
+            local.is_mix,
+        );
+
+        // a'' := a' + b' + y.
+        AddOperation::<AB::F>::eval(
+            builder,
+            local.a_1.value,
+            local.b_1.value,
+            local.a_1_plus_b_1,
+            local.is_mix.into(),
+        );
+        AddOperation::<AB::F>::eval(
+            builder,
+            local.a_1_plus_b_1.value,
+            y,
+            local.a_2,
+            local.is_mix.into(),
+        );
+
+        // d'' := (d' ^ a'') rightrotate 8.
+        XorOperation::<AB::F>::eval(
+            builder,
+            local.d_1.value,
+            local.a_2.value,
+            local.d_1_xor_a_2,
+            local.is_mix,
+        );
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            local.d_1_xor_a_2.value,
+            8,
+            local.d_2,
+            local.is_mix,
+        );
+
+        // c'' := c' + d''.
+        AddOperation::<AB::F>::eval(
+            builder,
+            local.c_1.value,
+            local.d_2.value,
+            local.c_2,
+            local.is_mix.into(),
+        );
+
+        // b'' := (b' ^ c'') rightrotate 7.
+        XorOperation::<AB::F>::eval(
+            builder,
+            local.b_1.value,
+            local.c_2.value,
+            local.b_1_xor_c_2,
+            local.is_mix,
+        );
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            local.b_1_xor_c_2.value,
+            7,
+            local.b_2,
+            local.is_mix,
+        );
+
+        // Write `a''`, `b''`, `c''` and `d''` back to the working vector.
+        let results = [local.a_2.value, local.b_2.value, local.c_2.value, local.d_2.value];
+        for (state, result) in local.state.iter().zip(results) {
+            builder.when(local.is_mix).assert_word_eq(*state.value(), result);
+        }
+
+        // In the finalization step `i`, write `v[i] ^ v[i + 8]`, `v[i + 4] ^ v[i + 12]`,
+        // `v[i + 8] ^ cv[i]` and `v[i + 12] ^ cv[i + 4]`.
+        XorOperation::<AB::F>::eval(builder, a, c, local.a_xor_c, local.is_finalize);
+        XorOperation::<AB::F>::eval(builder, b, d, local.b_xor_d, local.is_finalize);
+        XorOperation::<AB::F>::eval(builder, c, local.cv_lo, local.c_xor_cv_lo, local.is_finalize);
+        XorOperation::<AB::F>::eval(builder, d, local.cv_hi, local.d_xor_cv_hi, local.is_finalize);
+        let results = [
+            local.a_xor_c.value,
+            local.b_xor_d.value,
+            local.c_xor_cv_lo.value,
+            local.d_xor_cv_hi.value,
+        ];
+        for (state, result) in local.state.iter().zip(results) {
+            builder.when(local.is_finalize).assert_word_eq(*state.value(), result);
+        }
+
+        // Receive syscall event in first row of 60-cycle.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE3_COMPRESS.syscall_id()),
+            local.state_ptr,
+            local.message_ptr,
+            local.is_first,
+            InteractionScope::Local,
+        );
+
+        // Assert that is_real is a bool.
+        builder.assert_bool(local.is_real);
+
+        // Ensure that all rows in a 60 row cycle has the same `is_real` values.
+        builder
+            .when_transition()
+            .when(AB::Expr::one() - is_last)
+            .assert_eq(local.is_real, next.is_real);
+
+        // Assert that the table ends in nonreal columns. Since each compress ecall is 60 cycles and
+        // the table is padded to a power of 2, the last row of the table should always be padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+}
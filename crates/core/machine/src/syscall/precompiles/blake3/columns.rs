@@ -0,0 +1,103 @@
+use std::mem::size_of;
+
+use sp1_core_executor::events::BLAKE3_NUM_ROUNDS;
+use sp1_derive::AlignedBorrow;
+use sp1_stark::Word;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+};
+
+pub const NUM_BLAKE3_COMPRESS_COLS: usize = size_of::<Blake3CompressCols<u8>>();
+
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake3CompressCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub nonce: T,
+    pub clk: T,
+    pub state_ptr: T,
+    pub message_ptr: T,
+
+    /// One-hot flags for the index of the G function call within the round.
+    pub g_index: [T; 8],
+
+    /// One-hot flags for the round, the last flag being for the finalization steps.
+    pub round: [T; BLAKE3_NUM_ROUNDS + 1],
+
+    /// The indices of the two message words mixed in by the G function call.
+    pub message_indices: [T; 2],
+
+    /// Whether the current row is the first of a 60-row cycle and is real.
+    pub is_first: T,
+
+    /// Whether the current row is one of the first four G function calls, which initialize the
+    /// word `c` of the working vector by XORing it with the IV, and is real.
+    pub is_init: T,
+
+    /// Whether the current row is a G function call and is real.
+    pub is_mix: T,
+
+    /// Whether the current row is a finalization step and is real.
+    pub is_finalize: T,
+
+    /// The chaining value, copied over the whole compression.
+    pub cv: [Word<T>; 8],
+
+    /// The words `cv[i]` and `cv[i + 4]` of the chaining value, where `i < 4` is the index of the
+    /// G function call or of the finalization step.
+    pub cv_lo: Word<T>,
+    pub cv_hi: Word<T>,
+
+    /// The memory accesses of the working vector words `a`, `b`, `c` and `d`.
+    pub state: [MemoryWriteCols<T>; 4],
+
+    /// The memory reads of the message words `x` and `y`.
+    pub message: [MemoryReadCols<T>; 2],
+
+    /// The input `c` of the G function call, XORed with the IV in the first four calls.
+    pub c: Word<T>,
+
+    /// `a' := a + b + x`.
+    pub a_plus_b: AddOperation<T>,
+    pub a_1: AddOperation<T>,
+
+    /// `d' := (d ^ a') rightrotate 16`.
+    pub d_xor_a_1: XorOperation<T>,
+    pub d_1: FixedRotateRightOperation<T>,
+
+    /// `c' := c + d'`.
+    pub c_1: AddOperation<T>,
+
+    /// `b' := (b ^ c') rightrotate 12`.
+    pub b_xor_c_1: XorOperation<T>,
+    pub b_1: FixedRotateRightOperation<T>,
+
+    /// `a'' := a' + b' + y`.
+    pub a_1_plus_b_1: AddOperation<T>,
+    pub a_2: AddOperation<T>,
+
+    /// `d'' := (d' ^ a'') rightrotate 8`.
+    pub d_1_xor_a_2: XorOperation<T>,
+    pub d_2: FixedRotateRightOperation<T>,
+
+    /// `c'' := c' + d''`.
+    pub c_2: AddOperation<T>,
+
+    /// `b'' := (b' ^ c'') rightrotate 7`.
+    pub b_1_xor_c_2: XorOperation<T>,
+    pub b_2: FixedRotateRightOperation<T>,
+
+    /// `v[i] ^ v[i + 8]` and `v[i + 4] ^ v[i + 12]`, in the finalization step `i`.
+    pub a_xor_c: XorOperation<T>,
+    pub b_xor_d: XorOperation<T>,
+
+    /// `v[i + 8] ^ cv[i]` and `v[i + 12] ^ cv[i + 4]`, in the finalization step `i`.
+    pub c_xor_cv_lo: XorOperation<T>,
+    pub d_xor_cv_hi: XorOperation<T>,
+
+    /// Selector.
+    pub is_real: T,
+}
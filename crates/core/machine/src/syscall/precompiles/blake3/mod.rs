@@ -0,0 +1,43 @@
+mod air;
+mod columns;
+mod flags;
+mod trace;
+
+pub use columns::*;
+
+/// Implements the BLAKE3 compression function, which applies the G function eight times per round
+/// to the 16-word working vector `v`, mixing in the 16-word message `m`. The inputs to the syscall
+/// are the 4byte-aligned pointers to the 16-word state and to `m`.
+///
+/// The state is used as the working vector. It holds the chaining value `cv`, followed by four
+/// words which are XORed with the first half of the IV (zeros), the counter, the block length and
+/// the flags. The first four G function calls apply this initialization on the fly. After the last
+/// round, four finalization steps write the 16-word output, `v[i] ^ v[i + 8]` followed by
+/// `v[i + 8] ^ cv[i]`, to the state.
+///
+/// In the AIR, each BLAKE3 compress syscall takes up 60 rows, where each of the first 56 rows
+/// corresponds to a single call of the G function and each of the last 4 rows to a finalization
+/// step.
+#[derive(Default)]
+pub struct Blake3CompressChip;
+
+impl Blake3CompressChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+    use sp1_core_executor::programs::tests::blake3_compress_program;
+    use sp1_stark::CpuProver;
+
+    use crate::utils::{self, run_test};
+
+    #[test]
+    fn test_blake3_compress_prove() {
+        utils::setup_logger();
+        let program = blake3_compress_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{Blake3CompressEvent, ByteLookupEvent, ByteRecord, PrecompileEvent, BLAKE3_IV},
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_stark::{air::MachineAir, Word};
+use std::borrow::BorrowMut;
+
+use super::{Blake3CompressChip, Blake3CompressCols, NUM_BLAKE3_COMPRESS_COLS};
+
+impl<F: PrimeField32> MachineAir<F> for Blake3CompressChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Blake3Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+        let mut wrapped_rows = Some(rows);
+        for (_, event) in input.get_precompile_events(SyscallCode::BLAKE3_COMPRESS).iter() {
+            let event = if let PrecompileEvent::Blake3Compress(event) = event {
+                event
+            } else {
+                unreachable!()
+            };
+            self.event_to_rows(event, &mut wrapped_rows, &mut new_byte_lookup_events);
+        }
+
+        let mut rows = wrapped_rows.unwrap();
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = nb_rows.next_power_of_two();
+        if padded_nb_rows == 2 || padded_nb_rows == 1 {
+            padded_nb_rows = 4;
+        }
+        for i in nb_rows..padded_nb_rows {
+            let mut row = [F::zero(); NUM_BLAKE3_COMPRESS_COLS];
+            let cols: &mut Blake3CompressCols<F> = row.as_mut_slice().borrow_mut();
+            cols.populate_flags(i);
+            rows.push(row);
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE3_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Blake3CompressCols<F> = trace.values
+                [i * NUM_BLAKE3_COMPRESS_COLS..(i + 1) * NUM_BLAKE3_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let events = input.get_precompile_events(SyscallCode::BLAKE3_COMPRESS);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    let event = if let PrecompileEvent::Blake3Compress(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    };
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::BLAKE3_COMPRESS).is_empty()
+        }
+    }
+}
+
+impl Blake3CompressChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Blake3CompressEvent,
+        rows: &mut Option<Vec<[F; NUM_BLAKE3_COMPRESS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let shard = event.shard;
+
+        // The chaining value is read as `a` and `b` by the first four G function calls.
+        let mut cv = [0u32; 8];
+        for (i, state_writes) in event.state_writes[..4].iter().enumerate() {
+            cv[i] = state_writes[0].prev_value;
+            cv[i + 4] = state_writes[1].prev_value;
+        }
+
+        for (j, state_writes) in event.state_writes.iter().enumerate() {
+            let mut row = [F::zero(); NUM_BLAKE3_COMPRESS_COLS];
+            let cols: &mut Blake3CompressCols<F> = row.as_mut_slice().borrow_mut();
+            cols.is_real = F::one();
+            cols.populate_flags(j);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+            cols.message_ptr = F::from_canonical_u32(event.message_ptr);
+
+            for (state, record) in cols.state.iter_mut().zip(state_writes) {
+                state.populate(*record, blu);
+            }
+
+            cols.cv = cv.map(Word::from);
+            let g_index = j % 8;
+            if g_index < 4 {
+                cols.cv_lo = cols.cv[g_index];
+                cols.cv_hi = cols.cv[g_index + 4];
+            }
+
+            let [a, b, c, d] = state_writes.map(|record| record.prev_value);
+            let c_in = if cols.is_init == F::one() {
+                populate_xor_bytes(blu, shard, c, BLAKE3_IV[g_index])
+            } else {
+                c
+            };
+            cols.c = Word::from(c_in);
+
+            if cols.is_finalize == F::one() {
+                // `v[i] ^ v[i + 8]`, `v[i + 4] ^ v[i + 12]`, `v[i + 8] ^ cv[i]` and
+                // `v[i + 12] ^ cv[i + 4]`.
+                let a_xor_c = cols.a_xor_c.populate(blu, shard, a, c);
+                let b_xor_d = cols.b_xor_d.populate(blu, shard, b, d);
+                let c_xor_cv_lo = cols.c_xor_cv_lo.populate(blu, shard, c, cv[g_index]);
+                let d_xor_cv_hi = cols.d_xor_cv_hi.populate(blu, shard, d, cv[g_index + 4]);
+
+                debug_assert_eq!(
+                    [a_xor_c, b_xor_d, c_xor_cv_lo, d_xor_cv_hi],
+                    state_writes.map(|record| record.value),
+                    "blake3 finalization mismatch"
+                );
+            } else {
+                let message_reads = &event.message_reads[j];
+                for (message, record) in cols.message.iter_mut().zip(message_reads) {
+                    message.populate(*record, blu);
+                }
+                let [x, y] = message_reads.map(|record| record.value);
+
+                // `a' := a + b + x`.
+                let a_plus_b = cols.a_plus_b.populate(blu, shard, a, b);
+                let a_1 = cols.a_1.populate(blu, shard, a_plus_b, x);
+
+                // `d' := (d ^ a') rightrotate 16`.
+                let d_xor_a_1 = cols.d_xor_a_1.populate(blu, shard, d, a_1);
+                let d_1 = cols.d_1.populate(blu, shard, d_xor_a_1, 16);
+
+                // `c' := c + d'`.
+                let c_1 = cols.c_1.populate(blu, shard, c_in, d_1);
+
+                // `b' := (b ^ c') rightrotate 12`.
+                let b_xor_c_1 = cols.b_xor_c_1.populate(blu, shard, b, c_1);
+                let b_1 = cols.b_1.populate(blu, shard, b_xor_c_1, 12);
+
+                // `a'' := a' + b' + y`.
+                let a_1_plus_b_1 = cols.a_1_plus_b_1.populate(blu, shard, a_1, b_1);
+                let a_2 = cols.a_2.populate(blu, shard, a_1_plus_b_1, y);
+
+                // `d'' := (d' ^ a'') rightrotate 8`.
+                let d_1_xor_a_2 = cols.d_1_xor_a_2.populate(blu, shard, d_1, a_2);
+                let d_2 = cols.d_2.populate(blu, shard, d_1_xor_a_2, 8);
+
+                // `c'' := c' + d''`.
+                let c_2 = cols.c_2.populate(blu, shard, c_1, d_2);
+
+                // `b'' := (b' ^ c'') rightrotate 7`.
+                let b_1_xor_c_2 = cols.b_1_xor_c_2.populate(blu, shard, b_1, c_2);
+                let b_2 = cols.b_2.populate(blu, shard, b_1_xor_c_2, 7);
+
+                debug_assert_eq!(
+                    [a_2, b_2, c_2, d_2],
+                    state_writes.map(|record| record.value),
+                    "blake3 G function mismatch"
+                );
+            }
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+    }
+}
+
+/// Records the byte lookups of the xor of a u32 with a constant, returning the result.
+fn populate_xor_bytes(blu: &mut impl ByteRecord, shard: u32, x: u32, y: u32) -> u32 {
+    for (x_byte, y_byte) in x.to_le_bytes().into_iter().zip(y.to_le_bytes()) {
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            shard,
+            opcode: ByteOpcode::XOR,
+            a1: (x_byte ^ y_byte) as u16,
+            a2: 0,
+            b: x_byte,
+            c: y_byte,
+        });
+    }
+    x ^ y
+}
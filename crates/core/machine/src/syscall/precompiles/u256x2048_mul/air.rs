@@ -0,0 +1,339 @@
+use crate::{
+    air::MemoryAirBuilder,
+    memory::{value_as_limbs, MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+    utils::{limbs_from_access, pad_rows_fixed, words_to_bytes_le},
+};
+
+use num::{BigUint, One, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{ByteRecord, PrecompileEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program, Register,
+};
+use sp1_curves::{
+    params::{Limbs, NumLimbs},
+    uint256::U256Field,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{BaseAirBuilder, InteractionScope, MachineAir, Polynomial, SP1AirBuilder};
+use std::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+/// The number of columns in the U256x2048MulCols.
+const NUM_COLS: usize = size_of::<U256x2048MulCols<u8>>();
+
+/// The number of words in a uint256.
+const U256_NUM_WORDS: usize = 8;
+
+/// The number of words in a uint2048.
+const U2048_NUM_WORDS: usize = 64;
+
+/// The number of uint256 limbs in a uint2048.
+const U2048_NUM_LIMBS: usize = U2048_NUM_WORDS / U256_NUM_WORDS;
+
+/// Computes the 2304-bit product of a uint256 `a` by a uint2048 `b`, writing its low 2048 bits to
+/// `lo` and its high 256 bits to `hi`.
+///
+/// The pointers to `a` and `b` are the arguments of the syscall, while the pointers to `lo` and
+/// `hi` are passed in the registers `a2` and `a3`. This is the building block of `sys_bigint2048`
+/// and `sys_bigint4096`, which compute the product of two wide integers with several calls and
+/// check its reduction against a quotient and remainder hinted by the host, multiplied with the
+/// modulus by further calls.
+///
+/// In the AIR, `b` is split into eight uint256 limbs `b_i`, and each row computes
+/// `a * b_i + carry_i = lo_i + carry_{i+1} * 2^256` with `carry_0 = 0` and `carry_8 = hi`.
+#[derive(Default)]
+pub struct U256x2048MulChip;
+
+impl U256x2048MulChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// A set of columns for the U256x2048Mul operation.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct U256x2048MulCols<T> {
+    /// The shard number of the syscall.
+    pub shard: T,
+
+    /// The clock cycle of the syscall.
+    pub clk: T,
+
+    /// The nonce of the operation.
+    pub nonce: T,
+
+    /// The pointer to the first input.
+    pub a_ptr: T,
+
+    /// The pointer to the second input.
+    pub b_ptr: T,
+
+    /// The pointer to the low 2048 bits of the output.
+    pub lo_ptr: T,
+
+    /// The pointer to the high 256 bits of the output.
+    pub hi_ptr: T,
+
+    // Memory columns.
+    pub lo_ptr_memory: MemoryReadCols<T>,
+    pub hi_ptr_memory: MemoryReadCols<T>,
+    pub a_memory: [MemoryReadCols<T>; U256_NUM_WORDS],
+    pub b_memory: [MemoryReadCols<T>; U2048_NUM_WORDS],
+    pub lo_memory: [MemoryWriteCols<T>; U2048_NUM_WORDS],
+    pub hi_memory: [MemoryWriteCols<T>; U256_NUM_WORDS],
+
+    // Output values. We compute `a * b_i + carry_i` for each uint256 limb `b_i` of `b`.
+    pub a_mul_b: [FieldOpCols<T, U256Field>; U2048_NUM_LIMBS],
+
+    pub is_real: T,
+}
+
+impl<F: PrimeField32> MachineAir<F> for U256x2048MulChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "U256XU2048Mul".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let modulus = BigUint::one() << 256;
+        let mut new_byte_lookup_events = Vec::new();
+
+        let mut rows = input
+            .get_precompile_events(SyscallCode::U256XU2048_MUL)
+            .iter()
+            .map(|(_, event)| {
+                let event = if let PrecompileEvent::U256xU2048Mul(event) = event {
+                    event
+                } else {
+                    unreachable!()
+                };
+                let mut row: [F; NUM_COLS] = [F::zero(); NUM_COLS];
+                let cols: &mut U256x2048MulCols<F> = row.as_mut_slice().borrow_mut();
+
+                // Assign basic values to the columns.
+                cols.is_real = F::one();
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.a_ptr = F::from_canonical_u32(event.a_ptr);
+                cols.b_ptr = F::from_canonical_u32(event.b_ptr);
+                cols.lo_ptr = F::from_canonical_u32(event.lo_ptr);
+                cols.hi_ptr = F::from_canonical_u32(event.hi_ptr);
+
+                // Populate memory columns.
+                cols.lo_ptr_memory.populate(event.lo_ptr_memory, &mut new_byte_lookup_events);
+                cols.hi_ptr_memory.populate(event.hi_ptr_memory, &mut new_byte_lookup_events);
+                for (col, record) in cols.a_memory.iter_mut().zip(&event.a_memory_records) {
+                    col.populate(*record, &mut new_byte_lookup_events);
+                }
+                for (col, record) in cols.b_memory.iter_mut().zip(&event.b_memory_records) {
+                    col.populate(*record, &mut new_byte_lookup_events);
+                }
+                for (col, record) in cols.lo_memory.iter_mut().zip(&event.lo_memory_records) {
+                    col.populate(*record, &mut new_byte_lookup_events);
+                }
+                for (col, record) in cols.hi_memory.iter_mut().zip(&event.hi_memory_records) {
+                    col.populate(*record, &mut new_byte_lookup_events);
+                }
+
+                // Populate the multiplication of `a` by each limb of `b`, carrying over the high
+                // part of each product into the next one.
+                let a = BigUint::from_bytes_le(&words_to_bytes_le::<32>(&event.a));
+                let mut carry = BigUint::zero();
+                for (i, b_limb) in event.b.chunks_exact(U256_NUM_WORDS).enumerate() {
+                    let b_limb = BigUint::from_bytes_le(&words_to_bytes_le::<32>(b_limb));
+                    let (_, next_carry) = cols.a_mul_b[i].populate_mul_and_carry(
+                        &mut new_byte_lookup_events,
+                        event.shard,
+                        &a,
+                        &b_limb,
+                        &carry,
+                        &modulus,
+                    );
+                    carry = next_carry;
+                }
+
+                row
+            })
+            .collect::<Vec<_>>();
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows_fixed(
+            &mut rows,
+            || {
+                let mut row: [F; NUM_COLS] = [F::zero(); NUM_COLS];
+                let cols: &mut U256x2048MulCols<F> = row.as_mut_slice().borrow_mut();
+
+                let zero = BigUint::zero();
+                for a_mul_b in cols.a_mul_b.iter_mut() {
+                    a_mul_b.populate_mul_and_carry(&mut vec![], 0, &zero, &zero, &zero, &modulus);
+                }
+
+                row
+            },
+            input.fixed_log2_rows::<F, _>(self),
+        );
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_COLS);
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut U256x2048MulCols<F> =
+                trace.values[i * NUM_COLS..(i + 1) * NUM_COLS].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::U256XU2048_MUL).is_empty()
+        }
+    }
+}
+
+impl<F> BaseAir<F> for U256x2048MulChip {
+    fn width(&self) -> usize {
+        NUM_COLS
+    }
+}
+
+impl<AB> Air<AB> for U256x2048MulChip
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <U256Field as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &U256x2048MulCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &U256x2048MulCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // The modulus of each limb multiplication is 2^256.
+        let mut coeff_2_256 = vec![AB::Expr::zero(); 32];
+        coeff_2_256.push(AB::Expr::one());
+        let modulus_polynomial: Polynomial<AB::Expr> = Polynomial::from_coefficients(&coeff_2_256);
+
+        // Evaluate `a * b_i + carry_i = lo_i + carry_{i+1} * 2^256`, starting from a zero carry.
+        let a_limbs: Limbs<AB::Var, <U256Field as NumLimbs>::Limbs> =
+            limbs_from_access(&local.a_memory);
+        let mut carry: Polynomial<AB::Expr> = Polynomial::from_coefficients(&[AB::Expr::zero()]);
+        for (i, b_words) in local.b_memory.chunks_exact(U256_NUM_WORDS).enumerate() {
+            let b_limbs: Limbs<AB::Var, <U256Field as NumLimbs>::Limbs> =
+                limbs_from_access(b_words);
+            local.a_mul_b[i].eval_mul_and_carry(
+                builder,
+                &a_limbs,
+                &b_limbs,
+                &carry,
+                &modulus_polynomial,
+                local.is_real,
+            );
+            carry = local.a_mul_b[i].carry.into();
+
+            // Assert that the correct result is being written to lo.
+            builder.when(local.is_real).assert_all_eq(
+                local.a_mul_b[i].result,
+                value_as_limbs(&local.lo_memory[i * U256_NUM_WORDS..(i + 1) * U256_NUM_WORDS]),
+            );
+        }
+
+        // Assert that the final carry is being written to hi.
+        builder.when(local.is_real).assert_all_eq(
+            local.a_mul_b[U2048_NUM_LIMBS - 1].carry,
+            value_as_limbs(&local.hi_memory),
+        );
+
+        // Read the pointers to lo and hi from the registers.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(Register::X12 as u32),
+            &local.lo_ptr_memory,
+            local.is_real,
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(Register::X13 as u32),
+            &local.hi_ptr_memory,
+            local.is_real,
+        );
+        builder
+            .when(local.is_real)
+            .assert_eq(local.lo_ptr, local.lo_ptr_memory.value().reduce::<AB>());
+        builder
+            .when(local.is_real)
+            .assert_eq(local.hi_ptr, local.hi_ptr_memory.value().reduce::<AB>());
+
+        // Read a and b.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk,
+            local.a_ptr,
+            &local.a_memory,
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk,
+            local.b_ptr,
+            &local.b_memory,
+            local.is_real,
+        );
+
+        // Write lo and hi.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into() + AB::Expr::one(),
+            local.lo_ptr,
+            &local.lo_memory,
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into() + AB::Expr::one(),
+            local.hi_ptr,
+            &local.hi_memory,
+            local.is_real,
+        );
+
+        // Receive the arguments.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::U256XU2048_MUL.syscall_id()),
+            local.a_ptr,
+            local.b_ptr,
+            local.is_real,
+            InteractionScope::Local,
+        );
+
+        // Assert that is_real is a boolean.
+        builder.assert_bool(local.is_real);
+    }
+}
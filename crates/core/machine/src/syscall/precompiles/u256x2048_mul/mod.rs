@@ -0,0 +1,18 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::programs::tests::u256x2048_mul_program;
+    use sp1_stark::CpuProver;
+
+    use crate::utils::{self, run_test};
+
+    #[test]
+    fn test_u256x2048_mul() {
+        utils::setup_logger();
+        let program = u256x2048_mul_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
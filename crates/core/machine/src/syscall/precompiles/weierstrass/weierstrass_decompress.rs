@@ -17,7 +17,10 @@ use sp1_core_executor::{
 };
 use sp1_curves::{
     params::{limbs_from_vec, FieldParameters, Limbs, NumLimbs, NumWords},
-    weierstrass::{bls12_381::bls12381_sqrt, secp256k1::secp256k1_sqrt, WeierstrassParameters},
+    weierstrass::{
+        bls12_381::bls12381_sqrt, secp256k1::secp256k1_sqrt, secp256r1::secp256r1_sqrt,
+        WeierstrassParameters,
+    },
     CurveType, EllipticCurve,
 };
 use sp1_derive::AlignedBorrow;
@@ -68,6 +71,16 @@ pub struct LexicographicChoiceCols<T, P: FieldParameters + NumWords> {
     pub when_neg_y_res_is_lt: T,
 }
 
+/// A set of columns to add the `a * x` term of the curve equation, for curves with a non-zero `a`.
+///
+/// These columns come after the [`LexicographicChoiceCols`], if any.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct NonZeroACols<T, P: FieldParameters + NumWords> {
+    pub(crate) ax: FieldOpCols<T, P>,
+    pub(crate) x_3_plus_ax_plus_b: FieldOpCols<T, P>,
+}
+
 /// The convention for choosing the decompressed `y` value given a sign bit.
 pub enum SignChoiceRule {
     /// Lease significant bit convention.
@@ -101,25 +114,57 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDecompressChip<E> {
         Self { sign_rule: SignChoiceRule::Lexicographic, _marker: PhantomData::<E> }
     }
 
+    /// The number of columns of the [`LexicographicChoiceCols`], if the sign rule uses them.
+    fn num_choice_cols(&self) -> usize {
+        match self.sign_rule {
+            SignChoiceRule::LeastSignificantBit => 0,
+            SignChoiceRule::Lexicographic => size_of::<LexicographicChoiceCols<u8, E::BaseField>>(),
+        }
+    }
+
+    /// The number of columns of the [`NonZeroACols`], if the curve has a non-zero `a`.
+    fn num_a_cols() -> usize {
+        if E::a_int().is_zero() {
+            0
+        } else {
+            size_of::<NonZeroACols<u8, E::BaseField>>()
+        }
+    }
+
     fn populate_field_ops<F: PrimeField32>(
+        &self,
         record: &mut impl ByteRecord,
         shard: u32,
-        cols: &mut WeierstrassDecompressCols<F, E::BaseField>,
+        row: &mut [F],
         x: BigUint,
     ) {
-        // Y = sqrt(x^3 + b)
+        let (weierstrass_row, extra_row) =
+            row.split_at_mut(num_weierstrass_decompress_cols::<E::BaseField>());
+        let cols: &mut WeierstrassDecompressCols<F, E::BaseField> = weierstrass_row.borrow_mut();
+
+        // Y = sqrt(x^3 + b), or Y = sqrt(x^3 + b + a * x) if `a` is non-zero.
         cols.range_x.populate(record, shard, &x, &E::BaseField::modulus());
         let x_2 = cols.x_2.populate(record, shard, &x.clone(), &x.clone(), FieldOperation::Mul);
         let x_3 = cols.x_3.populate(record, shard, &x_2, &x, FieldOperation::Mul);
         let b = E::b_int();
-        let x_3_plus_b = cols.x_3_plus_b.populate(record, shard, &x_3, &b, FieldOperation::Add);
+        let mut y_2 = cols.x_3_plus_b.populate(record, shard, &x_3, &b, FieldOperation::Add);
+
+        let a = E::a_int();
+        if !a.is_zero() {
+            let offset = self.num_choice_cols();
+            let a_cols: &mut NonZeroACols<F, E::BaseField> =
+                extra_row[offset..offset + Self::num_a_cols()].borrow_mut();
+            let ax = a_cols.ax.populate(record, shard, &x, &a, FieldOperation::Mul);
+            y_2 = a_cols.x_3_plus_ax_plus_b.populate(record, shard, &y_2, &ax, FieldOperation::Add);
+        }
 
         let sqrt_fn = match E::CURVE_TYPE {
             CurveType::Secp256k1 => secp256k1_sqrt,
+            CurveType::Secp256r1 => secp256r1_sqrt,
             CurveType::Bls12381 => bls12381_sqrt,
             _ => panic!("Unsupported curve"),
         };
-        let y = cols.y.populate(record, shard, &x_3_plus_b, sqrt_fn);
+        let y = cols.y.populate(record, shard, &y_2, sqrt_fn);
 
         let zero = BigUint::zero();
         cols.neg_y.populate(record, shard, &zero, &y, FieldOperation::Sub);
@@ -135,6 +180,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn name(&self) -> String {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => "Secp256k1Decompress".to_string(),
+            CurveType::Secp256r1 => "Secp256r1Decompress".to_string(),
             CurveType::Bls12381 => "Bls12381Decompress".to_string(),
             _ => panic!("Unsupported curve"),
         }
@@ -147,6 +193,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     ) -> RowMajorMatrix<F> {
         let events = match E::CURVE_TYPE {
             CurveType::Secp256k1 => input.get_precompile_events(SyscallCode::SECP256K1_DECOMPRESS),
+            CurveType::Secp256r1 => input.get_precompile_events(SyscallCode::SECP256R1_DECOMPRESS),
             CurveType::Bls12381 => input.get_precompile_events(SyscallCode::BLS12381_DECOMPRESS),
             _ => panic!("Unsupported curve"),
         };
@@ -162,11 +209,15 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
         for (_, event) in events {
             let event = match (E::CURVE_TYPE, event) {
                 (CurveType::Secp256k1, PrecompileEvent::Secp256k1Decompress(event)) => event,
+                (CurveType::Secp256r1, PrecompileEvent::Secp256r1Decompress(event)) => event,
                 (CurveType::Bls12381, PrecompileEvent::Bls12381Decompress(event)) => event,
                 _ => panic!("Unsupported curve"),
             };
 
             let mut row = zeroed_f_vec(width);
+            let x = BigUint::from_bytes_le(&event.x_bytes);
+            self.populate_field_ops(&mut new_byte_lookup_events, event.shard, &mut row, x);
+
             let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
                 row[0..weierstrass_width].borrow_mut();
 
@@ -176,9 +227,6 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             cols.ptr = F::from_canonical_u32(event.ptr);
             cols.sign_bit = F::from_bool(event.sign_bit);
 
-            let x = BigUint::from_bytes_le(&event.x_bytes);
-            Self::populate_field_ops(&mut new_byte_lookup_events, event.shard, cols, x);
-
             for i in 0..cols.x_access.len() {
                 cols.x_access[i].populate(event.x_memory_records[i], &mut new_byte_lookup_events);
             }
@@ -190,7 +238,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             if matches!(self.sign_rule, SignChoiceRule::Lexicographic) {
                 let lsb = cols.y.lsb;
                 let choice_cols: &mut LexicographicChoiceCols<F, E::BaseField> =
-                    row[weierstrass_width..width].borrow_mut();
+                    row[weierstrass_width..weierstrass_width + self.num_choice_cols()].borrow_mut();
 
                 let decompressed_y = BigUint::from_bytes_le(&event.decompressed_y_bytes);
                 let neg_y = &modulus - &decompressed_y;
@@ -245,18 +293,18 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             &mut rows,
             || {
                 let mut row = zeroed_f_vec(width);
-                let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
-                    row.as_mut_slice()[0..weierstrass_width].borrow_mut();
 
                 // take X of the generator as a dummy value to make sure Y^2 = X^3 + b holds
                 let dummy_value = E::generator().0;
                 let dummy_bytes = dummy_value.to_bytes_le();
                 let words = bytes_to_words_le_vec(&dummy_bytes);
+                self.populate_field_ops(&mut vec![], 0, &mut row, dummy_value);
+
+                let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
+                    row.as_mut_slice()[0..weierstrass_width].borrow_mut();
                 for i in 0..cols.x_access.len() {
                     cols.x_access[i].access.value = words[i].into();
                 }
-
-                Self::populate_field_ops(&mut vec![], 0, cols, dummy_value);
                 row
             },
             input.fixed_log2_rows::<F, _>(self),
@@ -282,6 +330,9 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
                 CurveType::Secp256k1 => {
                     !shard.get_precompile_events(SyscallCode::SECP256K1_DECOMPRESS).is_empty()
                 }
+                CurveType::Secp256r1 => {
+                    !shard.get_precompile_events(SyscallCode::SECP256R1_DECOMPRESS).is_empty()
+                }
                 CurveType::Bls12381 => {
                     !shard.get_precompile_events(SyscallCode::BLS12381_DECOMPRESS).is_empty()
                 }
@@ -291,15 +342,11 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     }
 }
 
-impl<F, E: EllipticCurve> BaseAir<F> for WeierstrassDecompressChip<E> {
+impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassDecompressChip<E> {
     fn width(&self) -> usize {
         num_weierstrass_decompress_cols::<E::BaseField>()
-            + match self.sign_rule {
-                SignChoiceRule::LeastSignificantBit => 0,
-                SignChoiceRule::Lexicographic => {
-                    size_of::<LexicographicChoiceCols<u8, E::BaseField>>()
-                }
-            }
+            + self.num_choice_cols()
+            + Self::num_a_cols()
     }
 }
 
@@ -349,6 +396,26 @@ where
             local.is_real,
         );
 
+        // If `a` is non-zero, add the `a * x` term using the auxiliary columns.
+        let a = E::a_int();
+        let y_2 = if a.is_zero() {
+            local.x_3_plus_b.result
+        } else {
+            let offset = weierstrass_cols + self.num_choice_cols();
+            let a_cols: &NonZeroACols<AB::Var, E::BaseField> =
+                (*local_slice)[offset..offset + Self::num_a_cols()].borrow();
+            let a_const = E::BaseField::to_limbs_field::<AB::F, _>(&a);
+            a_cols.ax.eval(builder, &x, &a_const, FieldOperation::Mul, local.is_real);
+            a_cols.x_3_plus_ax_plus_b.eval(
+                builder,
+                &local.x_3_plus_b.result,
+                &a_cols.ax.result,
+                FieldOperation::Add,
+                local.is_real,
+            );
+            a_cols.x_3_plus_ax_plus_b.result
+        };
+
         local.neg_y.eval(
             builder,
             &[AB::Expr::zero()].iter(),
@@ -357,7 +424,7 @@ where
             local.is_real,
         );
 
-        local.y.eval(builder, &local.x_3_plus_b.result, local.y.lsb, local.is_real);
+        local.y.eval(builder, &y_2, local.y.lsb, local.is_real);
 
         let y_limbs: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
             limbs_from_access(&local.y_access);
@@ -492,6 +559,9 @@ where
             CurveType::Secp256k1 => {
                 AB::F::from_canonical_u32(SyscallCode::SECP256K1_DECOMPRESS.syscall_id())
             }
+            CurveType::Secp256r1 => {
+                AB::F::from_canonical_u32(SyscallCode::SECP256R1_DECOMPRESS.syscall_id())
+            }
             CurveType::Bls12381 => {
                 AB::F::from_canonical_u32(SyscallCode::BLS12381_DECOMPRESS.syscall_id())
             }
@@ -4,6 +4,7 @@ use std::{
     io::{
         Seek, {self},
     },
+    path::{Path, PathBuf},
     sync::{mpsc::sync_channel, Arc, Mutex},
 };
 use web_time::Instant;
@@ -34,12 +35,12 @@ use sp1_primitives::io::SP1PublicValues;
 
 use sp1_core_executor::{
     subproof::NoOpSubproofVerifier, ExecutionError, ExecutionRecord, ExecutionReport, Executor,
-    Program, SP1Context,
+    Program, ProofProgress, SP1Context,
 };
 use sp1_stark::{
     air::{MachineAir, PublicValues},
     Com, CpuProver, DebugConstraintBuilder, InteractionBuilder, MachineProof, MachineProver,
-    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, SP1CoreOpts,
+    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, SP1CoreOpts, ShardProof,
     StarkGenericConfig, StarkMachine, StarkProvingKey, StarkVerifyingKey, UniConfig, Val,
     VerifierConstraintFolder,
 };
@@ -52,6 +53,44 @@ pub enum SP1CoreProverError {
     IoError(io::Error),
     #[error("serialization error: {0}")]
     SerializationError(bincode::Error),
+    #[error("proving was cancelled")]
+    Cancelled,
+    #[error("the options are for a different security profile than the prover")]
+    SecurityProfileMismatch,
+    #[error("the program contains compressed instructions, which require the `rvc` feature")]
+    CompressedInstructions,
+}
+
+/// The path of the saved proof of `shard` in `dir`.
+fn shard_proof_path(dir: &Path, shard: u32) -> PathBuf {
+    dir.join(format!("shard-{shard}.bin"))
+}
+
+/// Loads a shard proof saved by [save_shard_proof], if there is one.
+fn load_shard_proof<SC: StarkGenericConfig>(dir: &Path, shard: u32) -> Option<ShardProof<SC>> {
+    let file = File::open(shard_proof_path(dir, shard)).ok()?;
+    match bincode::deserialize_from(io::BufReader::new(file)) {
+        Ok(proof) => Some(proof),
+        Err(e) => {
+            tracing::warn!("ignoring unreadable proof of shard {}: {}", shard, e);
+            None
+        }
+    }
+}
+
+/// Saves a shard proof so that it can be reused if proving is interrupted and resumed.
+///
+/// The proof is written to a temporary file first, so a crash never leaves a truncated proof behind.
+fn save_shard_proof<SC: StarkGenericConfig>(dir: &Path, shard: u32, proof: &ShardProof<SC>) {
+    let path = shard_proof_path(dir, shard);
+    let tmp_path = path.with_extension("tmp");
+    let result = File::create(&tmp_path)
+        .map_err(bincode::Error::from)
+        .and_then(|file| bincode::serialize_into(io::BufWriter::new(file), proof))
+        .and_then(|()| std::fs::rename(&tmp_path, &path).map_err(Into::into));
+    if let Err(e) = result {
+        tracing::warn!("failed to save proof of shard {}: {}", shard, e);
+    }
 }
 
 pub fn prove_simple<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<SC::Val>>>(
@@ -121,6 +160,7 @@ where
         opts,
         Default::default(),
         shape_config,
+        None,
     )
 }
 
@@ -132,6 +172,7 @@ pub fn prove_with_context<SC: StarkGenericConfig, P: MachineProver<SC, RiscvAir<
     opts: SP1CoreOpts,
     context: SP1Context,
     shape_config: Option<&CoreShapeConfig<SC::Val>>,
+    shard_proof_dir: Option<&Path>,
 ) -> Result<(MachineProof<SC>, Vec<u8>, u64), SP1CoreProverError>
 where
     SC::Val: PrimeField32,
@@ -140,7 +181,13 @@ where
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync,
 {
+    // The released circuit does not constrain the pc step of compressed instructions.
+    if !cfg!(feature = "rvc") && program.instruction_pcs.is_some() {
+        return Err(SP1CoreProverError::CompressedInstructions);
+    }
+
     // Setup the runtime.
+    let monitor = context.monitor.clone();
     let mut runtime = Executor::with_context(program.clone(), opts, context);
     let maximal_shapes = match shape_config.as_ref() {
         Some(shape_config) => shape_config.maximal_core_shapes(),
@@ -148,6 +195,7 @@ where
     };
     runtime.maximal_shapes = Some(maximal_shapes.into_iter().map(|s| s.inner).collect());
     runtime.write_vecs(&stdin.buffer);
+    runtime.write_files(&stdin.files);
     for proof in stdin.proofs.iter() {
         let (proof, vk) = proof.clone();
         runtime.write_proof(proof, vk);
@@ -166,12 +214,19 @@ where
         let checkpoint_generator_span = tracing::Span::current().clone();
         let (checkpoints_tx, checkpoints_rx) =
             sync_channel::<(usize, File, bool)>(opts.checkpoints_channel_capacity);
+        let checkpoint_generator_monitor = monitor.clone();
         let checkpoint_generator_handle: ScopedJoinHandle<Result<_, SP1CoreProverError>> =
             s.spawn(move || {
+                let monitor = checkpoint_generator_monitor;
                 let _span = checkpoint_generator_span.enter();
                 tracing::debug_span!("checkpoint generator").in_scope(|| {
                     let mut index = 0;
                     loop {
+                        // Stop generating checkpoints if proving was cancelled.
+                        if monitor.is_cancelled() {
+                            break Err(SP1CoreProverError::Cancelled);
+                        }
+
                         // Enter the span.
                         let span = tracing::debug_span!("batch");
                         let _span = span.enter();
@@ -415,7 +470,7 @@ where
         });
 
         // Wait until the checkpoint generator handle has fully finished.
-        let public_values_stream = checkpoint_generator_handle.join().unwrap().unwrap();
+        let public_values_stream = checkpoint_generator_handle.join().unwrap();
 
         // Wait until the records and traces have been fully generated.
         p1_record_and_trace_gen_handles.into_iter().for_each(|handle| handle.join().unwrap());
@@ -423,6 +478,13 @@ where
         // Wait until the phase 1 prover has completely finished.
         let mut challenger = phase_1_prover_handle.join().unwrap();
 
+        // Bail out if proving was cancelled during execution.
+        let public_values_stream = match public_values_stream {
+            Err(SP1CoreProverError::Cancelled) => return Err(SP1CoreProverError::Cancelled),
+            result => result.unwrap(),
+        };
+        monitor.report(ProofProgress::ShardsExecuted { num_shards: state.lock().unwrap().shard });
+
         // Sample for the global permutation challenges.
         // Obtain the challenges used for the global permutation argument.
         let mut global_permutation_challenges: Vec<SC::Challenge> = Vec::new();
@@ -457,6 +519,7 @@ where
             let state = Arc::clone(&state);
             let deferred = Arc::clone(&deferred);
             let program = program.clone();
+            let monitor = monitor.clone();
 
             let span = tracing::Span::current().clone();
 
@@ -467,6 +530,13 @@ where
                 let _span = span.enter();
                 tracing::debug_span!("phase 2 trace generation").in_scope(|| {
                     loop {
+                        // Stop tracing new checkpoints if proving was cancelled. Checkpoints that
+                        // were already taken are finished so that no worker waits on a skipped
+                        // turn.
+                        if monitor.is_cancelled() {
+                            break;
+                        }
+
                         // Receive the latest checkpoint.
                         let received = { checkpoints.lock().unwrap().pop_front() };
                         if let Some((index, mut checkpoint, done)) = received {
@@ -550,12 +620,25 @@ where
                             #[cfg(feature = "debug")]
                             all_records_tx.send(records.clone()).unwrap();
 
-                            // Generate the traces.
+                            // Generate the traces, except for the shards whose proof was saved by a
+                            // previous run.
+                            let reused = records
+                                .iter()
+                                .map(|record| {
+                                    shard_proof_dir.is_some_and(|dir| {
+                                        shard_proof_path(dir, record.public_values.shard).exists()
+                                    })
+                                })
+                                .collect::<Vec<_>>();
                             let mut local_traces = Vec::new();
                             tracing::debug_span!("generate local traces", index).in_scope(|| {
                                 local_traces = records
                                     .par_iter()
-                                    .map(|record| {
+                                    .zip(reused.par_iter())
+                                    .map(|(record, &reused)| {
+                                        if reused {
+                                            return Vec::new();
+                                        }
                                         prover.generate_traces(record, InteractionScope::Local)
                                     })
                                     .collect::<Vec<_>>();
@@ -565,7 +648,11 @@ where
                             tracing::debug_span!("generate global traces", index).in_scope(|| {
                                 global_traces = records
                                     .par_iter()
-                                    .map(|record| {
+                                    .zip(reused.par_iter())
+                                    .map(|(record, &reused)| {
+                                        if reused {
+                                            return Vec::new();
+                                        }
                                         prover.generate_traces(record, InteractionScope::Global)
                                     })
                                     .collect::<Vec<_>>();
@@ -606,7 +693,9 @@ where
 
         // Spawn the phase 2 prover thread.
         let p2_prover_span = tracing::Span::current().clone();
+        let p2_prover_monitor = monitor.clone();
         let p2_prover_handle = s.spawn(move || {
+            let monitor = p2_prover_monitor;
             let _span = p2_prover_span.enter();
             let mut shard_proofs = Vec::new();
             tracing::debug_span!("phase 2 prover").in_scope(|| {
@@ -615,9 +704,28 @@ where
                         let span = tracing::Span::current().clone();
                         shard_proofs.par_extend(
                             records.into_par_iter().zip(traces.into_par_iter()).map(
-                                |(record, (global_traces, local_traces))| {
+                                |(record, (mut global_traces, mut local_traces))| {
                                     let _span = span.enter();
 
+                                    // Reuse the proof of this shard if it was saved by a previous
+                                    // run.
+                                    let shard = record.public_values.shard;
+                                    if let Some(proof) = shard_proof_dir
+                                        .and_then(|dir| load_shard_proof::<SC>(dir, shard))
+                                    {
+                                        monitor.report(ProofProgress::ShardProven { shard });
+                                        return proof;
+                                    }
+
+                                    // The traces were skipped if the saved proof turned out to be
+                                    // unreadable, since every shard has a local trace otherwise.
+                                    if local_traces.is_empty() {
+                                        global_traces = prover
+                                            .generate_traces(&record, InteractionScope::Global);
+                                        local_traces = prover
+                                            .generate_traces(&record, InteractionScope::Local);
+                                    }
+
                                     let global_data = prover.commit(&record, global_traces);
                                     let local_data = prover.commit(&record, local_traces);
 
@@ -630,6 +738,10 @@ where
                                             &global_permutation_challenges,
                                         )
                                         .unwrap();
+                                    if let Some(dir) = shard_proof_dir {
+                                        save_shard_proof(dir, shard, &proof);
+                                    }
+                                    monitor.report(ProofProgress::ShardProven { shard });
 
                                     #[cfg(debug_assertions)]
                                     {
@@ -655,6 +767,10 @@ where
 
         // Wait until the phase 2 prover has finished.
         let shard_proofs = p2_prover_handle.join().unwrap();
+        if monitor.is_cancelled() {
+            return Err(SP1CoreProverError::Cancelled);
+        }
+        monitor.report(ProofProgress::CoreProven);
 
         // Log some of the `ExecutionReport` information.
         let report_aggregate = report_aggregate.lock().unwrap();
@@ -713,6 +829,7 @@ pub fn run_test_io<P: MachineProver<BabyBearPoseidon2, RiscvAir<BabyBear>>>(
         runtime.maximal_shapes =
             Some(shape_config.maximal_core_shapes().into_iter().map(|s| s.inner).collect());
         runtime.write_vecs(&inputs.buffer);
+        runtime.write_files(&inputs.files);
         runtime.run().unwrap();
         runtime
     });
@@ -756,6 +873,7 @@ pub fn run_test_core<P: MachineProver<BabyBearPoseidon2, RiscvAir<BabyBear>>>(
         SP1CoreOpts::default(),
         SP1Context::default(),
         shape_config,
+        None,
     )
     .unwrap();
 
@@ -829,9 +947,11 @@ where
     run_test_machine_with_prover::<SC, A, CpuProver<_, _>>(&prover, records, pk, vk)
 }
 
-fn trace_checkpoint<SC: StarkGenericConfig>(
+/// Re-executes the program from a serialized [ExecutionState] checkpoint and returns the execution
+/// records of the shards it covers.
+pub fn trace_checkpoint<SC: StarkGenericConfig>(
     program: Program,
-    file: &File,
+    checkpoint: impl io::Read,
     opts: SP1CoreOpts,
     shape_config: Option<&CoreShapeConfig<SC::Val>>,
 ) -> (Vec<ExecutionRecord>, ExecutionReport)
@@ -842,7 +962,7 @@ where
         Some(shape_config) => shape_config.maximal_core_shapes(),
         None => vec![],
     };
-    let mut reader = std::io::BufReader::new(file);
+    let mut reader = std::io::BufReader::new(checkpoint);
     let state: ExecutionState =
         bincode::deserialize_from(&mut reader).expect("failed to deserialize state");
     let mut runtime = Executor::recover(program.clone(), state.clone(), opts);
@@ -0,0 +1,168 @@
+//! Modulo defining the Secp256r1 (P-256) curve and its base field. The constants are all taken
+//! from https://neuromancer.sk/std/secg/secp256r1.
+
+use std::str::FromStr;
+
+use generic_array::GenericArray;
+use num::{
+    traits::{FromBytes, One},
+    BigUint,
+};
+use serde::{Deserialize, Serialize};
+use typenum::{U32, U62};
+
+use super::{SwCurve, WeierstrassParameters};
+use crate::{
+    params::{FieldParameters, NumLimbs},
+    AffinePoint, CurveType, EllipticCurve, EllipticCurveParameters,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Secp256r1 curve parameter
+pub struct Secp256r1Parameters;
+
+pub type Secp256r1 = SwCurve<Secp256r1Parameters>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Secp256r1 base field parameter
+pub struct Secp256r1BaseField;
+
+impl FieldParameters for Secp256r1BaseField {
+    const MODULUS: &'static [u8] = &[
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xff, 0xff,
+        0xff, 0xff,
+    ];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 14;
+
+    fn modulus() -> BigUint {
+        BigUint::from_bytes_le(Self::MODULUS)
+    }
+}
+
+impl NumLimbs for Secp256r1BaseField {
+    type Limbs = U32;
+    type Witness = U62;
+}
+
+impl EllipticCurveParameters for Secp256r1Parameters {
+    type BaseField = Secp256r1BaseField;
+    const CURVE_TYPE: CurveType = CurveType::Secp256r1;
+}
+
+impl WeierstrassParameters for Secp256r1Parameters {
+    const A: GenericArray<u8, U32> = GenericArray::from_array([
+        0xfc, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xff, 0xff,
+        0xff, 0xff,
+    ]);
+
+    const B: GenericArray<u8, U32> = GenericArray::from_array([
+        0x4b, 0x60, 0xd2, 0x27, 0x3e, 0x3c, 0xce, 0x3b, 0xf6, 0xb0, 0x53, 0xcc, 0xb0, 0x06, 0x1d,
+        0x65, 0xbc, 0x86, 0x98, 0x76, 0x55, 0xbd, 0xeb, 0xb3, 0xe7, 0x93, 0x3a, 0xaa, 0xd8, 0x35,
+        0xc6, 0x5a,
+    ]);
+    fn generator() -> (BigUint, BigUint) {
+        let x = BigUint::from_str(
+            "48439561293906451759052585252797914202762949526041747995844080717082404635286",
+        )
+        .unwrap();
+        let y = BigUint::from_str(
+            "36134250956749795798585127919587881956611106672985015071877198253568414405109",
+        )
+        .unwrap();
+        (x, y)
+    }
+
+    fn prime_group_order() -> num::BigUint {
+        BigUint::from_slice(&[
+            0xFC632551, 0xF3B9CAC2, 0xA7179E84, 0xBCE6FAAD, 0xFFFFFFFF, 0xFFFFFFFF, 0x00000000,
+            0xFFFFFFFF,
+        ])
+    }
+
+    fn a_int() -> BigUint {
+        BigUint::from_bytes_le(&Self::A)
+    }
+
+    fn b_int() -> BigUint {
+        BigUint::from_bytes_le(&Self::B)
+    }
+}
+
+pub fn secp256r1_decompress<E: EllipticCurve>(bytes_be: &[u8], sign: u32) -> AffinePoint<E> {
+    let modulus = Secp256r1BaseField::modulus();
+    let x = BigUint::from_be_bytes(bytes_be);
+    assert!(x < modulus, "x is not a field element");
+
+    // y^2 = x^3 + ax + b.
+    let a = Secp256r1Parameters::a_int();
+    let b = Secp256r1Parameters::b_int();
+    let y_2 = (&x * &x * &x + a * &x + b) % &modulus;
+    let mut y = secp256r1_sqrt(&y_2);
+    assert_eq!((&y * &y) % &modulus, y_2, "x is not the abscissa of a point on the curve");
+
+    if y.bit(0) != (sign == 1) {
+        y = (&modulus - y) % &modulus;
+    }
+    AffinePoint::<E>::new(x, y)
+}
+
+/// Computes a square root of `n` modulo the base field modulus. As `p = 3 mod 4`, a root is given
+/// by `n^((p + 1) / 4)` whenever `n` is a quadratic residue.
+pub fn secp256r1_sqrt(n: &BigUint) -> BigUint {
+    let modulus = Secp256r1BaseField::modulus();
+    let exponent = (&modulus + BigUint::one()) >> 2;
+    n.modpow(&exponent, &modulus)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::utils::biguint_from_limbs;
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    const NUM_TEST_CASES: usize = 10;
+
+    #[test]
+    fn test_weierstrass_biguint_scalar_mul() {
+        assert_eq!(biguint_from_limbs(Secp256r1BaseField::MODULUS), Secp256r1BaseField::modulus());
+    }
+
+    #[test]
+    fn test_secp256r1_decompress() {
+        // This test checks that decompression of generator, 2x generator, 4x generator, etc. works.
+        let mut point = {
+            let (x, y) = Secp256r1Parameters::generator();
+            AffinePoint::<Secp256r1>::new(x, y)
+        };
+        for _ in 0..NUM_TEST_CASES {
+            let mut x_bytes_be = [0u8; 32];
+            let x = point.x.to_bytes_be();
+            x_bytes_be[32 - x.len()..].copy_from_slice(&x);
+            let sign = point.y.bit(0) as u32;
+            assert_eq!(point, secp256r1_decompress(&x_bytes_be, sign));
+
+            // Double the point to create a "random" point for the next iteration.
+            point = point.clone().sw_double();
+        }
+    }
+
+    #[test]
+    fn test_secp256r1_sqrt() {
+        let mut rng = thread_rng();
+        for _ in 0..NUM_TEST_CASES {
+            // Check that sqrt(x^2)^2 == x^2
+            // We use x^2 since not all field elements have a square root
+            let x = rng.gen_biguint(256) % Secp256r1BaseField::modulus();
+            let x_2 = (&x * &x) % Secp256r1BaseField::modulus();
+            let sqrt = secp256r1_sqrt(&x_2);
+            let sqrt_2 = (&sqrt * &sqrt) % Secp256r1BaseField::modulus();
+            assert_eq!(sqrt_2, x_2);
+        }
+    }
+}
@@ -4,6 +4,51 @@ pub const MAXIMUM_MEMORY_SIZE: u32 = u32::MAX;
 /// The size of a word in bytes.
 pub const WORD_SIZE: usize = 4;
 
+/// The file descriptors through which programs write to the host, shared by the zkVM entrypoint
+/// and the executor.
+pub mod fd {
+    /// The file descriptor for public values.
+    ///
+    /// The public values of a program are the bytes it committed, followed by the digests the
+    /// entrypoint appends when the program halts, in this order:
+    ///
+    /// 1. The 32-byte digest of its environment, if the program read an environment variable.
+    /// 2. The 32-byte digest of the files it opened, if the program opened a file.
+    /// 3. The 32-byte randomness seed, if the program used randomness with the `verifiable-rand`
+    ///    feature of `sp1-zkvm`.
+    pub const FD_PUBLIC_VALUES: u32 = 3;
+
+    /// The file descriptor for hints.
+    pub const FD_HINT: u32 = 4;
+
+    /// The file descriptor through which to access `hook_ecrecover`.
+    pub const FD_ECRECOVER_HOOK: u32 = 5;
+
+    // Note: we skip 6 because we have an eddsa hook in dev.
+
+    /// The file descriptor through which to access `hook_ecrecover_2`.
+    pub const FD_ECRECOVER_HOOK_2: u32 = 7;
+
+    /// The file descriptor through which to access `hook_ed_decompress`.
+    pub const FD_EDDECOMPRESS: u32 = 8;
+
+    /// The file descriptor through which the program reports that it ran out of memory.
+    ///
+    /// The program writes the size of the allocation that failed and the heap high-water mark as
+    /// two little-endian `u32`s before halting with a non-zero exit code.
+    pub const FD_OUT_OF_MEMORY: u32 = 9;
+
+    /// The file descriptor through which the program reports where and why it panicked.
+    ///
+    /// The program writes the line and column of the panic as two little-endian `u32`s, the
+    /// little-endian `u32` length of the path of the source file, the path and the panic message
+    /// before halting with a non-zero exit code.
+    pub const FD_PANIC: u32 = 10;
+
+    /// The file descriptor through which to access `hook_bigint_divrem`.
+    pub const FD_BIGINT_DIVREM: u32 = 11;
+}
+
 /// Converts a slice of words to a byte vector in little endian.
 pub fn words_to_bytes_le_vec(words: &[u32]) -> Vec<u8> {
     words.iter().flat_map(|word| word.to_le_bytes().to_vec()).collect::<Vec<_>>()
@@ -0,0 +1,31 @@
+//! The digests of the files of the read-only file system of programs.
+//!
+//! Files are split into chunks of [`FILE_CHUNK_SIZE`] bytes, the last one possibly shorter. A
+//! program opening a file receives the sha256 hash of every chunk, and checks every chunk it reads
+//! against its hash, so that it never needs the whole file to commit to its contents.
+
+use sha2::{Digest, Sha256};
+
+/// The size of the chunks of a file, in bytes.
+pub const FILE_CHUNK_SIZE: usize = 4096;
+
+/// Returns the concatenated sha256 hashes of the chunks of `contents`.
+pub fn file_chunk_hashes(contents: &[u8]) -> Vec<u8> {
+    contents.chunks(FILE_CHUNK_SIZE).flat_map(|chunk| Sha256::digest(chunk)).collect()
+}
+
+/// Returns the digest of a file `len` bytes long with the given concatenated chunk hashes.
+///
+/// The digest is the sha256 hash of the little-endian `u64` length of the file followed by the
+/// hashes of its chunks.
+pub fn file_digest_from_chunk_hashes(len: u64, chunk_hashes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(len.to_le_bytes());
+    hasher.update(chunk_hashes);
+    hasher.finalize().into()
+}
+
+/// Returns the digest of a file with the given contents.
+pub fn file_digest(contents: &[u8]) -> [u8; 32] {
+    file_digest_from_chunk_hashes(contents.len() as u64, &file_chunk_hashes(contents))
+}
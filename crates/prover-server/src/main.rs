@@ -0,0 +1,185 @@
+//! A self-hostable prover server.
+//!
+//! Serves the `ProverNetwork` and `ArtifactStore` gRPC services used by the SDK's network prover,
+//! proving requests locally with the CPU prover. Artifacts are kept in a local directory and
+//! uploaded and downloaded over a small HTTP server, which serves them like an S3 bucket. Point a
+//! network prover at it by setting `PROVER_NETWORK_RPC` to the gRPC address and `AWS_ENDPOINT_URL`
+//! to the artifact URL.
+
+mod network;
+mod store;
+mod worker;
+
+use std::{net::SocketAddr, path::PathBuf};
+
+use alloy_primitives::Address;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use sp1_sdk::network_v2::proto::{
+    artifact::artifact_store_server::ArtifactStoreServer,
+    network::prover_network_server::ProverNetworkServer,
+};
+use tokio::{net::TcpListener, sync::mpsc};
+use tonic::transport::{server::TcpIncoming, Server};
+
+use crate::{
+    network::{LocalProverNetwork, ServerState},
+    store::LocalStore,
+    worker::Worker,
+};
+
+#[derive(Parser, Clone)]
+#[command(about = "Run a self-hosted SP1 prover network server.")]
+struct Args {
+    /// The address to serve the gRPC API on.
+    #[arg(long, env = "SP1_PROVER_SERVER_ADDR", default_value = "127.0.0.1:50051")]
+    pub addr: SocketAddr,
+    /// The address to serve artifact uploads and downloads on.
+    #[arg(long, env = "SP1_PROVER_SERVER_ARTIFACT_ADDR", default_value = "127.0.0.1:50052")]
+    pub artifact_addr: SocketAddr,
+    /// The URL clients use to reach the artifact server. Defaults to `http://<artifact-addr>`.
+    #[arg(long, env = "SP1_PROVER_SERVER_ARTIFACT_URL")]
+    pub artifact_url: Option<String>,
+    /// The directory to store artifacts in.
+    #[arg(long, env = "SP1_PROVER_SERVER_ARTIFACT_DIR", default_value = "artifacts")]
+    pub artifact_dir: PathBuf,
+    /// The name of the bucket in the artifact URIs.
+    #[arg(long, env = "SP1_PROVER_SERVER_ARTIFACT_BUCKET", default_value = "artifacts")]
+    pub artifact_bucket: String,
+    /// The address of the prover requests are assigned to, which is the only one allowed to fulfill
+    /// them over the API besides the local worker.
+    #[arg(long, env = "SP1_PROVER_SERVER_PROVER_ADDRESS", default_value_t = Address::ZERO)]
+    pub prover_address: Address,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    sp1_sdk::utils::setup_logger();
+    let args = Args::parse();
+
+    let listener = TcpListener::bind(args.addr).await?;
+    let artifact_listener = TcpListener::bind(args.artifact_addr).await?;
+    serve(args, listener, artifact_listener).await
+}
+
+/// Serves the gRPC API on `listener` and the artifacts on `artifact_listener`.
+async fn serve(args: Args, listener: TcpListener, artifact_listener: TcpListener) -> Result<()> {
+    let addr = listener.local_addr()?;
+    let artifact_url = match args.artifact_url {
+        Some(url) => url,
+        None => format!("http://{}", artifact_listener.local_addr()?),
+    };
+    let store = LocalStore::new(&args.artifact_dir, &artifact_url, &args.artifact_bucket)?;
+    let state = ServerState::default();
+    let (queue_tx, queue_rx) = mpsc::unbounded_channel();
+
+    let worker = Worker::new(state.clone(), store.clone(), args.prover_address);
+    tokio::spawn(worker.run(queue_rx));
+
+    let router = store.router();
+    tokio::spawn(async move { axum::serve(artifact_listener, router).await });
+    tracing::info!("serving artifacts on {}", artifact_url);
+
+    tracing::info!("serving prover network on {}", addr);
+    let incoming = TcpIncoming::from_listener(listener, true, None).map_err(|e| anyhow!(e))?;
+    Server::builder()
+        .add_service(ProverNetworkServer::new(LocalProverNetwork::new(
+            state,
+            store.clone(),
+            queue_tx,
+        )))
+        .add_service(ArtifactStoreServer::new(store))
+        .serve_with_incoming(incoming)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use sp1_sdk::{
+        network_v2::{
+            client::NetworkClient,
+            proto::network::{ProofMode, ProofStrategy},
+            prover::NetworkProver,
+        },
+        Prover, SP1ProofWithPublicValues, SP1Stdin, SP1_CIRCUIT_VERSION,
+    };
+
+    use super::*;
+
+    const ELF: &[u8] =
+        include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+
+    const PRIVATE_KEY: &str = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    /// Starts a server on free ports and points the SDK's network prover at it.
+    async fn start_server(artifact_dir: &std::path::Path) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let artifact_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        let artifact_url = format!("http://{}", artifact_listener.local_addr().unwrap());
+
+        std::env::set_var("PROVER_NETWORK_RPC", &rpc_url);
+        std::env::set_var("AWS_ENDPOINT_URL", &artifact_url);
+        std::env::set_var("AWS_REGION", "us-east-1");
+        std::env::set_var("AWS_ACCESS_KEY_ID", "sp1");
+        std::env::set_var("AWS_SECRET_ACCESS_KEY", "sp1");
+
+        let args = Args::parse_from([
+            "sp1-prover-server",
+            "--artifact-dir",
+            artifact_dir.to_str().unwrap(),
+        ]);
+        tokio::spawn(serve(args, listener, artifact_listener));
+        artifact_url
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_network_prover_e2e() {
+        sp1_sdk::utils::setup_logger();
+        let dir = tempfile::tempdir().unwrap();
+        let artifact_url = start_server(dir.path()).await;
+        let timeout = Some(Duration::from_secs(600));
+
+        let prover = NetworkProver::new_from_key(PRIVATE_KEY);
+        let (_, vk) = prover.setup(ELF);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10u32);
+
+        // A proof requested with the unchanged network prover is fulfilled and verifies.
+        let proof = prover.prove(ELF, stdin.clone(), ProofMode::Core, timeout).await.unwrap();
+        prover.verify(&proof, &vk).unwrap();
+
+        // A request which fails to prove stops the client waiting for it.
+        let client = NetworkClient::new(PRIVATE_KEY);
+        let response = client
+            .request_proof(
+                ELF,
+                &stdin,
+                &vk,
+                ProofMode::Core,
+                SP1_CIRCUIT_VERSION,
+                ProofStrategy::Hosted,
+                600,
+                1,
+            )
+            .await
+            .unwrap();
+        let request_id = response.body.unwrap().request_id;
+        let error =
+            prover.wait_proof::<SP1ProofWithPublicValues>(&request_id, timeout).await.unwrap_err();
+        assert!(error.to_string().contains("proof request failed"), "{error:#}");
+
+        // Artifacts can't be uploaded without the token of their presigned URL.
+        let response = reqwest::Client::new()
+            .put(format!("{artifact_url}/artifacts/0123456789abcdef?token=forged"))
+            .body(vec![0u8; 4])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,350 @@
+use std::{collections::HashMap, sync::Arc};
+
+use alloy_primitives::{keccak256, Address};
+use alloy_signer::Signature;
+use prost::Message;
+use sp1_sdk::{
+    network_v2::proto::network::{
+        prover_network_server::ProverNetwork, FulfillProofRequest, FulfillProofResponse,
+        FulfillProofResponseBody, GetBalanceRequest, GetBalanceResponse,
+        GetFilteredProofRequestsRequest, GetFilteredProofRequestsResponse, GetNonceRequest,
+        GetNonceResponse, GetProofRequestStatusRequest, GetProofRequestStatusResponse,
+        ProofRequest, ProofStatus, RequestProofRequest, RequestProofResponse,
+        RequestProofResponseBody,
+    },
+    SP1_CIRCUIT_VERSION,
+};
+use tokio::sync::{mpsc, Mutex};
+use tonic::{Request, Response, Status};
+
+use crate::store::LocalStore;
+
+/// A proof request tracked by the server, along with its fulfillment state.
+#[derive(Clone)]
+pub struct TrackedRequest {
+    /// The request as reported back to clients.
+    pub request: ProofRequest,
+    /// The serialized verifying key the requester expects the program to have.
+    pub vkey: Vec<u8>,
+    /// The hash standing in for the transaction that created the request.
+    pub request_tx_hash: Vec<u8>,
+    /// The hash standing in for the transaction that fulfilled the request.
+    pub fulfill_tx_hash: Option<Vec<u8>>,
+    /// The URI of the proof, once fulfilled.
+    pub proof_uri: Option<String>,
+    /// The prover the request is assigned to, which is the only one allowed to fulfill it.
+    pub prover: Option<Address>,
+    /// A description of why proving failed, if it did.
+    pub error: Option<String>,
+}
+
+/// The state shared between the RPC service and the proving worker.
+#[derive(Clone, Default)]
+pub struct ServerState {
+    inner: Arc<Mutex<ServerStateInner>>,
+}
+
+#[derive(Default)]
+struct ServerStateInner {
+    nonces: HashMap<Address, u64>,
+    requests: HashMap<Vec<u8>, TrackedRequest>,
+}
+
+impl ServerState {
+    /// Returns a copy of the request with the given id, if any.
+    pub async fn get(&self, request_id: &[u8]) -> Option<TrackedRequest> {
+        self.inner.lock().await.requests.get(request_id).cloned()
+    }
+
+    /// Assigns a request to `prover`.
+    pub async fn assign(&self, request_id: &[u8], prover: Address) {
+        if let Some(tracked) = self.inner.lock().await.requests.get_mut(request_id) {
+            tracked.request.status = ProofStatus::Assigned.into();
+            tracked.prover = Some(prover);
+        }
+    }
+
+    /// Marks a request as fulfilled with the proof stored at `proof_uri`, unless it already is.
+    pub async fn fulfill(&self, request_id: &[u8], proof_uri: String) {
+        if let Some(tracked) = self.inner.lock().await.requests.get_mut(request_id) {
+            if tracked.request.status() == ProofStatus::Fulfilled {
+                return;
+            }
+            tracked.request.status = ProofStatus::Fulfilled.into();
+            tracked.fulfill_tx_hash = Some(keccak256(proof_uri.as_bytes()).to_vec());
+            tracked.proof_uri = Some(proof_uri);
+        }
+    }
+
+    /// Records that proving a request failed. The request is left unfulfilled, and querying its
+    /// status returns the error from then on, so that clients waiting for the proof stop.
+    pub async fn fail(&self, request_id: &[u8], error: String) {
+        if let Some(tracked) = self.inner.lock().await.requests.get_mut(request_id) {
+            tracked.error = Some(error);
+        }
+    }
+
+    /// Checks the nonce of `address` against `nonce` and bumps it on success.
+    async fn use_nonce(&self, address: Address, nonce: u64) -> Result<(), Status> {
+        let mut inner = self.inner.lock().await;
+        let expected = inner.nonces.entry(address).or_default();
+        if *expected != nonce {
+            return Err(Status::invalid_argument(format!(
+                "invalid nonce for {address}: expected {expected}, got {nonce}"
+            )));
+        }
+        *expected += 1;
+        Ok(())
+    }
+}
+
+/// An implementation of the prover network RPC that proves requests on this machine.
+pub struct LocalProverNetwork {
+    state: ServerState,
+    store: LocalStore,
+    queue: mpsc::UnboundedSender<Vec<u8>>,
+}
+
+impl LocalProverNetwork {
+    pub fn new(
+        state: ServerState,
+        store: LocalStore,
+        queue: mpsc::UnboundedSender<Vec<u8>>,
+    ) -> Self {
+        Self { state, store, queue }
+    }
+}
+
+/// Recovers the address that signed the protobuf-encoded `body`.
+fn recover_signer<M: Message>(signature: &[u8], body: &M) -> Result<Address, Status> {
+    let signature = Signature::try_from(signature)
+        .map_err(|e| Status::invalid_argument(format!("invalid signature: {e}")))?;
+    signature
+        .recover_address_from_msg(body.encode_to_vec())
+        .map_err(|e| Status::unauthenticated(format!("failed to recover signer: {e}")))
+}
+
+fn parse_address(address: &[u8]) -> Result<Address, Status> {
+    Address::try_from(address).map_err(|_| Status::invalid_argument("invalid address"))
+}
+
+#[tonic::async_trait]
+impl ProverNetwork for LocalProverNetwork {
+    async fn request_proof(
+        &self,
+        request: Request<RequestProofRequest>,
+    ) -> Result<Response<RequestProofResponse>, Status> {
+        let request = request.into_inner();
+        let body = request.body.ok_or_else(|| Status::invalid_argument("missing body"))?;
+        let requester = recover_signer(&request.signature, &body)?;
+
+        let version = format!("sp1-{}", SP1_CIRCUIT_VERSION);
+        if body.version != version {
+            return Err(Status::failed_precondition(format!(
+                "unsupported version {}, this server proves {}",
+                body.version, version
+            )));
+        }
+        self.store
+            .id_from_uri(&body.program_uri)
+            .and_then(|_| self.store.id_from_uri(&body.stdin_uri))
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        self.state.use_nonce(requester, body.nonce).await?;
+
+        let request_id =
+            keccak256([requester.as_slice(), &body.nonce.to_be_bytes()].concat()).to_vec();
+        let request_tx_hash = keccak256(&request.signature).to_vec();
+        let tracked = TrackedRequest {
+            request: ProofRequest {
+                request_id: hex::encode(&request_id),
+                version: body.version,
+                mode: body.mode,
+                strategy: body.strategy,
+                program_uri: body.program_uri,
+                stdin_uri: body.stdin_uri,
+                deadline: body.deadline,
+                cycle_limit: body.cycle_limit,
+                status: ProofStatus::Requested.into(),
+                requester: requester.to_vec(),
+            },
+            vkey: body.vkey,
+            request_tx_hash: request_tx_hash.clone(),
+            fulfill_tx_hash: None,
+            proof_uri: None,
+            prover: None,
+            error: None,
+        };
+        self.state.inner.lock().await.requests.insert(request_id.clone(), tracked);
+        self.queue
+            .send(request_id.clone())
+            .map_err(|_| Status::unavailable("proving worker has shut down"))?;
+        tracing::info!("received proof request 0x{}", hex::encode(&request_id));
+
+        Ok(Response::new(RequestProofResponse {
+            tx_hash: request_tx_hash,
+            body: Some(RequestProofResponseBody { request_id }),
+        }))
+    }
+
+    async fn fulfill_proof(
+        &self,
+        request: Request<FulfillProofRequest>,
+    ) -> Result<Response<FulfillProofResponse>, Status> {
+        let request = request.into_inner();
+        let body = request.body.ok_or_else(|| Status::invalid_argument("missing body"))?;
+        let fulfiller = recover_signer(&request.signature, &body)?;
+
+        let tracked = self
+            .state
+            .get(&body.request_id)
+            .await
+            .ok_or_else(|| Status::not_found("unknown request"))?;
+        if tracked.request.status() != ProofStatus::Assigned || tracked.error.is_some() {
+            return Err(Status::failed_precondition("request is not assigned"));
+        }
+        if tracked.prover != Some(fulfiller) {
+            return Err(Status::permission_denied(format!(
+                "{fulfiller} is not the prover the request is assigned to"
+            )));
+        }
+        self.state.use_nonce(fulfiller, body.nonce).await?;
+
+        let (id, uri) = self.store.create();
+        self.store.put(&id, &body.proof).await.map_err(|e| Status::internal(e.to_string()))?;
+        self.state.fulfill(&body.request_id, uri).await;
+        let tracked = self.state.get(&body.request_id).await.expect("request exists");
+
+        Ok(Response::new(FulfillProofResponse {
+            tx_hash: tracked.fulfill_tx_hash.unwrap_or_default(),
+            body: Some(FulfillProofResponseBody {}),
+        }))
+    }
+
+    async fn get_filtered_proof_requests(
+        &self,
+        request: Request<GetFilteredProofRequestsRequest>,
+    ) -> Result<Response<GetFilteredProofRequestsResponse>, Status> {
+        let filter = request.into_inner();
+        let inner = self.state.inner.lock().await;
+        let requests = inner
+            .requests
+            .values()
+            .filter(|tracked| tracked.error.is_none())
+            .map(|tracked| &tracked.request)
+            .filter(|r| r.status == filter.status)
+            .filter(|r| filter.version.is_empty() || r.version == filter.version)
+            .cloned()
+            .collect();
+        Ok(Response::new(GetFilteredProofRequestsResponse { requests }))
+    }
+
+    async fn get_proof_request_status(
+        &self,
+        request: Request<GetProofRequestStatusRequest>,
+    ) -> Result<Response<GetProofRequestStatusResponse>, Status> {
+        let request_id = request.into_inner().request_id;
+        let tracked = self
+            .state
+            .get(&request_id)
+            .await
+            .ok_or_else(|| Status::not_found("unknown request"))?;
+        // A failed request never gets fulfilled, so report the failure as an error to stop
+        // clients polling for the proof.
+        if let Some(error) = tracked.error {
+            return Err(Status::aborted(format!("proof request failed: {error}")));
+        }
+        Ok(Response::new(GetProofRequestStatusResponse {
+            status: tracked.request.status,
+            request_tx_hash: tracked.request_tx_hash,
+            fulfill_tx_hash: tracked.fulfill_tx_hash,
+            proof_uri: tracked.proof_uri,
+            error_code: None,
+            error_description: None,
+        }))
+    }
+
+    async fn get_balance(
+        &self,
+        request: Request<GetBalanceRequest>,
+    ) -> Result<Response<GetBalanceResponse>, Status> {
+        parse_address(&request.into_inner().address)?;
+        // Proving on a self-hosted server is free.
+        Ok(Response::new(GetBalanceResponse { amount: "0".to_string() }))
+    }
+
+    async fn get_nonce(
+        &self,
+        request: Request<GetNonceRequest>,
+    ) -> Result<Response<GetNonceResponse>, Status> {
+        let address = parse_address(&request.into_inner().address)?;
+        let nonce = self.state.inner.lock().await.nonces.get(&address).copied().unwrap_or(0);
+        Ok(Response::new(GetNonceResponse { nonce }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_signer_local::PrivateKeySigner;
+    use sp1_sdk::network_v2::{proto::network::FulfillProofRequestBody, Signable};
+    use tonic::Code;
+
+    use super::*;
+
+    /// Tracks an assigned request and returns its id.
+    async fn assigned_request(state: &ServerState, prover: Address) -> Vec<u8> {
+        let request_id = vec![1u8; 32];
+        let tracked = TrackedRequest {
+            request: ProofRequest { status: ProofStatus::Requested.into(), ..Default::default() },
+            vkey: vec![],
+            request_tx_hash: vec![],
+            fulfill_tx_hash: None,
+            proof_uri: None,
+            prover: None,
+            error: None,
+        };
+        state.inner.lock().await.requests.insert(request_id.clone(), tracked);
+        state.assign(&request_id, prover).await;
+        request_id
+    }
+
+    fn fulfill_request(signer: &PrivateKeySigner, request_id: &[u8]) -> FulfillProofRequest {
+        let body =
+            FulfillProofRequestBody { nonce: 0, request_id: request_id.to_vec(), proof: vec![1] };
+        FulfillProofRequest { signature: body.sign(signer).as_bytes().to_vec(), body: Some(body) }
+    }
+
+    #[tokio::test]
+    async fn test_fulfill_proof_requires_assigned_prover() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(dir.path(), "http://127.0.0.1:50052", "artifacts").unwrap();
+        let state = ServerState::default();
+        let prover = PrivateKeySigner::random();
+        let request_id = assigned_request(&state, prover.address()).await;
+        let network = LocalProverNetwork::new(state.clone(), store, mpsc::unbounded_channel().0);
+
+        let forged = fulfill_request(&PrivateKeySigner::random(), &request_id);
+        let status = network.fulfill_proof(Request::new(forged)).await.unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+        assert_eq!(state.get(&request_id).await.unwrap().request.status(), ProofStatus::Assigned);
+
+        let fulfilled = fulfill_request(&prover, &request_id);
+        network.fulfill_proof(Request::new(fulfilled)).await.unwrap();
+        assert_eq!(state.get(&request_id).await.unwrap().request.status(), ProofStatus::Fulfilled);
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_status_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(dir.path(), "http://127.0.0.1:50052", "artifacts").unwrap();
+        let state = ServerState::default();
+        let request_id = assigned_request(&state, Address::ZERO).await;
+        state.fail(&request_id, "cycle limit exceeded".to_string()).await;
+        let network = LocalProverNetwork::new(state, store, mpsc::unbounded_channel().0);
+
+        let request = GetProofRequestStatusRequest { request_id };
+        let status = network.get_proof_request_status(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), Code::Aborted);
+        assert!(status.message().contains("cycle limit exceeded"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use alloy_signer::Signature;
+use anyhow::{Context, Result};
+use axum::{
+    body::Bytes,
+    extract::{Path as UrlPath, Query, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use sp1_sdk::network_v2::proto::artifact::{
+    artifact_store_server::ArtifactStore, CreateArtifactRequest, CreateArtifactResponse,
+};
+use tonic::{Request, Response, Status};
+
+/// The message clients sign to create an artifact.
+const CREATE_ARTIFACT_MESSAGE: &[u8] = b"create_artifact";
+
+/// An artifact store backed by a directory on the local file system.
+///
+/// Artifacts are addressed by an opaque id and have the URI `s3://<bucket>/<id>`. They are served
+/// over plain HTTP at `<public_url>/<bucket>/<id>`, which is the path-style URL of an S3 object, so
+/// the SDK's S3 client downloads them once its endpoint is set to `<public_url>`. Uploads go to the
+/// same URL with a single-use token, which makes up the presigned upload URL handed out to the
+/// client that created the artifact.
+#[derive(Clone)]
+pub struct LocalStore {
+    inner: Arc<LocalStoreInner>,
+}
+
+struct LocalStoreInner {
+    dir: PathBuf,
+    public_url: String,
+    bucket: String,
+    /// The upload tokens of the artifacts created by clients and not uploaded yet.
+    uploads: Mutex<HashMap<String, String>>,
+}
+
+/// The query parameters of an artifact upload.
+#[derive(Deserialize)]
+struct UploadParams {
+    token: String,
+}
+
+impl LocalStore {
+    /// Creates a new [LocalStore] rooted at `dir`, creating the directory if needed.
+    pub fn new(dir: impl AsRef<Path>, public_url: &str, bucket: &str) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create artifact directory {}", dir.display()))?;
+        validate_id(bucket).context("invalid bucket name")?;
+        let public_url = public_url.trim_end_matches('/').to_string();
+        let bucket = bucket.to_string();
+        Ok(Self {
+            inner: Arc::new(LocalStoreInner { dir, public_url, bucket, uploads: Mutex::default() }),
+        })
+    }
+
+    /// Allocates a fresh artifact id and returns it along with its URI.
+    pub fn create(&self) -> (String, String) {
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let uri = self.uri(&id);
+        (id, uri)
+    }
+
+    /// Allocates a fresh artifact to be uploaded by a client, and returns its URI along with the
+    /// presigned URL to upload it to.
+    pub fn create_upload(&self) -> (String, String) {
+        let (id, uri) = self.create();
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let url = format!("{}/{}/{}?token={}", self.inner.public_url, self.inner.bucket, id, token);
+        self.inner.uploads.lock().unwrap().insert(id, token);
+        (uri, url)
+    }
+
+    /// Returns the URI under which the artifact with the given id is served.
+    pub fn uri(&self, id: &str) -> String {
+        format!("s3://{}/{}", self.inner.bucket, id)
+    }
+
+    /// Resolves an artifact URI handed out by this store back to its id.
+    pub fn id_from_uri<'a>(&self, uri: &'a str) -> Result<&'a str> {
+        let prefix = format!("s3://{}/", self.inner.bucket);
+        let id = uri
+            .strip_prefix(&prefix)
+            .with_context(|| format!("artifact {uri} is not hosted by this server"))?;
+        validate_id(id)?;
+        Ok(id)
+    }
+
+    /// Writes the contents of an artifact.
+    pub async fn put(&self, id: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(id)?;
+        tokio::fs::write(&path, data)
+            .await
+            .with_context(|| format!("failed to write artifact {}", path.display()))
+    }
+
+    /// Reads the contents of an artifact.
+    pub async fn get(&self, id: &str) -> Result<Vec<u8>> {
+        let path = self.path(id)?;
+        tokio::fs::read(&path).await.with_context(|| format!("artifact {id} not found"))
+    }
+
+    /// Reads the contents of the artifact behind a URI handed out by this store.
+    pub async fn get_by_uri(&self, uri: &str) -> Result<Vec<u8>> {
+        let id = self.id_from_uri(uri)?;
+        self.get(id).await
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf> {
+        validate_id(id)?;
+        Ok(self.inner.dir.join(id))
+    }
+
+    /// Consumes the upload token of an artifact, returning whether it is the one handed out with
+    /// the presigned upload URL.
+    fn take_upload(&self, id: &str, token: &str) -> bool {
+        let mut uploads = self.inner.uploads.lock().unwrap();
+        if uploads.get(id).map(String::as_str) != Some(token) {
+            return false;
+        }
+        uploads.remove(id);
+        true
+    }
+
+    /// Returns the HTTP routes used by clients to upload and download artifacts.
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/:bucket/:id", get(download_artifact).put(upload_artifact))
+            .with_state(self.clone())
+    }
+}
+
+/// Artifact ids are generated by the store, so anything other than a plain alphanumeric id is
+/// rejected to avoid escaping the artifact directory.
+fn validate_id(id: &str) -> Result<()> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        anyhow::bail!("invalid artifact id {id:?}");
+    }
+    Ok(())
+}
+
+async fn upload_artifact(
+    State(store): State<LocalStore>,
+    UrlPath((bucket, id)): UrlPath<(String, String)>,
+    Query(params): Query<UploadParams>,
+    body: Bytes,
+) -> StatusCode {
+    if bucket != store.inner.bucket || !store.take_upload(&id, &params.token) {
+        return StatusCode::FORBIDDEN;
+    }
+    match store.put(&id, &body).await {
+        Ok(()) => StatusCode::OK,
+        Err(e) => {
+            tracing::warn!("artifact upload failed: {:?}", e);
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+async fn download_artifact(
+    State(store): State<LocalStore>,
+    UrlPath((bucket, id)): UrlPath<(String, String)>,
+) -> Result<Vec<u8>, StatusCode> {
+    if bucket != store.inner.bucket {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    store.get(&id).await.map_err(|_| StatusCode::NOT_FOUND)
+}
+
+#[tonic::async_trait]
+impl ArtifactStore for LocalStore {
+    async fn create_artifact(
+        &self,
+        request: Request<CreateArtifactRequest>,
+    ) -> Result<Response<CreateArtifactResponse>, Status> {
+        let signature = Signature::try_from(request.into_inner().signature.as_slice())
+            .map_err(|e| Status::invalid_argument(format!("invalid signature: {e}")))?;
+        let creator = signature
+            .recover_address_from_msg(CREATE_ARTIFACT_MESSAGE)
+            .map_err(|e| Status::unauthenticated(format!("failed to recover signer: {e}")))?;
+
+        let (uri, url) = self.create_upload();
+        tracing::debug!("{} created artifact {}", creator, uri);
+        Ok(Response::new(CreateArtifactResponse { artifact_uri: uri, artifact_presigned_url: url }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(dir.path(), "http://127.0.0.1:50052/", "artifacts").unwrap();
+
+        let (id, uri) = store.create();
+        assert_eq!(uri, format!("s3://artifacts/{id}"));
+        store.put(&id, b"hello").await.unwrap();
+        assert_eq!(store.get_by_uri(&uri).await.unwrap(), b"hello");
+
+        assert!(store.get("../secret").await.is_err());
+        assert!(store.id_from_uri("s3://bucket/key").is_err());
+    }
+
+    #[test]
+    fn test_local_store_upload_tokens() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(dir.path(), "http://127.0.0.1:50052", "artifacts").unwrap();
+
+        let (uri, url) = store.create_upload();
+        let id = store.id_from_uri(&uri).unwrap();
+        let token =
+            url.strip_prefix(&format!("http://127.0.0.1:50052/artifacts/{id}?token=")).unwrap();
+
+        assert!(!store.take_upload(id, "forged"));
+        assert!(store.take_upload(id, token));
+        // The token is single-use, so an uploaded artifact can't be overwritten.
+        assert!(!store.take_upload(id, token));
+    }
+}
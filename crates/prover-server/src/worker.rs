@@ -0,0 +1,94 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::Address;
+use anyhow::{Context, Result};
+use sp1_sdk::{
+    network_v2::proto::network::ProofMode, provers::ProofOpts, CpuProver, Prover, SP1Context,
+    SP1ProofKind, SP1Stdin,
+};
+use tokio::sync::mpsc;
+
+use crate::{network::ServerState, store::LocalStore};
+
+/// Proves queued requests one at a time, in the order they were received.
+pub struct Worker {
+    state: ServerState,
+    store: LocalStore,
+    prover: Arc<CpuProver>,
+    /// The address requests are assigned to while this worker proves them.
+    address: Address,
+}
+
+impl Worker {
+    pub fn new(state: ServerState, store: LocalStore, address: Address) -> Self {
+        Self { state, store, prover: Arc::new(CpuProver::new()), address }
+    }
+
+    /// Runs the worker until the request queue is closed.
+    pub async fn run(self, mut queue: mpsc::UnboundedReceiver<Vec<u8>>) {
+        while let Some(request_id) = queue.recv().await {
+            let request_id_hex = hex::encode(&request_id);
+            tracing::info!("proving request 0x{}", request_id_hex);
+            match self.process(&request_id).await {
+                Ok(proof_uri) => {
+                    tracing::info!("fulfilled request 0x{}", request_id_hex);
+                    self.state.fulfill(&request_id, proof_uri).await;
+                }
+                Err(e) => {
+                    tracing::error!("failed to prove request 0x{}: {:?}", request_id_hex, e);
+                    self.state.fail(&request_id, format!("{e:#}")).await;
+                }
+            }
+        }
+    }
+
+    /// Proves a single request and returns the URI of the resulting proof.
+    async fn process(&self, request_id: &[u8]) -> Result<String> {
+        let tracked = self.state.get(request_id).await.context("request not found")?;
+        let request = &tracked.request;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        if request.deadline != 0 && now > request.deadline {
+            anyhow::bail!("request deadline has passed");
+        }
+        let kind = match request.mode() {
+            ProofMode::Core => SP1ProofKind::Core,
+            ProofMode::Compressed => SP1ProofKind::Compressed,
+            ProofMode::Plonk => SP1ProofKind::Plonk,
+            ProofMode::Groth16 => SP1ProofKind::Groth16,
+            ProofMode::UnspecifiedMode => anyhow::bail!("unspecified proof mode"),
+        };
+
+        let elf: Vec<u8> =
+            bincode::deserialize(&self.store.get_by_uri(&request.program_uri).await?)
+                .context("failed to deserialize program")?;
+        let stdin: SP1Stdin =
+            bincode::deserialize(&self.store.get_by_uri(&request.stdin_uri).await?)
+                .context("failed to deserialize stdin")?;
+        let cycle_limit = request.cycle_limit;
+        self.state.assign(request_id, self.address).await;
+
+        let prover = self.prover.clone();
+        let vkey = tracked.vkey.clone();
+        let proof = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+            let (pk, vk) = prover.setup(&elf);
+            if bincode::serialize(&vk)? != vkey {
+                anyhow::bail!("verifying key does not match the program");
+            }
+            let mut context = SP1Context::builder();
+            if cycle_limit != 0 {
+                context.max_cycles(cycle_limit);
+            }
+            let proof = prover.prove(&pk, stdin, ProofOpts::default(), context.build(), kind)?;
+            Ok(bincode::serialize(&proof)?)
+        })
+        .await??;
+
+        let (id, uri) = self.store.create();
+        self.store.put(&id, &proof).await?;
+        Ok(uri)
+    }
+}
@@ -5,6 +5,7 @@ use sp1_core_machine::utils::setup_logger;
 use sp1_prover::{
     components::DefaultProverComponents, shapes::build_vk_map_to_file, REDUCE_BATCH_SIZE,
 };
+use sp1_stark::SecurityProfile;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -23,6 +24,16 @@ struct Args {
     start: Option<usize>,
     #[clap(short, long)]
     end: Option<usize>,
+    /// Build the vks for a profile targeting this many bits of security instead of the default
+    /// profile.
+    #[clap(long)]
+    target_bits: Option<usize>,
+    /// Target proven instead of conjectured security.
+    #[clap(long, default_value_t = false)]
+    proven: bool,
+    /// The log blowup of the profile.
+    #[clap(long)]
+    log_blowup: Option<usize>,
 }
 
 fn main() {
@@ -36,9 +47,19 @@ fn main() {
     let num_setup_workers = args.num_setup_workers;
     let range_start = args.start;
     let range_end = args.end;
+    let mut security_profile = match (args.target_bits, args.proven) {
+        (Some(bits), false) => SecurityProfile::conjectured(bits),
+        (Some(bits), true) => SecurityProfile::proven(bits),
+        (None, false) => SecurityProfile::default(),
+        (None, true) => panic!("--proven requires --target-bits"),
+    };
+    if let Some(log_blowup) = args.log_blowup {
+        security_profile = security_profile.with_log_blowup(log_blowup);
+    }
 
     build_vk_map_to_file::<DefaultProverComponents>(
         build_dir,
+        security_profile,
         reduce_batch_size,
         dummy,
         num_compiler_workers,
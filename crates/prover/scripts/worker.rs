@@ -0,0 +1,29 @@
+use std::net::{SocketAddr, TcpListener};
+
+use clap::Parser;
+use sp1_core_machine::utils::setup_logger;
+use sp1_prover::{components::DefaultProverComponents, SP1Prover};
+
+/// Runs a worker that proves the shards and recursion programs distributed to it by a
+/// coordinator. Start several workers and connect to them with `WorkerPool::connect` to prove
+/// across processes.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The address to listen for coordinators on.
+    #[clap(short, long, env = "SP1_WORKER_ADDR", default_value = "127.0.0.1:7000")]
+    addr: SocketAddr,
+    /// The secret shared with the coordinators, which they must know to be served.
+    #[clap(long, env = "SP1_WORKER_SECRET")]
+    secret: String,
+}
+
+fn main() -> std::io::Result<()> {
+    setup_logger();
+    let args = Args::parse();
+
+    // Listen before setting up the prover, so that coordinators can connect right away.
+    let listener = TcpListener::bind(args.addr)?;
+    let prover = SP1Prover::<DefaultProverComponents>::new();
+    prover.serve_worker(listener, args.secret.as_bytes())
+}
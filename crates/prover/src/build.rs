@@ -19,7 +19,7 @@ pub use sp1_recursion_core::stark::sp1_dev_mode;
 pub use sp1_recursion_circuit::witness::{OuterWitness, Witnessable};
 
 use sp1_recursion_gnark_ffi::{Groth16Bn254Prover, PlonkBn254Prover};
-use sp1_stark::{SP1ProverOpts, ShardProof, StarkVerifyingKey};
+use sp1_stark::{SP1ProverOpts, SecurityProfile, ShardProof, StarkVerifyingKey};
 
 use crate::{
     utils::{babybear_bytes_to_bn254, babybears_to_bn254, words_to_bytes},
@@ -27,27 +27,77 @@ use crate::{
 };
 
 /// Tries to build the PLONK artifacts inside the development directory.
+///
+/// The circuit verifies wrap proofs generated with the security profile, which is also how the
+/// artifacts of profiles other than the default one are built.
 pub fn try_build_plonk_bn254_artifacts_dev(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
+    security_profile: &SecurityProfile,
 ) -> PathBuf {
     let build_dir = plonk_bn254_artifacts_dev_dir();
     println!("[sp1] building plonk bn254 artifacts in development mode");
-    build_plonk_bn254_artifacts(template_vk, template_proof, &build_dir);
+    build_plonk_bn254_artifacts(template_vk, template_proof, security_profile, &build_dir);
     build_dir
 }
 
 /// Tries to build the groth16 bn254 artifacts in the current environment.
+///
+/// The circuit verifies wrap proofs generated with the security profile, which is also how the
+/// artifacts of profiles other than the default one are built.
 pub fn try_build_groth16_bn254_artifacts_dev(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
+    security_profile: &SecurityProfile,
 ) -> PathBuf {
     let build_dir = groth16_bn254_artifacts_dev_dir();
     println!("[sp1] building groth16 bn254 artifacts in development mode");
-    build_groth16_bn254_artifacts(template_vk, template_proof, &build_dir);
+    build_groth16_bn254_artifacts(template_vk, template_proof, security_profile, &build_dir);
     build_dir
 }
 
+/// Tries to build the groth16 bn254 artifacts of the arkworks backend, unless they are already
+/// built.
+///
+/// The keys of the arkworks backend are generated locally, so they are built once per circuit
+/// version and reused afterwards.
+#[cfg(feature = "ark")]
+pub fn try_build_ark_groth16_bn254_artifacts(
+    template_vk: &StarkVerifyingKey<OuterSC>,
+    template_proof: &ShardProof<OuterSC>,
+    security_profile: &SecurityProfile,
+) -> PathBuf {
+    let build_dir = ark_groth16_bn254_artifacts_dir(security_profile);
+    if !build_dir.join(sp1_recursion_gnark_ffi::ffi::ARK_GROTH16_PK_FILE).exists() {
+        println!("[sp1] building arkworks groth16 bn254 artifacts");
+        build_groth16_bn254_artifacts(template_vk, template_proof, security_profile, &build_dir);
+    }
+    build_dir
+}
+
+/// Gets the directory where the groth16 artifacts of the arkworks backend are built for the
+/// security profile.
+#[cfg(feature = "ark")]
+pub fn ark_groth16_bn254_artifacts_dir(security_profile: &SecurityProfile) -> PathBuf {
+    let build_dir = dirs::home_dir()
+        .unwrap()
+        .join(".sp1")
+        .join("circuits")
+        .join("ark-groth16")
+        .join(sp1_core_machine::SP1_CIRCUIT_VERSION);
+    if *security_profile == SecurityProfile::default() {
+        return build_dir;
+    }
+    let log_blowup = security_profile.log_blowup.map_or("default".to_string(), |b| b.to_string());
+    build_dir.join(format!(
+        "{:?}-{}-blowup-{}-pow-{}",
+        security_profile.soundness,
+        security_profile.target_bits,
+        log_blowup,
+        security_profile.proof_of_work_bits
+    ))
+}
+
 /// Gets the directory where the PLONK artifacts are installed in development mode.
 pub fn plonk_bn254_artifacts_dev_dir() -> PathBuf {
     dirs::home_dir().unwrap().join(".sp1").join("circuits").join("dev")
@@ -63,11 +113,13 @@ pub fn groth16_bn254_artifacts_dev_dir() -> PathBuf {
 pub fn build_plonk_bn254_artifacts(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
+    security_profile: &SecurityProfile,
     build_dir: impl Into<PathBuf>,
 ) {
     let build_dir = build_dir.into();
     std::fs::create_dir_all(&build_dir).expect("failed to create build directory");
-    let (constraints, witness) = build_constraints_and_witness(template_vk, template_proof);
+    let (constraints, witness) =
+        build_constraints_and_witness(template_vk, template_proof, security_profile);
     PlonkBn254Prover::build(constraints, witness, build_dir);
 }
 
@@ -76,11 +128,13 @@ pub fn build_plonk_bn254_artifacts(
 pub fn build_groth16_bn254_artifacts(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
+    security_profile: &SecurityProfile,
     build_dir: impl Into<PathBuf>,
 ) {
     let build_dir = build_dir.into();
     std::fs::create_dir_all(&build_dir).expect("failed to create build directory");
-    let (constraints, witness) = build_constraints_and_witness(template_vk, template_proof);
+    let (constraints, witness) =
+        build_constraints_and_witness(template_vk, template_proof, security_profile);
     Groth16Bn254Prover::build(constraints, witness, build_dir);
 }
 
@@ -98,7 +152,12 @@ pub fn build_plonk_bn254_artifacts_with_dummy(build_dir: impl Into<PathBuf>) {
     let wrapped_proof_bytes = std::fs::read("wrapped_proof.bin").unwrap();
     let wrap_vk = bincode::deserialize(&wrap_vk_bytes).unwrap();
     let wrapped_proof = bincode::deserialize(&wrapped_proof_bytes).unwrap();
-    crate::build::build_plonk_bn254_artifacts(&wrap_vk, &wrapped_proof, build_dir.into());
+    crate::build::build_plonk_bn254_artifacts(
+        &wrap_vk,
+        &wrapped_proof,
+        &SecurityProfile::default(),
+        build_dir.into(),
+    );
 }
 
 /// Builds the groth16 bn254 artifacts to the given directory.
@@ -115,21 +174,28 @@ pub fn build_groth16_bn254_artifacts_with_dummy(build_dir: impl Into<PathBuf>) {
     let wrapped_proof_bytes = std::fs::read("wrapped_proof.bin").unwrap();
     let wrap_vk = bincode::deserialize(&wrap_vk_bytes).unwrap();
     let wrapped_proof = bincode::deserialize(&wrapped_proof_bytes).unwrap();
-    crate::build::build_groth16_bn254_artifacts(&wrap_vk, &wrapped_proof, build_dir.into());
+    crate::build::build_groth16_bn254_artifacts(
+        &wrap_vk,
+        &wrapped_proof,
+        &SecurityProfile::default(),
+        build_dir.into(),
+    );
 }
 
-/// Build the verifier constraints and template witness for the circuit.
+/// Build the verifier constraints and template witness for the circuit verifying wrap proofs
+/// generated with the security profile.
 pub fn build_constraints_and_witness(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
+    security_profile: &SecurityProfile,
 ) -> (Vec<Constraint>, OuterWitness<OuterConfig>) {
     tracing::info!("building verifier constraints");
     let template_input = SP1CompressWitnessValues {
         vks_and_proofs: vec![(template_vk.clone(), template_proof.clone())],
         is_complete: true,
     };
-    let constraints =
-        tracing::info_span!("wrap circuit").in_scope(|| build_outer_circuit(&template_input));
+    let constraints = tracing::info_span!("wrap circuit")
+        .in_scope(|| build_outer_circuit(&template_input, security_profile));
 
     let pv: &RecursionPublicValues<BabyBear> = template_proof.public_values.as_slice().borrow();
     let vkey_hash = babybears_to_bn254(&pv.sp1_vk_digest);
@@ -176,8 +242,11 @@ pub fn dummy_proof() -> (StarkVerifyingKey<OuterSC>, ShardProof<OuterSC>) {
     (wrapped_proof.vk, wrapped_proof.proof)
 }
 
-fn build_outer_circuit(template_input: &SP1CompressWitnessValues<OuterSC>) -> Vec<Constraint> {
-    let wrap_machine = WrapAir::wrap_machine(OuterSC::default());
+fn build_outer_circuit(
+    template_input: &SP1CompressWitnessValues<OuterSC>,
+    security_profile: &SecurityProfile,
+) -> Vec<Constraint> {
+    let wrap_machine = WrapAir::wrap_machine(OuterSC::with_security_profile(security_profile));
 
     let wrap_span = tracing::debug_span!("build wrap circuit").entered();
     let mut builder = Builder::<OuterConfig>::default();
@@ -0,0 +1,323 @@
+//! On-disk checkpoints of the intermediate artifacts of the proving pipeline.
+//!
+//! A [ProofCheckpoints] directory holds the output of every stage that completed, so that a proof
+//! interrupted in a later stage can be resumed without redoing the earlier ones:
+//!
+//! - `manifest.bin`: the program, input and options the checkpoints belong to. See [Manifest].
+//! - `core/shard-<n>.bin`: the proof of every core shard, saved as soon as it is generated.
+//! - `core.bin`: the complete core proof.
+//! - `compress/<index>.bin`: every proof of the compress tree, saved as soon as it is generated.
+//! - `compress.bin`, `shrink.bin` and `wrap.bin`: the outputs of the recursion stages.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sp1_core_executor::{SP1Context, SP1ReduceProof};
+use sp1_core_machine::{io::SP1Stdin, utils::SP1CoreProverError};
+use sp1_stark::{
+    baby_bear_poseidon2::BabyBearPoseidon2, SP1ProverOpts, SecurityProfile, ShardProof, SplitOpts,
+    StarkVerifyingKey,
+};
+use thiserror::Error;
+
+use crate::InnerSC;
+
+/// A stage of the proving pipeline whose output is checkpointed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProofStage {
+    /// The core proof, produced by [crate::SP1Prover::prove_core].
+    Core,
+    /// The compressed proof, produced by [crate::SP1Prover::compress].
+    Compress,
+    /// The shrink proof, produced by [crate::SP1Prover::shrink].
+    Shrink,
+    /// The wrap proof, produced by [crate::SP1Prover::wrap_bn254].
+    Wrap,
+}
+
+impl ProofStage {
+    /// All stages, in the order they run.
+    pub const ALL: [ProofStage; 4] =
+        [ProofStage::Core, ProofStage::Compress, ProofStage::Shrink, ProofStage::Wrap];
+
+    fn file_name(self) -> &'static str {
+        match self {
+            ProofStage::Core => "core.bin",
+            ProofStage::Compress => "compress.bin",
+            ProofStage::Shrink => "shrink.bin",
+            ProofStage::Wrap => "wrap.bin",
+        }
+    }
+}
+
+/// An error that can occur while using [ProofCheckpoints].
+#[derive(Error, Debug)]
+pub enum SP1CheckpointError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("checkpoints in {0} belong to a different program, input or options")]
+    Mismatch(PathBuf),
+    #[error(transparent)]
+    Prover(#[from] SP1CoreProverError),
+}
+
+/// Everything a checkpointed proof depends on.
+///
+/// Checkpoints are only reused by a proof whose manifest is identical, since reusing a shard proof
+/// generated with a different input, environment or sharding would produce an invalid proof. Options
+/// which only affect the scheduling of the prover, such as the shard batch size, are left out.
+#[derive(Serialize)]
+pub struct Manifest<'a> {
+    /// The hash of the verifying key of the program.
+    pub vk_hash: [u32; 8],
+    /// The input buffer of the program.
+    pub buffer: &'a [Vec<u8>],
+    /// The files of the read-only file system of the program.
+    pub files: &'a BTreeMap<String, Vec<u8>>,
+    /// The proofs the program verifies, which are folded into the compressed proof.
+    pub deferred_proofs:
+        &'a [(SP1ReduceProof<BabyBearPoseidon2>, StarkVerifyingKey<BabyBearPoseidon2>)],
+    /// The environment variables of the program.
+    pub env: &'a BTreeMap<String, String>,
+    /// The seed of the random number generator of the program.
+    pub rand_seed: Option<[u8; 32]>,
+    /// The shard size of the core proof.
+    pub core_shard_size: usize,
+    /// How the deferred events of the core proof are split into shards.
+    pub core_split_opts: SplitOpts,
+    /// The shard size of the recursion proofs.
+    pub recursion_shard_size: usize,
+    /// How the deferred events of the recursion proofs are split into shards.
+    pub recursion_split_opts: SplitOpts,
+    /// The security profile the FRI parameters of the proofs are derived from.
+    pub security_profile: SecurityProfile,
+}
+
+impl<'a> Manifest<'a> {
+    /// The manifest of proving the program whose verifying key hashes to `vk_hash` on `stdin`.
+    pub fn new(
+        vk_hash: [u32; 8],
+        stdin: &'a SP1Stdin,
+        opts: &SP1ProverOpts,
+        context: &'a SP1Context,
+    ) -> Self {
+        Self {
+            vk_hash,
+            buffer: &stdin.buffer,
+            files: &stdin.files,
+            deferred_proofs: &stdin.proofs,
+            env: &context.env,
+            rand_seed: context.rand_seed,
+            core_shard_size: opts.core_opts.shard_size,
+            core_split_opts: opts.core_opts.split_opts,
+            recursion_shard_size: opts.recursion_opts.shard_size,
+            recursion_split_opts: opts.recursion_opts.split_opts,
+            security_profile: opts.security_profile,
+        }
+    }
+}
+
+/// A work directory in which [crate::SP1Prover] persists the intermediate artifacts of a proof.
+#[derive(Debug, Clone)]
+pub struct ProofCheckpoints {
+    dir: PathBuf,
+}
+
+impl ProofCheckpoints {
+    const MANIFEST: &'static str = "manifest.bin";
+    const CORE_SHARDS: &'static str = "core";
+    const COMPRESS_PROOFS: &'static str = "compress";
+
+    /// Opens the checkpoint directory `dir`, creating it if it does not exist.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, SP1CheckpointError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(dir.join(Self::CORE_SHARDS))?;
+        std::fs::create_dir_all(dir.join(Self::COMPRESS_PROOFS))?;
+        Ok(Self { dir })
+    }
+
+    /// The checkpoint directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Binds the checkpoints to a [Manifest], or checks that they are already bound to it.
+    ///
+    /// This prevents resuming from checkpoints left behind by a different proof.
+    pub fn check_manifest(&self, manifest: &Manifest) -> Result<(), SP1CheckpointError> {
+        let manifest = bincode::serialize(manifest)?;
+        let path = self.dir.join(Self::MANIFEST);
+        match std::fs::read(&path) {
+            Ok(existing) if existing == manifest => Ok(()),
+            Ok(_) => Err(SP1CheckpointError::Mismatch(self.dir.clone())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                write_atomic(&path, |writer| writer.write_all(&manifest).map_err(Into::into))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The last stage whose output is checkpointed, if any.
+    pub fn last_completed_stage(&self) -> Option<ProofStage> {
+        ProofStage::ALL.into_iter().rev().find(|stage| self.stage_path(*stage).exists())
+    }
+
+    /// Loads the checkpointed output of `stage`, if there is one.
+    pub fn load<T: DeserializeOwned>(&self, stage: ProofStage) -> Option<T> {
+        load(&self.stage_path(stage))
+    }
+
+    /// Checkpoints the output of `stage`.
+    pub fn save<T: Serialize>(
+        &self,
+        stage: ProofStage,
+        value: &T,
+    ) -> Result<(), SP1CheckpointError> {
+        save(&self.stage_path(stage), value)
+    }
+
+    /// Returns the checkpointed output of `stage`, or runs `prove` and checkpoints its output.
+    pub fn get_or_prove<T, E>(
+        &self,
+        stage: ProofStage,
+        prove: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        E: From<SP1CheckpointError>,
+    {
+        if let Some(output) = self.load(stage) {
+            tracing::info!("resuming from checkpointed {:?} proof", stage);
+            return Ok(output);
+        }
+        let output = prove()?;
+        self.save(stage, &output)?;
+        Ok(output)
+    }
+
+    /// Removes every checkpoint, for example once the final proof was produced.
+    pub fn clear(&self) -> Result<(), SP1CheckpointError> {
+        std::fs::remove_dir_all(&self.dir)?;
+        Ok(())
+    }
+
+    /// The directory holding the proofs of the individual core shards.
+    pub(crate) fn core_shards_dir(&self) -> PathBuf {
+        self.dir.join(Self::CORE_SHARDS)
+    }
+
+    /// Loads the proof of the compress tree node `index`, if it was checkpointed.
+    pub(crate) fn load_compress_proof(
+        &self,
+        index: usize,
+    ) -> Option<(StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>)> {
+        load(&self.compress_proof_path(index))
+    }
+
+    /// Checkpoints the proof of the compress tree node `index`.
+    pub(crate) fn save_compress_proof(
+        &self,
+        index: usize,
+        vk: &StarkVerifyingKey<InnerSC>,
+        proof: &ShardProof<InnerSC>,
+    ) {
+        if let Err(e) = save(&self.compress_proof_path(index), &(vk, proof)) {
+            tracing::warn!("failed to checkpoint compress proof {}: {}", index, e);
+        }
+    }
+
+    fn stage_path(&self, stage: ProofStage) -> PathBuf {
+        self.dir.join(stage.file_name())
+    }
+
+    fn compress_proof_path(&self, index: usize) -> PathBuf {
+        self.dir.join(Self::COMPRESS_PROOFS).join(format!("{index}.bin"))
+    }
+}
+
+fn load<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let file = File::open(path).ok()?;
+    match bincode::deserialize_from(BufReader::new(file)) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!("ignoring unreadable checkpoint {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn save<T: Serialize>(path: &Path, value: &T) -> Result<(), SP1CheckpointError> {
+    write_atomic(path, |writer| bincode::serialize_into(writer, value).map_err(Into::into))
+}
+
+/// Writes a file through a temporary file, so that a crash never leaves a truncated checkpoint.
+fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> Result<(), SP1CheckpointError>,
+) -> Result<(), SP1CheckpointError> {
+    let tmp_path = path.with_extension("tmp");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    write(&mut writer)?;
+    writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoints = ProofCheckpoints::new(dir.path().join("work")).unwrap();
+        assert_eq!(checkpoints.last_completed_stage(), None);
+        assert_eq!(checkpoints.load::<u64>(ProofStage::Core), None);
+
+        checkpoints.save(ProofStage::Core, &1u64).unwrap();
+        checkpoints.save(ProofStage::Compress, &2u64).unwrap();
+        assert_eq!(checkpoints.last_completed_stage(), Some(ProofStage::Compress));
+        assert_eq!(checkpoints.load::<u64>(ProofStage::Compress), Some(2));
+
+        checkpoints.clear().unwrap();
+        assert!(!dir.path().join("work").exists());
+    }
+
+    #[test]
+    fn test_manifest_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoints = ProofCheckpoints::new(dir.path()).unwrap();
+        let vk = [0; 8];
+        let stdin = SP1Stdin::new();
+        let opts = SP1ProverOpts::default();
+        let context = SP1Context::default();
+        checkpoints.check_manifest(&Manifest::new(vk, &stdin, &opts, &context)).unwrap();
+        checkpoints.check_manifest(&Manifest::new(vk, &stdin, &opts, &context)).unwrap();
+
+        // A different shard size or environment invalidates the checkpoints.
+        let mut other_opts = opts;
+        other_opts.core_opts.shard_size *= 2;
+        assert!(matches!(
+            checkpoints.check_manifest(&Manifest::new(vk, &stdin, &other_opts, &context)),
+            Err(SP1CheckpointError::Mismatch(_))
+        ));
+        let mut other_context = SP1Context::default();
+        other_context.env.insert("KEY".to_string(), "value".to_string());
+        assert!(matches!(
+            checkpoints.check_manifest(&Manifest::new(vk, &stdin, &opts, &other_context)),
+            Err(SP1CheckpointError::Mismatch(_))
+        ));
+
+        // Options which do not change the proofs do not.
+        let mut other_opts = opts;
+        other_opts.core_opts.shard_batch_size *= 2;
+        checkpoints.check_manifest(&Manifest::new(vk, &stdin, &other_opts, &context)).unwrap();
+    }
+}
@@ -0,0 +1,444 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    net::{SocketAddr, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::sync_channel,
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use sp1_core_executor::{
+    ExecutionRecord, Executor, Program, ProofMonitor, ProofProgress, SP1Context,
+};
+use sp1_core_machine::{
+    io::SP1Stdin,
+    reduce::SP1ReduceProof,
+    utils::{concurrency::TurnBasedSync, SP1CoreProverError},
+};
+use sp1_primitives::io::SP1PublicValues;
+use sp1_recursion_circuit::machine::SP1CompressWitnessValues;
+use sp1_stark::{
+    air::PublicValues, SP1CoreOpts, SP1ProverOpts, ShardProof, SplitOpts, StarkVerifyingKey,
+};
+use tracing::instrument;
+
+use super::{
+    protocol::{authenticate_worker, read_message, write_message, WorkerRequest, WorkerResponse},
+    SP1DistributedError,
+};
+use crate::{
+    components::SP1ProverComponents, InnerSC, SP1CircuitWitness, SP1CoreProof, SP1CoreProofData,
+    SP1Prover, SP1ProvingKey, SP1VerifyingKey, REDUCE_BATCH_SIZE,
+};
+
+/// A connection to a worker.
+struct WorkerConnection {
+    addr: SocketAddr,
+    stream: TcpStream,
+}
+
+impl WorkerConnection {
+    /// Sends a request to the worker and waits for its response.
+    fn request(&mut self, request: &WorkerRequest) -> Result<WorkerResponse, SP1DistributedError> {
+        write_message(&mut self.stream, request)?;
+        match read_message(&mut self.stream)? {
+            WorkerResponse::Error(e) => {
+                Err(SP1DistributedError::Worker(format!("{}: {}", self.addr, e)))
+            }
+            response => Ok(response),
+        }
+    }
+
+    /// Sends an execution checkpoint to the worker to regenerate its records.
+    fn trace(
+        &mut self,
+        checkpoint: &mut File,
+        monitor: &ProofMonitor,
+    ) -> Result<WorkerResponse, SP1DistributedError> {
+        if monitor.is_cancelled() {
+            return Err(SP1CoreProverError::Cancelled.into());
+        }
+        let mut bytes = Vec::new();
+        checkpoint.seek(SeekFrom::Start(0))?;
+        checkpoint.read_to_end(&mut bytes)?;
+        self.request(&WorkerRequest::Trace { checkpoint: bytes })
+    }
+}
+
+/// The workers a coordinator distributes proofs across.
+pub struct WorkerPool {
+    workers: Vec<WorkerConnection>,
+}
+
+impl WorkerPool {
+    /// Connects to the workers listening on `addrs`, authenticating them with the shared
+    /// `secret`.
+    pub fn connect(addrs: &[SocketAddr], secret: &[u8]) -> Result<Self, SP1DistributedError> {
+        if addrs.is_empty() {
+            return Err(SP1DistributedError::NoWorkers);
+        }
+        let workers = addrs
+            .iter()
+            .map(|&addr| {
+                let mut stream = TcpStream::connect(addr)?;
+                stream.set_nodelay(true)?;
+                authenticate_worker(&mut stream, secret)?;
+                Ok(WorkerConnection { addr, stream })
+            })
+            .collect::<Result<_, SP1DistributedError>>()?;
+        Ok(Self { workers })
+    }
+
+    /// The number of workers in the pool.
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Whether the pool has no workers.
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    /// Sends a request with no output to every worker.
+    fn broadcast(&mut self, request: &WorkerRequest) -> Result<(), SP1DistributedError> {
+        thread::scope(|s| {
+            let handles = self
+                .workers
+                .iter_mut()
+                .map(|worker| s.spawn(move || worker.request(request)))
+                .collect::<Vec<_>>();
+            handles.into_iter().try_for_each(|handle| match handle.join().unwrap()? {
+                WorkerResponse::Ready => Ok(()),
+                _ => Err(SP1DistributedError::Protocol("expected the worker to be ready")),
+            })
+        })
+    }
+
+    /// Runs `f` on every task with the next idle worker, and returns the results in the order of
+    /// the tasks.
+    ///
+    /// Once a task fails, no new tasks are started and the first error is returned.
+    fn run<T: Send, R: Send>(
+        &mut self,
+        tasks: impl Iterator<Item = T> + Send,
+        f: impl Fn(&mut WorkerConnection, T) -> Result<R, SP1DistributedError> + Sync,
+    ) -> Result<Vec<R>, SP1DistributedError> {
+        let tasks = Mutex::new(tasks.enumerate());
+        let results = Mutex::new(Vec::new());
+        let failed = AtomicBool::new(false);
+        thread::scope(|s| {
+            let handles = self
+                .workers
+                .iter_mut()
+                .map(|worker| {
+                    let (tasks, results, failed, f) = (&tasks, &results, &failed, &f);
+                    s.spawn(move || {
+                        while !failed.load(Ordering::SeqCst) {
+                            let Some((index, task)) = tasks.lock().unwrap().next() else {
+                                break;
+                            };
+                            match f(&mut *worker, task) {
+                                Ok(result) => results.lock().unwrap().push((index, result)),
+                                Err(e) => {
+                                    failed.store(true, Ordering::SeqCst);
+                                    return Err(e);
+                                }
+                            }
+                        }
+                        Ok(())
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().try_for_each(|handle| handle.join().unwrap())
+        })?;
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|(index, _)| *index);
+        Ok(results.into_iter().map(|(_, result)| result).collect())
+    }
+
+    /// Proves the nodes of the compress tree with the given inputs.
+    fn prove_compress(
+        &mut self,
+        inputs: Vec<SP1CircuitWitness>,
+        opts: SP1ProverOpts,
+    ) -> Result<Vec<(StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>)>, SP1DistributedError> {
+        self.run(inputs.into_iter(), |worker, input| {
+            match worker.request(&WorkerRequest::ProveCompress { input, opts })? {
+                WorkerResponse::CompressProven { vk, proof } => Ok((vk, proof)),
+                _ => Err(SP1DistributedError::Protocol("expected a compress proof")),
+            }
+        })
+    }
+}
+
+/// Assigns shard numbers and public values to the records of consecutive checkpoints, and splits
+/// their deferred events into shards of their own, like
+/// [sp1_core_machine::utils::prove_with_context] does.
+///
+/// This has to happen in the order of the checkpoints, so the checkpoints take turns.
+struct ShardAssigner {
+    sync: TurnBasedSync,
+    split_opts: SplitOpts,
+    state: Mutex<(PublicValues<u32, u32>, ExecutionRecord)>,
+}
+
+impl ShardAssigner {
+    fn new(program: &Program, opts: SP1CoreOpts) -> Self {
+        Self {
+            sync: TurnBasedSync::new(),
+            split_opts: opts.split_opts,
+            state: Mutex::new((
+                PublicValues::<u32, u32>::default().reset(),
+                ExecutionRecord::new(Arc::new(program.clone())),
+            )),
+        }
+    }
+
+    /// The number of shards assigned so far.
+    fn num_shards(&self) -> u32 {
+        self.state.lock().unwrap().0.shard
+    }
+
+    /// Waits for the turn of the checkpoint `index` and assigns the public values of the records
+    /// traced from it. Returns the public values of the records, and the deferred shards that are
+    /// ready to be proven after them.
+    ///
+    /// The turn is taken even if tracing the checkpoint failed, so that later checkpoints do not
+    /// wait forever.
+    fn assign(
+        &self,
+        index: usize,
+        done: bool,
+        traced: Result<WorkerResponse, SP1DistributedError>,
+    ) -> Result<(Vec<PublicValues<u32, u32>>, Vec<ExecutionRecord>), SP1DistributedError> {
+        self.sync.wait_for_turn(index);
+        let assigned = traced.and_then(|response| match response {
+            WorkerResponse::Traced { public_values, deferred } => {
+                Ok(self.assign_in_turn(public_values, deferred, done))
+            }
+            _ => Err(SP1DistributedError::Protocol("expected traced records")),
+        });
+        self.sync.advance_turn();
+        assigned
+    }
+
+    fn assign_in_turn(
+        &self,
+        mut public_values: Vec<PublicValues<u32, u32>>,
+        mut new_deferred: ExecutionRecord,
+        done: bool,
+    ) -> (Vec<PublicValues<u32, u32>>, Vec<ExecutionRecord>) {
+        let (state, deferred) = &mut *self.state.lock().unwrap();
+
+        // Update the public values & prover state for the shards which contain "cpu events".
+        for public_values in public_values.iter_mut() {
+            state.shard += 1;
+            state.execution_shard = public_values.execution_shard;
+            state.start_pc = public_values.start_pc;
+            state.next_pc = public_values.next_pc;
+            state.committed_value_digest = public_values.committed_value_digest;
+            state.deferred_proofs_digest = public_values.deferred_proofs_digest;
+            *public_values = *state;
+        }
+
+        // See if any deferred shards are ready to be committed to.
+        deferred.append(&mut new_deferred);
+        let mut deferred = deferred.split(done, self.split_opts);
+
+        // Update the public values & prover state for the shards which do not contain "cpu
+        // events".
+        if !done {
+            state.execution_shard += 1;
+        }
+        for record in deferred.iter_mut() {
+            state.shard += 1;
+            state.previous_init_addr_bits = record.public_values.previous_init_addr_bits;
+            state.last_init_addr_bits = record.public_values.last_init_addr_bits;
+            state.previous_finalize_addr_bits = record.public_values.previous_finalize_addr_bits;
+            state.last_finalize_addr_bits = record.public_values.last_finalize_addr_bits;
+            state.start_pc = state.next_pc;
+            record.public_values = *state;
+        }
+
+        (public_values, deferred)
+    }
+}
+
+impl<C: SP1ProverComponents> SP1Prover<C> {
+    /// Generate shard proofs like [Self::prove_core], distributing the work across the workers of
+    /// `pool`.
+    ///
+    /// The program is executed on this machine, and every checkpoint of its execution is sent to a
+    /// worker which regenerates the execution records and proves the shards of the checkpoint.
+    #[instrument(name = "prove_core_distributed", level = "info", skip_all)]
+    pub fn prove_core_distributed<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        opts: SP1ProverOpts,
+        mut context: SP1Context<'a>,
+        pool: &mut WorkerPool,
+    ) -> Result<SP1CoreProof, SP1DistributedError> {
+        context.subproof_verifier.replace(Arc::new(self));
+        let monitor = context.monitor.clone();
+        let program = self.get_program(&pk.elf).unwrap();
+        pool.broadcast(&WorkerRequest::Setup { elf: pk.elf.clone(), opts })?;
+
+        // Setup the runtime.
+        let mut runtime = Executor::with_context(program.clone(), opts.core_opts, context);
+        let maximal_shapes = match self.core_shape_config.as_ref() {
+            Some(shape_config) => shape_config.maximal_core_shapes(),
+            None => vec![],
+        };
+        runtime.maximal_shapes = Some(maximal_shapes.into_iter().map(|s| s.inner).collect());
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
+        for (proof, vk) in stdin.proofs.iter() {
+            runtime.write_proof(proof.clone(), vk.clone());
+        }
+
+        // Execute the program, and commit to the shards of every checkpoint as soon as it is
+        // generated.
+        let checkpoints = Mutex::new(Vec::new());
+        let assigner = ShardAssigner::new(&program, opts.core_opts);
+        let (commitments, execution) = thread::scope(|s| {
+            let (checkpoints_tx, checkpoints_rx) =
+                sync_channel::<(usize, File, bool)>(opts.core_opts.checkpoints_channel_capacity);
+            let generator_monitor = &monitor;
+            let generator = s.spawn(move || -> Result<_, SP1CoreProverError> {
+                let mut index = 0;
+                loop {
+                    // Stop generating checkpoints if proving was cancelled.
+                    if generator_monitor.is_cancelled() {
+                        break Err(SP1CoreProverError::Cancelled);
+                    }
+
+                    // Execute the runtime until we reach a checkpoint, and save it to a temp file.
+                    let (checkpoint, done) =
+                        runtime.execute_state().map_err(SP1CoreProverError::ExecutionError)?;
+                    let mut checkpoint_file =
+                        tempfile::tempfile().map_err(SP1CoreProverError::IoError)?;
+                    checkpoint.save(&mut checkpoint_file).map_err(SP1CoreProverError::IoError)?;
+
+                    // The checkpoints are only dropped if a worker failed, whose error is returned
+                    // instead.
+                    if checkpoints_tx.send((index, checkpoint_file, done)).is_err() {
+                        break Ok(None);
+                    }
+                    if done {
+                        break Ok(Some((
+                            runtime.state.public_values_stream,
+                            runtime.state.global_clk,
+                        )));
+                    }
+                    index += 1;
+                }
+            });
+
+            let commitments =
+                pool.run(checkpoints_rx.into_iter(), |worker, (index, mut file, done)| {
+                    let traced = worker.trace(&mut file, &monitor);
+                    let assigned = assigner.assign(index, done, traced);
+                    checkpoints.lock().unwrap().push((index, file, done));
+                    let (public_values, deferred) = assigned?;
+                    match worker.request(&WorkerRequest::Commit { public_values, deferred })? {
+                        WorkerResponse::Committed { commitments } => Ok(commitments),
+                        _ => Err(SP1DistributedError::Protocol("expected commitments")),
+                    }
+                });
+            (commitments, generator.join().unwrap())
+        });
+        let commitments = commitments?.into_iter().flatten().collect::<Vec<_>>();
+        let (public_values_stream, cycles) =
+            execution?.expect("the checkpoints are dropped only if a worker failed");
+        monitor.report(ProofProgress::ShardsExecuted { num_shards: assigner.num_shards() });
+
+        // Let the workers derive the challenges of the global permutation argument, and prove the
+        // shards of every checkpoint.
+        pool.broadcast(&WorkerRequest::StartOpening { commitments })?;
+        let mut checkpoints = checkpoints.into_inner().unwrap();
+        checkpoints.sort_by_key(|(index, _, _)| *index);
+        let assigner = ShardAssigner::new(&program, opts.core_opts);
+        let shard_proofs =
+            pool.run(checkpoints.into_iter(), |worker, (index, mut file, done)| {
+                let traced = worker.trace(&mut file, &monitor);
+                let (public_values, deferred) = assigner.assign(index, done, traced)?;
+                let shards = public_values
+                    .iter()
+                    .map(|public_values| public_values.shard)
+                    .chain(deferred.iter().map(|record| record.public_values.shard))
+                    .collect::<Vec<_>>();
+                match worker.request(&WorkerRequest::Prove { public_values, deferred })? {
+                    WorkerResponse::Proven { proofs } => {
+                        for shard in shards {
+                            monitor.report(ProofProgress::ShardProven { shard });
+                        }
+                        Ok(proofs)
+                    }
+                    _ => Err(SP1DistributedError::Protocol("expected shard proofs")),
+                }
+            })?;
+        let shard_proofs = shard_proofs.into_iter().flatten().collect();
+        monitor.report(ProofProgress::CoreProven);
+
+        Self::check_for_high_cycles(cycles);
+        Ok(SP1CoreProof {
+            proof: SP1CoreProofData(shard_proofs),
+            stdin: stdin.clone(),
+            public_values: SP1PublicValues::from(&public_values_stream),
+            cycles,
+        })
+    }
+
+    /// Reduce shards proofs to a single shard proof like [Self::compress], distributing the proofs
+    /// of every layer of the reduction tree across the workers of `pool`.
+    #[instrument(name = "compress_distributed", level = "info", skip_all)]
+    pub fn compress_distributed(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1CoreProof,
+        deferred_proofs: Vec<SP1ReduceProof<InnerSC>>,
+        opts: SP1ProverOpts,
+        pool: &mut WorkerPool,
+    ) -> Result<SP1ReduceProof<InnerSC>, SP1DistributedError> {
+        // The batch size for reducing the first layer of recursion.
+        let first_layer_batch_size = 1;
+
+        let shard_proofs = &proof.proof.0;
+        let leaf_challenger = self.leaf_challenger(vk, shard_proofs);
+        let first_layer_inputs = self.get_first_layer_inputs(
+            vk,
+            &leaf_challenger,
+            shard_proofs,
+            &deferred_proofs,
+            first_layer_batch_size,
+        );
+
+        // Reduce the proofs of each layer in batches until a single proof remains, and prove that
+        // it is the root of the tree.
+        let mut proofs = pool.prove_compress(first_layer_inputs, opts)?;
+        while proofs.len() > 1 {
+            let inputs = proofs
+                .chunks(REDUCE_BATCH_SIZE)
+                .map(|batch| {
+                    SP1CircuitWitness::Compress(SP1CompressWitnessValues {
+                        vks_and_proofs: batch.to_vec(),
+                        is_complete: false,
+                    })
+                })
+                .collect();
+            proofs = pool.prove_compress(inputs, opts)?;
+        }
+        let root = SP1CircuitWitness::Compress(SP1CompressWitnessValues {
+            vks_and_proofs: proofs,
+            is_complete: true,
+        });
+        let (vk, proof) = pool.prove_compress(vec![root], opts)?.pop().unwrap();
+
+        Ok(SP1ReduceProof { vk, proof })
+    }
+}
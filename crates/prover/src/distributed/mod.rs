@@ -0,0 +1,46 @@
+//! Proving across multiple worker processes.
+//!
+//! A coordinator splits a proof into independent pieces of work and sends them over TCP to worker
+//! processes, which may run on the same machine or on others:
+//!
+//! - Core proving: the coordinator executes the program and sends every checkpoint of its
+//!   execution state to a worker, which regenerates the execution records of the checkpoint and
+//!   commits to, and later proves, its shards. Assigning shard numbers and splitting off deferred
+//!   events has to happen in the order of the checkpoints, so the workers report the public values
+//!   and deferred events of their records back to the coordinator, which does this bookkeeping.
+//! - Compression: the coordinator distributes the proofs of every layer of the reduction tree.
+//!
+//! Workers are started with [crate::SP1Prover::serve_worker], for example by the `worker` binary of
+//! this crate, and a coordinator connects to them with [WorkerPool::connect]. The coordinator and
+//! the workers must use the same prover configuration, e.g. `FIX_CORE_SHAPES` and `VERIFY_VK`, and
+//! the same secret, which they authenticate each other with when connecting.
+
+mod coordinator;
+mod protocol;
+mod worker;
+
+use std::io;
+
+use sp1_core_machine::utils::SP1CoreProverError;
+use thiserror::Error;
+
+pub use coordinator::WorkerPool;
+
+/// An error that can occur while distributing a proof across workers.
+#[derive(Error, Debug)]
+pub enum SP1DistributedError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("there are no workers to distribute the proof to")]
+    NoWorkers,
+    #[error("worker failed: {0}")]
+    Worker(String),
+    #[error("protocol error: {0}")]
+    Protocol(&'static str),
+    #[error("the peer failed to authenticate")]
+    Unauthenticated,
+    #[error(transparent)]
+    Core(#[from] SP1CoreProverError),
+}
@@ -0,0 +1,230 @@
+//! The messages exchanged between a coordinator and its workers.
+//!
+//! Every message is encoded with bincode and prefixed with its length as a little-endian `u64`.
+//! Messages longer than [MAX_MESSAGE_LEN] are rejected.
+//!
+//! Before exchanging messages, the coordinator and the worker prove to each other that they know
+//! the shared secret of the deployment with a challenge-response handshake:
+//!
+//! 1. The worker sends a random nonce.
+//! 2. The coordinator answers with the tag of the worker's nonce and a random nonce of its own.
+//! 3. The worker checks the tag and answers with the tag of the coordinator's nonce.
+//!
+//! The handshake keeps processes without the secret from submitting work, but the messages
+//! themselves are not encrypted, so workers should only be reachable over a trusted network.
+
+use std::io::{self, Read, Write};
+
+use p3_baby_bear::BabyBear;
+use rand::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_core_executor::ExecutionRecord;
+use sp1_stark::{air::PublicValues, Com, SP1ProverOpts, ShardProof, StarkVerifyingKey};
+
+use super::SP1DistributedError;
+use crate::{CoreSC, InnerSC, SP1CircuitWitness};
+
+/// A request sent by the coordinator to a worker.
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum WorkerRequest {
+    /// Prepare to prove the shards of the program `elf`.
+    Setup { elf: Vec<u8>, opts: SP1ProverOpts },
+    /// Regenerate the execution records of a serialized execution checkpoint. The records are kept
+    /// until the next [WorkerRequest::Commit] or [WorkerRequest::Prove].
+    Trace { checkpoint: Vec<u8> },
+    /// Commit to the traced records, with the public values assigned to them by the coordinator,
+    /// and to the deferred records split off after them.
+    Commit { public_values: Vec<PublicValues<u32, u32>>, deferred: Vec<ExecutionRecord> },
+    /// Replay the commitments of every shard to derive the challenges shard proofs are opened
+    /// with.
+    StartOpening { commitments: Vec<(Com<CoreSC>, Vec<BabyBear>)> },
+    /// Prove the traced records, with the public values assigned to them by the coordinator, and
+    /// the deferred records split off after them.
+    Prove { public_values: Vec<PublicValues<u32, u32>>, deferred: Vec<ExecutionRecord> },
+    /// Prove a node of the compress tree.
+    ProveCompress { input: SP1CircuitWitness, opts: SP1ProverOpts },
+}
+
+/// The response of a worker to a [WorkerRequest].
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum WorkerResponse {
+    /// The request was handled and has no output.
+    Ready,
+    /// The public values of the traced records, and the events they deferred.
+    Traced { public_values: Vec<PublicValues<u32, u32>>, deferred: ExecutionRecord },
+    /// The main commitment and public values of every committed shard.
+    Committed { commitments: Vec<(Com<CoreSC>, Vec<BabyBear>)> },
+    /// The proof of every proven shard.
+    Proven { proofs: Vec<ShardProof<CoreSC>> },
+    /// The proof of a node of the compress tree.
+    CompressProven { vk: StarkVerifyingKey<InnerSC>, proof: ShardProof<InnerSC> },
+    /// Handling the request failed.
+    Error(String),
+}
+
+/// The largest message accepted from a peer.
+pub(crate) const MAX_MESSAGE_LEN: u64 = 1 << 32;
+
+/// The length of the nonces and tags of the handshake.
+const AUTH_LEN: usize = 32;
+
+/// Writes a length-prefixed message.
+pub(crate) fn write_message<T: Serialize>(
+    writer: &mut impl Write,
+    message: &T,
+) -> Result<(), SP1DistributedError> {
+    let bytes = bincode::serialize(message)?;
+    if bytes.len() as u64 > MAX_MESSAGE_LEN {
+        return Err(SP1DistributedError::Protocol("the message exceeds the maximum length"));
+    }
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a length-prefixed message.
+pub(crate) fn read_message<T: DeserializeOwned>(
+    reader: &mut impl Read,
+) -> Result<T, SP1DistributedError> {
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    if len > MAX_MESSAGE_LEN {
+        return Err(SP1DistributedError::Protocol("the message exceeds the maximum length"));
+    }
+
+    // Grow the buffer with the bytes actually received instead of allocating the claimed length.
+    let mut bytes = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+/// Runs the worker's side of the handshake, checking that the coordinator knows `secret`.
+pub(crate) fn authenticate_coordinator(
+    stream: &mut (impl Read + Write),
+    secret: &[u8],
+) -> Result<(), SP1DistributedError> {
+    let nonce = random_nonce();
+    stream.write_all(&nonce)?;
+    stream.flush()?;
+
+    let mut tag = [0u8; AUTH_LEN];
+    let mut coordinator_nonce = [0u8; AUTH_LEN];
+    stream.read_exact(&mut tag)?;
+    stream.read_exact(&mut coordinator_nonce)?;
+    if !tags_equal(&tag, &auth_tag(b"coordinator", secret, &nonce)) {
+        return Err(SP1DistributedError::Unauthenticated);
+    }
+
+    stream.write_all(&auth_tag(b"worker", secret, &coordinator_nonce))?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Runs the coordinator's side of the handshake, checking that the worker knows `secret`.
+pub(crate) fn authenticate_worker(
+    stream: &mut (impl Read + Write),
+    secret: &[u8],
+) -> Result<(), SP1DistributedError> {
+    let mut worker_nonce = [0u8; AUTH_LEN];
+    stream.read_exact(&mut worker_nonce)?;
+
+    let nonce = random_nonce();
+    stream.write_all(&auth_tag(b"coordinator", secret, &worker_nonce))?;
+    stream.write_all(&nonce)?;
+    stream.flush()?;
+
+    // A worker which rejects the coordinator's tag hangs up instead of answering.
+    let mut tag = [0u8; AUTH_LEN];
+    stream.read_exact(&mut tag).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof | io::ErrorKind::ConnectionReset => {
+            SP1DistributedError::Unauthenticated
+        }
+        _ => e.into(),
+    })?;
+    if !tags_equal(&tag, &auth_tag(b"worker", secret, &nonce)) {
+        return Err(SP1DistributedError::Unauthenticated);
+    }
+    Ok(())
+}
+
+fn random_nonce() -> [u8; AUTH_LEN] {
+    let mut nonce = [0u8; AUTH_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    nonce
+}
+
+/// The tag proving that the peer playing `role` knows `secret`, bound to the other peer's `nonce`.
+fn auth_tag(role: &[u8], secret: &[u8], nonce: &[u8; AUTH_LEN]) -> [u8; AUTH_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"sp1-distributed-");
+    hasher.update(role);
+    hasher.update((secret.len() as u64).to_le_bytes());
+    hasher.update(secret);
+    hasher.update(nonce);
+    hasher.finalize().into()
+}
+
+/// Compares two tags in constant time.
+fn tags_equal(a: &[u8; AUTH_LEN], b: &[u8; AUTH_LEN]) -> bool {
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Cursor,
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_handshake() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let worker = thread::spawn(move || {
+            (0..2)
+                .map(|_| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    authenticate_coordinator(&mut stream, b"secret").is_ok()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        authenticate_worker(&mut stream, b"secret").unwrap();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        assert!(matches!(
+            authenticate_worker(&mut stream, b"other secret"),
+            Err(SP1DistributedError::Unauthenticated)
+        ));
+        assert_eq!(worker.join().unwrap(), vec![true, false]);
+    }
+
+    #[test]
+    fn test_read_message_too_long() {
+        let mut bytes = (MAX_MESSAGE_LEN + 1).to_le_bytes().to_vec();
+        bytes.extend([0u8; 16]);
+        assert!(matches!(
+            read_message::<Vec<u8>>(&mut Cursor::new(bytes)),
+            Err(SP1DistributedError::Protocol(_))
+        ));
+
+        // A length longer than the data sent fails without allocating the claimed length.
+        let mut bytes = MAX_MESSAGE_LEN.to_le_bytes().to_vec();
+        bytes.extend([0u8; 16]);
+        assert!(matches!(
+            read_message::<Vec<u8>>(&mut Cursor::new(bytes)),
+            Err(SP1DistributedError::Io(_))
+        ));
+    }
+}
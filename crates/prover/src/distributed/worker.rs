@@ -0,0 +1,256 @@
+use std::{
+    io,
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+};
+
+use p3_baby_bear::BabyBear;
+use p3_challenger::FieldChallenger;
+use rayon::prelude::*;
+use sp1_core_executor::{ExecutionRecord, Program};
+use sp1_core_machine::{riscv::RiscvAir, utils::trace_checkpoint};
+use sp1_stark::{
+    air::{InteractionScope, PublicValues},
+    Challenge, Challenger, MachineProver, MachineProvingKey, MachineRecord, SP1ProverOpts,
+};
+
+use super::{
+    protocol::{
+        authenticate_coordinator, read_message, write_message, WorkerRequest, WorkerResponse,
+    },
+    SP1DistributedError,
+};
+use crate::{components::SP1ProverComponents, CoreSC, SP1Prover};
+
+/// The program a worker proves shards of, set up by [WorkerRequest::Setup].
+struct CoreSetup<C: SP1ProverComponents> {
+    program: Program,
+    pk: <C::CoreProver as MachineProver<CoreSC, RiscvAir<BabyBear>>>::DeviceProvingKey,
+    opts: SP1ProverOpts,
+}
+
+/// The state a worker keeps for a connected coordinator.
+struct WorkerSession<C: SP1ProverComponents> {
+    setup: Option<CoreSetup<C>>,
+    /// The records of the last traced checkpoint.
+    records: Vec<ExecutionRecord>,
+    /// The challenger and global permutation challenges shard proofs are opened with.
+    opening: Option<(Challenger<CoreSC>, Vec<Challenge<CoreSC>>)>,
+}
+
+impl<C: SP1ProverComponents> WorkerSession<C> {
+    fn setup(&self) -> Result<&CoreSetup<C>, SP1DistributedError> {
+        self.setup.as_ref().ok_or(SP1DistributedError::Protocol("the worker was not set up"))
+    }
+
+    /// Takes the records of the last traced checkpoint, assigns them the public values chosen by
+    /// the coordinator, and appends the deferred records split off after them.
+    fn take_records(
+        &mut self,
+        public_values: Vec<PublicValues<u32, u32>>,
+        deferred: Vec<ExecutionRecord>,
+    ) -> Result<Vec<ExecutionRecord>, SP1DistributedError> {
+        let mut records = std::mem::take(&mut self.records);
+        if records.len() != public_values.len() {
+            return Err(SP1DistributedError::Protocol(
+                "the public values do not match the traced records",
+            ));
+        }
+        for (record, public_values) in records.iter_mut().zip(public_values) {
+            record.public_values = public_values;
+        }
+        records.extend(deferred);
+        Ok(records)
+    }
+}
+
+impl<C: SP1ProverComponents> SP1Prover<C> {
+    /// Serves the coordinators connecting to `listener`, proving the work they distribute to this
+    /// process. Runs until accepting a connection fails.
+    ///
+    /// Only coordinators which know `secret` are served.
+    pub fn serve_worker(&self, listener: TcpListener, secret: &[u8]) -> io::Result<()> {
+        tracing::info!("worker listening on {}", listener.local_addr()?);
+        thread::scope(|s| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                s.spawn(move || {
+                    if let Err(e) = self.serve_worker_connection(stream, secret) {
+                        tracing::error!("coordinator connection failed: {}", e);
+                    }
+                });
+            }
+            Ok(())
+        })
+    }
+
+    /// Serves a single coordinator connected over `stream`, until it disconnects.
+    ///
+    /// Fails if the coordinator does not know `secret`.
+    pub fn serve_worker_connection(
+        &self,
+        mut stream: TcpStream,
+        secret: &[u8],
+    ) -> Result<(), SP1DistributedError> {
+        let peer = stream.peer_addr()?;
+        authenticate_coordinator(&mut stream, secret)?;
+        tracing::info!("coordinator {} connected", peer);
+        let mut session = WorkerSession::<C> { setup: None, records: Vec::new(), opening: None };
+        loop {
+            let request = match read_message(&mut stream) {
+                Ok(request) => request,
+                Err(SP1DistributedError::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    tracing::info!("coordinator {} disconnected", peer);
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            };
+            let response = self
+                .handle_worker_request(&mut session, request)
+                .unwrap_or_else(|e| WorkerResponse::Error(e.to_string()));
+            write_message(&mut stream, &response)?;
+        }
+    }
+
+    fn handle_worker_request(
+        &self,
+        session: &mut WorkerSession<C>,
+        request: WorkerRequest,
+    ) -> Result<WorkerResponse, SP1DistributedError> {
+        match request {
+            WorkerRequest::Setup { elf, opts } => {
+                if opts.security_profile != self.security_profile {
+                    return Err(SP1DistributedError::Protocol(
+                        "the worker has a different security profile",
+                    ));
+                }
+                let program = self
+                    .get_program(&elf)
+                    .map_err(|e| SP1DistributedError::Worker(e.to_string()))?;
+                let (pk, _) = self.core_prover.setup(&program);
+                session.setup = Some(CoreSetup { program, pk, opts });
+                session.records.clear();
+                session.opening = None;
+                Ok(WorkerResponse::Ready)
+            }
+            WorkerRequest::Trace { checkpoint } => {
+                let setup = session.setup()?;
+
+                // Trace the checkpoint and reconstruct the execution records.
+                let (mut records, _) = tracing::debug_span!("trace checkpoint").in_scope(|| {
+                    trace_checkpoint::<CoreSC>(
+                        setup.program.clone(),
+                        checkpoint.as_slice(),
+                        setup.opts.core_opts,
+                        self.core_shape_config.as_ref(),
+                    )
+                });
+
+                // Defer events that are too expensive to include in every shard. The coordinator
+                // decides which deferred shards are ready to be proven.
+                let mut deferred = ExecutionRecord::new(Arc::new(setup.program.clone()));
+                for record in records.iter_mut() {
+                    deferred.append(&mut record.defer());
+                }
+
+                let public_values = records.iter().map(|record| record.public_values).collect();
+                session.records = records;
+                Ok(WorkerResponse::Traced { public_values, deferred })
+            }
+            WorkerRequest::Commit { public_values, deferred } => {
+                let mut records = session.take_records(public_values, deferred)?;
+                self.fix_core_shapes(&mut records)?;
+
+                // Commit to each shard.
+                let commitments = records
+                    .par_iter()
+                    .map(|record| {
+                        let traces =
+                            self.core_prover.generate_traces(record, InteractionScope::Global);
+                        let public_values = record.public_values::<BabyBear>()
+                            [0..self.core_prover.num_pv_elts()]
+                            .to_vec();
+                        (self.core_prover.commit(record, traces).main_commit, public_values)
+                    })
+                    .collect();
+                Ok(WorkerResponse::Committed { commitments })
+            }
+            WorkerRequest::StartOpening { commitments } => {
+                let setup = session.setup()?;
+
+                // Observe the proving key and the commitments of every shard, as the coordinator
+                // would have.
+                let mut challenger = self.core_prover.config().challenger();
+                setup.pk.observe_into(&mut challenger);
+                for (commitment, public_values) in commitments {
+                    self.core_prover.observe(&mut challenger, commitment, &public_values);
+                }
+
+                // Sample the challenges of the global permutation argument.
+                let global_permutation_challenges: Vec<Challenge<CoreSC>> =
+                    (0..2).map(|_| challenger.sample_ext_element()).collect();
+                session.opening = Some((challenger, global_permutation_challenges));
+                Ok(WorkerResponse::Ready)
+            }
+            WorkerRequest::Prove { public_values, deferred } => {
+                let mut records = session.take_records(public_values, deferred)?;
+                let setup = session.setup()?;
+                let (challenger, global_permutation_challenges) = session
+                    .opening
+                    .as_ref()
+                    .ok_or(SP1DistributedError::Protocol("the worker did not start opening"))?;
+
+                // Generate the dependencies.
+                tracing::debug_span!("generate dependencies").in_scope(|| {
+                    self.core_prover.machine().generate_dependencies(
+                        &mut records,
+                        &setup.opts.core_opts,
+                        None,
+                    )
+                });
+                self.fix_core_shapes(&mut records)?;
+
+                // Prove each shard.
+                let proofs = records
+                    .into_par_iter()
+                    .map(|record| {
+                        let global_traces =
+                            self.core_prover.generate_traces(&record, InteractionScope::Global);
+                        let local_traces =
+                            self.core_prover.generate_traces(&record, InteractionScope::Local);
+                        let global_data = self.core_prover.commit(&record, global_traces);
+                        let local_data = self.core_prover.commit(&record, local_traces);
+                        self.core_prover
+                            .open(
+                                &setup.pk,
+                                Some(global_data),
+                                local_data,
+                                &mut challenger.clone(),
+                                global_permutation_challenges,
+                            )
+                            .map_err(|e| SP1DistributedError::Worker(e.to_string()))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(WorkerResponse::Proven { proofs })
+            }
+            WorkerRequest::ProveCompress { input, opts } => {
+                let (program, record, traces) = self.compress_record_and_traces(input, opts);
+                let (vk, proof) = self.prove_compress_record(&program, record, traces);
+                Ok(WorkerResponse::CompressProven { vk, proof })
+            }
+        }
+    }
+
+    /// Fixes the shapes of core records, if the prover is configured to.
+    fn fix_core_shapes(&self, records: &mut [ExecutionRecord]) -> Result<(), SP1DistributedError> {
+        if let Some(shape_config) = &self.core_shape_config {
+            for record in records.iter_mut() {
+                shape_config.fix_shape(record).map_err(|e| {
+                    SP1DistributedError::Worker(format!("failed to fix shard shape: {e:?}"))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
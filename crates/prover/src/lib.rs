@@ -12,7 +12,9 @@
 #![allow(clippy::collapsible_else_if)]
 
 pub mod build;
+pub mod checkpoint;
 pub mod components;
+pub mod distributed;
 pub mod shapes;
 pub mod types;
 pub mod utils;
@@ -26,10 +28,11 @@ use std::{
     path::Path,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        mpsc::sync_channel,
+        mpsc::{sync_channel, RecvTimeoutError},
         Arc, Mutex, OnceLock,
     },
     thread,
+    time::Duration,
 };
 
 use lru::LruCache;
@@ -41,11 +44,16 @@ use p3_baby_bear::BabyBear;
 use p3_challenger::CanObserve;
 use p3_field::{AbstractField, PrimeField, PrimeField32};
 use p3_matrix::dense::RowMajorMatrix;
-use sp1_core_executor::{ExecutionError, ExecutionReport, Executor, Program, SP1Context};
+use sp1_core_executor::{
+    ExecutionError, ExecutionReport, Executor, Program, ProofMonitor, ProofProgress, SP1Context,
+};
 use sp1_core_machine::{
     io::SP1Stdin,
     reduce::SP1ReduceProof,
-    riscv::{CoreShapeConfig, RiscvAir},
+    riscv::{
+        estimate::{estimate_with_context, EstimateError},
+        CoreShapeConfig, RiscvAir,
+    },
     utils::{concurrency::TurnBasedSync, SP1CoreProverError},
 };
 use sp1_primitives::{hash_deferred_proof, io::SP1PublicValues};
@@ -68,20 +76,26 @@ use sp1_recursion_compiler::{
     ir::{Builder, Witness},
 };
 use sp1_recursion_core::{
-    air::RecursionPublicValues, machine::RecursionAir, runtime::ExecutionRecord,
-    shape::RecursionShapeConfig, stark::BabyBearPoseidon2Outer, RecursionProgram,
-    Runtime as RecursionRuntime,
+    air::RecursionPublicValues,
+    machine::{RecursionAir, COMPRESS_DEGREE},
+    runtime::ExecutionRecord,
+    shape::RecursionShapeConfig,
+    stark::BabyBearPoseidon2Outer,
+    RecursionProgram, Runtime as RecursionRuntime,
 };
 pub use sp1_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
 use sp1_recursion_gnark_ffi::{groth16_bn254::Groth16Bn254Prover, plonk_bn254::PlonkBn254Prover};
 use sp1_stark::{air::InteractionScope, MachineProvingKey, ProofShape};
 use sp1_stark::{
-    air::PublicValues, baby_bear_poseidon2::BabyBearPoseidon2, Challenge, Challenger,
-    MachineProver, SP1CoreOpts, SP1ProverOpts, ShardProof, StarkGenericConfig, StarkVerifyingKey,
-    Val, Word, DIGEST_SIZE,
+    air::PublicValues,
+    baby_bear_poseidon2::{BabyBearPoseidon2, COMPRESSED_LOG_BLOWUP, DEFAULT_LOG_BLOWUP},
+    Challenge, Challenger, MachineProver, SP1CoreOpts, SP1ProverOpts, SecurityProfile, ShardProof,
+    StarkGenericConfig, StarkVerifyingKey, Val, Word, DIGEST_SIZE,
 };
 
 pub use types::*;
+
+use checkpoint::{Manifest, ProofCheckpoints, ProofStage, SP1CheckpointError};
 use utils::{sp1_committed_values_digest_bn254, sp1_vkey_digest_bn254, words_to_bytes};
 
 use components::{DefaultProverComponents, SP1ProverComponents};
@@ -97,7 +111,6 @@ pub type InnerSC = BabyBearPoseidon2;
 /// The configuration for the outer prover.
 pub type OuterSC = BabyBearPoseidon2Outer;
 
-const COMPRESS_DEGREE: usize = 3;
 const SHRINK_DEGREE: usize = 3;
 const WRAP_DEGREE: usize = 9;
 
@@ -117,6 +130,21 @@ pub type CompressAir<F> = RecursionAir<F, COMPRESS_DEGREE>;
 pub type ShrinkAir<F> = RecursionAir<F, SHRINK_DEGREE>;
 pub type WrapAir<F> = RecursionAir<F, WRAP_DEGREE>;
 
+/// A node of the compress tree handed from the trace generation workers to the provers.
+#[allow(clippy::large_enum_variant)]
+enum CompressWork {
+    /// The program, execution record and traces of a node that needs to be proven.
+    Prove(
+        Arc<RecursionProgram<BabyBear>>,
+        ExecutionRecord<BabyBear>,
+        Vec<(String, RowMajorMatrix<BabyBear>)>,
+    ),
+    /// The proof of a node checkpointed by an interrupted run.
+    Checkpointed(StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>),
+    /// A node skipped because proving was cancelled.
+    Cancelled,
+}
+
 /// A end-to-end prover implementation for the SP1 RISC-V zkVM.
 pub struct SP1Prover<C: SP1ProverComponents = DefaultProverComponents> {
     /// The machine used for proving the core step.
@@ -155,6 +183,12 @@ pub struct SP1Prover<C: SP1ProverComponents = DefaultProverComponents> {
     pub wrap_vk: OnceLock<StarkVerifyingKey<OuterSC>>,
 
     pub vk_verification: bool,
+
+    /// The security profile the FRI parameters of the machines are derived from.
+    pub security_profile: SecurityProfile,
+
+    /// The security profile the allowed verifying keys of the recursion programs were built for.
+    pub vk_map_security_profile: SecurityProfile,
 }
 
 impl<C: SP1ProverComponents> SP1Prover<C> {
@@ -166,18 +200,37 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
 
     /// Creates a new [SP1Prover] with lazily initialized components.
     pub fn uninitialized() -> Self {
+        Self::with_security_profile(SecurityProfile::default())
+    }
+
+    /// Creates a new [SP1Prover] with lazily initialized components, whose machines have the FRI
+    /// parameters of the security profile.
+    ///
+    /// The recursion programs, and so the shrink and wrap programs verifying them, are compiled
+    /// for these parameters. The allowed verifying keys of the recursion programs built into the
+    /// prover are the ones of the default profile: for any other profile, recursive proofs can only
+    /// be generated once the keys of the profile are set with [SP1Prover::with_vk_map], or with the
+    /// verification of these keys disabled by `VERIFY_VK=false`.
+    pub fn with_security_profile(security_profile: SecurityProfile) -> Self {
         // Initialize the provers.
-        let core_machine = RiscvAir::machine(CoreSC::default());
+        let core_machine =
+            RiscvAir::machine(CoreSC::with_security_profile(&security_profile, DEFAULT_LOG_BLOWUP));
         let core_prover = C::CoreProver::new(core_machine);
 
-        let compress_machine = CompressAir::compress_machine(InnerSC::default());
+        let compress_machine = CompressAir::compress_machine(InnerSC::with_security_profile(
+            &security_profile,
+            DEFAULT_LOG_BLOWUP,
+        ));
         let compress_prover = C::CompressProver::new(compress_machine);
 
         // TODO: Put the correct shrink and wrap machines here.
-        let shrink_machine = ShrinkAir::shrink_machine(InnerSC::compressed());
+        let shrink_machine = ShrinkAir::shrink_machine(InnerSC::with_security_profile(
+            &security_profile,
+            COMPRESSED_LOG_BLOWUP,
+        ));
         let shrink_prover = C::ShrinkProver::new(shrink_machine);
 
-        let wrap_machine = WrapAir::wrap_machine(OuterSC::default());
+        let wrap_machine = WrapAir::wrap_machine(OuterSC::with_security_profile(&security_profile));
         let wrap_prover = C::WrapProver::new(wrap_machine);
 
         let core_cache_size = NonZeroUsize::new(
@@ -237,7 +290,59 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             vk_verification,
             wrap_program: OnceLock::new(),
             wrap_vk: OnceLock::new(),
+            security_profile,
+            vk_map_security_profile: SecurityProfile::default(),
+        }
+    }
+
+    /// Sets the allowed verifying keys of the recursion programs, built for the security profile of
+    /// the prover by [shapes::build_vk_map], and enables their verification.
+    pub fn with_vk_map(
+        mut self,
+        allowed_vk_map: BTreeMap<<InnerSC as FieldHasher<BabyBear>>::Digest, usize>,
+    ) -> Self {
+        let (root, merkle_tree) = MerkleTree::commit(allowed_vk_map.keys().copied().collect());
+        self.vk_root = root;
+        self.vk_merkle_tree = merkle_tree;
+        self.allowed_vk_map = allowed_vk_map;
+        self.vk_verification = true;
+        self.vk_map_security_profile = self.security_profile;
+        self
+    }
+
+    /// The security levels achieved by the proofs of every stage.
+    pub fn security_levels(&self) -> SP1SecurityLevels {
+        let soundness = self.security_profile.soundness;
+        SP1SecurityLevels {
+            core: self.core_prover.config().fri_parameters().security_level(soundness),
+            compress: self.compress_prover.config().fri_parameters().security_level(soundness),
+            shrink: self.shrink_prover.config().fri_parameters().security_level(soundness),
+            wrap: self.wrap_prover.config().fri_parameters().security_level(soundness),
+        }
+    }
+
+    /// Checks that the options are for the security profile of the prover, since its machines can
+    /// only generate proofs for the latter.
+    fn check_core_security_profile(&self, opts: &SP1ProverOpts) -> Result<(), SP1CoreProverError> {
+        if opts.security_profile != self.security_profile {
+            return Err(SP1CoreProverError::SecurityProfileMismatch);
+        }
+        Ok(())
+    }
+
+    /// Checks that the options are for the security profile of the prover, and that the allowed
+    /// verifying keys of the recursion programs are known for it if they are verified.
+    fn check_recursion_security_profile(
+        &self,
+        opts: &SP1ProverOpts,
+    ) -> Result<(), SP1RecursionProverError> {
+        if opts.security_profile != self.security_profile {
+            return Err(SP1RecursionProverError::SecurityProfileMismatch);
+        }
+        if self.vk_verification && self.vk_map_security_profile != self.security_profile {
+            return Err(SP1RecursionProverError::MissingVkMap);
         }
+        Ok(())
     }
 
     /// Fully initializes the programs, proving keys, and verifying keys that are normally
@@ -276,6 +381,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         let opts = SP1CoreOpts::default();
         let mut runtime = Executor::with_context(program, opts, context);
         runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
         for (proof, vkey) in stdin.proofs.iter() {
             runtime.write_proof(proof.clone(), vkey.clone());
         }
@@ -283,16 +389,118 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         Ok((SP1PublicValues::from(&runtime.state.public_values_stream), runtime.report))
     }
 
+    /// Execute a program like [`Self::execute`], resuming from and saving execution snapshots.
+    ///
+    /// If `resume_from` is set, the execution continues from that snapshot and `stdin` is ignored.
+    /// If `snapshot_to` is set and the cycle limit of the context is exceeded, a snapshot is saved
+    /// there before returning the error, so that the execution can later be resumed with a higher
+    /// cycle limit.
+    #[instrument(name = "execute", level = "info", skip_all)]
+    pub fn execute_with_snapshots<'a>(
+        &'a self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        mut context: SP1Context<'a>,
+        resume_from: Option<&Path>,
+        snapshot_to: Option<&Path>,
+    ) -> Result<(SP1PublicValues, ExecutionReport), SP1ExecuteError> {
+        context.subproof_verifier.replace(Arc::new(self));
+        let program = self.get_program(elf).unwrap();
+        let opts = SP1CoreOpts::default();
+        let mut runtime = Executor::with_context(program, opts, context);
+        if let Some(path) = resume_from {
+            runtime.resume(path)?;
+        } else {
+            runtime.write_vecs(&stdin.buffer);
+            runtime.write_files(&stdin.files);
+            for (proof, vkey) in stdin.proofs.iter() {
+                runtime.write_proof(proof.clone(), vkey.clone());
+            }
+        }
+        if let Err(err) = runtime.run_fast() {
+            if let (ExecutionError::ExceededCycleLimit(_), Some(path)) = (&err, snapshot_to) {
+                runtime.snapshot(path)?;
+            }
+            return Err(err.into());
+        }
+        Ok((SP1PublicValues::from(&runtime.state.public_values_stream), runtime.report))
+    }
+
+    /// Estimate the cost of proving a program with the specified inputs, without generating any
+    /// traces.
+    #[instrument(name = "estimate", level = "info", skip_all)]
+    pub fn estimate<'a>(
+        &'a self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        opts: SP1CoreOpts,
+        mut context: SP1Context<'a>,
+    ) -> Result<SP1ProvingEstimate, EstimateError> {
+        context.subproof_verifier.replace(Arc::new(self));
+        let program = self.get_program(elf).unwrap();
+        let core =
+            estimate_with_context(program, stdin, opts, context, self.core_shape_config.as_ref())?;
+
+        // The first layer of recursion proves each shard and each deferred proof on its own, and
+        // the following layers reduce batches of proofs until a single proof remains.
+        let recursion_steps = core.num_shards() + stdin.proofs.len();
+        let mut compress_steps = 0;
+        let mut num_layer_inputs = recursion_steps;
+        while num_layer_inputs > 1 {
+            num_layer_inputs = num_layer_inputs.div_ceil(REDUCE_BATCH_SIZE);
+            compress_steps += num_layer_inputs;
+        }
+
+        Ok(SP1ProvingEstimate { core, recursion_steps, compress_steps })
+    }
+
     /// Generate shard proofs which split up and prove the valid execution of a RISC-V program with
     /// the core prover. Uses the provided context.
     #[instrument(name = "prove_core", level = "info", skip_all)]
     pub fn prove_core<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        opts: SP1ProverOpts,
+        context: SP1Context<'a>,
+    ) -> Result<SP1CoreProof, SP1CoreProverError> {
+        self.prove_core_inner(pk, stdin, opts, context, None)
+    }
+
+    /// Generate shard proofs like [Self::prove_core], checkpointing every shard proof and the
+    /// complete core proof to `checkpoints`.
+    ///
+    /// If the core proof was already checkpointed it is returned directly. Otherwise, the program
+    /// is executed again and only the shards without a checkpointed proof are proven.
+    #[instrument(name = "prove_core", level = "info", skip_all)]
+    pub fn prove_core_with_checkpoints<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        opts: SP1ProverOpts,
+        context: SP1Context<'a>,
+        checkpoints: &ProofCheckpoints,
+    ) -> Result<SP1CoreProof, SP1CheckpointError> {
+        checkpoints.check_manifest(&Manifest::new(pk.vk.hash_u32(), stdin, &opts, &context))?;
+        if let Some(proof) = checkpoints.load(ProofStage::Core) {
+            tracing::info!("resuming from checkpointed core proof");
+            return Ok(proof);
+        }
+        let proof =
+            self.prove_core_inner(pk, stdin, opts, context, Some(&checkpoints.core_shards_dir()))?;
+        checkpoints.save(ProofStage::Core, &proof)?;
+        Ok(proof)
+    }
+
+    fn prove_core_inner<'a>(
         &'a self,
         pk: &SP1ProvingKey,
         stdin: &SP1Stdin,
         opts: SP1ProverOpts,
         mut context: SP1Context<'a>,
+        shard_proof_dir: Option<&Path>,
     ) -> Result<SP1CoreProof, SP1CoreProverError> {
+        self.check_core_security_profile(&opts)?;
         context.subproof_verifier.replace(Arc::new(self));
         let program = self.get_program(&pk.elf).unwrap();
         let (proof, public_values_stream, cycles) = sp1_core_machine::utils::prove_with_context::<
@@ -308,6 +516,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             opts.core_opts,
             context,
             self.core_shape_config.as_ref(),
+            shard_proof_dir,
         )?;
         Self::check_for_high_cycles(cycles);
         let public_values = SP1PublicValues::from(&public_values_stream);
@@ -620,7 +829,6 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
     }
 
     /// Reduce shards proofs to a single shard proof using the recursion prover.
-    #[instrument(name = "compress", level = "info", skip_all)]
     pub fn compress(
         &self,
         vk: &SP1VerifyingKey,
@@ -628,6 +836,34 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         deferred_proofs: Vec<SP1ReduceProof<InnerSC>>,
         opts: SP1ProverOpts,
     ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        self.compress_with_monitor(vk, proof, deferred_proofs, opts, &ProofMonitor::default(), None)
+    }
+
+    /// Reduce shards proofs to a single shard proof using the recursion prover, reporting the
+    /// progress through the layers of the reduction tree to `monitor`.
+    ///
+    /// If `monitor` is cancelled, no new recursion programs are proven and
+    /// [SP1RecursionProverError::Cancelled] is returned once the work in flight has drained.
+    ///
+    /// If `checkpoints` is set, every proof of the reduction tree is checkpointed as soon as it is
+    /// generated, and checkpointed proofs from an interrupted run are reused instead of being
+    /// proven again.
+    #[instrument(name = "compress", level = "info", skip_all)]
+    pub fn compress_with_monitor(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1CoreProof,
+        deferred_proofs: Vec<SP1ReduceProof<InnerSC>>,
+        opts: SP1ProverOpts,
+        monitor: &ProofMonitor,
+        checkpoints: Option<&ProofCheckpoints>,
+    ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        self.check_recursion_security_profile(&opts)?;
+        if let Some(proof) = checkpoints.and_then(|c| c.load(ProofStage::Compress)) {
+            tracing::info!("resuming from checkpointed compressed proof");
+            return Ok(proof);
+        }
+
         // The batch size for reducing two layers of recursion.
         let batch_size = REDUCE_BATCH_SIZE;
         // The batch size for reducing the first layer of recursion.
@@ -636,12 +872,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         let shard_proofs = &proof.proof.0;
 
         // Get the leaf challenger.
-        let mut leaf_challenger = self.core_prover.config().challenger();
-        vk.vk.observe_into(&mut leaf_challenger);
-        shard_proofs.iter().for_each(|proof| {
-            leaf_challenger.observe(proof.commitment.global_main_commit);
-            leaf_challenger.observe_slice(&proof.public_values[0..self.core_prover.num_pv_elts()]);
-        });
+        let leaf_challenger = self.leaf_challenger(vk, shard_proofs);
 
         // Generate the first layer inputs.
         let first_layer_inputs = self.get_first_layer_inputs(
@@ -687,13 +918,9 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             // Spawn workers who generate the records and traces.
             let record_and_trace_sync = Arc::new(TurnBasedSync::new());
             let (record_and_trace_tx, record_and_trace_rx) =
-                sync_channel::<(
-                    usize,
-                    usize,
-                    Arc<RecursionProgram<BabyBear>>,
-                    ExecutionRecord<BabyBear>,
-                    Vec<(String, RowMajorMatrix<BabyBear>)>,
-                )>(opts.recursion_opts.records_and_traces_channel_capacity);
+                sync_channel::<(usize, usize, CompressWork)>(
+                    opts.recursion_opts.records_and_traces_channel_capacity,
+                );
             let record_and_trace_tx = Arc::new(Mutex::new(record_and_trace_tx));
             let record_and_trace_rx = Arc::new(Mutex::new(record_and_trace_rx));
             let input_rx = Arc::new(Mutex::new(input_rx));
@@ -707,68 +934,29 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                     loop {
                         let received = { input_rx.lock().unwrap().recv() };
                         if let Ok((index, height, input)) = received {
-                            // Get the program and witness stream.
-                            let (program, witness_stream) = tracing::debug_span!(
-                                "get program and witness stream"
-                            )
-                            .in_scope(|| match input {
-                                SP1CircuitWitness::Core(input) => {
-                                    let mut witness_stream = Vec::new();
-                                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
-                                    (self.recursion_program(&input), witness_stream)
-                                }
-                                SP1CircuitWitness::Deferred(input) => {
-                                    let mut witness_stream = Vec::new();
-                                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
-                                    (self.deferred_program(&input), witness_stream)
-                                }
-                                SP1CircuitWitness::Compress(input) => {
-                                    let mut witness_stream = Vec::new();
-
-                                    let input_with_merkle = self.make_merkle_proofs(input);
-
-                                    Witnessable::<InnerConfig>::write(
-                                        &input_with_merkle,
-                                        &mut witness_stream,
-                                    );
-
-                                    (self.compress_program(&input_with_merkle), witness_stream)
-                                }
-                            });
-
-                            // Execute the runtime.
-                            let record = tracing::debug_span!("execute runtime").in_scope(|| {
-                                let mut runtime =
-                                    RecursionRuntime::<Val<InnerSC>, Challenge<InnerSC>, _>::new(
-                                        program.clone(),
-                                        self.compress_prover.config().perm.clone(),
-                                    );
-                                runtime.witness_stream = witness_stream.into();
-                                runtime
-                                    .run()
-                                    .map_err(|e| {
-                                        SP1RecursionProverError::RuntimeError(e.to_string())
-                                    })
+                            // If proving was cancelled, or the proof of this node was checkpointed
+                            // by an interrupted run, pass the input on without doing any work.
+                            let shortcut = if monitor.is_cancelled() {
+                                Some(CompressWork::Cancelled)
+                            } else {
+                                checkpoints
+                                    .and_then(|c| c.load_compress_proof(index))
+                                    .map(|(vk, proof)| CompressWork::Checkpointed(vk, proof))
+                            };
+                            if let Some(work) = shortcut {
+                                record_and_trace_sync.wait_for_turn(index);
+                                record_and_trace_tx
+                                    .lock()
+                                    .unwrap()
+                                    .send((index, height, work))
                                     .unwrap();
-                                runtime.record
-                            });
+                                record_and_trace_sync.advance_turn();
+                                continue;
+                            }
 
-                            // Generate the dependencies.
-                            let mut records = vec![record];
-                            tracing::debug_span!("generate dependencies").in_scope(|| {
-                                self.compress_prover.machine().generate_dependencies(
-                                    &mut records,
-                                    &opts.recursion_opts,
-                                    None,
-                                )
-                            });
-
-                            // Generate the traces.
-                            let record = records.into_iter().next().unwrap();
-                            let traces = tracing::debug_span!("generate traces").in_scope(|| {
-                                self.compress_prover
-                                    .generate_traces(&record, InteractionScope::Local)
-                            });
+                            // Execute the program and generate the record and traces.
+                            let (program, record, traces) =
+                                self.compress_record_and_traces(input, opts);
 
                             // Wait for our turn to update the state.
                             record_and_trace_sync.wait_for_turn(index);
@@ -777,7 +965,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                             record_and_trace_tx
                                 .lock()
                                 .unwrap()
-                                .send((index, height, program, record, traces))
+                                .send((index, height, CompressWork::Prove(program, record, traces)))
                                 .unwrap();
 
                             // Advance the turn.
@@ -807,64 +995,41 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                     let _span = span.enter();
                     loop {
                         let received = { record_and_trace_rx.lock().unwrap().recv() };
-                        if let Ok((index, height, program, record, traces)) = received {
-                            tracing::debug_span!("batch").in_scope(|| {
-                                // Get the keys.
-                                let (pk, vk) = tracing::debug_span!("Setup compress program")
-                                    .in_scope(|| self.compress_prover.setup(&program));
-
-                                // Observe the proving key.
-                                let mut challenger = self.compress_prover.config().challenger();
-                                tracing::debug_span!("observe proving key").in_scope(|| {
-                                    pk.observe_into(&mut challenger);
-                                });
-
-                                #[cfg(feature = "debug")]
-                                self.compress_prover.debug_constraints(
-                                    &pk.to_host(),
-                                    vec![record.clone()],
-                                    &mut challenger.clone(),
-                                );
-
-                                // Commit to the record and traces.
-                                let local_data = tracing::debug_span!("commit")
-                                    .in_scope(|| self.compress_prover.commit(&record, traces));
-
-                                // Observe the commitment.
-                                tracing::debug_span!("observe public values").in_scope(|| {
-                                    challenger.observe_slice(
-                                        &local_data.public_values[0..self.compress_prover.num_pv_elts()],
-                                    );
-                                });
+                        if let Ok((index, height, work)) = received {
+                            let (program, record, traces) = match work {
+                                CompressWork::Prove(program, record, traces)
+                                    if !monitor.is_cancelled() =>
+                                {
+                                    (program, record, traces)
+                                }
+                                // Checkpointed proofs are passed on as if they were just proven.
+                                CompressWork::Checkpointed(vk, proof) => {
+                                    prover_sync.wait_for_turn(index);
+                                    proofs_tx
+                                        .lock()
+                                        .unwrap()
+                                        .send((index, height, vk, proof))
+                                        .unwrap();
+                                    prover_sync.advance_turn();
+                                    continue;
+                                }
+                                // If proving was cancelled, skip proving but still take our turn.
+                                _ => {
+                                    prover_sync.wait_for_turn(index);
+                                    prover_sync.advance_turn();
+                                    continue;
+                                }
+                            };
 
+                            tracing::debug_span!("batch").in_scope(|| {
                                 // Generate the proof.
-                                let proof = tracing::debug_span!("open").in_scope(|| {
-                                    self.compress_prover
-                                        .open(
-                                            &pk,
-                                            None,
-                                            local_data,
-                                            &mut challenger,
-                                            &[
-                                                <BabyBearPoseidon2 as StarkGenericConfig>::Challenge::zero(),
-                                                <BabyBearPoseidon2 as StarkGenericConfig>::Challenge::zero(),
-                                            ],
-                                        )
-                                        .unwrap()
-                                });
+                                let (vk, proof) =
+                                    self.prove_compress_record(&program, record, traces);
 
-                                // Verify the proof.
-                                #[cfg(feature = "debug")]
-                                self.compress_prover
-                                    .machine()
-                                    .verify(
-                                        &vk,
-                                        &sp1_stark::MachineProof {
-                                            shard_proofs: vec![proof.clone()],
-                                        },
-                                        &mut self.compress_prover.config().challenger(),
-                                    )
-                                    .unwrap();
+                                // Checkpoint the proof.
+                                if let Some(checkpoints) = checkpoints {
+                                    checkpoints.save_compress_proof(index, &vk, &proof);
+                                }
 
                                 // Wait for our turn to update the state.
                                 prover_sync.wait_for_turn(index);
@@ -897,9 +1062,26 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                         StarkVerifyingKey<InnerSC>,
                         ShardProof<InnerSC>,
                     )> = Vec::new();
+                    let mut reported_height = None;
                     loop {
-                        let received = { proofs_rx.lock().unwrap().recv() };
+                        // Poll for proofs so that a cancellation is noticed even if no more proofs
+                        // are coming.
+                        let received =
+                            { proofs_rx.lock().unwrap().recv_timeout(Duration::from_millis(100)) };
+                        if let Err(RecvTimeoutError::Timeout) = received {
+                            if monitor.is_cancelled() {
+                                break;
+                            }
+                            continue;
+                        }
                         if let Ok((index, height, vk, proof)) = received {
+                            if reported_height.map_or(true, |h| h < height) {
+                                reported_height = Some(height);
+                                monitor.report(ProofProgress::RecursionLayer {
+                                    layer: height + 1,
+                                    num_layers: expected_height + 1,
+                                });
+                            }
                             batch.push((index, height, vk, proof));
 
                             // Compute whether we've reached the root of the tree.
@@ -969,11 +1151,166 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             }
             handle.join().unwrap();
 
+            if monitor.is_cancelled() {
+                return Err(SP1RecursionProverError::Cancelled);
+            }
             let (_, _, vk, proof) = proofs_rx.lock().unwrap().recv().unwrap();
-            (vk, proof)
+            Ok((vk, proof))
+        })?;
+
+        let proof = SP1ReduceProof { vk, proof };
+        if let Some(checkpoints) = checkpoints {
+            if let Err(e) = checkpoints.save(ProofStage::Compress, &proof) {
+                tracing::warn!("failed to checkpoint compressed proof: {}", e);
+            }
+        }
+        Ok(proof)
+    }
+
+    /// The challenger of the core proof after observing the commitments of all its shards.
+    fn leaf_challenger(
+        &self,
+        vk: &SP1VerifyingKey,
+        shard_proofs: &[ShardProof<CoreSC>],
+    ) -> Challenger<InnerSC> {
+        let mut leaf_challenger = self.core_prover.config().challenger();
+        vk.vk.observe_into(&mut leaf_challenger);
+        shard_proofs.iter().for_each(|proof| {
+            leaf_challenger.observe(proof.commitment.global_main_commit);
+            leaf_challenger.observe_slice(&proof.public_values[0..self.core_prover.num_pv_elts()]);
+        });
+        leaf_challenger
+    }
+
+    /// Executes the recursion program that verifies `input`, and generates the execution record
+    /// and traces to prove.
+    fn compress_record_and_traces(
+        &self,
+        input: SP1CircuitWitness,
+        opts: SP1ProverOpts,
+    ) -> (
+        Arc<RecursionProgram<BabyBear>>,
+        ExecutionRecord<BabyBear>,
+        Vec<(String, RowMajorMatrix<BabyBear>)>,
+    ) {
+        // Get the program and witness stream.
+        let (program, witness_stream) = tracing::debug_span!("get program and witness stream")
+            .in_scope(|| match input {
+                SP1CircuitWitness::Core(input) => {
+                    let mut witness_stream = Vec::new();
+                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
+                    (self.recursion_program(&input), witness_stream)
+                }
+                SP1CircuitWitness::Deferred(input) => {
+                    let mut witness_stream = Vec::new();
+                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
+                    (self.deferred_program(&input), witness_stream)
+                }
+                SP1CircuitWitness::Compress(input) => {
+                    let mut witness_stream = Vec::new();
+
+                    let input_with_merkle = self.make_merkle_proofs(input);
+
+                    Witnessable::<InnerConfig>::write(&input_with_merkle, &mut witness_stream);
+
+                    (self.compress_program(&input_with_merkle), witness_stream)
+                }
+            });
+
+        // Execute the runtime.
+        let record = tracing::debug_span!("execute runtime").in_scope(|| {
+            let mut runtime = RecursionRuntime::<Val<InnerSC>, Challenge<InnerSC>, _>::new(
+                program.clone(),
+                self.compress_prover.config().perm.clone(),
+            );
+            runtime.witness_stream = witness_stream.into();
+            runtime
+                .run()
+                .map_err(|e| SP1RecursionProverError::RuntimeError(e.to_string()))
+                .unwrap();
+            runtime.record
+        });
+
+        // Generate the dependencies.
+        let mut records = vec![record];
+        tracing::debug_span!("generate dependencies").in_scope(|| {
+            self.compress_prover.machine().generate_dependencies(
+                &mut records,
+                &opts.recursion_opts,
+                None,
+            )
         });
 
-        Ok(SP1ReduceProof { vk, proof })
+        // Generate the traces.
+        let record = records.into_iter().next().unwrap();
+        let traces = tracing::debug_span!("generate traces")
+            .in_scope(|| self.compress_prover.generate_traces(&record, InteractionScope::Local));
+
+        (program, record, traces)
+    }
+
+    /// Proves the execution record of a recursion program of the compress tree.
+    fn prove_compress_record(
+        &self,
+        program: &RecursionProgram<BabyBear>,
+        record: ExecutionRecord<BabyBear>,
+        traces: Vec<(String, RowMajorMatrix<BabyBear>)>,
+    ) -> (StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>) {
+        // Get the keys.
+        let (pk, vk) = tracing::debug_span!("Setup compress program")
+            .in_scope(|| self.compress_prover.setup(program));
+
+        // Observe the proving key.
+        let mut challenger = self.compress_prover.config().challenger();
+        tracing::debug_span!("observe proving key").in_scope(|| {
+            pk.observe_into(&mut challenger);
+        });
+
+        #[cfg(feature = "debug")]
+        self.compress_prover.debug_constraints(
+            &pk.to_host(),
+            vec![record.clone()],
+            &mut challenger.clone(),
+        );
+
+        // Commit to the record and traces.
+        let local_data = tracing::debug_span!("commit")
+            .in_scope(|| self.compress_prover.commit(&record, traces));
+
+        // Observe the commitment.
+        tracing::debug_span!("observe public values").in_scope(|| {
+            challenger
+                .observe_slice(&local_data.public_values[0..self.compress_prover.num_pv_elts()]);
+        });
+
+        // Generate the proof.
+        let proof = tracing::debug_span!("open").in_scope(|| {
+            self.compress_prover
+                .open(
+                    &pk,
+                    None,
+                    local_data,
+                    &mut challenger,
+                    &[
+                        <BabyBearPoseidon2 as StarkGenericConfig>::Challenge::zero(),
+                        <BabyBearPoseidon2 as StarkGenericConfig>::Challenge::zero(),
+                    ],
+                )
+                .unwrap()
+        });
+
+        // Verify the proof.
+        #[cfg(feature = "debug")]
+        self.compress_prover
+            .machine()
+            .verify(
+                &vk,
+                &sp1_stark::MachineProof { shard_proofs: vec![proof.clone()] },
+                &mut self.compress_prover.config().challenger(),
+            )
+            .unwrap();
+
+        (vk, proof)
     }
 
     /// Wrap a reduce proof into a STARK proven over a SNARK-friendly field.
@@ -983,6 +1320,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         reduced_proof: SP1ReduceProof<InnerSC>,
         opts: SP1ProverOpts,
     ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        self.check_recursion_security_profile(&opts)?;
         // Make the compress proof.
         let SP1ReduceProof { vk: compressed_vk, proof: compressed_proof } = reduced_proof;
         let input = SP1CompressWitnessValues {
@@ -1030,6 +1368,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         compressed_proof: SP1ReduceProof<InnerSC>,
         opts: SP1ProverOpts,
     ) -> Result<SP1ReduceProof<OuterSC>, SP1RecursionProverError> {
+        self.check_recursion_security_profile(&opts)?;
         let SP1ReduceProof { vk: compressed_vk, proof: compressed_proof } = compressed_proof;
         let input = SP1CompressWitnessValues {
             vks_and_proofs: vec![(compressed_vk, compressed_proof)],
@@ -1368,10 +1707,17 @@ pub mod tests {
         tracing::info!("checking vkey hash bn254");
         let vk_digest_bn254 = sp1_vkey_digest_bn254(&wrapped_bn254_proof);
         assert_eq!(vk_digest_bn254, vk.hash_bn254());
+        assert_eq!(
+            vk.bytes32(),
+            format!("0x{:0>64}", vk_digest_bn254.as_canonical_biguint().to_str_radix(16))
+        );
 
         tracing::info!("Test the outer Plonk circuit");
-        let (constraints, witness) =
-            build_constraints_and_witness(&wrapped_bn254_proof.vk, &wrapped_bn254_proof.proof);
+        let (constraints, witness) = build_constraints_and_witness(
+            &wrapped_bn254_proof.vk,
+            &wrapped_bn254_proof.proof,
+            &prover.security_profile,
+        );
         PlonkBn254Prover::test(constraints, witness);
         tracing::info!("Circuit test succeeded");
 
@@ -1383,6 +1729,7 @@ pub mod tests {
         let artifacts_dir = try_build_plonk_bn254_artifacts_dev(
             &wrapped_bn254_proof.vk,
             &wrapped_bn254_proof.proof,
+            &prover.security_profile,
         );
         let plonk_bn254_proof =
             prover.wrap_plonk_bn254(wrapped_bn254_proof.clone(), &artifacts_dir);
@@ -1394,6 +1741,7 @@ pub mod tests {
         let artifacts_dir = try_build_groth16_bn254_artifacts_dev(
             &wrapped_bn254_proof.vk,
             &wrapped_bn254_proof.proof,
+            &prover.security_profile,
         );
         let groth16_bn254_proof = prover.wrap_groth16_bn254(wrapped_bn254_proof, &artifacts_dir);
         println!("{:?}", groth16_bn254_proof);
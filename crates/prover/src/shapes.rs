@@ -17,7 +17,7 @@ use sp1_recursion_circuit::machine::{
     SP1DeferredWitnessValues, SP1RecursionShape, SP1RecursionWitnessValues,
 };
 use sp1_recursion_core::{shape::RecursionShapeConfig, RecursionProgram};
-use sp1_stark::{MachineProver, ProofShape, DIGEST_SIZE};
+use sp1_stark::{MachineProver, ProofShape, SecurityProfile, DIGEST_SIZE};
 
 use crate::{components::SP1ProverComponents, CompressAir, HashableKey, SP1Prover};
 
@@ -45,14 +45,17 @@ pub enum VkBuildError {
     Bincode(#[from] bincode::Error),
 }
 
+/// Builds the verifying keys of the recursion programs of every shape, compiled for the security
+/// profile.
 pub fn build_vk_map<C: SP1ProverComponents>(
+    security_profile: SecurityProfile,
     reduce_batch_size: usize,
     dummy: bool,
     num_compiler_workers: usize,
     num_setup_workers: usize,
     indices: Option<Vec<usize>>,
 ) -> (BTreeSet<[BabyBear; DIGEST_SIZE]>, Vec<usize>, usize) {
-    let mut prover = SP1Prover::<C>::new();
+    let mut prover = SP1Prover::<C>::with_security_profile(security_profile);
     prover.vk_verification = !dummy;
     let core_shape_config = prover.core_shape_config.as_ref().expect("core shape config not found");
     let recursion_shape_config =
@@ -177,6 +180,7 @@ pub fn build_vk_map<C: SP1ProverComponents>(
 
 pub fn build_vk_map_to_file<C: SP1ProverComponents>(
     build_dir: PathBuf,
+    security_profile: SecurityProfile,
     reduce_batch_size: usize,
     dummy: bool,
     num_compiler_workers: usize,
@@ -189,6 +193,7 @@ pub fn build_vk_map_to_file<C: SP1ProverComponents>(
     tracing::info!("Building vk set");
 
     let (vk_set, _, _) = build_vk_map::<C>(
+        security_profile,
         reduce_batch_size,
         dummy,
         num_compiler_workers,
@@ -293,6 +298,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::REDUCE_BATCH_SIZE;
 
     #[test]
     #[ignore]
@@ -306,4 +312,20 @@ mod tests {
 
         println!("Number of compress shapes: {}", all_shapes.len());
     }
+
+    /// Changing the chips of the machines changes their shapes, which requires regenerating the vk
+    /// maps with `make build-vk-maps`.
+    #[test]
+    fn test_dummy_vk_map_matches_shapes() {
+        let core_shape_config = CoreShapeConfig::default();
+        let recursion_shape_config = RecursionShapeConfig::default();
+        let dummy_vk_map = SP1ProofShape::dummy_vk_map(
+            &core_shape_config,
+            &recursion_shape_config,
+            REDUCE_BATCH_SIZE,
+        );
+        let checked_in: BTreeMap<[BabyBear; DIGEST_SIZE], usize> =
+            bincode::deserialize(include_bytes!("../dummy_vk_map.bin")).unwrap();
+        assert_eq!(checked_in, dummy_vk_map, "dummy_vk_map.bin is stale");
+    }
 }
@@ -4,10 +4,11 @@ use anyhow::Result;
 use p3_baby_bear::BabyBear;
 use p3_bn254_fr::Bn254Fr;
 use p3_commit::{Pcs, TwoAdicMultiplicativeCoset};
-use p3_field::{AbstractField, PrimeField, PrimeField32, TwoAdicField};
+use p3_field::PrimeField32;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use sp1_core_machine::{io::SP1Stdin, reduce::SP1ReduceProof};
-use sp1_primitives::{io::SP1PublicValues, poseidon2_hash};
+use sp1_core_executor::{ExecutionError, SnapshotError};
+use sp1_core_machine::{io::SP1Stdin, reduce::SP1ReduceProof, riscv::estimate::CoreEstimate};
+use sp1_primitives::io::SP1PublicValues;
 
 use sp1_recursion_circuit::machine::{
     SP1CompressWitnessValues, SP1DeferredWitnessValues, SP1RecursionWitnessValues,
@@ -15,7 +16,10 @@ use sp1_recursion_circuit::machine::{
 
 use sp1_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
 
-use sp1_stark::{ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey, DIGEST_SIZE};
+use sp1_stark::{
+    babybears_to_bytes32, SecurityLevel, ShardProof, StarkGenericConfig, StarkProvingKey,
+    StarkVerifyingKey, DIGEST_SIZE,
+};
 use thiserror::Error;
 
 use crate::{
@@ -51,8 +55,8 @@ pub trait HashableKey {
     }
 
     fn bytes32(&self) -> String {
-        let vkey_digest_bn254 = self.hash_bn254();
-        format!("0x{:0>64}", vkey_digest_bn254.as_canonical_biguint().to_str_radix(16))
+        let bytes = babybears_to_bytes32(&self.hash_babybear());
+        format!("0x{}", bytes.iter().map(|byte| format!("{byte:02x}")).collect::<String>())
     }
 
     /// Hash the key into a digest of bytes elements.
@@ -77,21 +81,7 @@ where
     <SC::Pcs as Pcs<SC::Challenge, SC::Challenger>>::Commitment: AsRef<[BabyBear; DIGEST_SIZE]>,
 {
     fn hash_babybear(&self) -> [BabyBear; DIGEST_SIZE] {
-        let prep_domains = self.chip_information.iter().map(|(_, domain, _)| domain);
-        let num_inputs = DIGEST_SIZE + 1 + (4 * prep_domains.len());
-        let mut inputs = Vec::with_capacity(num_inputs);
-        inputs.extend(self.commit.as_ref());
-        inputs.push(self.pc_start);
-        for domain in prep_domains {
-            inputs.push(BabyBear::from_canonical_usize(domain.log_n));
-            let size = 1 << domain.log_n;
-            inputs.push(BabyBear::from_canonical_usize(size));
-            let g = BabyBear::two_adic_generator(domain.log_n);
-            inputs.push(domain.shift);
-            inputs.push(g);
-        }
-
-        poseidon2_hash(inputs)
+        StarkVerifyingKey::hash_babybear(self)
     }
 
     fn hash_u32(&self) -> [u32; 8] {
@@ -198,6 +188,55 @@ impl ProofSystem {
     }
 }
 
+/// The security levels achieved by the proofs of every stage of an [SP1Prover](crate::SP1Prover),
+/// in the soundness regime of its security profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SP1SecurityLevels {
+    /// The level of the core shard proofs.
+    pub core: SecurityLevel,
+    /// The level of the recursion and compress proofs.
+    pub compress: SecurityLevel,
+    /// The level of the shrink proof.
+    pub shrink: SecurityLevel,
+    /// The level of the wrap proof.
+    pub wrap: SecurityLevel,
+}
+
+impl SP1SecurityLevels {
+    /// The level of a core proof.
+    pub fn core_proof(&self) -> SecurityLevel {
+        self.core
+    }
+
+    /// The level of a compressed proof, the lowest level of the core and compress proofs.
+    pub fn compressed_proof(&self) -> SecurityLevel {
+        min_level([self.core, self.compress])
+    }
+
+    /// The level of the STARK proofs a PLONK or Groth16 proof is wrapping, the lowest level of
+    /// every stage.
+    pub fn wrapped_proof(&self) -> SecurityLevel {
+        min_level([self.core, self.compress, self.shrink, self.wrap])
+    }
+}
+
+fn min_level<const N: usize>(levels: [SecurityLevel; N]) -> SecurityLevel {
+    levels.into_iter().min_by_key(|level| level.bits).unwrap()
+}
+
+/// An estimate of the cost of proving an execution, computed without generating any traces.
+#[derive(Debug, Clone)]
+pub struct SP1ProvingEstimate {
+    /// The predicted shards of the core proof.
+    pub core: CoreEstimate,
+    /// The number of proofs in the first layer of recursion, one for each shard and each deferred
+    /// proof.
+    pub recursion_steps: usize,
+    /// The number of proofs needed to reduce the first layer of recursion into a single
+    /// compressed proof.
+    pub compress_steps: usize,
+}
+
 /// A proof that can be reduced along with other proofs into one proof.
 #[derive(Serialize, Deserialize, Clone)]
 pub enum SP1ReduceProofWrapper {
@@ -205,12 +244,28 @@ pub enum SP1ReduceProofWrapper {
     Recursive(SP1ReduceProof<InnerSC>),
 }
 
+/// An error that can occur while executing a program with snapshots.
+#[derive(Error, Debug)]
+pub enum SP1ExecuteError {
+    #[error(transparent)]
+    Execution(#[from] ExecutionError),
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+}
+
 #[derive(Error, Debug)]
 pub enum SP1RecursionProverError {
     #[error("Runtime error: {0}")]
     RuntimeError(String),
+    #[error("Proving was cancelled")]
+    Cancelled,
+    #[error("The options are for a different security profile than the prover")]
+    SecurityProfileMismatch,
+    #[error("The allowed recursion vks are not known for the security profile of the prover")]
+    MissingVkMap,
 }
 
+#[derive(Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum SP1CircuitWitness {
     Core(SP1RecursionWitnessValues<CoreSC>),
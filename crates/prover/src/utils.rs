@@ -13,10 +13,8 @@ use p3_symmetric::CryptographicHasher;
 use sp1_core_executor::{Executor, Program};
 use sp1_core_machine::{io::SP1Stdin, reduce::SP1ReduceProof};
 use sp1_recursion_circuit::machine::RootPublicValues;
-use sp1_recursion_core::{
-    air::{RecursionPublicValues, NUM_PV_ELMS_TO_HASH},
-    stark::BabyBearPoseidon2Outer,
-};
+pub use sp1_recursion_core::air::recursion_public_values_digest;
+use sp1_recursion_core::{air::RecursionPublicValues, stark::BabyBearPoseidon2Outer};
 use sp1_stark::{baby_bear_poseidon2::MyHash as InnerHash, SP1CoreOpts, Word};
 
 use crate::{InnerSC, SP1CoreProofData};
@@ -34,15 +32,6 @@ pub fn sp1_vkey_digest_bn254(proof: &SP1ReduceProof<BabyBearPoseidon2Outer>) ->
 }
 
 /// Compute the digest of the public values.
-pub fn recursion_public_values_digest(
-    config: &InnerSC,
-    public_values: &RecursionPublicValues<BabyBear>,
-) -> [BabyBear; 8] {
-    let hash = InnerHash::new(config.perm.clone());
-    let pv_array = public_values.as_array();
-    hash.hash_slice(&pv_array[0..NUM_PV_ELMS_TO_HASH])
-}
-
 pub fn root_public_values_digest(
     config: &InnerSC,
     public_values: &RootPublicValues<BabyBear>,
@@ -104,6 +93,7 @@ pub fn get_cycles(elf: &[u8], stdin: &SP1Stdin) -> u64 {
     let program = Program::from(elf).unwrap();
     let mut runtime = Executor::new(program, SP1CoreOpts::default());
     runtime.write_vecs(&stdin.buffer);
+    runtime.write_files(&stdin.files);
     runtime.run_fast().unwrap();
     runtime.state.global_clk
 }
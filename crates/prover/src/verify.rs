@@ -389,17 +389,7 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         public_values: &SP1PublicValues,
         build_dir: &Path,
     ) -> Result<()> {
-        let prover = PlonkBn254Prover::new();
-
-        let vkey_hash = BigUint::from_str(&proof.public_inputs[0])?;
-        let committed_values_digest = BigUint::from_str(&proof.public_inputs[1])?;
-
-        // Verify the proof with the corresponding public inputs.
-        prover.verify(proof, &vkey_hash, &committed_values_digest, build_dir);
-
-        verify_plonk_bn254_public_inputs(vk, public_values, &proof.public_inputs)?;
-
-        Ok(())
+        verify_plonk_bn254_proof(proof, vk, public_values, build_dir)
     }
 
     /// Verifies a Groth16 proof using the circuit artifacts in the build directory.
@@ -410,18 +400,54 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         public_values: &SP1PublicValues,
         build_dir: &Path,
     ) -> Result<()> {
-        let prover = Groth16Bn254Prover::new();
+        verify_groth16_bn254_proof(proof, vk, public_values, build_dir)
+    }
+}
 
-        let vkey_hash = BigUint::from_str(&proof.public_inputs[0])?;
-        let committed_values_digest = BigUint::from_str(&proof.public_inputs[1])?;
+/// Verifies a PLONK proof using the circuit artifacts in the build directory.
+///
+/// The build directory may hold the artifacts of any circuit version, which allows verifying
+/// proofs generated with older versions of SP1.
+pub fn verify_plonk_bn254_proof(
+    proof: &PlonkBn254Proof,
+    vk: &SP1VerifyingKey,
+    public_values: &SP1PublicValues,
+    build_dir: &Path,
+) -> Result<()> {
+    let prover = PlonkBn254Prover::new();
 
-        // Verify the proof with the corresponding public inputs.
-        prover.verify(proof, &vkey_hash, &committed_values_digest, build_dir);
+    let vkey_hash = BigUint::from_str(&proof.public_inputs[0])?;
+    let committed_values_digest = BigUint::from_str(&proof.public_inputs[1])?;
 
-        verify_groth16_bn254_public_inputs(vk, public_values, &proof.public_inputs)?;
+    // Verify the proof with the corresponding public inputs.
+    prover.verify(proof, &vkey_hash, &committed_values_digest, build_dir);
 
-        Ok(())
-    }
+    verify_plonk_bn254_public_inputs(vk, public_values, &proof.public_inputs)?;
+
+    Ok(())
+}
+
+/// Verifies a Groth16 proof using the circuit artifacts in the build directory.
+///
+/// The build directory may hold the artifacts of any circuit version, which allows verifying
+/// proofs generated with older versions of SP1.
+pub fn verify_groth16_bn254_proof(
+    proof: &Groth16Bn254Proof,
+    vk: &SP1VerifyingKey,
+    public_values: &SP1PublicValues,
+    build_dir: &Path,
+) -> Result<()> {
+    let prover = Groth16Bn254Prover::new();
+
+    let vkey_hash = BigUint::from_str(&proof.public_inputs[0])?;
+    let committed_values_digest = BigUint::from_str(&proof.public_inputs[1])?;
+
+    // Verify the proof with the corresponding public inputs.
+    prover.verify(proof, &vkey_hash, &committed_values_digest, build_dir);
+
+    verify_groth16_bn254_public_inputs(vk, public_values, &proof.public_inputs)?;
+
+    Ok(())
 }
 
 /// Verify the vk_hash and public_values_hash in the public inputs of the PlonkBn254Proof match the
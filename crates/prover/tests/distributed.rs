@@ -0,0 +1,104 @@
+//! Tests proving across worker processes started from the `worker` binary.
+
+use std::{
+    io,
+    net::{SocketAddr, TcpListener},
+    process::{Child, Command},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use serial_test::serial;
+use sp1_core_executor::SP1Context;
+use sp1_core_machine::{io::SP1Stdin, utils::setup_logger};
+use sp1_prover::{
+    components::DefaultProverComponents,
+    distributed::{SP1DistributedError, WorkerPool},
+    SP1Prover,
+};
+use sp1_stark::SP1ProverOpts;
+
+const SECRET: &str = "test-secret";
+
+/// A worker process, killed when dropped.
+struct Worker {
+    addr: SocketAddr,
+    process: Child,
+}
+
+impl Worker {
+    fn spawn() -> Result<Self> {
+        // Reserve a free port for the worker to listen on.
+        let addr = TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let process = Command::new(env!("CARGO_BIN_EXE_worker"))
+            .arg("--addr")
+            .arg(addr.to_string())
+            .env("SP1_WORKER_SECRET", SECRET)
+            .spawn()?;
+        Ok(Self { addr, process })
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// Connects to the workers, waiting for them to start listening.
+fn connect(workers: &[Worker], secret: &str) -> Result<WorkerPool, SP1DistributedError> {
+    let addrs = workers.iter().map(|worker| worker.addr).collect::<Vec<_>>();
+    let start = Instant::now();
+    loop {
+        match WorkerPool::connect(&addrs, secret.as_bytes()) {
+            Err(SP1DistributedError::Io(e))
+                if e.kind() == io::ErrorKind::ConnectionRefused
+                    && start.elapsed() < Duration::from_secs(60) =>
+            {
+                thread::sleep(Duration::from_millis(100));
+            }
+            result => return result,
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_distributed_prove() -> Result<()> {
+    let elf = include_bytes!("../../../tests/fibonacci/elf/riscv32im-succinct-zkvm-elf");
+    setup_logger();
+
+    let workers = [Worker::spawn()?, Worker::spawn()?];
+    let mut pool = connect(&workers, SECRET)?;
+
+    let prover = SP1Prover::<DefaultProverComponents>::new();
+    let opts = SP1ProverOpts::default();
+    let (pk, vk) = prover.setup(elf);
+
+    let core_proof = prover.prove_core_distributed(
+        &pk,
+        &SP1Stdin::new(),
+        opts,
+        SP1Context::default(),
+        &mut pool,
+    )?;
+    prover.verify(&core_proof.proof, &vk)?;
+
+    let compressed_proof = prover.compress_distributed(&vk, core_proof, vec![], opts, &mut pool)?;
+    prover.verify_compressed(&compressed_proof, &vk)?;
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_distributed_wrong_secret() -> Result<()> {
+    setup_logger();
+
+    let workers = [Worker::spawn()?];
+    let result = connect(&workers, "wrong-secret");
+    assert!(matches!(result, Err(SP1DistributedError::Unauthenticated)));
+    Ok(())
+}
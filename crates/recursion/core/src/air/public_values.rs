@@ -1,13 +1,18 @@
 use crate::runtime::{DIGEST_SIZE, HASH_RATE, PERMUTATION_WIDTH};
 
 use core::fmt::Debug;
+use p3_baby_bear::BabyBear;
 use p3_challenger::DuplexChallenger;
 use p3_field::PrimeField32;
-use p3_symmetric::CryptographicPermutation;
+use p3_symmetric::{CryptographicHasher, CryptographicPermutation};
 use serde::{Deserialize, Serialize};
 use sp1_core_machine::utils::indices_arr;
 use sp1_derive::AlignedBorrow;
-use sp1_stark::{air::POSEIDON_NUM_WORDS, Word, PROOF_MAX_NUM_PVS};
+use sp1_stark::{
+    air::POSEIDON_NUM_WORDS,
+    baby_bear_poseidon2::{BabyBearPoseidon2, MyHash as InnerHash},
+    Word, PROOF_MAX_NUM_PVS,
+};
 use static_assertions::const_assert_eq;
 use std::{
     borrow::BorrowMut,
@@ -165,6 +170,16 @@ impl<F: Copy> RecursionPublicValues<F> {
     }
 }
 
+/// Computes the digest of the public values elements before `digest`, which `digest` must equal.
+pub fn recursion_public_values_digest(
+    config: &BabyBearPoseidon2,
+    public_values: &RecursionPublicValues<BabyBear>,
+) -> [BabyBear; DIGEST_SIZE] {
+    let hash = InnerHash::new(config.perm.clone());
+    let pv_array = public_values.as_array();
+    hash.hash_slice(&pv_array[0..NUM_PV_ELMS_TO_HASH])
+}
+
 impl<T: Copy> IntoIterator for RecursionPublicValues<T> {
     type Item = T;
     type IntoIter = std::array::IntoIter<T, RECURSIVE_PROOF_NUM_PV_ELTS>;
@@ -26,6 +26,9 @@ use crate::{
     ExpReverseBitsInstr, Instruction, RecursionProgram, D,
 };
 
+/// The degree of the constraints of the compress machine.
+pub const COMPRESS_DEGREE: usize = 3;
+
 #[derive(sp1_derive::MachineAir)]
 #[sp1_core_path = "sp1_core_machine"]
 #[execution_record_path = "crate::ExecutionRecord<F>"]
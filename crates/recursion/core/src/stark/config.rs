@@ -12,7 +12,7 @@ use p3_merkle_tree::FieldMerkleTreeMmcs;
 use p3_poseidon2::{Poseidon2, Poseidon2ExternalMatrixGeneral};
 use p3_symmetric::{Hash, MultiField32PaddingFreeSponge, TruncatedPermutation};
 use serde::{Deserialize, Serialize};
-use sp1_stark::{Com, StarkGenericConfig, ZeroCommitment};
+use sp1_stark::{Com, FriParameters, SecurityProfile, StarkGenericConfig, ZeroCommitment};
 
 use super::{poseidon2::bn254_poseidon2_rc3, sp1_dev_mode};
 
@@ -70,38 +70,50 @@ pub fn outer_perm() -> OuterPerm {
     )
 }
 
-/// The FRI config for outer recursion.
-pub fn outer_fri_config() -> FriConfig<OuterChallengeMmcs> {
+/// The log blowup of the default config for outer recursion.
+pub const OUTER_LOG_BLOWUP: usize = 4;
+
+/// The FRI parameters for outer recursion with the given default log blowup under the security
+/// profile, with a single query in development mode and the number of queries overridden by
+/// `FRI_QUERIES` if set.
+pub fn outer_fri_parameters(profile: &SecurityProfile, default_log_blowup: usize) -> FriParameters {
+    let mut parameters = profile.fri_parameters(default_log_blowup, 1);
+    if sp1_dev_mode() {
+        parameters.num_queries = 1;
+    } else if let Ok(value) = std::env::var("FRI_QUERIES") {
+        parameters.num_queries = value.parse().unwrap();
+    }
+    parameters
+}
+
+/// The FRI config for outer recursion with the given parameters.
+pub fn outer_fri_config_with_parameters(
+    parameters: &FriParameters,
+) -> FriConfig<OuterChallengeMmcs> {
     let perm = outer_perm();
     let hash = OuterHash::new(perm.clone()).unwrap();
     let compress = OuterCompress::new(perm.clone());
     let challenge_mmcs = OuterChallengeMmcs::new(OuterValMmcs::new(hash, compress));
-    let num_queries = if sp1_dev_mode() {
-        1
-    } else {
-        match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 25,
-        }
-    };
-    FriConfig { log_blowup: 4, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    FriConfig {
+        log_blowup: parameters.log_blowup,
+        num_queries: parameters.num_queries,
+        proof_of_work_bits: parameters.proof_of_work_bits,
+        mmcs: challenge_mmcs,
+    }
+}
+
+/// The FRI config for outer recursion.
+pub fn outer_fri_config() -> FriConfig<OuterChallengeMmcs> {
+    outer_fri_config_with_parameters(&outer_fri_parameters(
+        &SecurityProfile::default(),
+        OUTER_LOG_BLOWUP,
+    ))
 }
 
 /// The FRI config for outer recursion.
 pub fn outer_fri_config_with_blowup(log_blowup: usize) -> FriConfig<OuterChallengeMmcs> {
-    let perm = outer_perm();
-    let hash = OuterHash::new(perm.clone()).unwrap();
-    let compress = OuterCompress::new(perm.clone());
-    let challenge_mmcs = OuterChallengeMmcs::new(OuterValMmcs::new(hash, compress));
-    let num_queries = if sp1_dev_mode() {
-        1
-    } else {
-        match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 100 / log_blowup,
-        }
-    };
-    FriConfig { log_blowup, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    let profile = SecurityProfile::default().with_log_blowup(log_blowup);
+    outer_fri_config_with_parameters(&outer_fri_parameters(&profile, log_blowup))
 }
 
 #[derive(Deserialize)]
@@ -109,11 +121,12 @@ pub fn outer_fri_config_with_blowup(log_blowup: usize) -> FriConfig<OuterChallen
 pub struct BabyBearPoseidon2Outer {
     pub perm: OuterPerm,
     pub pcs: OuterPcs,
+    fri_parameters: FriParameters,
 }
 
 impl Clone for BabyBearPoseidon2Outer {
     fn clone(&self) -> Self {
-        Self::new()
+        Self::with_fri_parameters(self.fri_parameters)
     }
 }
 
@@ -134,24 +147,33 @@ impl From<std::marker::PhantomData<BabyBearPoseidon2Outer>> for BabyBearPoseidon
 
 impl BabyBearPoseidon2Outer {
     pub fn new() -> Self {
-        let perm = outer_perm();
-        let hash = OuterHash::new(perm.clone()).unwrap();
-        let compress = OuterCompress::new(perm.clone());
-        let val_mmcs = OuterValMmcs::new(hash, compress);
-        let dft = OuterDft {};
-        let fri_config = outer_fri_config();
-        let pcs = OuterPcs::new(27, dft, val_mmcs, fri_config);
-        Self { pcs, perm }
+        Self::with_security_profile(&SecurityProfile::default())
     }
+
     pub fn new_with_log_blowup(log_blowup: usize) -> Self {
+        let profile = SecurityProfile::default().with_log_blowup(log_blowup);
+        Self::with_fri_parameters(outer_fri_parameters(&profile, log_blowup))
+    }
+
+    /// A config with the FRI parameters of the security profile.
+    pub fn with_security_profile(profile: &SecurityProfile) -> Self {
+        Self::with_fri_parameters(outer_fri_parameters(profile, OUTER_LOG_BLOWUP))
+    }
+
+    pub fn with_fri_parameters(fri_parameters: FriParameters) -> Self {
         let perm = outer_perm();
         let hash = OuterHash::new(perm.clone()).unwrap();
         let compress = OuterCompress::new(perm.clone());
         let val_mmcs = OuterValMmcs::new(hash, compress);
         let dft = OuterDft {};
-        let fri_config = outer_fri_config_with_blowup(log_blowup);
+        let fri_config = outer_fri_config_with_parameters(&fri_parameters);
         let pcs = OuterPcs::new(27, dft, val_mmcs, fri_config);
-        Self { pcs, perm }
+        Self { pcs, perm, fri_parameters }
+    }
+
+    /// The FRI parameters of the config.
+    pub fn fri_parameters(&self) -> FriParameters {
+        self.fri_parameters
     }
 }
 
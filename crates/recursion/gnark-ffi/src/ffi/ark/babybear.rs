@@ -0,0 +1,387 @@
+//! BabyBear arithmetic emulated over BN254, mirroring `go/sp1/babybear/babybear.go`.
+//!
+//! Every variable tracks an upper bound of its value, so that reductions modulo the BabyBear
+//! modulus can be delayed until the value might overflow.
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::BigUint;
+use p3_baby_bear::BabyBear;
+use p3_field::{
+    extension::BinomialExtensionField, AbstractExtensionField, AbstractField, Field, PrimeField32,
+};
+
+/// The BabyBear modulus.
+pub(crate) const MODULUS: u32 = 2013265921;
+
+/// A BabyBear element that is not necessarily reduced.
+#[derive(Clone)]
+pub(crate) struct Felt {
+    pub value: FpVar<Fr>,
+    pub upper_bound: BigUint,
+}
+
+/// An element of the degree 4 extension of BabyBear.
+#[derive(Clone)]
+pub(crate) struct Ext(pub [Felt; 4]);
+
+impl Felt {
+    /// A variable only known to be smaller than `2^32`.
+    pub fn new(value: FpVar<Fr>) -> Self {
+        Self { value, upper_bound: BigUint::from(1u64 << 32) }
+    }
+
+    /// A constant, bounded by its value.
+    pub fn constant(value: impl Into<BigUint>) -> Self {
+        let value = value.into();
+        Self { value: FpVar::Constant(Fr::from(value.clone())), upper_bound: value }
+    }
+}
+
+impl Ext {
+    /// A constant, bounded by its values.
+    pub fn constant(values: [u32; 4]) -> Self {
+        Self(values.map(Felt::constant))
+    }
+}
+
+/// Returns the value assigned to `x`, or zero if the constraint system is only being set up.
+pub(crate) fn value_of(x: &FpVar<Fr>) -> BigUint {
+    x.value().map(Into::into).unwrap_or_default()
+}
+
+/// Returns the BabyBear element `x` represents.
+fn babybear_value_of(x: &FpVar<Fr>) -> BabyBear {
+    let value = value_of(x) % MODULUS;
+    BabyBear::from_canonical_u32(value.to_u32_digits().first().copied().unwrap_or(0))
+}
+
+/// Allocates a witness computed out of circuit.
+pub(crate) fn hint(
+    cs: &ConstraintSystemRef<Fr>,
+    value: impl Into<BigUint>,
+) -> Result<FpVar<Fr>, SynthesisError> {
+    let value = Fr::from(value.into());
+    FpVar::new_witness(cs.clone(), || Ok(value))
+}
+
+/// Decomposes `x` into `num_bits` little-endian bits, constraining it to fit in them.
+///
+/// Decompositions into as many bits as the field has are constrained to be canonical.
+pub(crate) fn to_binary(
+    cs: &ConstraintSystemRef<Fr>,
+    x: &FpVar<Fr>,
+    num_bits: usize,
+) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+    if num_bits >= Fr::MODULUS_BIT_SIZE as usize {
+        let mut bits = x.to_bits_le()?;
+        bits.resize(num_bits, Boolean::FALSE);
+        return Ok(bits);
+    }
+
+    let value = value_of(x);
+    if let FpVar::Constant(_) = x {
+        if value.bits() > num_bits as u64 {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        return Ok((0..num_bits).map(|i| Boolean::constant(value.bit(i as u64))).collect());
+    }
+    let bits = (0..num_bits)
+        .map(|i| Boolean::new_witness(cs.clone(), || Ok(value.bit(i as u64))))
+        .collect::<Result<Vec<_>, _>>()?;
+    Boolean::le_bits_to_fp_var(&bits)?.enforce_equal(x)?;
+    Ok(bits)
+}
+
+/// Converts a variable the circuit assumes to be a bit into a [Boolean].
+pub(crate) fn to_boolean(
+    cs: &ConstraintSystemRef<Fr>,
+    x: &FpVar<Fr>,
+) -> Result<Boolean<Fr>, SynthesisError> {
+    if let FpVar::Constant(c) = x {
+        return match BigUint::from(*c) {
+            v if v == BigUint::from(0u32) => Ok(Boolean::FALSE),
+            v if v == BigUint::from(1u32) => Ok(Boolean::TRUE),
+            _ => Err(SynthesisError::Unsatisfiable),
+        };
+    }
+    let bit = Boolean::new_witness(cs.clone(), || Ok(value_of(x) == BigUint::from(1u32)))?;
+    FpVar::from(bit.clone()).enforce_equal(x)?;
+    Ok(bit)
+}
+
+/// Constrains the BabyBear arithmetic of the circuit.
+pub(crate) struct BabyBearChip {
+    cs: ConstraintSystemRef<Fr>,
+}
+
+impl BabyBearChip {
+    pub fn new(cs: ConstraintSystemRef<Fr>) -> Self {
+        Self { cs }
+    }
+
+    /// Constrains `x` to fit in `num_bits` bits.
+    pub fn range_check(&self, x: &FpVar<Fr>, num_bits: usize) -> Result<(), SynthesisError> {
+        to_binary(&self.cs, x, num_bits).map(|_| ())
+    }
+
+    fn add_unreduced(a: &Felt, b: &Felt) -> Felt {
+        Felt { value: &a.value + &b.value, upper_bound: &a.upper_bound + &b.upper_bound }
+    }
+
+    fn mul_unreduced(a: &Felt, b: &Felt) -> Felt {
+        Felt { value: &a.value * &b.value, upper_bound: &a.upper_bound * &b.upper_bound }
+    }
+
+    fn mul_const_unreduced(a: &Felt, b: u32) -> Felt {
+        Felt { value: &a.value * Fr::from(b), upper_bound: &a.upper_bound * b }
+    }
+
+    pub fn add_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        self.reduce_fast(Self::add_unreduced(a, b))
+    }
+
+    pub fn sub_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        let neg_b = self.neg_f(b)?;
+        self.add_f(a, &neg_b)
+    }
+
+    pub fn mul_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        self.reduce_fast(Self::mul_unreduced(a, b))
+    }
+
+    pub fn mul_f_const(&self, a: &Felt, b: u32) -> Result<Felt, SynthesisError> {
+        self.reduce_fast(Self::mul_const_unreduced(a, b))
+    }
+
+    fn neg_f(&self, a: &Felt) -> Result<Felt, SynthesisError> {
+        // Subtract from a multiple of the modulus that is larger than `a`.
+        let lifted_modulus = (&a.upper_bound / MODULUS + 1u32) * MODULUS;
+        self.reduce_fast(Felt {
+            value: FpVar::Constant(Fr::from(lifted_modulus.clone())) - &a.value,
+            upper_bound: lifted_modulus,
+        })
+    }
+
+    fn inv_f(&self, a: &Felt) -> Result<Felt, SynthesisError> {
+        let inv = babybear_value_of(&a.value).try_inverse().unwrap_or(BabyBear::zero());
+        let inv = hint(&self.cs, inv.as_canonical_u32())?;
+        self.range_check(&inv, 31)?;
+        let inv = Felt { value: inv, upper_bound: BigUint::from(1u64 << 31) };
+
+        let product = self.mul_f(a, &inv)?;
+        self.assert_eq_f(&product, &Felt::constant(1u32))?;
+        Ok(inv)
+    }
+
+    pub fn div_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        let b_inv = self.inv_f(b)?;
+        self.mul_f(a, &b_inv)
+    }
+
+    pub fn assert_eq_f(&self, a: &Felt, b: &Felt) -> Result<(), SynthesisError> {
+        let a = self.reduce_slow(a)?;
+        let b = self.reduce_slow(b)?;
+        a.value.enforce_equal(&b.value)
+    }
+
+    pub fn assert_ne_f(&self, a: &Felt, b: &Felt) -> Result<(), SynthesisError> {
+        let a = self.reduce_slow(a)?;
+        let b = self.reduce_slow(b)?;
+        a.value.enforce_not_equal(&b.value)
+    }
+
+    pub fn select_f(&self, cond: &Boolean<Fr>, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        Ok(Felt {
+            value: FpVar::conditionally_select(cond, &a.value, &b.value)?,
+            upper_bound: (&a.upper_bound).max(&b.upper_bound).clone(),
+        })
+    }
+
+    pub fn add_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.add_f(&a.0[0], &b.0[0])?,
+            self.add_f(&a.0[1], &b.0[1])?,
+            self.add_f(&a.0[2], &b.0[2])?,
+            self.add_f(&a.0[3], &b.0[3])?,
+        ]))
+    }
+
+    pub fn add_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        let mut result = a.clone();
+        result.0[0] = self.add_f(&a.0[0], b)?;
+        Ok(result)
+    }
+
+    pub fn sub_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.sub_f(&a.0[0], &b.0[0])?,
+            self.sub_f(&a.0[1], &b.0[1])?,
+            self.sub_f(&a.0[2], &b.0[2])?,
+            self.sub_f(&a.0[3], &b.0[3])?,
+        ]))
+    }
+
+    pub fn sub_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        let mut result = a.clone();
+        result.0[0] = self.sub_f(&a.0[0], b)?;
+        Ok(result)
+    }
+
+    pub fn mul_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        // Multiply the polynomials, reducing by `X^4 = 11` and delaying the reductions modulo the
+        // BabyBear modulus until the end.
+        let mut result = Ext::constant([0; 4]).0;
+        for i in 0..4 {
+            for j in 0..4 {
+                let product = Self::mul_unreduced(&a.0[i], &b.0[j]);
+                if i + j >= 4 {
+                    let product = Self::mul_const_unreduced(&product, 11);
+                    result[i + j - 4] = Self::add_unreduced(&result[i + j - 4], &product);
+                } else {
+                    result[i + j] = Self::add_unreduced(&result[i + j], &product);
+                }
+            }
+        }
+        let [r0, r1, r2, r3] = result;
+        Ok(Ext([
+            self.reduce_fast(r0)?,
+            self.reduce_fast(r1)?,
+            self.reduce_fast(r2)?,
+            self.reduce_fast(r3)?,
+        ]))
+    }
+
+    pub fn mul_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.mul_f(&a.0[0], b)?,
+            self.mul_f(&a.0[1], b)?,
+            self.mul_f(&a.0[2], b)?,
+            self.mul_f(&a.0[3], b)?,
+        ]))
+    }
+
+    pub fn inv_e(&self, a: &Ext) -> Result<Ext, SynthesisError> {
+        let values = a.0.each_ref().map(|x| babybear_value_of(&x.value));
+        let inv = BinomialExtensionField::<BabyBear, 4>::from_base_slice(&values)
+            .try_inverse()
+            .unwrap_or(BinomialExtensionField::zero());
+        let mut result = Ext::constant([0; 4]);
+        for (result, x) in result.0.iter_mut().zip(inv.as_base_slice()) {
+            let x = hint(&self.cs, x.as_canonical_u32())?;
+            self.range_check(&x, 31)?;
+            *result = Felt { value: x, upper_bound: BigUint::from(1u64 << 31) };
+        }
+
+        let product = self.mul_e(a, &result)?;
+        self.assert_eq_e(&product, &Ext::constant([1, 0, 0, 0]))?;
+        Ok(result)
+    }
+
+    pub fn div_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        let b_inv = self.inv_e(b)?;
+        self.mul_e(a, &b_inv)
+    }
+
+    pub fn div_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        let b_inv = self.inv_f(b)?;
+        self.mul_ef(a, &b_inv)
+    }
+
+    pub fn neg_e(&self, a: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.neg_f(&a.0[0])?,
+            self.neg_f(&a.0[1])?,
+            self.neg_f(&a.0[2])?,
+            self.neg_f(&a.0[3])?,
+        ]))
+    }
+
+    pub fn assert_eq_e(&self, a: &Ext, b: &Ext) -> Result<(), SynthesisError> {
+        for (a, b) in a.0.iter().zip(b.0.iter()) {
+            self.assert_eq_f(a, b)?;
+        }
+        Ok(())
+    }
+
+    pub fn select_e(&self, cond: &Boolean<Fr>, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.select_f(cond, &a.0[0], &b.0[0])?,
+            self.select_f(cond, &a.0[1], &b.0[1])?,
+            self.select_f(cond, &a.0[2], &b.0[2])?,
+            self.select_f(cond, &a.0[3], &b.0[3])?,
+        ]))
+    }
+
+    pub fn reduce_e(&self, a: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.reduce_slow(&a.0[0])?,
+            self.reduce_slow(&a.0[1])?,
+            self.reduce_slow(&a.0[2])?,
+            self.reduce_slow(&a.0[3])?,
+        ]))
+    }
+
+    /// Decomposes a BabyBear element into its 31 canonical bits.
+    pub fn to_binary(&self, a: &Felt) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+        to_binary(&self.cs, &self.reduce_slow(a)?.value, 31)
+    }
+
+    /// Reduces `x` if its upper bound gets close to overflowing the intermediate products.
+    fn reduce_fast(&self, x: Felt) -> Result<Felt, SynthesisError> {
+        if x.upper_bound.bits() >= 120 {
+            return Ok(Felt {
+                value: self.reduce_with_max_bits(&x.value, x.upper_bound.bits() as usize)?,
+                upper_bound: BigUint::from(MODULUS - 1),
+            });
+        }
+        Ok(x)
+    }
+
+    /// Reduces `x` to its canonical representative.
+    pub fn reduce_slow(&self, x: &Felt) -> Result<Felt, SynthesisError> {
+        if x.upper_bound < BigUint::from(MODULUS) {
+            return Ok(x.clone());
+        }
+        Ok(Felt {
+            value: self.reduce_with_max_bits(&x.value, x.upper_bound.bits() as usize)?,
+            upper_bound: BigUint::from(MODULUS - 1),
+        })
+    }
+
+    fn reduce_with_max_bits(
+        &self,
+        x: &FpVar<Fr>,
+        max_bits: usize,
+    ) -> Result<FpVar<Fr>, SynthesisError> {
+        if max_bits <= 30 {
+            return Ok(x.clone());
+        }
+        let value = value_of(x);
+        if let FpVar::Constant(_) = x {
+            return Ok(FpVar::Constant(Fr::from(value % MODULUS)));
+        }
+
+        let quotient = hint(&self.cs, &value / MODULUS)?;
+        let remainder = &value % MODULUS;
+        self.range_check(&quotient, max_bits - 30)?;
+
+        // Check that the remainder is smaller than the BabyBear modulus, by decomposing it into a
+        // 27 bit limb and a 4 bit limb.
+        let low_limb = hint(&self.cs, &remainder % (1u32 << 27))?;
+        let high_limb = hint(&self.cs, &remainder >> 27)?;
+        let remainder = &high_limb * Fr::from(1u32 << 27) + &low_limb;
+        self.range_check(&high_limb, 4)?;
+        self.range_check(&low_limb, 27)?;
+
+        // If the most significant bits are all 1, then the least significant bits must all be zero
+        // for the element to be smaller than the BabyBear modulus.
+        let should_check = high_limb.is_eq(&FpVar::Constant(Fr::from(15u32)))?;
+        low_limb.mul_equals(&FpVar::from(should_check), &FpVar::Constant(Fr::from(0u32)))?;
+
+        (&quotient * Fr::from(MODULUS) + &remainder).enforce_equal(x)?;
+        Ok(remainder)
+    }
+}
@@ -0,0 +1,277 @@
+//! The wrap circuit, defined by the constraints emitted by the constraint compiler, mirroring
+//! `go/sp1/sp1.go`.
+
+use std::{collections::HashMap, str::FromStr};
+
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use sp1_recursion_compiler::constraints::{opcodes::ConstraintOpcode, Constraint};
+
+use super::{
+    babybear::{to_binary, to_boolean, BabyBearChip, Ext, Felt},
+    poseidon2::{permute_babybear, permute_bn254},
+};
+use crate::witness::GnarkWitness;
+
+/// The wrap circuit with an assignment of its witness.
+pub(crate) struct WrapCircuit {
+    pub constraints: Vec<Constraint>,
+    pub witness: GnarkWitness,
+}
+
+fn parse(value: &str) -> Result<Fr, SynthesisError> {
+    Fr::from_str(value).map_err(|_| SynthesisError::AssignmentMissing)
+}
+
+fn parse_index(value: &str) -> usize {
+    value.parse().expect("invalid witness index")
+}
+
+/// The variables of the circuit by their identifiers in the constraints.
+#[derive(Default)]
+struct Variables {
+    vars: HashMap<String, FpVar<Fr>>,
+    felts: HashMap<String, Felt>,
+    exts: HashMap<String, Ext>,
+}
+
+impl Variables {
+    fn var(&self, id: &[String]) -> &FpVar<Fr> {
+        self.vars.get(&id[0]).unwrap_or_else(|| panic!("unknown var: {}", id[0]))
+    }
+
+    fn felt(&self, id: &[String]) -> &Felt {
+        self.felts.get(&id[0]).unwrap_or_else(|| panic!("unknown felt: {}", id[0]))
+    }
+
+    fn ext(&self, id: &[String]) -> &Ext {
+        self.exts.get(&id[0]).unwrap_or_else(|| panic!("unknown ext: {}", id[0]))
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for WrapCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let Self { constraints, witness } = self;
+
+        // Allocate the public inputs, and then the witness.
+        let vkey_hash = FpVar::new_input(cs.clone(), || parse(&witness.vkey_hash))?;
+        let committed_values_digest =
+            FpVar::new_input(cs.clone(), || parse(&witness.committed_values_digest))?;
+        let witness_vars = witness
+            .vars
+            .iter()
+            .map(|v| FpVar::new_witness(cs.clone(), || parse(v)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let witness_felts = witness
+            .felts
+            .iter()
+            .map(|v| FpVar::new_witness(cs.clone(), || parse(v)).map(Felt::new))
+            .collect::<Result<Vec<_>, _>>()?;
+        let witness_exts = witness
+            .exts
+            .iter()
+            .map(|e| {
+                let mut ext = Ext::constant([0; 4]);
+                for (felt, v) in ext.0.iter_mut().zip(e) {
+                    *felt = Felt::new(FpVar::new_witness(cs.clone(), || parse(v))?);
+                }
+                Ok(ext)
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        // Range check the witnessed BabyBear elements.
+        let chip = BabyBearChip::new(cs.clone());
+        for felt in witness_felts.iter().chain(witness_exts.iter().flat_map(|ext| ext.0.iter())) {
+            chip.range_check(&felt.value, 31)?;
+        }
+
+        let mut v = Variables::default();
+        for cs_ in constraints {
+            let args = &cs_.args;
+            let out = args[0][0].clone();
+            match cs_.opcode {
+                ConstraintOpcode::ImmV => {
+                    v.vars.insert(out, FpVar::Constant(parse(&args[1][0])?));
+                }
+                ConstraintOpcode::ImmF => {
+                    v.felts.insert(out, Felt::new(FpVar::Constant(parse(&args[1][0])?)));
+                }
+                ConstraintOpcode::ImmE => {
+                    let mut ext = Ext::constant([0; 4]);
+                    for (felt, value) in ext.0.iter_mut().zip(&args[1]) {
+                        *felt = Felt::new(FpVar::Constant(parse(value)?));
+                    }
+                    v.exts.insert(out, ext);
+                }
+                ConstraintOpcode::AddV => {
+                    let value = v.var(&args[1]) + v.var(&args[2]);
+                    v.vars.insert(out, value);
+                }
+                ConstraintOpcode::AddF => {
+                    let value = chip.add_f(v.felt(&args[1]), v.felt(&args[2]))?;
+                    v.felts.insert(out, value);
+                }
+                ConstraintOpcode::AddE => {
+                    let value = chip.add_e(v.ext(&args[1]), v.ext(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::AddEF => {
+                    let value = chip.add_ef(v.ext(&args[1]), v.felt(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::SubV => {
+                    let value = v.var(&args[1]) - v.var(&args[2]);
+                    v.vars.insert(out, value);
+                }
+                ConstraintOpcode::SubF => {
+                    let value = chip.sub_f(v.felt(&args[1]), v.felt(&args[2]))?;
+                    v.felts.insert(out, value);
+                }
+                ConstraintOpcode::SubE => {
+                    let value = chip.sub_e(v.ext(&args[1]), v.ext(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::SubEF => {
+                    let value = chip.sub_ef(v.ext(&args[1]), v.felt(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::MulV => {
+                    let value = v.var(&args[1]) * v.var(&args[2]);
+                    v.vars.insert(out, value);
+                }
+                ConstraintOpcode::MulF => {
+                    let value = chip.mul_f(v.felt(&args[1]), v.felt(&args[2]))?;
+                    v.felts.insert(out, value);
+                }
+                ConstraintOpcode::MulE => {
+                    let value = chip.mul_e(v.ext(&args[1]), v.ext(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::MulEF => {
+                    let value = chip.mul_ef(v.ext(&args[1]), v.felt(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::DivF => {
+                    let value = chip.div_f(v.felt(&args[1]), v.felt(&args[2]))?;
+                    v.felts.insert(out, value);
+                }
+                ConstraintOpcode::DivE => {
+                    let value = chip.div_e(v.ext(&args[1]), v.ext(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::DivEF => {
+                    let value = chip.div_ef(v.ext(&args[1]), v.felt(&args[2]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::NegE => {
+                    let value = chip.neg_e(v.ext(&args[1]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::InvE => {
+                    let value = chip.inv_e(v.ext(&args[1]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::Num2BitsV => {
+                    let num_bits = parse_index(&args[2][0]);
+                    let bits = to_binary(&cs, v.var(&args[1]), num_bits)?;
+                    for (id, bit) in args[0].iter().zip(bits) {
+                        v.vars.insert(id.clone(), bit.into());
+                    }
+                }
+                ConstraintOpcode::Num2BitsF => {
+                    let bits = chip.to_binary(v.felt(&args[1]))?;
+                    for (id, bit) in args[0].iter().zip(bits) {
+                        v.vars.insert(id.clone(), bit.into());
+                    }
+                }
+                ConstraintOpcode::Permute => {
+                    let mut state =
+                        [v.var(&args[0]), v.var(&args[1]), v.var(&args[2])].map(|x| x.clone());
+                    permute_bn254(&mut state);
+                    for (id, x) in args.iter().zip(state) {
+                        v.vars.insert(id[0].clone(), x);
+                    }
+                }
+                ConstraintOpcode::PermuteBabyBear => {
+                    let mut state: [Felt; 16] = std::array::from_fn(|i| v.felt(&args[i]).clone());
+                    permute_babybear(&chip, &mut state)?;
+                    for (id, x) in args.iter().zip(state) {
+                        v.felts.insert(id[0].clone(), x);
+                    }
+                }
+                ConstraintOpcode::SelectV => {
+                    let cond = to_boolean(&cs, v.var(&args[1]))?;
+                    let value =
+                        FpVar::conditionally_select(&cond, v.var(&args[2]), v.var(&args[3]))?;
+                    v.vars.insert(out, value);
+                }
+                ConstraintOpcode::SelectF => {
+                    let cond = to_boolean(&cs, v.var(&args[1]))?;
+                    let value = chip.select_f(&cond, v.felt(&args[2]), v.felt(&args[3]))?;
+                    v.felts.insert(out, value);
+                }
+                ConstraintOpcode::SelectE => {
+                    let cond = to_boolean(&cs, v.var(&args[1]))?;
+                    let value = chip.select_e(&cond, v.ext(&args[2]), v.ext(&args[3]))?;
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::Ext2Felt => {
+                    let ext = v.ext(&args[4]).clone();
+                    for (id, felt) in args.iter().zip(ext.0) {
+                        v.felts.insert(id[0].clone(), felt);
+                    }
+                }
+                ConstraintOpcode::AssertEqV => {
+                    v.var(&args[0]).enforce_equal(v.var(&args[1]))?;
+                }
+                ConstraintOpcode::AssertEqF => {
+                    chip.assert_eq_f(v.felt(&args[0]), v.felt(&args[1]))?;
+                }
+                ConstraintOpcode::AssertNeF => {
+                    chip.assert_ne_f(v.felt(&args[0]), v.felt(&args[1]))?;
+                }
+                ConstraintOpcode::AssertEqE => {
+                    chip.assert_eq_e(v.ext(&args[0]), v.ext(&args[1]))?;
+                }
+                ConstraintOpcode::PrintV | ConstraintOpcode::PrintF | ConstraintOpcode::PrintE => {}
+                ConstraintOpcode::WitnessV => {
+                    let value = witness_vars[parse_index(&args[1][0])].clone();
+                    v.vars.insert(out, value);
+                }
+                ConstraintOpcode::WitnessF => {
+                    let value = witness_felts[parse_index(&args[1][0])].clone();
+                    v.felts.insert(out, value);
+                }
+                ConstraintOpcode::WitnessE => {
+                    let value = witness_exts[parse_index(&args[1][0])].clone();
+                    v.exts.insert(out, value);
+                }
+                ConstraintOpcode::CommitVkeyHash => {
+                    vkey_hash.enforce_equal(v.var(&args[0]))?;
+                }
+                ConstraintOpcode::CommitCommitedValuesDigest => {
+                    committed_values_digest.enforce_equal(v.var(&args[0]))?;
+                }
+                ConstraintOpcode::CircuitFelts2Ext => {
+                    let ext = Ext(std::array::from_fn(|i| v.felt(&args[i + 1]).clone()));
+                    v.exts.insert(out, ext);
+                }
+                ConstraintOpcode::CircuitFelt2Var => {
+                    let value = chip.reduce_slow(v.felt(&args[1]))?.value;
+                    v.vars.insert(out, value);
+                }
+                ConstraintOpcode::ReduceE => {
+                    let value = chip.reduce_e(v.ext(&args[0]))?;
+                    v.exts.insert(out, value);
+                }
+                opcode @ (ConstraintOpcode::NegV
+                | ConstraintOpcode::NegF
+                | ConstraintOpcode::InvV
+                | ConstraintOpcode::InvF) => panic!("unhandled opcode: {:?}", opcode),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+//! A pure Rust backend proving the wrap circuit with the Groth16 implementation of arkworks.
+//!
+//! The circuit is the one defined by `go/sp1/sp1.go`, so the proofs have the same public inputs
+//! and the same Solidity encoding as the proofs of the gnark backends. The keys are generated by
+//! [build_groth16_bn254] with a local, circuit-specific setup instead of the gnark trusted setup, so
+//! they are a separate key set: the proofs verify against the `Groth16Verifier.sol` written next
+//! to them, and not against the contracts of the released gnark artifacts.
+//!
+//! PLONK is not supported by this backend: the SDK rejects PLONK proofs when it is enabled, and the
+//! PLONK entry points below panic, or fail for [verify_plonk_bn254].
+
+mod babybear;
+mod circuit;
+mod poseidon2;
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::SNARK;
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, PrimeField32};
+use p3_symmetric::Permutation;
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use sp1_recursion_compiler::constraints::Constraint;
+use sp1_stark::inner_perm;
+
+use self::{
+    babybear::{BabyBearChip, Felt},
+    circuit::WrapCircuit,
+    poseidon2::permute_babybear,
+};
+use crate::{witness::GnarkWitness, Groth16Bn254Proof, PlonkBn254Proof};
+
+/// The file the proving key is stored in, in the build directory.
+pub const ARK_GROTH16_PK_FILE: &str = "ark_groth16_pk.bin";
+
+/// The file the verifying key is stored in, in the build directory.
+pub const ARK_GROTH16_VK_FILE: &str = "ark_groth16_vk.bin";
+
+/// Why the PLONK entry points fail.
+const PLONK_UNSUPPORTED: &str = "PLONK is not supported by the arkworks backend, use Groth16";
+
+/// The Solidity verifier of the proofs, filled in with the verifying key.
+const GROTH16_VERIFIER_TEMPLATE: &str = include_str!("../../../assets/ArkGroth16Verifier.txt");
+
+fn read_json<T: serde::de::DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let path = path.as_ref();
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
+
+/// Reads the circuit of the build directory, assigned the witness at `witness_path`.
+fn read_circuit(data_dir: &Path, witness_path: impl AsRef<Path>) -> Result<WrapCircuit> {
+    let constraints: Vec<Constraint> = read_json(data_dir.join("constraints.json"))?;
+    let witness: GnarkWitness = read_json(witness_path)?;
+    Ok(WrapCircuit { constraints, witness })
+}
+
+fn read_proving_key(data_dir: &Path) -> Result<ProvingKey<Bn254>> {
+    let path = data_dir.join(ARK_GROTH16_PK_FILE);
+    let file = File::open(&path).with_context(|| {
+        format!(
+            "failed to open {}, the arkworks groth16 artifacts have not been built",
+            path.display()
+        )
+    })?;
+    Ok(ProvingKey::deserialize_uncompressed_unchecked(BufReader::new(file))?)
+}
+
+fn read_verifying_key(data_dir: &Path) -> Result<VerifyingKey<Bn254>> {
+    let path = data_dir.join(ARK_GROTH16_VK_FILE);
+    let file = File::open(&path).with_context(|| format!("failed to open {}", path.display()))?;
+    Ok(VerifyingKey::deserialize_compressed(BufReader::new(file))?)
+}
+
+fn fq_to_hex(x: &Fq) -> String {
+    format!("0x{}", hex::encode(x.into_bigint().to_bytes_be()))
+}
+
+/// Encodes a point of G1 as the two words `(x, y)`.
+fn g1_to_words(p: &G1Affine) -> [Vec<u8>; 2] {
+    [p.x.into_bigint().to_bytes_be(), p.y.into_bigint().to_bytes_be()]
+}
+
+/// Encodes a point of G2 as the four words `(x.c1, x.c0, y.c1, y.c0)`, as the precompiles expect.
+fn g2_to_words(p: &G2Affine) -> [Vec<u8>; 4] {
+    [p.x.c1, p.x.c0, p.y.c1, p.y.c0].map(|x| x.into_bigint().to_bytes_be())
+}
+
+/// Writes the Solidity verifier of the verifying key, as gnark would export it.
+fn export_solidity(vk: &VerifyingKey<Bn254>, path: &Path) -> Result<()> {
+    if vk.gamma_abc_g1.len() != 3 {
+        return Err(anyhow!("the circuit must have exactly two public inputs"));
+    }
+    let g2 = |name: &str, p: &G2Affine| {
+        [
+            (format!("{{{name}_X_1}}"), fq_to_hex(&p.x.c1)),
+            (format!("{{{name}_X_0}}"), fq_to_hex(&p.x.c0)),
+            (format!("{{{name}_Y_1}}"), fq_to_hex(&p.y.c1)),
+            (format!("{{{name}_Y_0}}"), fq_to_hex(&p.y.c0)),
+        ]
+    };
+    let g1 = |name: &str, p: &G1Affine| {
+        [(format!("{{{name}_X}}"), fq_to_hex(&p.x)), (format!("{{{name}_Y}}"), fq_to_hex(&p.y))]
+    };
+
+    let replacements = g1("ALPHA", &vk.alpha_g1)
+        .into_iter()
+        .chain(g2("BETA", &vk.beta_g2))
+        .chain(g2("GAMMA", &vk.gamma_g2))
+        .chain(g2("DELTA", &vk.delta_g2))
+        .chain(g1("CONSTANT", &vk.gamma_abc_g1[0]))
+        .chain(g1("PUB_0", &vk.gamma_abc_g1[1]))
+        .chain(g1("PUB_1", &vk.gamma_abc_g1[2]));
+    let mut contract = GROTH16_VERIFIER_TEMPLATE.to_string();
+    for (placeholder, value) in replacements {
+        contract = contract.replace(&placeholder, &value);
+    }
+    std::fs::write(path, contract)?;
+    Ok(())
+}
+
+fn build(data_dir: &Path) -> Result<()> {
+    let circuit = read_circuit(data_dir, data_dir.join("groth16_witness.json"))?;
+
+    log::info!("Generating the arkworks groth16 keys");
+    let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut OsRng)?;
+
+    let pk_file = File::create(data_dir.join(ARK_GROTH16_PK_FILE))?;
+    pk.serialize_uncompressed(BufWriter::new(pk_file))?;
+    let vk_file = File::create(data_dir.join(ARK_GROTH16_VK_FILE))?;
+    vk.serialize_compressed(BufWriter::new(vk_file))?;
+    export_solidity(&vk, &data_dir.join("Groth16Verifier.sol"))
+}
+
+pub fn build_groth16_bn254(data_dir: &str) {
+    build(Path::new(data_dir)).expect("failed to build with arkworks");
+}
+
+pub fn build_plonk_bn254(_data_dir: &str) {
+    panic!("{PLONK_UNSUPPORTED}")
+}
+
+fn prove(data_dir: &Path, witness_path: &str) -> Result<Groth16Bn254Proof> {
+    let pk = read_proving_key(data_dir)?;
+    let circuit = read_circuit(data_dir, witness_path)?;
+    let public_inputs =
+        [circuit.witness.vkey_hash.clone(), circuit.witness.committed_values_digest.clone()];
+
+    let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut OsRng)?;
+
+    let encoded_proof = g1_to_words(&proof.a)
+        .into_iter()
+        .chain(g2_to_words(&proof.b))
+        .chain(g1_to_words(&proof.c))
+        .flatten()
+        .collect::<Vec<u8>>();
+    let mut raw_proof = Vec::new();
+    proof.serialize_compressed(&mut raw_proof)?;
+    let vk_path = data_dir.join(ARK_GROTH16_VK_FILE);
+    let vk_bytes =
+        std::fs::read(&vk_path).with_context(|| format!("failed to read {}", vk_path.display()))?;
+
+    Ok(Groth16Bn254Proof {
+        public_inputs,
+        encoded_proof: hex::encode(encoded_proof),
+        raw_proof: hex::encode(raw_proof),
+        groth16_vkey_hash: Sha256::digest(vk_bytes).into(),
+    })
+}
+
+pub fn prove_groth16_bn254(data_dir: &str, witness_path: &str) -> Groth16Bn254Proof {
+    prove(Path::new(data_dir), witness_path).expect("failed to prove with arkworks")
+}
+
+pub fn prove_plonk_bn254(_data_dir: &str, _witness_path: &str) -> PlonkBn254Proof {
+    panic!("{PLONK_UNSUPPORTED}")
+}
+
+pub fn verify_groth16_bn254(
+    data_dir: &str,
+    proof: &str,
+    vkey_hash: &str,
+    committed_values_digest: &str,
+) -> Result<()> {
+    let vk = read_verifying_key(Path::new(data_dir))?;
+    let proof = Proof::<Bn254>::deserialize_compressed(hex::decode(proof)?.as_slice())?;
+    let public_inputs = [vkey_hash, committed_values_digest]
+        .map(|input| Fr::from_str(input).map_err(|_| anyhow!("invalid public input: {input}")));
+    let public_inputs = public_inputs.into_iter().collect::<Result<Vec<_>>>()?;
+
+    if Groth16::<Bn254>::verify(&vk, &public_inputs, &proof)? {
+        Ok(())
+    } else {
+        Err(anyhow!("invalid proof"))
+    }
+}
+
+pub fn verify_plonk_bn254(
+    _data_dir: &str,
+    _proof: &str,
+    _vkey_hash: &str,
+    _committed_values_digest: &str,
+) -> Result<()> {
+    Err(anyhow!(PLONK_UNSUPPORTED))
+}
+
+fn test(witness_json: &str, constraints_json: &str) -> Result<()> {
+    let constraints: Vec<Constraint> = read_json(constraints_json)?;
+    let witness: GnarkWitness = read_json(witness_json)?;
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    WrapCircuit { constraints, witness }.generate_constraints(cs.clone())?;
+    if !cs.is_satisfied()? {
+        return Err(anyhow!("constraint {:?} is not satisfied", cs.which_is_unsatisfied()?));
+    }
+    Ok(())
+}
+
+pub fn test_groth16_bn254(witness_json: &str, constraints_json: &str) {
+    test(witness_json, constraints_json).expect("failed to test with arkworks");
+}
+
+pub fn test_plonk_bn254(_witness_json: &str, _constraints_json: &str) {
+    panic!("{PLONK_UNSUPPORTED}")
+}
+
+pub fn test_babybear_poseidon2() {
+    let input = [BabyBear::zero(); 16];
+    let expected_output = inner_perm().permute(input);
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let chip = BabyBearChip::new(cs.clone());
+    let mut state = input.map(|x| {
+        let value = FpVar::new_witness(cs.clone(), || Ok(Fr::from(x.as_canonical_u32())));
+        Felt::new(value.expect("failed to allocate the input"))
+    });
+    permute_babybear(&chip, &mut state).expect("failed to permute");
+    for (x, expected) in state.iter().zip(expected_output) {
+        chip.assert_eq_f(x, &Felt::constant(expected.as_canonical_u32()))
+            .expect("failed to constrain the output");
+    }
+
+    assert!(cs.is_satisfied().unwrap(), "TestPoseidonBabyBear2 failed");
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    pub fn test_babybear_poseidon2() {
+        super::test_babybear_poseidon2();
+    }
+}
@@ -0,0 +1,171 @@
+//! The Poseidon2 permutations over BN254 and BabyBear, mirroring `go/sp1/poseidon2`.
+
+use std::sync::OnceLock;
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::SynthesisError;
+use num_bigint::BigUint;
+use sp1_primitives::RC_16_30_U32;
+use zkhash::{
+    ark_ff::{BigInteger as _, PrimeField as _},
+    poseidon2::poseidon2_instance_bn256::RC3,
+};
+
+use super::babybear::{BabyBearChip, Felt, MODULUS};
+
+const NUM_EXTERNAL_ROUNDS: usize = 8;
+const NUM_INTERNAL_ROUNDS: usize = 56;
+const BABYBEAR_NUM_EXTERNAL_ROUNDS: usize = 8;
+const BABYBEAR_NUM_INTERNAL_ROUNDS: usize = 13;
+
+/// The round constants of the BN254 permutation.
+fn rc3() -> &'static [[Fr; 3]] {
+    static RC: OnceLock<Vec<[Fr; 3]>> = OnceLock::new();
+    RC.get_or_init(|| {
+        RC3.iter()
+            .map(|round| {
+                std::array::from_fn(|i| {
+                    Fr::from_le_bytes_mod_order(&round[i].into_bigint().to_bytes_le())
+                })
+            })
+            .collect()
+    })
+}
+
+/// Permutes a state of three BN254 elements.
+pub(crate) fn permute_bn254(state: &mut [FpVar<Fr>; 3]) {
+    fn sbox(x: &FpVar<Fr>) -> FpVar<Fr> {
+        let x2 = x * x;
+        let x4 = &x2 * &x2;
+        x4 * x
+    }
+
+    fn external_linear_layer(state: &mut [FpVar<Fr>; 3]) {
+        let sum = &state[0] + &state[1] + &state[2];
+        for x in state.iter_mut() {
+            *x += &sum;
+        }
+    }
+
+    fn internal_linear_layer(state: &mut [FpVar<Fr>; 3]) {
+        let sum = &state[0] + &state[1] + &state[2];
+        state[2] = &state[2] * Fr::from(2u32);
+        for x in state.iter_mut() {
+            *x += &sum;
+        }
+    }
+
+    let rc = rc3();
+    let rounds_f_beginning = NUM_EXTERNAL_ROUNDS / 2;
+    let p_end = rounds_f_beginning + NUM_INTERNAL_ROUNDS;
+
+    external_linear_layer(state);
+    for (r, rc) in rc.iter().enumerate().take(NUM_EXTERNAL_ROUNDS + NUM_INTERNAL_ROUNDS) {
+        if (rounds_f_beginning..p_end).contains(&r) {
+            state[0] = sbox(&(&state[0] + rc[0]));
+            internal_linear_layer(state);
+        } else {
+            for (x, rc) in state.iter_mut().zip(rc) {
+                *x = sbox(&(&*x + *rc));
+            }
+            external_linear_layer(state);
+        }
+    }
+}
+
+/// Permutes a state of sixteen BabyBear elements.
+pub(crate) fn permute_babybear(
+    chip: &BabyBearChip,
+    state: &mut [Felt; 16],
+) -> Result<(), SynthesisError> {
+    fn sbox(chip: &BabyBearChip, x: &Felt) -> Result<Felt, SynthesisError> {
+        let x = chip.reduce_slow(x)?.value;
+        let x2 = &x * &x;
+        let x4 = &x2 * &x2;
+        let x6 = &x4 * &x2;
+        let x7 = x6 * &x;
+        chip.reduce_slow(&Felt { value: x7, upper_bound: BigUint::from(MODULUS).pow(7) })
+    }
+
+    fn mds_light_permutation_4x4(
+        chip: &BabyBearChip,
+        state: &mut [Felt],
+    ) -> Result<(), SynthesisError> {
+        let t01 = chip.add_f(&state[0], &state[1])?;
+        let t23 = chip.add_f(&state[2], &state[3])?;
+        let t0123 = chip.add_f(&t01, &t23)?;
+        let t01123 = chip.add_f(&t0123, &state[1])?;
+        let t01233 = chip.add_f(&t0123, &state[3])?;
+        state[3] = chip.add_f(&t01233, &chip.mul_f_const(&state[0], 2)?)?;
+        state[1] = chip.add_f(&t01123, &chip.mul_f_const(&state[2], 2)?)?;
+        state[0] = chip.add_f(&t01123, &t01)?;
+        state[2] = chip.add_f(&t01233, &t23)?;
+        Ok(())
+    }
+
+    fn external_linear_layer(
+        chip: &BabyBearChip,
+        state: &mut [Felt; 16],
+    ) -> Result<(), SynthesisError> {
+        for chunk in state.chunks_mut(4) {
+            mds_light_permutation_4x4(chip, chunk)?;
+        }
+        let mut sums = [state[0].clone(), state[1].clone(), state[2].clone(), state[3].clone()];
+        for chunk in state[4..].chunks(4) {
+            for (sum, x) in sums.iter_mut().zip(chunk) {
+                *sum = chip.add_f(sum, x)?;
+            }
+        }
+        for (i, x) in state.iter_mut().enumerate() {
+            *x = chip.add_f(x, &sums[i % 4])?;
+        }
+        Ok(())
+    }
+
+    fn internal_linear_layer(
+        chip: &BabyBearChip,
+        state: &mut [Felt; 16],
+    ) -> Result<(), SynthesisError> {
+        const MAT_INTERNAL_DIAG_M1: [u32; 16] =
+            [2013265919, 1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096, 8192, 32768];
+        const MONTY_INVERSE: u32 = 943718400;
+
+        let mut sum = Felt::constant(0u32);
+        for x in state.iter() {
+            sum = chip.add_f(&sum, x)?;
+        }
+        for (x, diag) in state.iter_mut().zip(MAT_INTERNAL_DIAG_M1) {
+            *x = chip.mul_f(x, &Felt::constant(diag))?;
+            *x = chip.add_f(x, &sum)?;
+        }
+        for x in state.iter_mut() {
+            *x = chip.mul_f(x, &Felt::constant(MONTY_INVERSE))?;
+        }
+        Ok(())
+    }
+
+    let rounds_f_beginning = BABYBEAR_NUM_EXTERNAL_ROUNDS / 2;
+    let p_end = rounds_f_beginning + BABYBEAR_NUM_INTERNAL_ROUNDS;
+
+    external_linear_layer(chip, state)?;
+    for (r, rc) in RC_16_30_U32
+        .iter()
+        .enumerate()
+        .take(BABYBEAR_NUM_EXTERNAL_ROUNDS + BABYBEAR_NUM_INTERNAL_ROUNDS)
+    {
+        if (rounds_f_beginning..p_end).contains(&r) {
+            state[0] = chip.add_f(&state[0], &Felt::constant(rc[0]))?;
+            state[0] = sbox(chip, &state[0])?;
+            internal_linear_layer(chip, state)?;
+        } else {
+            for (x, rc) in state.iter_mut().zip(rc) {
+                *x = chip.add_f(x, &Felt::constant(*rc))?;
+                *x = sbox(chip, x)?;
+            }
+            external_linear_layer(chip, state)?;
+        }
+    }
+    Ok(())
+}
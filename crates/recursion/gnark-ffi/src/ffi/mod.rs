@@ -1,5 +1,8 @@
 cfg_if::cfg_if! {
-    if #[cfg(feature = "native")] {
+    if #[cfg(feature = "ark")] {
+        mod ark;
+        pub use ark::*;
+    } else if #[cfg(feature = "native")] {
         mod native;
         pub use native::*;
     } else {
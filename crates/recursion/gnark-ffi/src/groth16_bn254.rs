@@ -18,6 +18,12 @@ use sp1_recursion_compiler::{
     ir::{Config, Witness},
 };
 
+/// The file the Groth16 verifying key is stored in, in the build directory.
+#[cfg(not(feature = "ark"))]
+const GROTH16_VK_FILE: &str = "groth16_vk.bin";
+#[cfg(feature = "ark")]
+const GROTH16_VK_FILE: &str = crate::ffi::ARK_GROTH16_VK_FILE;
+
 /// A prover that can generate proofs with the PLONK protocol using bindings to Gnark.
 #[derive(Debug, Clone)]
 pub struct Groth16Bn254Prover;
@@ -30,7 +36,7 @@ impl Groth16Bn254Prover {
     }
 
     pub fn get_vkey_hash(build_dir: &Path) -> [u8; 32] {
-        let vkey_path = build_dir.join("groth16_vk.bin");
+        let vkey_path = build_dir.join(GROTH16_VK_FILE);
         let vk_bin_bytes = std::fs::read(vkey_path).unwrap();
         Sha256::digest(vk_bin_bytes).into()
     }
@@ -1,13 +1,23 @@
-use sp1_core_executor::{ExecutionReport, HookEnv, SP1ContextBuilder};
+use sp1_core_executor::{
+    CancellationToken, ExecutionReport, HookEnv, ProofProgress, SP1Context, SP1ContextBuilder,
+};
 use sp1_core_machine::io::SP1Stdin;
 use sp1_primitives::io::SP1PublicValues;
-use sp1_prover::{components::DefaultProverComponents, SP1ProvingKey};
+use sp1_prover::{
+    components::DefaultProverComponents, SP1ProvingEstimate, SP1ProvingKey, SP1VerifyingKey,
+};
 
 use anyhow::{Ok, Result};
+use futures::{future::BoxFuture, Stream};
 use sp1_stark::{SP1CoreOpts, SP1ProverOpts};
-use std::time::Duration;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::sync::mpsc;
 
-use crate::{provers::ProofOpts, Prover, SP1ProofKind, SP1ProofWithPublicValues};
+use crate::{
+    aggregation::{aggregation_stdin, AGGREGATION_ELF},
+    provers::ProofOpts,
+    Prover, SP1ProofKind, SP1ProofWithPublicValues,
+};
 
 /// Builder to prepare and configure execution of a program on an input.
 /// May be run with [Self::run].
@@ -16,6 +26,8 @@ pub struct Execute<'a> {
     context_builder: SP1ContextBuilder<'a>,
     elf: &'a [u8],
     stdin: SP1Stdin,
+    resume_from: Option<PathBuf>,
+    snapshot_to: Option<PathBuf>,
 }
 
 impl<'a> Execute<'a> {
@@ -28,14 +40,48 @@ impl<'a> Execute<'a> {
         elf: &'a [u8],
         stdin: SP1Stdin,
     ) -> Self {
-        Self { prover, elf, stdin, context_builder: Default::default() }
+        Self {
+            prover,
+            elf,
+            stdin,
+            context_builder: Default::default(),
+            resume_from: None,
+            snapshot_to: None,
+        }
     }
 
     /// Execute the program on the input, consuming the built action `self`.
     pub fn run(self) -> Result<(SP1PublicValues, ExecutionReport)> {
-        let Self { prover, elf, stdin, mut context_builder } = self;
+        let Self { prover, elf, stdin, mut context_builder, resume_from, snapshot_to } = self;
         let context = context_builder.build();
-        Ok(prover.sp1_prover().execute(elf, &stdin, context)?)
+        if resume_from.is_none() && snapshot_to.is_none() {
+            return Ok(prover.sp1_prover().execute(elf, &stdin, context)?);
+        }
+        Ok(prover.sp1_prover().execute_with_snapshots(
+            elf,
+            &stdin,
+            context,
+            resume_from.as_deref(),
+            snapshot_to.as_deref(),
+        )?)
+    }
+
+    /// Resume the execution from a snapshot previously saved with [Self::snapshot_to].
+    ///
+    /// The input is ignored, since it is part of the snapshot. Snapshots taken while executing a
+    /// different program are rejected.
+    pub fn resume_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.resume_from = Some(path.into());
+        self
+    }
+
+    /// Save a snapshot of the execution to `path` if it exceeds the cycle limit set with
+    /// [Self::max_cycles].
+    ///
+    /// The execution can then be continued with [Self::resume_from] and a higher cycle limit.
+    pub fn snapshot_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.snapshot_to = Some(path.into());
+        self
     }
 
     /// Add a runtime [Hook](super::Hook) into the context.
@@ -69,19 +115,132 @@ impl<'a> Execute<'a> {
         self.context_builder.max_cycles(max_cycles);
         self
     }
+
+    /// Set an environment variable of the program, read in the program with `std::env::var`.
+    ///
+    /// A program which reads its environment appends the [crate::environment_digest] of the
+    /// environment to its public values.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context_builder.env(key, value);
+        self
+    }
+
+    /// Set the seed of the random number generator of the program.
+    ///
+    /// Only programs built with the `verifiable-rand` feature of `sp1-zkvm` use the seed, and
+    /// append it to their public values.
+    pub fn rand_seed(mut self, seed: [u8; 32]) -> Self {
+        self.context_builder.rand_seed(seed);
+        self
+    }
 }
 
-/// Builder to prepare and configure proving execution of a program on an input.
+/// Builder to prepare and configure estimating the cost of proving a program on an input.
 /// May be run with [Self::run].
-pub struct Prove<'a> {
+pub struct Estimate<'a> {
     prover: &'a dyn Prover<DefaultProverComponents>,
-    kind: SP1ProofKind,
     context_builder: SP1ContextBuilder<'a>,
+    elf: &'a [u8],
+    stdin: SP1Stdin,
+    core_opts: SP1CoreOpts,
+}
+
+impl<'a> Estimate<'a> {
+    /// Prepare to estimate the cost of proving the given program on the given input (without
+    /// generating any traces).
+    ///
+    /// Prefer using [ProverClient::estimate](super::ProverClient::estimate).
+    /// See there for more documentation.
+    pub fn new(
+        prover: &'a dyn Prover<DefaultProverComponents>,
+        elf: &'a [u8],
+        stdin: SP1Stdin,
+    ) -> Self {
+        Self {
+            prover,
+            elf,
+            stdin,
+            context_builder: Default::default(),
+            core_opts: SP1CoreOpts::default(),
+        }
+    }
+
+    /// Estimate the cost of proving the program on the input, consuming the built action `self`.
+    pub fn run(self) -> Result<SP1ProvingEstimate> {
+        let Self { prover, elf, stdin, mut context_builder, core_opts } = self;
+        let context = context_builder.build();
+        Ok(prover.sp1_prover().estimate(elf, &stdin, core_opts, context)?)
+    }
+
+    /// Add a runtime [Hook](super::Hook) into the context.
+    ///
+    /// Hooks may be invoked from within SP1 by writing to the specified file descriptor `fd`
+    /// with [`sp1_zkvm::io::write`], returning a list of arbitrary data that may be read
+    /// with successive calls to [`sp1_zkvm::io::read`].
+    pub fn with_hook(
+        mut self,
+        fd: u32,
+        f: impl FnMut(HookEnv, &[u8]) -> Vec<Vec<u8>> + Send + Sync + 'a,
+    ) -> Self {
+        self.context_builder.hook(fd, f);
+        self
+    }
+
+    /// Avoid registering the default hooks in the runtime.
+    ///
+    /// It is not necessary to call this to override hooks --- instead, simply
+    /// register a hook with the same value of `fd` by calling [`Self::hook`].
+    pub fn without_default_hooks(mut self) -> Self {
+        self.context_builder.without_default_hooks();
+        self
+    }
+
+    /// Set the shard size the estimate is computed for.
+    pub fn shard_size(mut self, value: usize) -> Self {
+        self.core_opts.shard_size = value;
+        self
+    }
+
+    /// Set the maximum number of cpu cycles to use for execution.
+    ///
+    /// If the cycle limit is exceeded, execution will return
+    /// [sp1_core_machine::runtime::ExecutionError::ExceededCycleLimit].
+    pub fn max_cycles(mut self, max_cycles: u64) -> Self {
+        self.context_builder.max_cycles(max_cycles);
+        self
+    }
+
+    /// Set an environment variable of the program, read in the program with `std::env::var`.
+    ///
+    /// A program which reads its environment appends the [crate::environment_digest] of the
+    /// environment to its public values.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context_builder.env(key, value);
+        self
+    }
+
+    /// Set the seed of the random number generator of the program.
+    ///
+    /// Only programs built with the `verifiable-rand` feature of `sp1-zkvm` use the seed, and
+    /// append it to their public values.
+    pub fn rand_seed(mut self, seed: [u8; 32]) -> Self {
+        self.context_builder.rand_seed(seed);
+        self
+    }
+}
+
+/// Builder to prepare and configure proving execution of a program on an input.
+/// May be run with [Self::run], [Self::run_async] or [Self::run_with_progress].
+pub struct Prove<'a> {
+    prover: Arc<dyn Prover<DefaultProverComponents>>,
+    kind: SP1ProofKind,
+    context_builder: SP1ContextBuilder<'static>,
     pk: &'a SP1ProvingKey,
     stdin: SP1Stdin,
     core_opts: SP1CoreOpts,
     recursion_opts: SP1CoreOpts,
     timeout: Option<Duration>,
+    checkpoint_dir: Option<PathBuf>,
 }
 
 impl<'a> Prove<'a> {
@@ -90,7 +249,7 @@ impl<'a> Prove<'a> {
     /// Prefer using [ProverClient::prove](super::ProverClient::prove).
     /// See there for more documentation.
     pub fn new(
-        prover: &'a dyn Prover<DefaultProverComponents>,
+        prover: Arc<dyn Prover<DefaultProverComponents>>,
         pk: &'a SP1ProvingKey,
         stdin: SP1Stdin,
     ) -> Self {
@@ -103,11 +262,84 @@ impl<'a> Prove<'a> {
             core_opts: SP1CoreOpts::default(),
             recursion_opts: SP1CoreOpts::recursion(),
             timeout: None,
+            checkpoint_dir: None,
         }
     }
 
     /// Prove the execution of the program on the input, consuming the built action `self`.
     pub fn run(self) -> Result<SP1ProofWithPublicValues> {
+        let (prover, pk, stdin, proof_opts, context, kind) = self.prepare();
+        prover.prove(pk, stdin, proof_opts, context.scoped(), kind)
+    }
+
+    /// Asynchronously prove the execution of the program on the input, consuming the built action
+    /// `self`.
+    ///
+    /// The proof is generated on Tokio's blocking thread pool, so it must be awaited within a Tokio
+    /// runtime. Progress can be observed with [Self::on_progress] and the proof can be cancelled
+    /// with [Self::cancellation]. Dropping the future before it completes cancels the proof.
+    pub async fn run_async(self) -> Result<SP1ProofWithPublicValues> {
+        self.spawn().await
+    }
+
+    /// Asynchronously prove the execution of the program on the input like [Self::run_async],
+    /// returning a stream of the [ProofProgress] events emitted while proving alongside the
+    /// proof's future.
+    ///
+    /// The stream ends once proving has finished. It replaces any callback set with
+    /// [Self::on_progress].
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use sp1_sdk::{ProverClient, SP1Stdin};
+    ///
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, _) = client.setup(elf);
+    /// let (mut progress, proof) = client.prove(&pk, SP1Stdin::new()).run_with_progress();
+    /// let proof = tokio::spawn(proof);
+    /// while let Some(event) = progress.next().await {
+    ///     println!("{event:?}");
+    /// }
+    /// let proof = proof.await??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_with_progress(
+        mut self,
+    ) -> (
+        impl Stream<Item = ProofProgress> + Send + Unpin,
+        BoxFuture<'static, Result<SP1ProofWithPublicValues>>,
+    ) {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        self.context_builder.on_progress(move |event| {
+            // The events are dropped if the caller stopped listening to the stream.
+            let _ = sender.send(event);
+        });
+        let progress = futures::stream::poll_fn(move |cx| receiver.poll_recv(cx));
+        (progress, self.spawn())
+    }
+
+    /// Start proving in the background, owning everything the proof needs.
+    fn spawn(self) -> BoxFuture<'static, Result<SP1ProofWithPublicValues>> {
+        let (prover, pk, stdin, proof_opts, context, kind) = self.prepare();
+        prover.prove_async(Arc::new(pk.clone()), stdin, proof_opts, context, kind)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn prepare(
+        self,
+    ) -> (
+        Arc<dyn Prover<DefaultProverComponents>>,
+        &'a SP1ProvingKey,
+        SP1Stdin,
+        ProofOpts,
+        SP1Context<'static>,
+        SP1ProofKind,
+    ) {
         let Self {
             prover,
             kind,
@@ -117,9 +349,11 @@ impl<'a> Prove<'a> {
             core_opts,
             recursion_opts,
             timeout,
+            checkpoint_dir,
         } = self;
-        let opts = SP1ProverOpts { core_opts, recursion_opts };
-        let proof_opts = ProofOpts { sp1_prover_opts: opts, timeout };
+        let security_profile = prover.sp1_prover().security_profile;
+        let opts = SP1ProverOpts { core_opts, recursion_opts, security_profile };
+        let proof_opts = ProofOpts { sp1_prover_opts: opts, timeout, checkpoint_dir };
         let context = context_builder.build();
 
         // Dump the program and stdin to files for debugging if `SP1_DUMP` is set.
@@ -133,7 +367,7 @@ impl<'a> Prove<'a> {
             std::fs::write("stdin.bin", stdin.clone()).unwrap();
         }
 
-        prover.prove(pk, stdin, proof_opts, context, kind)
+        (prover, pk, stdin, proof_opts, context, kind)
     }
 
     /// Set the proof kind to the core mode. This is the default.
@@ -165,10 +399,12 @@ impl<'a> Prove<'a> {
     /// Hooks may be invoked from within SP1 by writing to the specified file descriptor `fd`
     /// with [`sp1_zkvm::io::write`], returning a list of arbitrary data that may be read
     /// with successive calls to [`sp1_zkvm::io::read`].
+    ///
+    /// The hook must be `'static`, since the proof may be generated on another thread.
     pub fn with_hook(
         mut self,
         fd: u32,
-        f: impl FnMut(HookEnv, &[u8]) -> Vec<Vec<u8>> + Send + Sync + 'a,
+        f: impl FnMut(HookEnv, &[u8]) -> Vec<Vec<u8>> + Send + Sync + 'static,
     ) -> Self {
         self.context_builder.hook(fd, f);
         self
@@ -210,6 +446,24 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Set an environment variable of the program, read in the program with `std::env::var`.
+    ///
+    /// A program which reads its environment appends the [crate::environment_digest] of the
+    /// environment to its public values.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context_builder.env(key, value);
+        self
+    }
+
+    /// Set the seed of the random number generator of the program.
+    ///
+    /// Only programs built with the `verifiable-rand` feature of `sp1-zkvm` use the seed, and
+    /// append it to their public values.
+    pub fn rand_seed(mut self, seed: [u8; 32]) -> Self {
+        self.context_builder.rand_seed(seed);
+        self
+    }
+
     /// Set the timeout for the proof's generation.
     ///
     /// This parameter is only used when the prover is run in network mode.
@@ -217,4 +471,120 @@ impl<'a> Prove<'a> {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Checkpoint the intermediate proofs of every stage to `dir`, and resume from the proofs
+    /// already checkpointed there.
+    ///
+    /// If proving is interrupted, running the same proof again with the same directory continues
+    /// from the last completed stage instead of starting over. Checkpoints made for a different
+    /// program or input are rejected.
+    ///
+    /// This parameter is only used when the prover is run in CPU mode.
+    pub fn checkpoint_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.checkpoint_dir = Some(dir.into());
+        self
+    }
+
+    /// Set a callback invoked with the [ProofProgress] events emitted while proving, such as
+    /// shards being proven or recursion layers being reached.
+    ///
+    /// To consume the events as a stream, use [Self::run_with_progress] instead.
+    pub fn on_progress(mut self, f: impl Fn(ProofProgress) + Send + Sync + 'static) -> Self {
+        self.context_builder.on_progress(f);
+        self
+    }
+
+    /// Set the token used to cancel the proof's generation.
+    ///
+    /// Cancelling the token makes the prover stop scheduling new work and return an error once
+    /// the work in flight has finished.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.context_builder.cancellation(token);
+        self
+    }
+}
+
+/// Builder to prepare and configure aggregating many proofs into a single proof.
+/// May be run with [Self::run].
+pub struct Aggregate<'a> {
+    prover: &'a dyn Prover<DefaultProverComponents>,
+    kind: SP1ProofKind,
+    context_builder: SP1ContextBuilder<'a>,
+    proofs: Vec<(SP1ProofWithPublicValues, SP1VerifyingKey)>,
+    core_opts: SP1CoreOpts,
+    recursion_opts: SP1CoreOpts,
+    timeout: Option<Duration>,
+}
+
+impl<'a> Aggregate<'a> {
+    /// Prepare to aggregate the given proofs, each with the verifying key it is verified with.
+    ///
+    /// Prefer using [ProverClient::aggregate](super::ProverClient::aggregate).
+    /// See there for more documentation.
+    pub fn new(
+        prover: &'a dyn Prover<DefaultProverComponents>,
+        proofs: Vec<(SP1ProofWithPublicValues, SP1VerifyingKey)>,
+    ) -> Self {
+        Self {
+            prover,
+            kind: SP1ProofKind::Compressed,
+            proofs,
+            context_builder: Default::default(),
+            core_opts: SP1CoreOpts::default(),
+            recursion_opts: SP1CoreOpts::recursion(),
+            timeout: None,
+        }
+    }
+
+    /// Aggregate the proofs, consuming the built action `self`.
+    ///
+    /// Fails if any of the proofs is not compressed.
+    pub fn run(self) -> Result<SP1ProofWithPublicValues> {
+        let Self { prover, kind, mut context_builder, proofs, core_opts, recursion_opts, timeout } =
+            self;
+        let stdin = aggregation_stdin(proofs)?;
+        let (pk, _) = prover.setup(&AGGREGATION_ELF);
+        let security_profile = prover.sp1_prover().security_profile;
+        let opts = SP1ProverOpts { core_opts, recursion_opts, security_profile };
+        let proof_opts = ProofOpts { sp1_prover_opts: opts, timeout, checkpoint_dir: None };
+        prover.prove(&pk, stdin, proof_opts, context_builder.build(), kind)
+    }
+
+    /// Set the proof kind to the compressed mode. This is the default.
+    pub fn compressed(mut self) -> Self {
+        self.kind = SP1ProofKind::Compressed;
+        self
+    }
+
+    /// Set the proof mode to the plonk bn254 mode.
+    pub fn plonk(mut self) -> Self {
+        self.kind = SP1ProofKind::Plonk;
+        self
+    }
+
+    /// Set the proof mode to the groth16 bn254 mode.
+    pub fn groth16(mut self) -> Self {
+        self.kind = SP1ProofKind::Groth16;
+        self
+    }
+
+    /// Set the timeout for the proof's generation.
+    ///
+    /// This parameter is only used when the prover is run in network mode.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set a callback invoked with the [ProofProgress] events emitted while proving.
+    pub fn on_progress(mut self, f: impl Fn(ProofProgress) + Send + Sync + 'static) -> Self {
+        self.context_builder.on_progress(f);
+        self
+    }
+
+    /// Set the token used to cancel the proof's generation.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.context_builder.cancellation(token);
+        self
+    }
 }
@@ -0,0 +1,310 @@
+//! Aggregation of many proofs into a single proof.
+//!
+//! The proofs are aggregated natively, through the deferred proofs of the compressed proof: while
+//! compressing the proof of a program, the recursion prover verifies the deferred proofs and
+//! checks that the digest the program committed to is the digest of their verifying keys and
+//! public values. [AGGREGATION_ELF] is a fixed program, assembled here rather than compiled from a
+//! guest crate, which commits to that digest and makes it its public values.
+//!
+//! The aggregated proof therefore commits to the (vkey hash, public values digest) pair of every
+//! proof, hashed by [commit_proof_pairs].
+
+use std::sync::LazyLock;
+
+use anyhow::{bail, Result};
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, PrimeField32};
+use sp1_core_executor::syscalls::SyscallCode;
+use sp1_core_machine::io::SP1Stdin;
+use sp1_primitives::hash_deferred_proof;
+use sp1_prover::{SP1Prover, SP1VerifyingKey};
+
+use crate::{SP1Proof, SP1ProofWithPublicValues};
+
+/// The program that aggregates proofs.
+///
+/// Aggregated proofs are verified with the verifying key of this program, obtained with
+/// [ProverClient::setup](crate::ProverClient::setup).
+pub static AGGREGATION_ELF: LazyLock<Vec<u8>> = LazyLock::new(assemble_aggregation_elf);
+
+/// Writes the inputs of [AGGREGATION_ELF] for aggregating `proofs`.
+///
+/// Only compressed proofs can be aggregated.
+pub(crate) fn aggregation_stdin(
+    proofs: Vec<(SP1ProofWithPublicValues, SP1VerifyingKey)>,
+) -> Result<SP1Stdin> {
+    if proofs.is_empty() {
+        bail!("there are no proofs to aggregate");
+    }
+
+    let mut reduce_proofs = Vec::with_capacity(proofs.len());
+    let mut vks = Vec::with_capacity(proofs.len());
+    for (proof, vk) in proofs {
+        let SP1Proof::Compressed(proof) = proof.proof else {
+            bail!("only compressed proofs can be aggregated");
+        };
+        reduce_proofs.push(*proof);
+        vks.push(vk.vk);
+    }
+
+    // Hint the digest the recursion prover reconstructs from the deferred proofs, which the
+    // program commits to.
+    let digest = <SP1Prover>::hash_deferred_proofs([BabyBear::zero(); 8], &reduce_proofs);
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&digest_to_bytes(&digest));
+
+    for (proof, vk) in reduce_proofs.into_iter().zip(vks) {
+        stdin.write_proof(proof, vk);
+    }
+    Ok(stdin)
+}
+
+/// The public values of the proof aggregating the proofs with the given verifying key hashes, as
+/// returned by [HashableKey::hash_u32](crate::HashableKey::hash_u32), and public values digests, as
+/// returned by [SP1PublicValues::hash](crate::SP1PublicValues::hash).
+///
+/// The pairs are hashed in the order the proofs were aggregated in, as by `verify_sp1_proof`. The
+/// result is the Poseidon2 digest, with its words encoded in little endian.
+pub fn commit_proof_pairs(pairs: &[([u32; 8], [u8; 32])]) -> [u8; 32] {
+    let digest = pairs.iter().fold([BabyBear::zero(); 8], |digest, (vkey, public_values)| {
+        hash_deferred_proof(
+            &digest,
+            &vkey.map(BabyBear::from_canonical_u32),
+            &public_values.map(BabyBear::from_canonical_u8),
+        )
+    });
+    digest_to_bytes(&digest)
+}
+
+fn digest_to_bytes(digest: &[BabyBear; 8]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(digest) {
+        chunk.copy_from_slice(&word.as_canonical_u32().to_le_bytes());
+    }
+    bytes
+}
+
+/// The address [AGGREGATION_ELF] is loaded at.
+const TEXT_ADDR: u32 = 0x0020_0800;
+
+/// The address of the hinted digest of the deferred proofs.
+const DIGEST_ADDR: u32 = 0x1000_0000;
+
+/// The address of the message schedule of the SHA-256 block of the public values.
+const W_ADDR: u32 = DIGEST_ADDR + 0x40;
+
+/// The address of the SHA-256 state.
+const H_ADDR: u32 = W_ADDR + 0x100;
+
+/// The initial SHA-256 state.
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const ZERO: u32 = 0;
+const T0: u32 = 5;
+const T1: u32 = 6;
+const T2: u32 = 7;
+const S0: u32 = 8;
+const S1: u32 = 9;
+const A0: u32 = 10;
+const A1: u32 = 11;
+const A2: u32 = 12;
+const S2: u32 = 18;
+const T3: u32 = 28;
+const T4: u32 = 29;
+
+/// A minimal RV32I assembler for the straight-line code of [AGGREGATION_ELF].
+#[derive(Default)]
+struct Assembler {
+    code: Vec<u32>,
+}
+
+impl Assembler {
+    fn i_type(&mut self, opcode: u32, funct3: u32, rd: u32, rs1: u32, imm: i32) {
+        let imm = (imm as u32) & 0xfff;
+        self.code.push((imm << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | opcode);
+    }
+
+    fn r_type(&mut self, funct3: u32, rd: u32, rs1: u32, rs2: u32) {
+        self.code.push((rs2 << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | 0x33);
+    }
+
+    fn lui(&mut self, rd: u32, imm: u32) {
+        self.code.push((imm << 12) | (rd << 7) | 0x37);
+    }
+
+    fn addi(&mut self, rd: u32, rs1: u32, imm: i32) {
+        self.i_type(0x13, 0, rd, rs1, imm);
+    }
+
+    fn slli(&mut self, rd: u32, rs1: u32, shamt: i32) {
+        self.i_type(0x13, 1, rd, rs1, shamt);
+    }
+
+    fn srli(&mut self, rd: u32, rs1: u32, shamt: i32) {
+        self.i_type(0x13, 5, rd, rs1, shamt);
+    }
+
+    fn or(&mut self, rd: u32, rs1: u32, rs2: u32) {
+        self.r_type(6, rd, rs1, rs2);
+    }
+
+    fn and(&mut self, rd: u32, rs1: u32, rs2: u32) {
+        self.r_type(7, rd, rs1, rs2);
+    }
+
+    fn lw(&mut self, rd: u32, rs1: u32, offset: i32) {
+        self.i_type(0x03, 2, rd, rs1, offset);
+    }
+
+    fn sw(&mut self, rs2: u32, rs1: u32, offset: i32) {
+        let imm = (offset as u32) & 0xfff;
+        self.code.push(
+            ((imm >> 5) << 25) | (rs2 << 20) | (rs1 << 15) | (2 << 12) | ((imm & 0x1f) << 7) | 0x23,
+        );
+    }
+
+    /// Loads the constant `value` into `rd`.
+    fn li(&mut self, rd: u32, value: u32) {
+        // The low 12 bits are sign extended by `addi`, so round the upper 20 bits accordingly.
+        let lo = ((value << 20) as i32) >> 20;
+        let hi = value.wrapping_sub(lo as u32) >> 12;
+        if hi == 0 {
+            self.addi(rd, ZERO, lo);
+        } else {
+            self.lui(rd, hi);
+            self.addi(rd, rd, lo);
+        }
+    }
+
+    /// Invokes `syscall` with the arguments already in `a0` and `a1`.
+    fn ecall(&mut self, syscall: SyscallCode) {
+        self.li(T0, syscall as u32);
+        self.code.push(0x73);
+    }
+
+    /// Reverses the bytes of `rs` into `rd`, using `t2` as scratch and `t4` as the mask `0xff00`.
+    fn bswap(&mut self, rd: u32, rs: u32) {
+        self.slli(rd, rs, 24);
+        self.srli(T2, rs, 24);
+        self.or(rd, rd, T2);
+        self.and(T2, rs, T4);
+        self.slli(T2, T2, 8);
+        self.or(rd, rd, T2);
+        self.srli(T2, rs, 8);
+        self.and(T2, T2, T4);
+        self.or(rd, rd, T2);
+    }
+}
+
+/// Assembles the aggregation program, which:
+///
+/// 1. reads the digest of the deferred proofs from the input and commits to it as the deferred
+///    proofs digest, which the recursion prover checks against the deferred proofs,
+/// 2. writes the digest to the public values, and
+/// 3. commits to the SHA-256 digest of the public values, a single padded block, as the guest
+///    entrypoint does when halting.
+fn assemble_aggregation_elf() -> Vec<u8> {
+    let mut asm = Assembler::default();
+    asm.li(S0, DIGEST_ADDR);
+    asm.li(S1, W_ADDR);
+    asm.li(S2, H_ADDR);
+    asm.li(T4, 0xff00);
+
+    // Read the digest of the deferred proofs.
+    asm.addi(A0, S0, 0);
+    asm.addi(A1, ZERO, 32);
+    asm.ecall(SyscallCode::HINT_READ);
+
+    // Commit to it as the deferred proofs digest.
+    for i in 0..8 {
+        asm.addi(A0, ZERO, i);
+        asm.lw(A1, S0, 4 * i);
+        asm.ecall(SyscallCode::COMMIT_DEFERRED_PROOFS);
+    }
+
+    // Write it to the public values.
+    asm.addi(A0, ZERO, 3);
+    asm.addi(A1, S0, 0);
+    asm.addi(A2, ZERO, 32);
+    asm.ecall(SyscallCode::WRITE);
+
+    // Pad the public values into a single SHA-256 block of big-endian words.
+    for i in 0..8 {
+        asm.lw(T3, S0, 4 * i);
+        asm.bswap(T1, T3);
+        asm.sw(T1, S1, 4 * i);
+    }
+    asm.li(T1, 0x8000_0000);
+    asm.sw(T1, S1, 32);
+    for i in 9..15 {
+        asm.sw(ZERO, S1, 4 * i);
+    }
+    asm.addi(T1, ZERO, 256);
+    asm.sw(T1, S1, 60);
+
+    // Hash the block.
+    asm.addi(A0, S1, 0);
+    asm.addi(A1, ZERO, 0);
+    asm.ecall(SyscallCode::SHA_EXTEND);
+    for (i, word) in SHA256_IV.into_iter().enumerate() {
+        asm.li(T1, word);
+        asm.sw(T1, S2, 4 * i as i32);
+    }
+    asm.addi(A0, S1, 0);
+    asm.addi(A1, S2, 0);
+    asm.ecall(SyscallCode::SHA_COMPRESS);
+
+    // Commit to the digest, whose bytes are read back as little-endian words.
+    for i in 0..8 {
+        asm.lw(T3, S2, 4 * i);
+        asm.bswap(A1, T3);
+        asm.addi(A0, ZERO, i);
+        asm.ecall(SyscallCode::COMMIT);
+    }
+
+    // Halt with exit code 0.
+    asm.addi(A0, ZERO, 0);
+    asm.ecall(SyscallCode::HALT);
+
+    elf_executable(&asm.code)
+}
+
+/// Wraps `code` into a RISC-V ELF executable with a single segment loaded at [TEXT_ADDR].
+fn elf_executable(code: &[u32]) -> Vec<u8> {
+    const EHDR_LEN: u16 = 52;
+    const PHDR_LEN: u16 = 32;
+    let code_len = (code.len() * 4) as u32;
+
+    let mut elf = Vec::new();
+    // e_ident: magic, 32-bit, little endian, version 1.
+    elf.extend_from_slice(&[0x7f, b'E', b'L', b'F', 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    elf.extend_from_slice(&2u16.to_le_bytes()); // e_type: ET_EXEC
+    elf.extend_from_slice(&243u16.to_le_bytes()); // e_machine: EM_RISCV
+    elf.extend_from_slice(&1u32.to_le_bytes()); // e_version
+    elf.extend_from_slice(&TEXT_ADDR.to_le_bytes()); // e_entry
+    elf.extend_from_slice(&u32::from(EHDR_LEN).to_le_bytes()); // e_phoff
+    elf.extend_from_slice(&0u32.to_le_bytes()); // e_shoff
+    elf.extend_from_slice(&0u32.to_le_bytes()); // e_flags
+    elf.extend_from_slice(&EHDR_LEN.to_le_bytes()); // e_ehsize
+    elf.extend_from_slice(&PHDR_LEN.to_le_bytes()); // e_phentsize
+    elf.extend_from_slice(&1u16.to_le_bytes()); // e_phnum
+    elf.extend_from_slice(&40u16.to_le_bytes()); // e_shentsize
+    elf.extend_from_slice(&0u16.to_le_bytes()); // e_shnum
+    elf.extend_from_slice(&0u16.to_le_bytes()); // e_shstrndx
+
+    elf.extend_from_slice(&1u32.to_le_bytes()); // p_type: PT_LOAD
+    elf.extend_from_slice(&u32::from(EHDR_LEN + PHDR_LEN).to_le_bytes()); // p_offset
+    elf.extend_from_slice(&TEXT_ADDR.to_le_bytes()); // p_vaddr
+    elf.extend_from_slice(&TEXT_ADDR.to_le_bytes()); // p_paddr
+    elf.extend_from_slice(&code_len.to_le_bytes()); // p_filesz
+    elf.extend_from_slice(&code_len.to_le_bytes()); // p_memsz
+    elf.extend_from_slice(&5u32.to_le_bytes()); // p_flags: PF_R | PF_X
+    elf.extend_from_slice(&4u32.to_le_bytes()); // p_align
+
+    for instruction in code {
+        elf.extend_from_slice(&instruction.to_le_bytes());
+    }
+    elf
+}
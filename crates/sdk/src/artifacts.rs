@@ -12,7 +12,9 @@ use {
 
 pub use sp1_prover::build::build_plonk_bn254_artifacts_with_dummy;
 
-use crate::install::try_install_circuit_artifacts;
+use sp1_stark::SecurityProfile;
+
+use crate::install::{groth16_bn254_artifacts_dir, plonk_bn254_artifacts_dir};
 
 /// Exports the solidity verifier for PLONK proofs to the specified output directory.
 ///
@@ -20,11 +22,7 @@ use crate::install::try_install_circuit_artifacts;
 /// already been built.
 pub fn export_solidity_plonk_bn254_verifier(output_dir: impl Into<PathBuf>) -> Result<()> {
     let output_dir: PathBuf = output_dir.into();
-    let artifacts_dir = if sp1_prover::build::sp1_dev_mode() {
-        sp1_prover::build::plonk_bn254_artifacts_dev_dir()
-    } else {
-        try_install_circuit_artifacts("plonk")
-    };
+    let artifacts_dir = plonk_bn254_artifacts_dir(&SecurityProfile::default());
     let verifier_path = artifacts_dir.join("SP1VerifierPlonk.sol");
 
     if !verifier_path.exists() {
@@ -45,15 +43,11 @@ pub fn export_solidity_plonk_bn254_verifier(output_dir: impl Into<PathBuf>) -> R
 
 /// Exports the solidity verifier for Groth16 proofs to the specified output directory.
 ///
-/// WARNING: If you are on development mode, this function assumes that the Groth16 artifacts have
-/// already been built.
+/// WARNING: If you are on development mode or using the `ark` feature, this function assumes that
+/// the Groth16 artifacts have already been built.
 pub fn export_solidity_groth16_bn254_verifier(output_dir: impl Into<PathBuf>) -> Result<()> {
     let output_dir: PathBuf = output_dir.into();
-    let artifacts_dir = if sp1_prover::build::sp1_dev_mode() {
-        sp1_prover::build::groth16_bn254_artifacts_dev_dir()
-    } else {
-        try_install_circuit_artifacts("groth16")
-    };
+    let artifacts_dir = groth16_bn254_artifacts_dir(&SecurityProfile::default());
     let verifier_path = artifacts_dir.join("SP1VerifierGroth16.sol");
 
     if !verifier_path.exists() {
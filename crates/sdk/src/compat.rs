@@ -0,0 +1,138 @@
+//! Verification of proofs generated with other versions of SP1.
+//!
+//! [Prover::verify](crate::Prover::verify) rejects proofs whose `sp1_version` differs from the
+//! circuit version of the prover with [SP1VerificationError::VersionMismatch], unless a verifier
+//! is registered for the version of the proof. Registering verifiers keeps archived proofs
+//! verifiable after upgrading SP1:
+//!
+//! ```no_run
+//! use sp1_sdk::compat::{register_verifier, CircuitArtifactsVerifier};
+//!
+//! // Verify the Plonk and Groth16 proofs of v3.0.0 with the circuit artifacts of v3.0.0.
+//! register_verifier("v3.0.0", CircuitArtifactsVerifier);
+//! ```
+//!
+//! Core and compressed proofs can only be verified by the machines of the version they were
+//! generated with, so verifying them requires registering a verifier backed by that version of
+//! SP1, for example through a renamed dependency on an older `sp1-sdk`.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use sp1_prover::{
+    verify::{verify_groth16_bn254_proof, verify_plonk_bn254_proof},
+    SP1VerifyingKey,
+};
+
+use crate::{
+    install::try_install_circuit_artifacts_for_version, SP1Proof, SP1ProofWithPublicValues,
+    SP1VerificationError,
+};
+
+/// A verifier for the proofs of a circuit version other than the one of the prover.
+pub trait VersionVerifier: Send + Sync {
+    /// Verifies that the proof is valid given its vkey and metadata.
+    fn verify(
+        &self,
+        bundle: &SP1ProofWithPublicValues,
+        vkey: &SP1VerifyingKey,
+    ) -> Result<(), SP1VerificationError>;
+}
+
+impl<F> VersionVerifier for F
+where
+    F: Fn(&SP1ProofWithPublicValues, &SP1VerifyingKey) -> Result<(), SP1VerificationError>
+        + Send
+        + Sync,
+{
+    fn verify(
+        &self,
+        bundle: &SP1ProofWithPublicValues,
+        vkey: &SP1VerifyingKey,
+    ) -> Result<(), SP1VerificationError> {
+        self(bundle, vkey)
+    }
+}
+
+type Registry = RwLock<HashMap<String, Arc<dyn VersionVerifier>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Registers the verifier of the proofs of a circuit version, replacing any verifier previously
+/// registered for it.
+pub fn register_verifier(version: impl Into<String>, verifier: impl VersionVerifier + 'static) {
+    registry().write().unwrap().insert(version.into(), Arc::new(verifier));
+}
+
+/// Unregisters the verifier of the proofs of a circuit version, returning whether one was
+/// registered.
+pub fn unregister_verifier(version: &str) -> bool {
+    registry().write().unwrap().remove(version).is_some()
+}
+
+/// Returns the verifier registered for the proofs of a circuit version.
+pub fn registered_verifier(version: &str) -> Option<Arc<dyn VersionVerifier>> {
+    registry().read().unwrap().get(version).cloned()
+}
+
+/// Verifies Plonk and Groth16 proofs with the circuit artifacts of the version they were generated
+/// with, installing the artifacts if they are not already installed.
+///
+/// The program vkey is hashed as in the current version, so this requires the hashing of the
+/// vkey to be unchanged between the versions. Core and compressed proofs are rejected with
+/// [SP1VerificationError::VersionMismatch].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CircuitArtifactsVerifier;
+
+impl VersionVerifier for CircuitArtifactsVerifier {
+    fn verify(
+        &self,
+        bundle: &SP1ProofWithPublicValues,
+        vkey: &SP1VerifyingKey,
+    ) -> Result<(), SP1VerificationError> {
+        let version = &bundle.sp1_version;
+        match &bundle.proof {
+            SP1Proof::Plonk(proof) => verify_plonk_bn254_proof(
+                proof,
+                vkey,
+                &bundle.public_values,
+                &try_install_circuit_artifacts_for_version("plonk", version),
+            )
+            .map_err(SP1VerificationError::Plonk),
+            SP1Proof::Groth16(proof) => verify_groth16_bn254_proof(
+                proof,
+                vkey,
+                &bundle.public_values,
+                &try_install_circuit_artifacts_for_version("groth16", version),
+            )
+            .map_err(SP1VerificationError::Groth16),
+            SP1Proof::Core(_) | SP1Proof::Compressed(_) => {
+                Err(SP1VerificationError::VersionMismatch(version.clone()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_verifier() {
+        let version = "v0.0.0-test";
+        assert!(registered_verifier(version).is_none());
+
+        register_verifier(version, |bundle: &SP1ProofWithPublicValues, _: &SP1VerifyingKey| {
+            Err(SP1VerificationError::VersionMismatch(bundle.sp1_version.clone()))
+        });
+        assert!(registered_verifier(version).is_some());
+
+        assert!(unregister_verifier(version));
+        assert!(registered_verifier(version).is_none());
+    }
+}
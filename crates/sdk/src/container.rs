@@ -0,0 +1,497 @@
+//! A self-describing, versioned encoding of [SP1ProofWithPublicValues].
+//!
+//! [SP1ProofWithPublicValues::save] writes proofs in this encoding, so that the kind, the circuit
+//! version and the program of an archived proof can be read without decoding the proof itself,
+//! even by a different version of SP1. All integers are little endian:
+//!
+//! | Offset | Size | Field                                                                   |
+//! |--------|------|-------------------------------------------------------------------------|
+//! | 0      | 8    | The magic bytes `SP1PROOF`.                                             |
+//! | 8      | 2    | The format version, currently 1.                                        |
+//! | 10     | 1    | The proof kind: 0 for core, 1 for compressed, 2 for plonk, 3 for groth16. |
+//! | 11     | 1    | The length `n` of the circuit version.                                  |
+//! | 12     | n    | The circuit version the proof was generated with, in UTF-8.             |
+//! | 12 + n | 32   | The vkey hash of the program, as `vk.bytes32()`, or zeros if unknown.   |
+//! | 44 + n | 1    | The soundness of the security level: 0 if unknown, 1 for conjectured, 2 for proven. |
+//! | 45 + n | 2    | The bits of the security level, or zero if unknown.                     |
+//! | 47 + n | 8    | The length `m` of the payload.                                          |
+//! | 55 + n | m    | The payload.                                                            |
+//!
+//! The payload is a sequence of fields, where a byte string is encoded as its length in 8 bytes
+//! followed by its bytes:
+//!
+//! | Field         | Encoding                                                                  |
+//! |---------------|---------------------------------------------------------------------------|
+//! | Public values | A byte string of the public values committed by the program.             |
+//! | Stdin         | A byte string of the versioned bincode encoding of the [SP1Stdin].         |
+//! | Proof         | The proof of the kind of the header, as below.                            |
+//!
+//! Core and compressed proofs are STARK proofs, which only verify against the circuit of the
+//! version in the header, and are stored as a byte string of the bincode 1 encoding, with its
+//! default fixed width little endian integers, of the proof types of that version. Plonk and
+//! groth16 proofs are stored field by field, so that they can be read without SP1:
+//!
+//! | Kind       | Proof                                                                        |
+//! |------------|------------------------------------------------------------------------------|
+//! | core       | A byte string of the bincode encoding of the shard proofs.                  |
+//! | compressed | A byte string of the bincode encoding of the reduce proof and its vkey.     |
+//! | plonk      | The gnark proof fields, then the 32 byte plonk vkey hash.                    |
+//! | groth16    | The gnark proof fields, then the 32 byte groth16 vkey hash.                  |
+//!
+//! The gnark proof fields are the two public inputs in decimal, the hex encoded gnark proof and the
+//! raw proof, each a UTF-8 byte string. The public inputs are empty for mock proofs.
+//!
+//! The kind determines where the vkey hash of the header comes from:
+//!
+//! | Kind       | Vkey hash                                                                   |
+//! |------------|-----------------------------------------------------------------------------|
+//! | core       | Zeros, unless set with [SP1ProofContainer::with_vkey].                       |
+//! | compressed | The `sp1_vk_digest` of its public values.                                   |
+//! | plonk      | The first public input, or zeros for mock proofs.                           |
+//! | groth16    | The first public input, or zeros for mock proofs.                           |
+//!
+//! Loading a container fails if the vkey hash of its header does not match the decoded proof, or
+//! if the payload has bytes left after the proof.
+//!
+//! Files without the magic bytes are proofs saved by earlier versions of SP1 as plain bincode, and
+//! are still loaded by [SP1ProofWithPublicValues::load].
+
+use std::{
+    borrow::Borrow,
+    io::{Read, Write},
+    str::FromStr,
+};
+
+use num_bigint::BigUint;
+use p3_field::PrimeField;
+use sp1_core_machine::io::SP1Stdin;
+use sp1_primitives::io::SP1PublicValues;
+use sp1_prover::{
+    utils::babybears_to_bn254, Groth16Bn254Proof, HashableKey, PlonkBn254Proof, SP1VerifyingKey,
+};
+use sp1_recursion_core::air::RecursionPublicValues;
+use sp1_stark::{SecurityLevel, Soundness};
+use thiserror::Error;
+
+use crate::{SP1Proof, SP1ProofKind, SP1ProofWithPublicValues};
+
+/// The magic bytes every proof container starts with.
+pub const SP1_PROOF_MAGIC: [u8; 8] = *b"SP1PROOF";
+
+/// The version of the encoding of the proof containers written by this version of SP1.
+pub const SP1_PROOF_FORMAT_VERSION: u16 = 1;
+
+#[derive(Error, Debug)]
+pub enum SP1ProofContainerError {
+    #[error("Not an SP1 proof container")]
+    InvalidMagic,
+    #[error("Unsupported proof format version: {0}")]
+    UnsupportedFormatVersion(u16),
+    #[error("Invalid proof kind: {0}")]
+    InvalidProofKind(u8),
+    #[error("Invalid circuit version")]
+    InvalidCircuitVersion,
+    #[error("Invalid security level")]
+    InvalidSecurityLevel,
+    #[error("Invalid payload")]
+    InvalidPayload,
+    #[error("The header does not match the proof")]
+    HeaderMismatch,
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+}
+
+/// The header of a proof container, describing the proof it holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SP1ProofHeader {
+    /// The kind of the proof.
+    pub kind: SP1ProofKind,
+    /// The circuit version the proof was generated with.
+    pub sp1_version: String,
+    /// The vkey hash of the program, as `vk.bytes32()`, or zeros if unknown.
+    pub vkey_hash: [u8; 32],
+    /// The security level of the proof, which is not part of the bincode encoding of the proof.
+    pub security_level: Option<SecurityLevel>,
+}
+
+/// A proof together with the header describing it.
+///
+/// The payload is kept encoded, so that the header of a proof can be read even if this version of
+/// SP1 can no longer decode the proof.
+#[derive(Debug, Clone)]
+pub struct SP1ProofContainer {
+    /// The header describing the proof.
+    pub header: SP1ProofHeader,
+    /// The payload, in the encoding described in the [module documentation](self).
+    pub payload: Vec<u8>,
+}
+
+impl SP1ProofContainer {
+    /// Creates a container for the proof.
+    ///
+    /// The vkey hash is read from the proof if it commits to it, which is the case for compressed,
+    /// plonk and groth16 proofs. Use [SP1ProofContainer::with_vkey] to set it for core proofs.
+    pub fn new(proof: &SP1ProofWithPublicValues) -> Result<Self, SP1ProofContainerError> {
+        let header = SP1ProofHeader {
+            kind: SP1ProofKind::from(&proof.proof),
+            sp1_version: proof.sp1_version.clone(),
+            vkey_hash: committed_vkey_hash(&proof.proof)?.unwrap_or_default(),
+            security_level: proof.security_level,
+        };
+        Ok(Self { header, payload: encode_payload(proof)? })
+    }
+
+    /// Sets the vkey hash of the header to the hash of the verifying key.
+    pub fn with_vkey(mut self, vk: &SP1VerifyingKey) -> Self {
+        self.header.vkey_hash = bn254_to_bytes32(&vk.hash_bn254().as_canonical_biguint())
+            .expect("a bn254 field element fits in 32 bytes");
+        self
+    }
+
+    /// Returns whether the bytes start with the magic bytes of a proof container.
+    pub fn is_container(bytes: &[u8]) -> bool {
+        bytes.starts_with(&SP1_PROOF_MAGIC)
+    }
+
+    /// Decodes the proof, and checks that it matches the header.
+    pub fn decode(&self) -> Result<SP1ProofWithPublicValues, SP1ProofContainerError> {
+        let mut reader = self.payload.as_slice();
+        let public_values = SP1PublicValues::from(&read_bytes(&mut reader)?);
+        let stdin: SP1Stdin = bincode::deserialize(&read_bytes(&mut reader)?)?;
+        let proof = match self.header.kind {
+            SP1ProofKind::Core => SP1Proof::Core(bincode::deserialize(&read_bytes(&mut reader)?)?),
+            SP1ProofKind::Compressed => {
+                SP1Proof::Compressed(Box::new(bincode::deserialize(&read_bytes(&mut reader)?)?))
+            }
+            SP1ProofKind::Plonk => {
+                let (public_inputs, encoded_proof, raw_proof) = read_gnark_proof(&mut reader)?;
+                SP1Proof::Plonk(PlonkBn254Proof {
+                    public_inputs,
+                    encoded_proof,
+                    raw_proof,
+                    plonk_vkey_hash: read_array(&mut reader)?,
+                })
+            }
+            SP1ProofKind::Groth16 => {
+                let (public_inputs, encoded_proof, raw_proof) = read_gnark_proof(&mut reader)?;
+                SP1Proof::Groth16(Groth16Bn254Proof {
+                    public_inputs,
+                    encoded_proof,
+                    raw_proof,
+                    groth16_vkey_hash: read_array(&mut reader)?,
+                })
+            }
+        };
+        if !reader.is_empty() {
+            return Err(SP1ProofContainerError::InvalidPayload);
+        }
+        if let Some(vkey_hash) = committed_vkey_hash(&proof)? {
+            if vkey_hash != self.header.vkey_hash {
+                return Err(SP1ProofContainerError::HeaderMismatch);
+            }
+        }
+        Ok(SP1ProofWithPublicValues {
+            proof,
+            stdin,
+            public_values,
+            sp1_version: self.header.sp1_version.clone(),
+            security_level: self.header.security_level,
+        })
+    }
+
+    /// Writes the container in the encoding described in the [module documentation](self).
+    pub fn write_to(&self, mut writer: impl Write) -> Result<(), SP1ProofContainerError> {
+        let version = self.header.sp1_version.as_bytes();
+        let version_len = u8::try_from(version.len())
+            .map_err(|_| SP1ProofContainerError::InvalidCircuitVersion)?;
+        let (soundness, bits) = match self.header.security_level {
+            None => (0, 0),
+            Some(SecurityLevel { soundness: Soundness::Conjectured, bits }) => (1, bits),
+            Some(SecurityLevel { soundness: Soundness::Proven, bits }) => (2, bits),
+        };
+        let bits = u16::try_from(bits).map_err(|_| SP1ProofContainerError::InvalidSecurityLevel)?;
+
+        writer.write_all(&SP1_PROOF_MAGIC)?;
+        writer.write_all(&SP1_PROOF_FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&[kind_to_u8(self.header.kind)])?;
+        writer.write_all(&[version_len])?;
+        writer.write_all(version)?;
+        writer.write_all(&self.header.vkey_hash)?;
+        writer.write_all(&[soundness])?;
+        writer.write_all(&bits.to_le_bytes())?;
+        writer.write_all(&(self.payload.len() as u64).to_le_bytes())?;
+        writer.write_all(&self.payload)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a container in the encoding described in the [module documentation](self).
+    pub fn read_from(mut reader: impl Read) -> Result<Self, SP1ProofContainerError> {
+        let magic: [u8; 8] = read_array(&mut reader)?;
+        if magic != SP1_PROOF_MAGIC {
+            return Err(SP1ProofContainerError::InvalidMagic);
+        }
+        let format_version = u16::from_le_bytes(read_array(&mut reader)?);
+        if format_version != SP1_PROOF_FORMAT_VERSION {
+            return Err(SP1ProofContainerError::UnsupportedFormatVersion(format_version));
+        }
+        let [kind] = read_array(&mut reader)?;
+        let kind = kind_from_u8(kind)?;
+        let [version_len] = read_array(&mut reader)?;
+        let mut version = vec![0; version_len as usize];
+        reader.read_exact(&mut version)?;
+        let sp1_version = String::from_utf8(version)
+            .map_err(|_| SP1ProofContainerError::InvalidCircuitVersion)?;
+        let vkey_hash = read_array(&mut reader)?;
+        let [soundness] = read_array(&mut reader)?;
+        let bits = u16::from_le_bytes(read_array(&mut reader)?) as usize;
+        let security_level = match soundness {
+            0 => None,
+            1 => Some(SecurityLevel { soundness: Soundness::Conjectured, bits }),
+            2 => Some(SecurityLevel { soundness: Soundness::Proven, bits }),
+            _ => return Err(SP1ProofContainerError::InvalidSecurityLevel),
+        };
+        let payload = read_bytes(&mut reader)?;
+
+        Ok(Self {
+            header: SP1ProofHeader { kind, sp1_version, vkey_hash, security_level },
+            payload,
+        })
+    }
+}
+
+/// Encodes the payload of the proof, in the encoding described in the
+/// [module documentation](self).
+fn encode_payload(proof: &SP1ProofWithPublicValues) -> Result<Vec<u8>, SP1ProofContainerError> {
+    let mut payload = Vec::new();
+    write_bytes(&mut payload, proof.public_values.as_slice());
+    write_bytes(&mut payload, &bincode::serialize(&proof.stdin)?);
+    match &proof.proof {
+        SP1Proof::Core(proof) => write_bytes(&mut payload, &bincode::serialize(proof)?),
+        SP1Proof::Compressed(proof) => write_bytes(&mut payload, &bincode::serialize(proof)?),
+        SP1Proof::Plonk(proof) => {
+            write_gnark_proof(
+                &mut payload,
+                &proof.public_inputs,
+                &proof.encoded_proof,
+                &proof.raw_proof,
+            );
+            payload.extend_from_slice(&proof.plonk_vkey_hash);
+        }
+        SP1Proof::Groth16(proof) => {
+            write_gnark_proof(
+                &mut payload,
+                &proof.public_inputs,
+                &proof.encoded_proof,
+                &proof.raw_proof,
+            );
+            payload.extend_from_slice(&proof.groth16_vkey_hash);
+        }
+    }
+    Ok(payload)
+}
+
+fn write_gnark_proof(
+    payload: &mut Vec<u8>,
+    public_inputs: &[String; 2],
+    encoded_proof: &str,
+    raw_proof: &str,
+) {
+    for field in public_inputs.iter().map(String::as_str).chain([encoded_proof, raw_proof]) {
+        write_bytes(payload, field.as_bytes());
+    }
+}
+
+fn read_gnark_proof(
+    reader: &mut impl Read,
+) -> Result<([String; 2], String, String), SP1ProofContainerError> {
+    let public_inputs = [read_string(reader)?, read_string(reader)?];
+    Ok((public_inputs, read_string(reader)?, read_string(reader)?))
+}
+
+/// Writes the bytes prefixed with their length.
+fn write_bytes(payload: &mut Vec<u8>, bytes: &[u8]) {
+    payload.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    payload.extend_from_slice(bytes);
+}
+
+/// Reads bytes prefixed with their length.
+fn read_bytes(reader: &mut impl Read) -> Result<Vec<u8>, SP1ProofContainerError> {
+    let len = u64::from_le_bytes(read_array(reader)?);
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(bytes)
+}
+
+fn read_string(reader: &mut impl Read) -> Result<String, SP1ProofContainerError> {
+    String::from_utf8(read_bytes(reader)?).map_err(|_| SP1ProofContainerError::InvalidPayload)
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> std::io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn kind_to_u8(kind: SP1ProofKind) -> u8 {
+    match kind {
+        SP1ProofKind::Core => 0,
+        SP1ProofKind::Compressed => 1,
+        SP1ProofKind::Plonk => 2,
+        SP1ProofKind::Groth16 => 3,
+    }
+}
+
+fn kind_from_u8(kind: u8) -> Result<SP1ProofKind, SP1ProofContainerError> {
+    match kind {
+        0 => Ok(SP1ProofKind::Core),
+        1 => Ok(SP1ProofKind::Compressed),
+        2 => Ok(SP1ProofKind::Plonk),
+        3 => Ok(SP1ProofKind::Groth16),
+        _ => Err(SP1ProofContainerError::InvalidProofKind(kind)),
+    }
+}
+
+/// Encodes the value as a big endian 32 byte word, failing if it does not fit.
+fn bn254_to_bytes32(value: &BigUint) -> Result<[u8; 32], SP1ProofContainerError> {
+    let bytes = value.to_bytes_be();
+    let mut result = [0; 32];
+    let offset =
+        result.len().checked_sub(bytes.len()).ok_or(SP1ProofContainerError::HeaderMismatch)?;
+    result[offset..].copy_from_slice(&bytes);
+    Ok(result)
+}
+
+/// Returns the vkey hash of the program the proof commits to, if any.
+///
+/// Fails if the public input holding the vkey hash does not fit in 32 bytes.
+fn committed_vkey_hash(proof: &SP1Proof) -> Result<Option<[u8; 32]>, SP1ProofContainerError> {
+    let public_inputs = match proof {
+        SP1Proof::Core(_) => return Ok(None),
+        SP1Proof::Compressed(proof) => {
+            let public_values: &RecursionPublicValues<_> =
+                proof.proof.public_values.as_slice().borrow();
+            let vkey_hash = babybears_to_bn254(&public_values.sp1_vk_digest);
+            return bn254_to_bytes32(&vkey_hash.as_canonical_biguint()).map(Some);
+        }
+        SP1Proof::Plonk(proof) => &proof.public_inputs,
+        SP1Proof::Groth16(proof) => &proof.public_inputs,
+    };
+    // Mock proofs have no public inputs.
+    match public_inputs.first().and_then(|input| BigUint::from_str(input).ok()) {
+        Some(vkey_hash) => bn254_to_bytes32(&vkey_hash).map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groth16_proof() -> SP1ProofWithPublicValues {
+        SP1ProofWithPublicValues {
+            proof: SP1Proof::Groth16(Groth16Bn254Proof {
+                public_inputs: ["258".to_string(), "1".to_string()],
+                encoded_proof: "abcd".to_string(),
+                raw_proof: String::new(),
+                groth16_vkey_hash: [7; 32],
+            }),
+            stdin: SP1Stdin::new(),
+            public_values: SP1PublicValues::from(&[1, 2, 3]),
+            sp1_version: "v3.0.0".to_string(),
+            security_level: Some(SecurityLevel { soundness: Soundness::Proven, bits: 100 }),
+        }
+    }
+
+    #[test]
+    fn test_container_roundtrip() {
+        let proof = groth16_proof();
+        let container = SP1ProofContainer::new(&proof).unwrap();
+        let mut bytes = Vec::new();
+        container.write_to(&mut bytes).unwrap();
+        assert!(SP1ProofContainer::is_container(&bytes));
+        assert_eq!(&bytes[8..12], &[1, 0, 3, 6]);
+
+        let container = SP1ProofContainer::read_from(bytes.as_slice()).unwrap();
+        let mut expected_vkey_hash = [0; 32];
+        expected_vkey_hash[30..].copy_from_slice(&[1, 2]);
+        assert_eq!(
+            container.header,
+            SP1ProofHeader {
+                kind: SP1ProofKind::Groth16,
+                sp1_version: "v3.0.0".to_string(),
+                vkey_hash: expected_vkey_hash,
+                security_level: proof.security_level,
+            }
+        );
+        let decoded = container.decode().unwrap();
+        assert_eq!(decoded.public_values.as_slice(), proof.public_values.as_slice());
+        assert_eq!(decoded.security_level, proof.security_level);
+        assert_eq!(decoded.sp1_version, proof.sp1_version);
+        let (SP1Proof::Groth16(decoded), SP1Proof::Groth16(expected)) =
+            (decoded.proof, proof.proof)
+        else {
+            panic!("expected a groth16 proof");
+        };
+        assert_eq!(decoded.public_inputs, expected.public_inputs);
+        assert_eq!(decoded.encoded_proof, expected.encoded_proof);
+        assert_eq!(decoded.groth16_vkey_hash, expected.groth16_vkey_hash);
+    }
+
+    #[test]
+    fn test_container_payload_encoding() {
+        let container = SP1ProofContainer::new(&groth16_proof()).unwrap();
+        let stdin = bincode::serialize(&SP1Stdin::new()).unwrap();
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&3u64.to_le_bytes());
+        expected.extend_from_slice(&[1, 2, 3]);
+        expected.extend_from_slice(&(stdin.len() as u64).to_le_bytes());
+        expected.extend_from_slice(&stdin);
+        for field in ["258", "1", "abcd", ""] {
+            expected.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            expected.extend_from_slice(field.as_bytes());
+        }
+        expected.extend_from_slice(&[7; 32]);
+        assert_eq!(container.payload, expected);
+    }
+
+    #[test]
+    fn test_container_rejects_trailing_payload() {
+        let mut container = SP1ProofContainer::new(&groth16_proof()).unwrap();
+        container.payload.push(0);
+        assert!(matches!(container.decode(), Err(SP1ProofContainerError::InvalidPayload)));
+    }
+
+    #[test]
+    fn test_container_rejects_oversized_vkey_hash() {
+        let mut proof = groth16_proof();
+        let SP1Proof::Groth16(groth16) = &mut proof.proof else { unreachable!() };
+        // 10^80 does not fit in 32 bytes.
+        groth16.public_inputs[0] = format!("1{}", "0".repeat(80));
+        assert!(matches!(
+            SP1ProofContainer::new(&proof),
+            Err(SP1ProofContainerError::HeaderMismatch)
+        ));
+
+        // A payload whose proof has such a public input is rejected when decoded.
+        let mut container = SP1ProofContainer::new(&groth16_proof()).unwrap();
+        container.payload = encode_payload(&proof).unwrap();
+        assert!(matches!(container.decode(), Err(SP1ProofContainerError::HeaderMismatch)));
+    }
+
+    #[test]
+    fn test_container_rejects_unknown_format_version() {
+        let mut bytes = Vec::new();
+        SP1ProofContainer::new(&groth16_proof()).unwrap().write_to(&mut bytes).unwrap();
+        bytes[8] = 2;
+        assert!(matches!(
+            SP1ProofContainer::read_from(bytes.as_slice()),
+            Err(SP1ProofContainerError::UnsupportedFormatVersion(2))
+        ));
+    }
+}
@@ -10,6 +10,8 @@ use {
     std::{cmp::min, io::Write, process::Command},
 };
 
+use sp1_stark::SecurityProfile;
+
 use crate::SP1_CIRCUIT_VERSION;
 
 /// The base URL for the S3 bucket containing the circuit artifacts.
@@ -17,23 +19,62 @@ pub const CIRCUIT_ARTIFACTS_URL_BASE: &str = "https://sp1-circuits.s3-us-east-2.
 
 /// The directory where the groth16 circuit artifacts will be stored.
 pub fn groth16_circuit_artifacts_dir() -> PathBuf {
-    dirs::home_dir().unwrap().join(".sp1").join("circuits/groth16").join(SP1_CIRCUIT_VERSION)
+    circuit_artifacts_dir("groth16", SP1_CIRCUIT_VERSION)
 }
 
 /// The directory where the plonk circuit artifacts will be stored.
 pub fn plonk_circuit_artifacts_dir() -> PathBuf {
-    dirs::home_dir().unwrap().join(".sp1").join("circuits/plonk").join(SP1_CIRCUIT_VERSION)
+    circuit_artifacts_dir("plonk", SP1_CIRCUIT_VERSION)
+}
+
+/// The directory where the circuit artifacts of the given type and circuit version will be stored.
+pub fn circuit_artifacts_dir(artifacts_type: &str, version: &str) -> PathBuf {
+    dirs::home_dir().unwrap().join(".sp1").join("circuits").join(artifacts_type).join(version)
+}
+
+/// The directory of the plonk circuit artifacts the proofs of the security profile are verified
+/// with.
+///
+/// The artifacts of profiles other than the default one are built locally, as in development mode.
+pub(crate) fn plonk_bn254_artifacts_dir(security_profile: &SecurityProfile) -> PathBuf {
+    if sp1_prover::build::sp1_dev_mode() || *security_profile != SecurityProfile::default() {
+        sp1_prover::build::plonk_bn254_artifacts_dev_dir()
+    } else {
+        try_install_circuit_artifacts("plonk")
+    }
+}
+
+/// The directory of the groth16 circuit artifacts the proofs of the security profile are verified
+/// with.
+///
+/// With the `ark` feature, these are the artifacts built locally by the arkworks backend. The
+/// artifacts of profiles other than the default one are built locally, as in development mode.
+pub(crate) fn groth16_bn254_artifacts_dir(security_profile: &SecurityProfile) -> PathBuf {
+    cfg_if! {
+        if #[cfg(feature = "ark")] {
+            sp1_prover::build::ark_groth16_bn254_artifacts_dir(security_profile)
+        } else {
+            if sp1_prover::build::sp1_dev_mode() || *security_profile != SecurityProfile::default() {
+                sp1_prover::build::groth16_bn254_artifacts_dev_dir()
+            } else {
+                try_install_circuit_artifacts("groth16")
+            }
+        }
+    }
 }
 
 /// Tries to install the groth16 circuit artifacts if they are not already installed.
 pub fn try_install_circuit_artifacts(artifacts_type: &str) -> PathBuf {
-    let build_dir = if artifacts_type == "groth16" {
-        groth16_circuit_artifacts_dir()
-    } else if artifacts_type == "plonk" {
-        plonk_circuit_artifacts_dir()
-    } else {
+    try_install_circuit_artifacts_for_version(artifacts_type, SP1_CIRCUIT_VERSION)
+}
+
+/// Tries to install the circuit artifacts of the given circuit version if they are not already
+/// installed.
+pub fn try_install_circuit_artifacts_for_version(artifacts_type: &str, version: &str) -> PathBuf {
+    if artifacts_type != "groth16" && artifacts_type != "plonk" {
         unimplemented!("unsupported artifacts type: {}", artifacts_type);
-    };
+    }
+    let build_dir = circuit_artifacts_dir(artifacts_type, version);
 
     if build_dir.exists() {
         println!(
@@ -47,10 +88,10 @@ pub fn try_install_circuit_artifacts(artifacts_type: &str) -> PathBuf {
                 println!(
                     "[sp1] {} circuit artifacts for version {} do not exist at {}. downloading...",
                     artifacts_type,
-                    SP1_CIRCUIT_VERSION,
+                    version,
                     build_dir.display()
                 );
-                install_circuit_artifacts(build_dir.clone(), artifacts_type);
+                install_circuit_artifacts_for_version(build_dir.clone(), artifacts_type, version);
             }
         }
     }
@@ -63,12 +104,25 @@ pub fn try_install_circuit_artifacts(artifacts_type: &str) -> PathBuf {
 /// to the directory specified by [groth16_bn254_artifacts_dir()].
 #[cfg(any(feature = "network", feature = "network-v2"))]
 pub fn install_circuit_artifacts(build_dir: PathBuf, artifacts_type: &str) {
+    install_circuit_artifacts_for_version(build_dir, artifacts_type, SP1_CIRCUIT_VERSION)
+}
+
+/// Install the circuit artifacts of the given circuit version.
+///
+/// This function will download the circuit artifacts of the version from the S3 bucket and
+/// extract them to the build directory.
+#[cfg(any(feature = "network", feature = "network-v2"))]
+pub fn install_circuit_artifacts_for_version(
+    build_dir: PathBuf,
+    artifacts_type: &str,
+    version: &str,
+) {
     // Create the build directory.
     std::fs::create_dir_all(&build_dir).expect("failed to create build directory");
 
     // Download the artifacts.
     let download_url =
-        format!("{}/{}-{}.tar.gz", CIRCUIT_ARTIFACTS_URL_BASE, SP1_CIRCUIT_VERSION, artifacts_type);
+        format!("{}/{}-{}.tar.gz", CIRCUIT_ARTIFACTS_URL_BASE, version, artifacts_type);
     let mut artifacts_tar_gz_file =
         tempfile::NamedTempFile::new().expect("failed to create tempfile");
     let client = Client::builder().build().expect("failed to create reqwest client");
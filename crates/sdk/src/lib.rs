@@ -6,7 +6,10 @@
 //! in the official SP1 documentation for a quick start guide.
 
 pub mod action;
+pub mod aggregation;
 pub mod artifacts;
+pub mod compat;
+pub mod container;
 pub mod install;
 #[cfg(feature = "network")]
 pub mod network;
@@ -26,30 +29,43 @@ pub mod utils {
     pub use sp1_core_machine::utils::setup_logger;
 }
 
+pub use aggregation::AGGREGATION_ELF;
 use cfg_if::cfg_if;
+pub use container::{SP1ProofContainer, SP1ProofHeader};
 pub use proof::*;
 pub use provers::SP1VerificationError;
 use sp1_prover::components::DefaultProverComponents;
 
-use std::env;
+use std::{env, sync::Arc};
 
 #[cfg(any(feature = "network", feature = "network-v2"))]
 use {std::future::Future, tokio::task::block_in_place};
 
 pub use provers::{CpuProver, MockProver, Prover};
 
-pub use sp1_core_executor::{ExecutionReport, HookEnv, SP1Context, SP1ContextBuilder};
-pub use sp1_core_machine::{io::SP1Stdin, riscv::cost::CostEstimator, SP1_CIRCUIT_VERSION};
+pub use sp1_core_executor::{
+    syscalls::{environment_digest, file_system_digest},
+    CancellationToken, ExecutionReport, HookEnv, ProofProgress, SP1Context, SP1ContextBuilder,
+};
+pub use sp1_core_machine::{
+    io::SP1Stdin,
+    riscv::{
+        cost::CostEstimator,
+        estimate::{CoreEstimate, ShardEstimate, ShardKind},
+    },
+    SP1_CIRCUIT_VERSION,
+};
 pub use sp1_primitives::io::SP1PublicValues;
 pub use sp1_prover::{
-    CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, SP1Prover, SP1ProvingKey,
-    SP1VerifyingKey,
+    CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, SP1Prover, SP1ProvingEstimate,
+    SP1ProvingKey, SP1SecurityLevels, SP1VerifyingKey,
 };
+pub use sp1_stark::{SecurityLevel, SecurityProfile, Soundness};
 
 /// A client for interacting with SP1.
 pub struct ProverClient {
     /// The underlying prover implementation.
-    pub prover: Box<dyn Prover<DefaultProverComponents>>,
+    pub prover: Arc<dyn Prover<DefaultProverComponents>>,
 }
 
 impl ProverClient {
@@ -72,26 +88,26 @@ impl ProverClient {
     pub fn new() -> Self {
         #[allow(unreachable_code)]
         match env::var("SP1_PROVER").unwrap_or("local".to_string()).to_lowercase().as_str() {
-            "mock" => Self { prover: Box::new(MockProver::new()) },
+            "mock" => Self { prover: Arc::new(MockProver::new()) },
             "local" => {
                 #[cfg(debug_assertions)]
                 println!("Warning: Local prover in dev mode is not recommended. Proof generation may be slow.");
                 Self {
                     #[cfg(not(feature = "cuda"))]
-                    prover: Box::new(CpuProver::new()),
+                    prover: Arc::new(CpuProver::new()),
                     #[cfg(feature = "cuda")]
-                    prover: Box::new(CudaProver::new(SP1Prover::new())),
+                    prover: Arc::new(CudaProver::new(SP1Prover::new())),
                 }
             }
             "network" => {
                 cfg_if! {
                     if #[cfg(feature = "network-v2")] {
                         Self {
-                            prover: Box::new(NetworkProverV2::new()),
+                            prover: Arc::new(NetworkProverV2::new()),
                         }
                     } else if #[cfg(feature = "network")] {
                         Self {
-                            prover: Box::new(NetworkProverV1::new()),
+                            prover: Arc::new(NetworkProverV1::new()),
                         }
                     } else {
                         panic!("network feature is not enabled")
@@ -117,7 +133,7 @@ impl ProverClient {
     /// let client = ProverClient::mock();
     /// ```
     pub fn mock() -> Self {
-        Self { prover: Box::new(MockProver::new()) }
+        Self { prover: Arc::new(MockProver::new()) }
     }
 
     /// Creates a new [ProverClient] with the local prover.
@@ -133,7 +149,7 @@ impl ProverClient {
     /// let client = ProverClient::local();
     /// ```
     pub fn local() -> Self {
-        Self { prover: Box::new(CpuProver::new()) }
+        Self { prover: Arc::new(CpuProver::new()) }
     }
 
     /// Creates a new [ProverClient] with the network prover.
@@ -152,11 +168,11 @@ impl ProverClient {
         cfg_if! {
             if #[cfg(feature = "network-v2")] {
                 Self {
-                    prover: Box::new(NetworkProverV2::new()),
+                    prover: Arc::new(NetworkProverV2::new()),
                 }
             } else if #[cfg(feature = "network")] {
                 Self {
-                    prover: Box::new(NetworkProverV1::new()),
+                    prover: Arc::new(NetworkProverV1::new()),
                 }
             } else {
                 panic!("network feature is not enabled")
@@ -192,6 +208,35 @@ impl ProverClient {
         action::Execute::new(self.prover.as_ref(), elf, stdin)
     }
 
+    /// Prepare to estimate the cost of proving the given program with the given input, without
+    /// generating any traces.
+    ///
+    /// To estimate, call [action::Estimate::run], which returns the predicted number of shards,
+    /// the trace heights of each chip in each shard, the total number of trace cells, and the
+    /// number of recursion and compress steps needed for a compressed proof.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin};
+    ///
+    /// // Load the program.
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    ///
+    /// // Initialize the prover client.
+    /// let client = ProverClient::new();
+    ///
+    /// // Setup the inputs.
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    ///
+    /// // Estimate the cost of proving the program on the inputs.
+    /// let estimate = client.estimate(elf, stdin).run().unwrap();
+    /// println!("shards: {}, cells: {}", estimate.core.num_shards(), estimate.core.total_cells());
+    /// ```
+    pub fn estimate<'a>(&'a self, elf: &'a [u8], stdin: SP1Stdin) -> action::Estimate<'a> {
+        action::Estimate::new(self.prover.as_ref(), elf, stdin)
+    }
+
     /// Prepare to prove the execution of the given program with the given input in the default
     /// mode. The returned [action::Prove] may be configured via its methods before running.
     /// For example, calling [action::Prove::compress] sets the mode to compressed mode.
@@ -222,7 +267,44 @@ impl ProverClient {
     /// let proof = client.prove(&pk, stdin).run().unwrap();
     /// ```
     pub fn prove<'a>(&'a self, pk: &'a SP1ProvingKey, stdin: SP1Stdin) -> action::Prove<'a> {
-        action::Prove::new(self.prover.as_ref(), pk, stdin)
+        action::Prove::new(self.prover.clone(), pk, stdin)
+    }
+
+    /// Prepare to aggregate the given compressed proofs, each with the verifying key it is verified
+    /// with, into a single proof. The returned [action::Aggregate] may be configured via its
+    /// methods before running. For example, calling [action::Aggregate::groth16] sets the mode to
+    /// groth16 mode.
+    ///
+    /// The proofs are verified by [AGGREGATION_ELF] through the deferred proofs of its compressed
+    /// proof, and the aggregated proof is verified with the verifying key of [AGGREGATION_ELF].
+    /// Its public values commit to the verifying key hash and public values digest of every proof,
+    /// as computed by [aggregation::commit_proof_pairs].
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin, AGGREGATION_ELF};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let proofs = [10usize, 20]
+    ///     .into_iter()
+    ///     .map(|n| {
+    ///         let mut stdin = SP1Stdin::new();
+    ///         stdin.write(&n);
+    ///         (client.prove(&pk, stdin).compressed().run().unwrap(), vk.clone())
+    ///     })
+    ///     .collect();
+    ///
+    /// let proof = client.aggregate(proofs).run().unwrap();
+    /// let (_, aggregation_vk) = client.setup(&AGGREGATION_ELF);
+    /// client.verify(&proof, &aggregation_vk).unwrap();
+    /// ```
+    pub fn aggregate<'a>(
+        &'a self,
+        proofs: Vec<(SP1ProofWithPublicValues, SP1VerifyingKey)>,
+    ) -> action::Aggregate<'a> {
+        action::Aggregate::new(self.prover.as_ref(), proofs)
     }
 
     /// Verifies that the given proof is valid and matches the given verification key produced by
@@ -317,9 +399,14 @@ macro_rules! include_elf {
 #[cfg(test)]
 mod tests {
 
+    use futures::StreamExt;
+    use sp1_core_executor::{CancellationToken, ProofProgress};
     use sp1_primitives::io::SP1PublicValues;
 
-    use crate::{utils, CostEstimator, ProverClient, SP1Stdin};
+    use crate::{
+        aggregation::commit_proof_pairs, utils, CostEstimator, HashableKey, ProverClient, SP1Stdin,
+        AGGREGATION_ELF,
+    };
 
     #[test]
     fn test_execute() {
@@ -333,6 +420,17 @@ mod tests {
         tracing::info!("gas = {}", report.estimate_gas());
     }
 
+    #[test]
+    fn test_execute_aggregation() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let digest = commit_proof_pairs(&[([1; 8], [2; 32]), ([3; 8], [4; 32])]);
+        let mut stdin = SP1Stdin::new();
+        stdin.write_slice(&digest);
+        let (public_values, _) = client.execute(&AGGREGATION_ELF, stdin).run().unwrap();
+        assert_eq!(public_values.as_slice(), digest);
+    }
+
     #[test]
     #[should_panic]
     fn test_execute_panic() {
@@ -344,6 +442,23 @@ mod tests {
         client.execute(elf, stdin).run().unwrap();
     }
 
+    #[test]
+    fn test_execute_resume_from_snapshot() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let (expected, _) = client.execute(elf, stdin.clone()).run().unwrap();
+
+        let snapshot = tempfile::NamedTempFile::new().unwrap();
+        client.execute(elf, stdin).max_cycles(500).snapshot_to(snapshot.path()).run().unwrap_err();
+        let (public_values, _) =
+            client.execute(elf, SP1Stdin::new()).resume_from(snapshot.path()).run().unwrap();
+        assert_eq!(public_values.as_slice(), expected.as_slice());
+    }
+
     #[should_panic]
     #[test]
     fn test_cycle_limit_fail() {
@@ -376,6 +491,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_e2e_core_with_progress() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+
+        // Generate proof while collecting the progress events, then verify.
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let (progress, proof) = client.prove(&pk, stdin).run_with_progress();
+        let proof = runtime.spawn(proof);
+        let events: Vec<ProofProgress> = runtime.block_on(progress.collect());
+        let proof = runtime.block_on(proof).unwrap().unwrap();
+        client.verify(&proof, &vk).unwrap();
+        assert!(matches!(events.first(), Some(ProofProgress::ShardsExecuted { .. })));
+        assert_eq!(events.last(), Some(&ProofProgress::CoreProven));
+    }
+
+    #[test]
+    fn test_prove_async_cancelled_on_drop() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, _) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+
+        // Dropping the future of the proof cancels it through its token.
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let token = CancellationToken::new();
+        let proof = client.prove(&pk, stdin).cancellation(token.clone()).run_async();
+        runtime.block_on(async {
+            let _ = futures::poll!(Box::pin(proof));
+        });
+        assert!(token.is_cancelled());
+    }
+
     #[test]
     fn test_e2e_compressed() {
         utils::setup_logger();
@@ -418,6 +574,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_e2e_aggregate() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let proofs = [10usize, 20]
+            .into_iter()
+            .map(|n| {
+                let mut stdin = SP1Stdin::new();
+                stdin.write(&n);
+                (client.prove(&pk, stdin).compressed().run().unwrap(), vk.clone())
+            })
+            .collect::<Vec<_>>();
+
+        // Aggregate the proofs & verify.
+        let proof = client.aggregate(proofs.clone()).run().unwrap();
+        let (_, aggregation_vk) = client.setup(&AGGREGATION_ELF);
+        client.verify(&proof, &aggregation_vk).unwrap();
+
+        // Check the aggregated proof commits to every proof.
+        let pairs = proofs
+            .iter()
+            .map(|(proof, vk)| (vk.hash_u32(), proof.public_values.hash().try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(proof.public_values.as_slice(), commit_proof_pairs(&pairs));
+
+        // Test aggregating core proofs.
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let core_proof = client.prove(&pk, stdin).run().unwrap();
+        assert!(client.aggregate(vec![(core_proof, vk)]).run().is_err());
+    }
+
     #[test]
     fn test_e2e_prove_plonk_mock() {
         utils::setup_logger();
@@ -1,5 +1,6 @@
 use std::{
     env,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -9,7 +10,9 @@ use crate::{
     Prover, SP1Context, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
 };
 use anyhow::Result;
+use futures::future::BoxFuture;
 use serde::de::DeserializeOwned;
+use sp1_core_executor::CancellationToken;
 use sp1_core_machine::io::SP1Stdin;
 use sp1_prover::{components::DefaultProverComponents, SP1Prover, SP1_CIRCUIT_VERSION};
 use sp1_stark::SP1ProverOpts;
@@ -110,10 +113,24 @@ impl NetworkProver {
         &self,
         request_id: &[u8],
         timeout: Option<Duration>,
+    ) -> Result<P> {
+        self.wait_proof_with_cancellation(request_id, timeout, &CancellationToken::new()).await
+    }
+
+    /// Waits for a proof to be generated like [Self::wait_proof], returning an error as soon as
+    /// `cancellation` is cancelled.
+    pub async fn wait_proof_with_cancellation<P: DeserializeOwned>(
+        &self,
+        request_id: &[u8],
+        timeout: Option<Duration>,
+        cancellation: &CancellationToken,
     ) -> Result<P> {
         let mut is_assigned = false;
         let start_time = Instant::now();
         loop {
+            if cancellation.is_cancelled() {
+                return Err(anyhow::anyhow!("Proof request cancelled."));
+            }
             if let Some(timeout) = timeout {
                 if start_time.elapsed() > timeout {
                     return Err(anyhow::anyhow!("Proof request timed out."));
@@ -176,6 +193,26 @@ impl Prover<DefaultProverComponents> for NetworkProver {
         warn_if_not_default(&opts.sp1_prover_opts, &context);
         block_on(self.prove(&pk.elf, stdin, kind.into(), opts.timeout))
     }
+
+    fn prove_async(
+        self: Arc<Self>,
+        pk: Arc<SP1ProvingKey>,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        context: SP1Context<'static>,
+        kind: SP1ProofKind,
+    ) -> BoxFuture<'static, Result<SP1ProofWithPublicValues>> {
+        warn_if_not_default(&opts.sp1_prover_opts, &context);
+        Box::pin(async move {
+            let request_id = self.request_proof(&pk.elf, stdin, kind.into(), opts.timeout).await?;
+            self.wait_proof_with_cancellation(
+                &request_id,
+                opts.timeout,
+                context.monitor.cancellation(),
+            )
+            .await
+        })
+    }
 }
 
 impl Default for NetworkProver {
@@ -192,7 +229,7 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
         tracing::warn!("custom SP1ProverOpts are currently unsupported by the network prover");
     }
     // Exhaustive match is done to ensure we update the warnings if the types change.
-    let SP1Context { hook_registry, subproof_verifier, .. } = context;
+    let SP1Context { hook_registry, subproof_verifier, env, rand_seed, .. } = context;
     if hook_registry.is_some() {
         tracing::warn!("non-default context.hook_registry will be ignored: {:?}", hook_registry);
         tracing::warn!("custom runtime hooks are currently unsupported by the network prover");
@@ -202,6 +239,14 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
         tracing::warn!("non-default context.subproof_verifier will be ignored");
         tracing::warn!("custom subproof verifiers are currently unsupported by the network prover");
     }
+    if !env.is_empty() {
+        tracing::warn!("non-default context.env will be ignored: {:?}", env);
+        tracing::warn!("program environments are currently unsupported by the network prover");
+    }
+    if rand_seed.is_some() {
+        tracing::warn!("non-default context.rand_seed will be ignored");
+        tracing::warn!("randomness seeds are currently unsupported by the network prover");
+    }
 }
 
 impl From<SP1ProofKind> for ProofMode {
@@ -196,7 +196,7 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
         tracing::warn!("custom SP1ProverOpts are currently unsupported by the network prover");
     }
     // Exhaustive match is done to ensure we update the warnings if the types change.
-    let SP1Context { hook_registry, subproof_verifier, .. } = context;
+    let SP1Context { hook_registry, subproof_verifier, env, rand_seed, .. } = context;
     if hook_registry.is_some() {
         tracing::warn!("non-default context.hook_registry will be ignored: {:?}", hook_registry);
         tracing::warn!("custom runtime hooks are currently unsupported by the network prover");
@@ -206,6 +206,14 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
         tracing::warn!("non-default context.subproof_verifier will be ignored");
         tracing::warn!("custom subproof verifiers are currently unsupported by the network prover");
     }
+    if !env.is_empty() {
+        tracing::warn!("non-default context.env will be ignored: {:?}", env);
+        tracing::warn!("program environments are currently unsupported by the network prover");
+    }
+    if rand_seed.is_some() {
+        tracing::warn!("non-default context.rand_seed will be ignored");
+        tracing::warn!("randomness seeds are currently unsupported by the network prover");
+    }
 }
 
 impl From<SP1ProofKind> for ProofMode {
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, fs::File, path::Path};
+use std::{fmt::Debug, fs::File, io::BufWriter, path::Path};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -8,7 +8,9 @@ use sp1_primitives::io::SP1PublicValues;
 use strum_macros::{EnumDiscriminants, EnumTryAs};
 
 use sp1_prover::{CoreSC, Groth16Bn254Proof, InnerSC, PlonkBn254Proof};
-use sp1_stark::{MachineVerificationError, ShardProof};
+use sp1_stark::{MachineVerificationError, SecurityLevel, ShardProof};
+
+use crate::SP1ProofContainer;
 
 /// A proof generated with SP1 of a particular proof mode.
 #[derive(Debug, Clone, Serialize, Deserialize, EnumDiscriminants, EnumTryAs)]
@@ -29,19 +31,32 @@ pub struct SP1ProofWithPublicValues {
     pub stdin: SP1Stdin,
     pub public_values: SP1PublicValues,
     pub sp1_version: String,
+    /// The security level the proof achieves, as reported by the prover that generated it.
+    ///
+    /// It is not part of the bincode encoding of the proof, which stays readable by earlier
+    /// versions of SP1, but [SP1ProofWithPublicValues::save] keeps it in the header of the
+    /// [SP1ProofContainer]. It is `None` for proofs generated by the network or in mock mode, and
+    /// for proofs loaded from files without it.
+    #[serde(skip)]
+    pub security_level: Option<SecurityLevel>,
 }
 
 impl SP1ProofWithPublicValues {
-    /// Saves the proof to a path.
+    /// Saves the proof to a path, in the encoding of [SP1ProofContainer].
     pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        bincode::serialize_into(File::create(path).expect("failed to open file"), self)
-            .map_err(Into::into)
+        let file = File::create(path).expect("failed to open file");
+        SP1ProofContainer::new(self)?.write_to(BufWriter::new(file)).map_err(Into::into)
     }
 
-    /// Loads a proof from a path.
+    /// Loads a proof from a path, either in the encoding of [SP1ProofContainer] or as the plain
+    /// bincode proofs were saved as by earlier versions of SP1.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        bincode::deserialize_from(File::open(path).expect("failed to open file"))
-            .map_err(Into::into)
+        let bytes = std::fs::read(path).expect("failed to open file");
+        if SP1ProofContainer::is_container(&bytes) {
+            SP1ProofContainer::read_from(bytes.as_slice())?.decode().map_err(Into::into)
+        } else {
+            bincode::deserialize(&bytes).map_err(Into::into)
+        }
     }
 
     /// Returns the raw proof as a string.
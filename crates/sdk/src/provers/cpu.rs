@@ -1,7 +1,14 @@
 use anyhow::Result;
-use sp1_core_executor::SP1Context;
+use serde::{de::DeserializeOwned, Serialize};
+use sp1_core_executor::{ProofMonitor, ProofProgress, SP1Context};
 use sp1_core_machine::io::SP1Stdin;
-use sp1_prover::{components::DefaultProverComponents, SP1Prover};
+use sp1_prover::{
+    checkpoint::{ProofCheckpoints, ProofStage},
+    components::DefaultProverComponents,
+    SP1Prover, SP1RecursionProverError,
+};
+
+use sp1_stark::SecurityProfile;
 
 use crate::install::try_install_circuit_artifacts;
 use crate::{
@@ -23,6 +30,17 @@ impl CpuProver {
         Self { prover }
     }
 
+    /// Creates a new [LocalProver] whose proofs have the FRI parameters of the security profile.
+    ///
+    /// The PLONK and Groth16 circuit artifacts of any profile other than the default one are built
+    /// locally, as in development mode. Recursive proofs of such a profile also need the allowed
+    /// verifying keys of its recursion programs, set with [SP1Prover::with_vk_map] on a prover
+    /// passed to [CpuProver::from_prover].
+    pub fn with_security_profile(security_profile: SecurityProfile) -> Self {
+        let prover = SP1Prover::with_security_profile(security_profile);
+        Self { prover }
+    }
+
     /// Creates a new [LocalProver] from an existing [SP1Prover].
     pub fn from_prover(prover: SP1Prover<DefaultProverComponents>) -> Self {
         Self { prover }
@@ -50,15 +68,38 @@ impl Prover<DefaultProverComponents> for CpuProver {
         context: SP1Context<'a>,
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
+        if opts.sp1_prover_opts.security_profile != self.prover.security_profile {
+            anyhow::bail!("the options are for a different security profile than the prover");
+        }
+        #[cfg(feature = "ark")]
+        if kind == SP1ProofKind::Plonk {
+            anyhow::bail!("PLONK proofs are not supported by the arkworks backend, use Groth16");
+        }
+        let build_artifacts = sp1_prover::build::sp1_dev_mode()
+            || self.prover.security_profile != SecurityProfile::default();
+        let checkpoints = opts.checkpoint_dir.map(ProofCheckpoints::new).transpose()?;
+        let checkpoints = checkpoints.as_ref();
+
         // Generate the core proof.
+        let monitor = context.monitor.clone();
         let proof: sp1_prover::SP1ProofWithMetadata<sp1_prover::SP1CoreProofData> =
-            self.prover.prove_core(pk, &stdin, opts.sp1_prover_opts, context)?;
+            match checkpoints {
+                Some(checkpoints) => self.prover.prove_core_with_checkpoints(
+                    pk,
+                    &stdin,
+                    opts.sp1_prover_opts,
+                    context,
+                    checkpoints,
+                )?,
+                None => self.prover.prove_core(pk, &stdin, opts.sp1_prover_opts, context)?,
+            };
         if kind == SP1ProofKind::Core {
             return Ok(SP1ProofWithPublicValues {
                 proof: SP1Proof::Core(proof.proof.0),
                 stdin: proof.stdin,
                 public_values: proof.public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().core_proof()),
             });
         }
 
@@ -67,28 +108,45 @@ impl Prover<DefaultProverComponents> for CpuProver {
         let public_values = proof.public_values.clone();
 
         // Generate the compressed proof.
-        let reduce_proof =
-            self.prover.compress(&pk.vk, proof, deferred_proofs, opts.sp1_prover_opts)?;
+        let reduce_proof = self.prover.compress_with_monitor(
+            &pk.vk,
+            proof,
+            deferred_proofs,
+            opts.sp1_prover_opts,
+            &monitor,
+            checkpoints,
+        )?;
         if kind == SP1ProofKind::Compressed {
             return Ok(SP1ProofWithPublicValues {
                 proof: SP1Proof::Compressed(Box::new(reduce_proof)),
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().compressed_proof()),
             });
         }
 
         // Generate the shrink proof.
-        let compress_proof = self.prover.shrink(reduce_proof, opts.sp1_prover_opts)?;
+        check_cancelled(&monitor)?;
+        monitor.report(ProofProgress::ShrinkStarted);
+        let compress_proof = checkpointed(checkpoints, ProofStage::Shrink, || {
+            Ok(self.prover.shrink(reduce_proof, opts.sp1_prover_opts)?)
+        })?;
 
         // Genenerate the wrap proof.
-        let outer_proof = self.prover.wrap_bn254(compress_proof, opts.sp1_prover_opts)?;
+        check_cancelled(&monitor)?;
+        monitor.report(ProofProgress::WrapStarted);
+        let outer_proof = checkpointed(checkpoints, ProofStage::Wrap, || {
+            Ok(self.prover.wrap_bn254(compress_proof, opts.sp1_prover_opts)?)
+        })?;
+        check_cancelled(&monitor)?;
 
         if kind == SP1ProofKind::Plonk {
-            let plonk_bn254_artifacts = if sp1_prover::build::sp1_dev_mode() {
+            let plonk_bn254_artifacts = if build_artifacts {
                 sp1_prover::build::try_build_plonk_bn254_artifacts_dev(
                     &outer_proof.vk,
                     &outer_proof.proof,
+                    &self.prover.security_profile,
                 )
             } else {
                 try_install_circuit_artifacts("plonk")
@@ -100,12 +158,22 @@ impl Prover<DefaultProverComponents> for CpuProver {
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().wrapped_proof()),
             });
         } else if kind == SP1ProofKind::Groth16 {
-            let groth16_bn254_artifacts = if sp1_prover::build::sp1_dev_mode() {
+            // The arkworks backend can only prove with the keys it built itself.
+            #[cfg(feature = "ark")]
+            let groth16_bn254_artifacts = sp1_prover::build::try_build_ark_groth16_bn254_artifacts(
+                &outer_proof.vk,
+                &outer_proof.proof,
+                &self.prover.security_profile,
+            );
+            #[cfg(not(feature = "ark"))]
+            let groth16_bn254_artifacts = if build_artifacts {
                 sp1_prover::build::try_build_groth16_bn254_artifacts_dev(
                     &outer_proof.vk,
                     &outer_proof.proof,
+                    &self.prover.security_profile,
                 )
             } else {
                 try_install_circuit_artifacts("groth16")
@@ -117,6 +185,7 @@ impl Prover<DefaultProverComponents> for CpuProver {
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().wrapped_proof()),
             });
         }
 
@@ -124,6 +193,26 @@ impl Prover<DefaultProverComponents> for CpuProver {
     }
 }
 
+/// Runs `prove`, or returns its checkpointed output if `checkpoints` already holds it.
+fn checkpointed<T: Serialize + DeserializeOwned>(
+    checkpoints: Option<&ProofCheckpoints>,
+    stage: ProofStage,
+    prove: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    match checkpoints {
+        Some(checkpoints) => checkpoints.get_or_prove(stage, prove),
+        None => prove(),
+    }
+}
+
+/// Returns an error if proving was cancelled.
+fn check_cancelled(monitor: &ProofMonitor) -> Result<()> {
+    if monitor.is_cancelled() {
+        return Err(SP1RecursionProverError::Cancelled.into());
+    }
+    Ok(())
+}
+
 impl Default for CpuProver {
     fn default() -> Self {
         Self::new()
@@ -2,6 +2,7 @@ use anyhow::Result;
 use sp1_core_machine::io::SP1Stdin;
 use sp1_cuda::SP1CudaProver;
 use sp1_prover::{components::DefaultProverComponents, SP1Prover};
+use sp1_stark::SecurityProfile;
 
 use super::ProverType;
 use crate::install::try_install_circuit_artifacts;
@@ -46,6 +47,13 @@ impl Prover<DefaultProverComponents> for CudaProver {
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
         tracing::warn!("opts and context are ignored for the cuda prover");
+        if self.prover.security_profile != SecurityProfile::default() {
+            anyhow::bail!("the cuda prover only supports the default security profile");
+        }
+        #[cfg(feature = "ark")]
+        if kind == SP1ProofKind::Plonk {
+            anyhow::bail!("PLONK proofs are not supported by the arkworks backend, use Groth16");
+        }
 
         // Generate the core proof.
         let proof = self.cuda_prover.prove_core(pk, &stdin)?;
@@ -55,6 +63,7 @@ impl Prover<DefaultProverComponents> for CudaProver {
                 stdin: proof.stdin,
                 public_values: proof.public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().core_proof()),
             });
         }
 
@@ -70,6 +79,7 @@ impl Prover<DefaultProverComponents> for CudaProver {
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().compressed_proof()),
             });
         }
 
@@ -84,6 +94,7 @@ impl Prover<DefaultProverComponents> for CudaProver {
                 sp1_prover::build::try_build_plonk_bn254_artifacts_dev(
                     &outer_proof.vk,
                     &outer_proof.proof,
+                    &self.prover.security_profile,
                 )
             } else {
                 try_install_circuit_artifacts("plonk")
@@ -94,12 +105,22 @@ impl Prover<DefaultProverComponents> for CudaProver {
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().wrapped_proof()),
             });
         } else if kind == SP1ProofKind::Groth16 {
+            // The arkworks backend can only prove with the keys it built itself.
+            #[cfg(feature = "ark")]
+            let groth16_bn254_artifacts = sp1_prover::build::try_build_ark_groth16_bn254_artifacts(
+                &outer_proof.vk,
+                &outer_proof.proof,
+                &self.prover.security_profile,
+            );
+            #[cfg(not(feature = "ark"))]
             let groth16_bn254_artifacts = if sp1_prover::build::sp1_dev_mode() {
                 sp1_prover::build::try_build_groth16_bn254_artifacts_dev(
                     &outer_proof.vk,
                     &outer_proof.proof,
+                    &self.prover.security_profile,
                 )
             } else {
                 try_install_circuit_artifacts("groth16")
@@ -111,6 +132,7 @@ impl Prover<DefaultProverComponents> for CudaProver {
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                security_level: Some(self.prover.security_levels().wrapped_proof()),
             });
         }
 
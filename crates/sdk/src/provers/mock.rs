@@ -62,6 +62,7 @@ impl Prover<DefaultProverComponents> for MockProver {
                     stdin,
                     public_values,
                     sp1_version: self.version().to_string(),
+                    security_level: None,
                 })
             }
             SP1ProofKind::Compressed => {
@@ -105,6 +106,7 @@ impl Prover<DefaultProverComponents> for MockProver {
                     stdin,
                     public_values,
                     sp1_version: self.version().to_string(),
+                    security_level: None,
                 })
             }
             SP1ProofKind::Plonk => {
@@ -122,6 +124,7 @@ impl Prover<DefaultProverComponents> for MockProver {
                     stdin,
                     public_values,
                     sp1_version: self.version().to_string(),
+                    security_level: None,
                 })
             }
             SP1ProofKind::Groth16 => {
@@ -139,6 +142,7 @@ impl Prover<DefaultProverComponents> for MockProver {
                     stdin,
                     public_values,
                     sp1_version: self.version().to_string(),
+                    security_level: None,
                 })
             }
         }
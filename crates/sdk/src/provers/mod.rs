@@ -11,9 +11,10 @@ pub use mock::MockProver;
 use itertools::Itertools;
 use p3_field::PrimeField32;
 use std::borrow::Borrow;
-use std::time::Duration;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use anyhow::Result;
+use futures::future::BoxFuture;
 use sp1_core_executor::SP1Context;
 use sp1_core_machine::{io::SP1Stdin, SP1_CIRCUIT_VERSION};
 use sp1_prover::{
@@ -24,7 +25,8 @@ use sp1_stark::{air::PublicValues, MachineVerificationError, SP1ProverOpts, Word
 use strum_macros::EnumString;
 use thiserror::Error;
 
-use crate::install::try_install_circuit_artifacts;
+use crate::compat::registered_verifier;
+use crate::install::{groth16_bn254_artifacts_dir, plonk_bn254_artifacts_dir};
 use crate::{SP1Proof, SP1ProofKind, SP1ProofWithPublicValues};
 
 /// The type of prover.
@@ -43,6 +45,8 @@ pub struct ProofOpts {
     pub sp1_prover_opts: SP1ProverOpts,
     /// Optional timeout duration for proof generation.
     pub timeout: Option<Duration>,
+    /// Optional directory to checkpoint intermediate proofs to, and to resume proving from.
+    pub checkpoint_dir: Option<PathBuf>,
 }
 
 #[derive(Error, Debug)]
@@ -62,7 +66,7 @@ pub enum SP1VerificationError {
 }
 
 /// An implementation of [crate::ProverClient].
-pub trait Prover<C: SP1ProverComponents>: Send + Sync {
+pub trait Prover<C: SP1ProverComponents>: Send + Sync + 'static {
     fn id(&self) -> ProverType;
 
     fn sp1_prover(&self) -> &SP1Prover<C>;
@@ -83,7 +87,34 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues>;
 
+    /// Asynchronously prove the execution of a RISCV ELF with the given inputs, according to the
+    /// given proof mode.
+    ///
+    /// Progress events are reported to, and cancellation is requested through, the monitor of the
+    /// `context`. The default implementation runs [Prover::prove] on Tokio's blocking thread pool,
+    /// and cancels the proof if the returned future is dropped before it completes.
+    fn prove_async(
+        self: Arc<Self>,
+        pk: Arc<SP1ProvingKey>,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        context: SP1Context<'static>,
+        kind: SP1ProofKind,
+    ) -> BoxFuture<'static, Result<SP1ProofWithPublicValues>> {
+        Box::pin(async move {
+            let guard = context.monitor.cancellation().drop_guard();
+            let proof = tokio::task::spawn_blocking(move || {
+                self.prove(&pk, stdin, opts, context.scoped(), kind)
+            })
+            .await?;
+            guard.disarm();
+            proof
+        })
+    }
+
     /// Verify that an SP1 proof is valid given its vkey and metadata.
+    /// Proofs of other circuit versions are verified by the verifier registered for their version
+    /// with [crate::compat::register_verifier].
     /// For Plonk proofs, verifies that the public inputs of the PlonkBn254 proof match
     /// the hash of the VK and the committed public values of the SP1ProofWithPublicValues.
     fn verify(
@@ -92,7 +123,11 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
         vkey: &SP1VerifyingKey,
     ) -> Result<(), SP1VerificationError> {
         if bundle.sp1_version != self.version() {
-            return Err(SP1VerificationError::VersionMismatch(bundle.sp1_version.clone()));
+            // Proofs of other versions can only be verified by the verifier registered for them.
+            return match registered_verifier(&bundle.sp1_version) {
+                Some(verifier) => verifier.verify(bundle, vkey),
+                None => Err(SP1VerificationError::VersionMismatch(bundle.sp1_version.clone())),
+            };
         }
         match &bundle.proof {
             SP1Proof::Core(proof) => {
@@ -150,11 +185,7 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
                     proof,
                     vkey,
                     &bundle.public_values,
-                    &if sp1_prover::build::sp1_dev_mode() {
-                        sp1_prover::build::plonk_bn254_artifacts_dev_dir()
-                    } else {
-                        try_install_circuit_artifacts("plonk")
-                    },
+                    &plonk_bn254_artifacts_dir(&self.sp1_prover().security_profile),
                 )
                 .map_err(SP1VerificationError::Plonk),
             SP1Proof::Groth16(proof) => self
@@ -163,11 +194,7 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
                     proof,
                     vkey,
                     &bundle.public_values,
-                    &if sp1_prover::build::sp1_dev_mode() {
-                        sp1_prover::build::groth16_bn254_artifacts_dev_dir()
-                    } else {
-                        try_install_circuit_artifacts("groth16")
-                    },
+                    &groth16_bn254_artifacts_dir(&self.sp1_prover().security_profile),
                 )
                 .map_err(SP1VerificationError::Groth16),
         }
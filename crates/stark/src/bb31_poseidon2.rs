@@ -164,7 +164,9 @@ pub mod baby_bear_poseidon2 {
     use serde::{Deserialize, Serialize};
     use sp1_primitives::RC_16_30;
 
-    use crate::{Com, StarkGenericConfig, ZeroCommitment, DIGEST_SIZE};
+    use crate::{
+        Com, FriParameters, SecurityProfile, StarkGenericConfig, ZeroCommitment, DIGEST_SIZE,
+    };
 
     pub type Val = BabyBear;
     pub type Challenge = BinomialExtensionField<Val, 4>;
@@ -207,48 +209,53 @@ pub mod baby_bear_poseidon2 {
         )
     }
 
+    /// The log blowup of the default config.
+    pub const DEFAULT_LOG_BLOWUP: usize = 1;
+
+    /// The log blowup of the compressed config.
+    pub const COMPRESSED_LOG_BLOWUP: usize = 2;
+
+    /// The log blowup of the ultra compressed config.
+    pub const ULTRA_COMPRESSED_LOG_BLOWUP: usize = 3;
+
+    /// The FRI parameters of a config with the given default log blowup under the security
+    /// profile, with the number of queries overridden by `FRI_QUERIES` if set.
     #[must_use]
-    pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 100,
-        };
-        FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    pub fn fri_parameters(profile: &SecurityProfile, default_log_blowup: usize) -> FriParameters {
+        let mut parameters = profile.fri_parameters(default_log_blowup, 1);
+        if let Ok(value) = std::env::var("FRI_QUERIES") {
+            parameters.num_queries = value.parse().unwrap();
+        }
+        parameters
     }
 
     #[must_use]
-    pub fn compressed_fri_config() -> FriConfig<ChallengeMmcs> {
+    pub fn fri_config(parameters: &FriParameters) -> FriConfig<ChallengeMmcs> {
         let perm = my_perm();
         let hash = MyHash::new(perm.clone());
         let compress = MyCompress::new(perm.clone());
         let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 50,
-        };
-        FriConfig { log_blowup: 2, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+        FriConfig {
+            log_blowup: parameters.log_blowup,
+            num_queries: parameters.num_queries,
+            proof_of_work_bits: parameters.proof_of_work_bits,
+            mmcs: challenge_mmcs,
+        }
     }
 
     #[must_use]
-    pub fn ultra_compressed_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 33,
-        };
-        FriConfig { log_blowup: 3, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
+        fri_config(&fri_parameters(&SecurityProfile::default(), DEFAULT_LOG_BLOWUP))
     }
 
-    enum BabyBearPoseidon2Type {
-        Default,
-        Compressed,
+    #[must_use]
+    pub fn compressed_fri_config() -> FriConfig<ChallengeMmcs> {
+        fri_config(&fri_parameters(&SecurityProfile::default(), COMPRESSED_LOG_BLOWUP))
+    }
+
+    #[must_use]
+    pub fn ultra_compressed_fri_config() -> FriConfig<ChallengeMmcs> {
+        fri_config(&fri_parameters(&SecurityProfile::default(), ULTRA_COMPRESSED_LOG_BLOWUP))
     }
 
     #[derive(Deserialize)]
@@ -256,53 +263,54 @@ pub mod baby_bear_poseidon2 {
     pub struct BabyBearPoseidon2 {
         pub perm: Perm,
         pcs: Pcs,
-        config_type: BabyBearPoseidon2Type,
+        fri_parameters: FriParameters,
     }
 
     impl BabyBearPoseidon2 {
         #[must_use]
         pub fn new() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft {};
-            let fri_config = default_fri_config();
-            let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Default }
+            Self::with_security_profile(&SecurityProfile::default(), DEFAULT_LOG_BLOWUP)
         }
 
         #[must_use]
         pub fn compressed() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft {};
-            let fri_config = compressed_fri_config();
-            let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Compressed }
+            Self::with_security_profile(&SecurityProfile::default(), COMPRESSED_LOG_BLOWUP)
         }
 
         #[must_use]
         pub fn ultra_compressed() -> Self {
+            Self::with_security_profile(&SecurityProfile::default(), ULTRA_COMPRESSED_LOG_BLOWUP)
+        }
+
+        /// A config with the FRI parameters of the security profile, for the given default log
+        /// blowup.
+        #[must_use]
+        pub fn with_security_profile(profile: &SecurityProfile, default_log_blowup: usize) -> Self {
+            Self::with_fri_parameters(fri_parameters(profile, default_log_blowup))
+        }
+
+        #[must_use]
+        pub fn with_fri_parameters(fri_parameters: FriParameters) -> Self {
             let perm = my_perm();
             let hash = MyHash::new(perm.clone());
             let compress = MyCompress::new(perm.clone());
             let val_mmcs = ValMmcs::new(hash, compress);
             let dft = Dft {};
-            let fri_config = ultra_compressed_fri_config();
+            let fri_config = fri_config(&fri_parameters);
             let pcs = Pcs::new(27, dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: BabyBearPoseidon2Type::Compressed }
+            Self { pcs, perm, fri_parameters }
+        }
+
+        /// The FRI parameters of the config.
+        #[must_use]
+        pub fn fri_parameters(&self) -> FriParameters {
+            self.fri_parameters
         }
     }
 
     impl Clone for BabyBearPoseidon2 {
         fn clone(&self) -> Self {
-            match self.config_type {
-                BabyBearPoseidon2Type::Default => Self::new(),
-                BabyBearPoseidon2Type::Compressed => Self::compressed(),
-            }
+            Self::with_fri_parameters(self.fri_parameters)
         }
     }
 
@@ -33,6 +33,7 @@ mod permutation;
 mod prover;
 mod quotient;
 mod record;
+mod security;
 mod types;
 mod util;
 mod verifier;
@@ -50,6 +51,7 @@ pub use permutation::*;
 pub use prover::*;
 pub use quotient::*;
 pub use record::*;
+pub use security::*;
 pub use types::*;
 pub use verifier::*;
 pub use word::*;
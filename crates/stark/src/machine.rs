@@ -1,12 +1,14 @@
 use hashbrown::HashMap;
 use itertools::Itertools;
 use p3_air::Air;
+use p3_baby_bear::BabyBear;
 use p3_challenger::{CanObserve, FieldChallenger};
-use p3_commit::Pcs;
-use p3_field::{AbstractExtensionField, AbstractField, Field, PrimeField32};
+use p3_commit::{Pcs, TwoAdicMultiplicativeCoset};
+use p3_field::{AbstractExtensionField, AbstractField, Field, PrimeField32, TwoAdicField};
 use p3_matrix::{dense::RowMajorMatrix, Dimensions, Matrix};
 use p3_maybe_rayon::prelude::*;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp1_primitives::poseidon2_hash;
 use std::{array, cmp::Reverse, env, fmt::Debug, time::Instant};
 use tracing::instrument;
 
@@ -15,7 +17,7 @@ use crate::{
     air::{InteractionScope, MachineAir, MachineProgram},
     lookup::{debug_interactions_with_all_chips, InteractionKind},
     record::MachineRecord,
-    DebugConstraintBuilder, ShardProof, VerifierConstraintFolder,
+    DebugConstraintBuilder, ShardProof, VerifierConstraintFolder, DIGEST_SIZE,
 };
 
 use super::{
@@ -79,6 +81,52 @@ impl<SC: StarkGenericConfig> StarkProvingKey<SC> {
     }
 }
 
+impl<SC: StarkGenericConfig<Val = BabyBear, Domain = TwoAdicMultiplicativeCoset<BabyBear>>>
+    StarkVerifyingKey<SC>
+where
+    Com<SC>: AsRef<[BabyBear; DIGEST_SIZE]>,
+{
+    /// Hashes the verifying key into a digest of `BabyBear` elements.
+    ///
+    /// This is the digest the allowed verifying keys of the recursion programs are keyed by, and the
+    /// digest of the program verifying keys the recursion public values commit to.
+    #[must_use]
+    pub fn hash_babybear(&self) -> [BabyBear; DIGEST_SIZE] {
+        let prep_domains = self.chip_information.iter().map(|(_, domain, _)| domain);
+        let num_inputs = DIGEST_SIZE + 1 + (4 * prep_domains.len());
+        let mut inputs = Vec::with_capacity(num_inputs);
+        inputs.extend(self.commit.as_ref());
+        inputs.push(self.pc_start);
+        for domain in prep_domains {
+            inputs.push(BabyBear::from_canonical_usize(domain.log_n));
+            let size = 1 << domain.log_n;
+            inputs.push(BabyBear::from_canonical_usize(size));
+            let g = BabyBear::two_adic_generator(domain.log_n);
+            inputs.push(domain.shift);
+            inputs.push(g);
+        }
+
+        poseidon2_hash(inputs)
+    }
+}
+
+/// Packs a digest of `BabyBear` elements into a big endian 32 byte word, 31 bits per element with
+/// the last element in the least significant bits.
+///
+/// This is the encoding of the vkey hash of a program, as returned by `vk.bytes32()`.
+#[must_use]
+pub fn babybears_to_bytes32(digest: &[BabyBear; DIGEST_SIZE]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, word) in digest.iter().rev().enumerate() {
+        let word = word.as_canonical_u32();
+        for bit in (0..31).filter(|bit| (word >> bit) & 1 == 1) {
+            let position = 31 * i + bit;
+            bytes[31 - position / 8] |= 1 << (position % 8);
+        }
+    }
+    bytes
+}
+
 /// A verifying key for a STARK.
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(bound(serialize = "Dom<SC>: Serialize"))]
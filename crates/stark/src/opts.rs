@@ -3,6 +3,8 @@ use std::env;
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
+use crate::SecurityProfile;
+
 const MAX_SHARD_SIZE: usize = 1 << 21;
 const RECURSION_MAX_SHARD_SIZE: usize = 1 << 22;
 const MAX_SHARD_BATCH_SIZE: usize = 8;
@@ -20,11 +22,17 @@ pub struct SP1ProverOpts {
     pub core_opts: SP1CoreOpts,
     /// Options for the recursion prover.
     pub recursion_opts: SP1CoreOpts,
+    /// The security profile the FRI parameters of the proofs are derived from.
+    pub security_profile: SecurityProfile,
 }
 
 impl Default for SP1ProverOpts {
     fn default() -> Self {
-        Self { core_opts: SP1CoreOpts::default(), recursion_opts: SP1CoreOpts::recursion() }
+        Self {
+            core_opts: SP1CoreOpts::default(),
+            recursion_opts: SP1CoreOpts::recursion(),
+            security_profile: SecurityProfile::default(),
+        }
     }
 }
 
@@ -142,6 +150,10 @@ pub struct SplitOpts {
     pub sha_extend: usize,
     /// The threshold for sha compress events.
     pub sha_compress: usize,
+    /// The threshold for blake2b compress events.
+    pub blake2b_compress: usize,
+    /// The threshold for blake3 compress events.
+    pub blake3_compress: usize,
     /// The threshold for memory events.
     pub memory: usize,
 }
@@ -155,6 +167,8 @@ impl SplitOpts {
             keccak: deferred_shift_threshold / 24,
             sha_extend: deferred_shift_threshold / 48,
             sha_compress: deferred_shift_threshold / 80,
+            blake2b_compress: deferred_shift_threshold / 100,
+            blake3_compress: deferred_shift_threshold / 60,
             memory: deferred_shift_threshold * 4,
         }
     }
@@ -0,0 +1,172 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// The soundness regime the security of FRI is estimated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Soundness {
+    /// The conjectured soundness of FRI, where each query contributes `log_blowup` bits.
+    #[default]
+    Conjectured,
+    /// The proven soundness of FRI in the Johnson bound regime, where each query contributes
+    /// `log_blowup / 2` bits.
+    Proven,
+}
+
+/// The security level of a proof, in bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SecurityLevel {
+    /// The soundness regime the level is estimated in.
+    pub soundness: Soundness,
+    /// The number of bits of security.
+    pub bits: usize,
+}
+
+impl Display for SecurityLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let soundness = match self.soundness {
+            Soundness::Conjectured => "conjectured",
+            Soundness::Proven => "proven",
+        };
+        write!(f, "{} bits ({soundness})", self.bits)
+    }
+}
+
+/// The parameters of FRI of a STARK config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FriParameters {
+    /// The log of the blowup factor of the low degree extension.
+    pub log_blowup: usize,
+    /// The number of queries.
+    pub num_queries: usize,
+    /// The number of bits of the proof of work.
+    pub proof_of_work_bits: usize,
+}
+
+impl FriParameters {
+    /// The security level achieved by these parameters in the given soundness regime.
+    #[must_use]
+    pub fn security_level(&self, soundness: Soundness) -> SecurityLevel {
+        let query_bits = match soundness {
+            Soundness::Conjectured => self.num_queries * self.log_blowup,
+            Soundness::Proven => self.num_queries * self.log_blowup / 2,
+        };
+        SecurityLevel { soundness, bits: query_bits + self.proof_of_work_bits }
+    }
+}
+
+/// The security profile the FRI parameters of the core, compress, shrink and wrap configs are
+/// derived from.
+///
+/// Every config has a default blowup, picked for the degree of its constraints and the size of
+/// its proofs. The number of queries of a config is the bits the queries must contribute to reach
+/// `target_bits`, divided by the bits of a query and rounded down, as for the parameters SP1 has
+/// always used: with a blowup not dividing these bits, as the log blowup 3 of the ultra compressed
+/// config, a proof falls short of `target_bits` by less than one query, which the reported
+/// [SecurityLevel] reflects. A larger blowup means fewer queries and smaller proofs, at the cost of
+/// a slower prover.
+///
+/// The default profile targets 116 bits of conjectured security, 100 bits from the queries and 16
+/// bits from the proof of work. Proofs generated with any other profile are only valid for the
+/// verifying keys and circuit artifacts generated with the same profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SecurityProfile {
+    /// The soundness regime `target_bits` is reached in.
+    pub soundness: Soundness,
+    /// The number of bits of security to target.
+    pub target_bits: usize,
+    /// The log of the blowup factor to use instead of the default blowup of every config.
+    ///
+    /// It is raised to the minimum blowup the degree of the constraints of a config requires.
+    pub log_blowup: Option<usize>,
+    /// The number of bits of the proof of work.
+    pub proof_of_work_bits: usize,
+}
+
+impl Default for SecurityProfile {
+    fn default() -> Self {
+        Self::conjectured(116)
+    }
+}
+
+impl SecurityProfile {
+    /// A profile targeting `target_bits` of conjectured security with the default blowups.
+    #[must_use]
+    pub const fn conjectured(target_bits: usize) -> Self {
+        Self {
+            soundness: Soundness::Conjectured,
+            target_bits,
+            log_blowup: None,
+            proof_of_work_bits: 16,
+        }
+    }
+
+    /// A profile targeting `target_bits` of proven security with the default blowups.
+    #[must_use]
+    pub const fn proven(target_bits: usize) -> Self {
+        Self { soundness: Soundness::Proven, target_bits, log_blowup: None, proof_of_work_bits: 16 }
+    }
+
+    /// Sets the log of the blowup factor of every config.
+    #[must_use]
+    pub const fn with_log_blowup(mut self, log_blowup: usize) -> Self {
+        self.log_blowup = Some(log_blowup);
+        self
+    }
+
+    /// Sets the number of bits of the proof of work.
+    #[must_use]
+    pub const fn with_proof_of_work_bits(mut self, proof_of_work_bits: usize) -> Self {
+        self.proof_of_work_bits = proof_of_work_bits;
+        self
+    }
+
+    /// The FRI parameters of a config with the given default and minimum log blowups.
+    #[must_use]
+    pub fn fri_parameters(
+        &self,
+        default_log_blowup: usize,
+        min_log_blowup: usize,
+    ) -> FriParameters {
+        let log_blowup = self.log_blowup.unwrap_or(default_log_blowup).max(min_log_blowup).max(1);
+        let query_bits = self.target_bits.saturating_sub(self.proof_of_work_bits);
+        let num_queries = match self.soundness {
+            Soundness::Conjectured => query_bits / log_blowup,
+            Soundness::Proven => 2 * query_bits / log_blowup,
+        };
+        FriParameters { log_blowup, num_queries, proof_of_work_bits: self.proof_of_work_bits }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_fri_parameters() {
+        let profile = SecurityProfile::default();
+        for (log_blowup, num_queries) in [(1, 100), (2, 50), (4, 25)] {
+            let parameters = profile.fri_parameters(log_blowup, 1);
+            assert_eq!(parameters.num_queries, num_queries);
+            assert_eq!(parameters.proof_of_work_bits, 16);
+            assert_eq!(
+                parameters.security_level(Soundness::Conjectured),
+                SecurityLevel { soundness: Soundness::Conjectured, bits: 116 }
+            );
+        }
+
+        // The ultra compressed config keeps its historical 33 queries.
+        let parameters = profile.fri_parameters(3, 1);
+        assert_eq!(parameters.num_queries, 33);
+        assert_eq!(parameters.security_level(Soundness::Conjectured).bits, 115);
+    }
+
+    #[test]
+    fn test_proven_fri_parameters() {
+        let profile = SecurityProfile::proven(100).with_log_blowup(2);
+        let parameters = profile.fri_parameters(1, 3);
+        assert_eq!(parameters.log_blowup, 3);
+        assert_eq!(parameters.num_queries, 56);
+        assert_eq!(parameters.security_level(Soundness::Proven).bits, 100);
+    }
+}
@@ -10,9 +10,9 @@ use p3_matrix::{
     stack::VerticalPair,
     Matrix,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::{Challenge, Com, OpeningProof, StarkGenericConfig, Val};
+use super::{Challenge, Com, Dom, OpeningProof, StarkGenericConfig, StarkVerifyingKey, Val};
 use crate::air::InteractionScope;
 
 pub type QuotientOpenedValues<T> = Vec<T>;
@@ -164,6 +164,26 @@ impl<SC: StarkGenericConfig> Debug for MachineProof<SC> {
     }
 }
 
+/// An intermediate proof which proves the execution.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(bound(serialize = "ShardProof<SC>: Serialize, Dom<SC>: Serialize"))]
+#[serde(bound(deserialize = "ShardProof<SC>: Deserialize<'de>, Dom<SC>: DeserializeOwned"))]
+pub struct SP1ReduceProof<SC: StarkGenericConfig> {
+    /// The compress verifying key associated with the proof.
+    pub vk: StarkVerifyingKey<SC>,
+    /// The shard proof representing the compressed proof.
+    pub proof: ShardProof<SC>,
+}
+
+impl<SC: StarkGenericConfig> Debug for SP1ReduceProof<SC> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug_struct = f.debug_struct("SP1ReduceProof");
+        debug_struct.field("vk", &self.vk);
+        debug_struct.field("proof", &self.proof);
+        debug_struct.finish()
+    }
+}
+
 /// The hash of all the public values that a zkvm program has committed to.
 pub struct PublicValuesDigest(pub [u8; 32]);
 
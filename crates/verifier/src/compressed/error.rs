@@ -0,0 +1,20 @@
+use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, MachineVerificationError};
+use thiserror_no_std::Error;
+
+#[derive(Debug, Error)]
+pub enum CompressedError {
+    #[error("Proof verification failed: {0}")]
+    ProofVerificationFailed(MachineVerificationError<BabyBearPoseidon2>),
+    #[error("Recursion public values digest mismatch")]
+    RecursionPublicValuesDigestMismatch,
+    #[error("Compress verifying key is not allowed")]
+    VerifyingKeyNotAllowed,
+    #[error("Proof is not complete")]
+    IncompleteProof,
+    #[error("Program vkey hash mismatch")]
+    ProgramVkeyHashMismatch,
+    #[error("Public values digest mismatch")]
+    PublicValuesDigestMismatch,
+    #[error("General error")]
+    GeneralError(#[from] crate::error::Error),
+}
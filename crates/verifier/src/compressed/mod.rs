@@ -0,0 +1,113 @@
+pub mod error;
+
+use alloc::{collections::BTreeMap, vec};
+use core::borrow::Borrow;
+
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, PrimeField32};
+use sha2::{Digest, Sha256};
+use sp1_recursion_core::{
+    air::{recursion_public_values_digest, RecursionPublicValues},
+    machine::{RecursionAir, COMPRESS_DEGREE},
+};
+use sp1_stark::{
+    baby_bear_poseidon2::{BabyBearPoseidon2, DEFAULT_LOG_BLOWUP},
+    babybears_to_bytes32, MachineProof, SP1ReduceProof, SecurityProfile, StarkGenericConfig,
+    DIGEST_SIZE,
+};
+
+use error::CompressedError;
+
+use crate::decode_sp1_vkey_hash;
+
+/// A verifier for compressed STARK proofs.
+#[derive(Debug)]
+pub struct CompressedVerifier;
+impl CompressedVerifier {
+    /// Verifies a compressed proof.
+    ///
+    /// # Arguments
+    ///
+    /// * `proof` - The compressed proof.
+    ///   This is generated in the following manner:
+    ///
+    /// ```ignore
+    /// let proof = client.prove(&pk, stdin).compressed().run()?;
+    /// let proof = proof.proof.try_as_compressed().unwrap();
+    /// ```
+    /// * `public_inputs` - The SP1 public inputs.
+    /// * `sp1_vkey_hash` - The SP1 vkey hash.
+    ///   This is generated in the following manner:
+    ///
+    /// ```ignore
+    /// use sp1_sdk::ProverClient;
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(ELF);
+    /// let sp1_vkey_hash = vk.bytes32();
+    /// ```
+    /// * `allowed_vks` - The map of the verifying key digests of the recursion programs allowed to
+    ///   produce compressed proofs. Usually this will be the `allowed_vk_map` of the SP1 prover of
+    ///   the current SP1 version, deserialized from its `vk_map.bin`.
+    /// * `security_profile` - The security profile the proof was generated with, which determines
+    ///   the FRI parameters it is verified with. Usually this will be the default profile.
+    ///
+    /// # Returns
+    ///
+    /// A success [`Result`] if verification succeeds, or a [`CompressedError`] if verification
+    /// fails.
+    pub fn verify(
+        proof: &SP1ReduceProof<BabyBearPoseidon2>,
+        sp1_public_inputs: &[u8],
+        sp1_vkey_hash: &str,
+        allowed_vks: &BTreeMap<[BabyBear; DIGEST_SIZE], usize>,
+        security_profile: &SecurityProfile,
+    ) -> Result<(), CompressedError> {
+        let SP1ReduceProof { vk: compress_vk, proof } = proof;
+
+        // Verify the proof against the compress machine, with the config the prover compresses
+        // proofs with.
+        let machine = RecursionAir::<BabyBear, COMPRESS_DEGREE>::compress_machine(
+            BabyBearPoseidon2::with_security_profile(security_profile, DEFAULT_LOG_BLOWUP),
+        );
+        let mut challenger = machine.config().challenger();
+        let machine_proof = MachineProof { shard_proofs: vec![proof.clone()] };
+        machine
+            .verify(compress_vk, &machine_proof, &mut challenger)
+            .map_err(CompressedError::ProofVerificationFailed)?;
+
+        // Check the digest of the recursion public values.
+        let public_values: &RecursionPublicValues<BabyBear> =
+            proof.public_values.as_slice().borrow();
+        if public_values.digest != recursion_public_values_digest(machine.config(), public_values) {
+            return Err(CompressedError::RecursionPublicValuesDigestMismatch);
+        }
+
+        // Check that the proof was generated by an allowed recursion program.
+        if !allowed_vks.contains_key(&compress_vk.hash_babybear()) {
+            return Err(CompressedError::VerifyingKeyNotAllowed);
+        }
+
+        // `is_complete` should be 1. In the reduce program, this ensures that the proof is fully
+        // reduced.
+        if public_values.is_complete != BabyBear::one() {
+            return Err(CompressedError::IncompleteProof);
+        }
+
+        // Check that the proof is for the program of the given vkey hash.
+        let sp1_vkey_hash = decode_sp1_vkey_hash(sp1_vkey_hash)?;
+        if babybears_to_bytes32(&public_values.sp1_vk_digest) != sp1_vkey_hash {
+            return Err(CompressedError::ProgramVkeyHashMismatch);
+        }
+
+        // Check that the proof commits to the given public inputs.
+        let committed_value_digest = public_values
+            .committed_value_digest
+            .iter()
+            .flat_map(|word| word.0.iter().map(|x| x.as_canonical_u32() as u8));
+        if !committed_value_digest.eq(Sha256::digest(sp1_public_inputs)) {
+            return Err(CompressedError::PublicValuesDigestMismatch);
+        }
+
+        Ok(())
+    }
+}
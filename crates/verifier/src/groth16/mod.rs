@@ -42,6 +42,11 @@ impl Groth16Verifier {
         sp1_vkey_hash: &str,
         groth16_vk: &[u8],
     ) -> Result<(), Groth16Error> {
+        // The proof must at least hold the prefix checked below.
+        if proof.len() < 4 {
+            return Err(Groth16Error::GeneralError(Error::InvalidData));
+        }
+
         // Hash the vk and get the first 4 bytes.
         let groth16_vk_hash: [u8; 4] = Sha256::digest(groth16_vk)[..4]
             .try_into()
@@ -1,5 +1,11 @@
 //! This crate provides verifiers for SP1 Groth16 and Plonk BN254 proofs in a no-std environment.
 //! It is patched for efficient verification within the SP1 ZKVM context.
+//!
+//! With the `wasm` feature, it provides JavaScript bindings of the Groth16 and Plonk verifiers for
+//! WebAssembly targets, packaged by the `sp1-verifier-wasm` crate.
+//!
+//! With the `compressed` feature, it also provides a verifier for compressed STARK proofs, which
+//! requires std and pulls in the core machine.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
@@ -31,5 +37,13 @@ pub use plonk::error::PlonkError;
 pub use plonk::PlonkVerifier;
 mod plonk;
 
+#[cfg(feature = "compressed")]
+pub use compressed::{error::CompressedError, CompressedVerifier};
+#[cfg(feature = "compressed")]
+mod compressed;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 #[cfg(test)]
 mod tests;
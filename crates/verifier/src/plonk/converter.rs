@@ -109,12 +109,22 @@ pub(crate) fn load_plonk_verifying_key_from_bytes(
     Ok(result)
 }
 
+/// The length of a Plonk proof with `num_bsb22_commitments` BSB22 commitments: 7 uncompressed g1
+/// points and 6 Fr elements, plus 1 Fr element and 1 uncompressed g1 point for each commitment.
+fn plonk_proof_len(num_bsb22_commitments: usize) -> usize {
+    768 + 96 * num_bsb22_commitments
+}
+
 /// See https://github.com/jtguibas/gnark/blob/26e3df73fc223292be8b7fc0b7451caa4059a649/backend/plonk/bn254/solidity.go
 /// for how the proof is serialized.
 pub(crate) fn load_plonk_proof_from_bytes(
     buffer: &[u8],
     num_bsb22_commitments: usize,
 ) -> Result<PlonkProof, PlonkError> {
+    if buffer.len() != plonk_proof_len(num_bsb22_commitments) {
+        return Err(PlonkError::GeneralError(Error::InvalidData));
+    }
+
     let lro0 = uncompressed_bytes_to_g1_point(&buffer[..64])?;
     let lro1 = uncompressed_bytes_to_g1_point(&buffer[64..128])?;
     let lro2 = uncompressed_bytes_to_g1_point(&buffer[128..192])?;
@@ -38,6 +38,102 @@ fn test_verify_plonk() {
         .expect("Plonk proof is invalid");
 }
 
+#[test]
+fn test_verify_truncated_proofs() {
+    use crate::{error::Error, Groth16Error, PlonkError};
+
+    let vkey_hash = "0x00e60860c07bfc6e4c480286c0ddbb879674eb47f84b4ef041cf858b17aa0ed1";
+
+    let groth16 = SP1ProofWithPublicValues::load("test_binaries/fibonacci-groth16.bin").unwrap();
+    let proof = groth16.bytes();
+    let public_inputs = groth16.public_values.to_vec();
+    for len in [0, 3, 100, proof.len() - 1] {
+        let result = crate::Groth16Verifier::verify(
+            &proof[..len],
+            &public_inputs,
+            vkey_hash,
+            &crate::GROTH16_VK_BYTES,
+        );
+        assert!(matches!(result, Err(Groth16Error::GeneralError(Error::InvalidData))));
+    }
+
+    let plonk = SP1ProofWithPublicValues::load("test_binaries/fibonacci-plonk.bin").unwrap();
+    let proof = plonk.bytes();
+    let public_inputs = plonk.public_values.to_vec();
+    for len in [0, 3, 100, proof.len() - 1] {
+        let result = crate::PlonkVerifier::verify(
+            &proof[..len],
+            &public_inputs,
+            vkey_hash,
+            &crate::PLONK_VK_BYTES,
+        );
+        assert!(matches!(result, Err(PlonkError::GeneralError(Error::InvalidData))));
+    }
+}
+
+#[test]
+fn test_decode_malformed_vkey_hashes() {
+    use crate::{decode_sp1_vkey_hash, error::Error};
+
+    let vkey_hash = "0x00e60860c07bfc6e4c480286c0ddbb879674eb47f84b4ef041cf858b17aa0ed1";
+    assert!(decode_sp1_vkey_hash(vkey_hash).is_ok());
+
+    let too_long = std::format!("{vkey_hash}00");
+    for malformed in ["", "0", "0x", "é", "0xé", "0x00e608", &vkey_hash[2..], &too_long] {
+        assert!(matches!(decode_sp1_vkey_hash(malformed), Err(Error::InvalidProgramVkeyHash)));
+    }
+}
+
+#[test]
+#[cfg(feature = "compressed")]
+fn test_verify_compressed() {
+    use sp1_sdk::{HashableKey, Prover, ProverClient, SP1Stdin, SecurityProfile};
+
+    let client = ProverClient::local();
+    let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    let (pk, vk) = client.setup(elf);
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&10usize);
+    let sp1_proof_with_public_values = client.prove(&pk, stdin).compressed().run().unwrap();
+
+    let public_inputs = sp1_proof_with_public_values.public_values.to_vec();
+    let proof = sp1_proof_with_public_values.proof.try_as_compressed().unwrap();
+    let vkey_hash = vk.bytes32();
+
+    // The verifying keys the prover allows recursion programs to have.
+    let allowed_vks = &client.prover.sp1_prover().allowed_vk_map;
+    let security_profile = SecurityProfile::default();
+
+    crate::CompressedVerifier::verify(
+        &proof,
+        &public_inputs,
+        &vkey_hash,
+        allowed_vks,
+        &security_profile,
+    )
+    .expect("Compressed proof is invalid");
+
+    // Public values that the proof does not commit to are rejected.
+    let result = crate::CompressedVerifier::verify(
+        &proof,
+        &[255, 4, 84],
+        &vkey_hash,
+        allowed_vks,
+        &security_profile,
+    );
+    assert!(matches!(result, Err(crate::CompressedError::PublicValuesDigestMismatch)));
+
+    // Proofs are not valid for another security profile.
+    let result = crate::CompressedVerifier::verify(
+        &proof,
+        &public_inputs,
+        &vkey_hash,
+        allowed_vks,
+        &SecurityProfile::conjectured(80),
+    );
+    assert!(matches!(result, Err(crate::CompressedError::ProofVerificationFailed(_))));
+}
+
 #[test]
 fn test_vkeys() {
     let groth16_path = try_install_circuit_artifacts("groth16");
@@ -23,7 +23,10 @@ pub fn bn254_public_values(sp1_vkey_hash: &[u8; 32], sp1_public_inputs: &[u8]) -
 }
 
 /// Decodes the sp1 vkey hash from the string from a call to `vk.bytes32`.
+///
+/// The string must be `0x` followed by 64 hex characters.
 pub fn decode_sp1_vkey_hash(sp1_vkey_hash: &str) -> Result<[u8; 32], Error> {
-    let bytes = hex::decode(&sp1_vkey_hash[2..]).map_err(|_| Error::InvalidProgramVkeyHash)?;
+    let hex = sp1_vkey_hash.strip_prefix("0x").ok_or(Error::InvalidProgramVkeyHash)?;
+    let bytes = hex::decode(hex).map_err(|_| Error::InvalidProgramVkeyHash)?;
     bytes.try_into().map_err(|_| Error::InvalidProgramVkeyHash)
 }
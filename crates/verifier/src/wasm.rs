@@ -0,0 +1,182 @@
+//! JavaScript bindings of the verifiers, for WebAssembly targets.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    error::Error, Groth16Error, Groth16Verifier, PlonkError, PlonkVerifier, GROTH16_VK_BYTES,
+    PLONK_VK_BYTES,
+};
+
+/// The kind of a [VerificationError], one for each variant of [Groth16Error] and [PlonkError].
+///
+/// General errors caused by malformed proofs or vkey hashes have kinds of their own.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationErrorKind {
+    ProofVerificationFailed,
+    ProcessVerifyingKeyFailed,
+    PrepareInputsFailed,
+    GeneralError,
+    Groth16VkeyHashMismatch,
+    BeyondTheModulus,
+    Bsb22CommitmentMismatch,
+    ChallengeAlreadyComputed,
+    ChallengeNotFound,
+    DSTTooLarge,
+    EllTooLarge,
+    InverseNotFound,
+    InvalidNumberOfDigests,
+    InvalidWitness,
+    PairingCheckFailed,
+    PreviousChallengeNotComputed,
+    TranscriptError,
+    PlonkVkeyHashMismatch,
+    InvalidData,
+    InvalidProgramVkeyHash,
+}
+
+impl From<&Error> for VerificationErrorKind {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::InvalidData => Self::InvalidData,
+            Error::InvalidProgramVkeyHash => Self::InvalidProgramVkeyHash,
+            _ => Self::GeneralError,
+        }
+    }
+}
+
+/// The error thrown when a proof fails to verify.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct VerificationError {
+    kind: VerificationErrorKind,
+    message: String,
+}
+
+#[wasm_bindgen]
+impl VerificationError {
+    /// The kind of the error.
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> VerificationErrorKind {
+        self.kind
+    }
+
+    /// The description of the error.
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<Groth16Error> for VerificationError {
+    fn from(error: Groth16Error) -> Self {
+        let kind = match error {
+            Groth16Error::ProofVerificationFailed => VerificationErrorKind::ProofVerificationFailed,
+            Groth16Error::ProcessVerifyingKeyFailed => {
+                VerificationErrorKind::ProcessVerifyingKeyFailed
+            }
+            Groth16Error::PrepareInputsFailed => VerificationErrorKind::PrepareInputsFailed,
+            Groth16Error::GeneralError(ref inner) => inner.into(),
+            Groth16Error::Groth16VkeyHashMismatch => VerificationErrorKind::Groth16VkeyHashMismatch,
+        };
+        let message = match &error {
+            Groth16Error::GeneralError(inner) => format!("{error}: {inner}"),
+            _ => error.to_string(),
+        };
+        Self { kind, message }
+    }
+}
+
+impl From<PlonkError> for VerificationError {
+    fn from(error: PlonkError) -> Self {
+        let kind = match error {
+            PlonkError::BeyondTheModulus => VerificationErrorKind::BeyondTheModulus,
+            PlonkError::Bsb22CommitmentMismatch => VerificationErrorKind::Bsb22CommitmentMismatch,
+            PlonkError::ChallengeAlreadyComputed => VerificationErrorKind::ChallengeAlreadyComputed,
+            PlonkError::ChallengeNotFound => VerificationErrorKind::ChallengeNotFound,
+            PlonkError::DSTTooLarge => VerificationErrorKind::DSTTooLarge,
+            PlonkError::EllTooLarge => VerificationErrorKind::EllTooLarge,
+            PlonkError::InverseNotFound => VerificationErrorKind::InverseNotFound,
+            PlonkError::InvalidNumberOfDigests => VerificationErrorKind::InvalidNumberOfDigests,
+            PlonkError::InvalidWitness => VerificationErrorKind::InvalidWitness,
+            PlonkError::PairingCheckFailed => VerificationErrorKind::PairingCheckFailed,
+            PlonkError::PreviousChallengeNotComputed => {
+                VerificationErrorKind::PreviousChallengeNotComputed
+            }
+            PlonkError::TranscriptError => VerificationErrorKind::TranscriptError,
+            PlonkError::GeneralError(ref inner) => inner.into(),
+            PlonkError::PlonkVkeyHashMismatch => VerificationErrorKind::PlonkVkeyHashMismatch,
+        };
+        let message = match &error {
+            PlonkError::GeneralError(inner) => format!("{error}: {inner}"),
+            _ => error.to_string(),
+        };
+        Self { kind, message }
+    }
+}
+
+/// Decodes a hex string, with or without a `0x` prefix.
+fn decode_hex(hex: &str) -> Result<Vec<u8>, Error> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    hex::decode(hex).map_err(|_| Error::InvalidData)
+}
+
+/// Verifies a Groth16 proof against the Groth16 verifying key of this SP1 version.
+///
+/// The `proof` is the output of `proof.bytes()` in the SP1 SDK, and `sp1_vkey_hash` is the output
+/// of `vk.bytes32()`.
+#[wasm_bindgen]
+pub fn verify_groth16(
+    proof: &[u8],
+    public_inputs: &[u8],
+    sp1_vkey_hash: &str,
+) -> Result<(), VerificationError> {
+    Groth16Verifier::verify(proof, public_inputs, sp1_vkey_hash, &GROTH16_VK_BYTES)?;
+    Ok(())
+}
+
+/// Verifies a Groth16 proof like [verify_groth16], taking the proof and the public inputs as hex
+/// strings, with or without a `0x` prefix.
+#[wasm_bindgen]
+pub fn verify_groth16_hex(
+    proof: &str,
+    public_inputs: &str,
+    sp1_vkey_hash: &str,
+) -> Result<(), VerificationError> {
+    let proof = decode_hex(proof).map_err(Groth16Error::from)?;
+    let public_inputs = decode_hex(public_inputs).map_err(Groth16Error::from)?;
+    verify_groth16(&proof, &public_inputs, sp1_vkey_hash)
+}
+
+/// Verifies a Plonk proof against the Plonk verifying key of this SP1 version.
+///
+/// The `proof` is the output of `proof.bytes()` in the SP1 SDK, and `sp1_vkey_hash` is the output
+/// of `vk.bytes32()`.
+#[wasm_bindgen]
+pub fn verify_plonk(
+    proof: &[u8],
+    public_inputs: &[u8],
+    sp1_vkey_hash: &str,
+) -> Result<(), VerificationError> {
+    PlonkVerifier::verify(proof, public_inputs, sp1_vkey_hash, &PLONK_VK_BYTES)?;
+    Ok(())
+}
+
+/// Verifies a Plonk proof like [verify_plonk], taking the proof and the public inputs as hex
+/// strings, with or without a `0x` prefix.
+#[wasm_bindgen]
+pub fn verify_plonk_hex(
+    proof: &str,
+    public_inputs: &str,
+    sp1_vkey_hash: &str,
+) -> Result<(), VerificationError> {
+    let proof = decode_hex(proof).map_err(PlonkError::from)?;
+    let public_inputs = decode_hex(public_inputs).map_err(PlonkError::from)?;
+    verify_plonk(&proof, &public_inputs, sp1_vkey_hash)
+}
@@ -0,0 +1,7 @@
+//! The WebAssembly package of `sp1-verifier`, exposing the Groth16 and Plonk verifiers to
+//! JavaScript.
+//!
+//! Build it with [wasm-pack](https://rustwasm.github.io/wasm-pack/), for example with
+//! `wasm-pack build --target web` in this directory.
+
+pub use sp1_verifier::wasm::*;
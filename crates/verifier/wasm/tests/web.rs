@@ -0,0 +1,86 @@
+//! Tests of the bindings, run on `wasm32-unknown-unknown` with `wasm-pack test --node`.
+
+#![cfg(target_arch = "wasm32")]
+
+use sp1_verifier_wasm::{
+    verify_groth16, verify_groth16_hex, verify_plonk, verify_plonk_hex, VerificationErrorKind,
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// The proofs of the fibonacci example in `../test_binaries`, encoded by `proof.bytes()`.
+const GROTH16_PROOF: &str = include_str!("../test_binaries/fibonacci-groth16-proof.hex");
+const PLONK_PROOF: &str = include_str!("../test_binaries/fibonacci-plonk-proof.hex");
+
+// The public values of the fibonacci example.
+const PUBLIC_INPUTS: &str = "f4010000f404000086070000";
+
+// This vkey hash was derived by calling `vk.bytes32()` on the verifying key.
+const VKEY_HASH: &str = "0x00e60860c07bfc6e4c480286c0ddbb879674eb47f84b4ef041cf858b17aa0ed1";
+
+#[wasm_bindgen_test]
+fn test_verify_groth16() {
+    let proof = hex::decode(GROTH16_PROOF).unwrap();
+    let public_inputs = hex::decode(PUBLIC_INPUTS).unwrap();
+    verify_groth16(&proof, &public_inputs, VKEY_HASH).expect("Groth16 proof is invalid");
+}
+
+#[wasm_bindgen_test]
+fn test_verify_plonk() {
+    let proof = hex::decode(PLONK_PROOF).unwrap();
+    let public_inputs = hex::decode(PUBLIC_INPUTS).unwrap();
+    verify_plonk(&proof, &public_inputs, VKEY_HASH).expect("Plonk proof is invalid");
+}
+
+#[wasm_bindgen_test]
+fn test_verify_groth16_invalid_public_inputs() {
+    let proof = hex::decode(GROTH16_PROOF).unwrap();
+    let error = verify_groth16(&proof, &[255, 4, 84], VKEY_HASH).unwrap_err();
+    assert_eq!(error.kind(), VerificationErrorKind::ProofVerificationFailed);
+}
+
+#[wasm_bindgen_test]
+fn test_verify_plonk_wrong_system() {
+    let proof = hex::decode(GROTH16_PROOF).unwrap();
+    let public_inputs = hex::decode(PUBLIC_INPUTS).unwrap();
+    let error = verify_plonk(&proof, &public_inputs, VKEY_HASH).unwrap_err();
+    assert_eq!(error.kind(), VerificationErrorKind::PlonkVkeyHashMismatch);
+}
+
+#[wasm_bindgen_test]
+fn test_verify_hex() {
+    verify_groth16_hex(GROTH16_PROOF, PUBLIC_INPUTS, VKEY_HASH).expect("Groth16 proof is invalid");
+    let proof = format!("0x{PLONK_PROOF}");
+    verify_plonk_hex(&proof, PUBLIC_INPUTS, VKEY_HASH).expect("Plonk proof is invalid");
+
+    let error = verify_groth16_hex("0xzz", PUBLIC_INPUTS, VKEY_HASH).unwrap_err();
+    assert_eq!(error.kind(), VerificationErrorKind::InvalidData);
+}
+
+#[wasm_bindgen_test]
+fn test_verify_truncated_proofs() {
+    let public_inputs = hex::decode(PUBLIC_INPUTS).unwrap();
+
+    let proof = hex::decode(GROTH16_PROOF).unwrap();
+    for len in [0, 3, 100, proof.len() - 1] {
+        let error = verify_groth16(&proof[..len], &public_inputs, VKEY_HASH).unwrap_err();
+        assert_eq!(error.kind(), VerificationErrorKind::InvalidData);
+    }
+
+    let proof = hex::decode(PLONK_PROOF).unwrap();
+    for len in [0, 3, 100, proof.len() - 1] {
+        let error = verify_plonk(&proof[..len], &public_inputs, VKEY_HASH).unwrap_err();
+        assert_eq!(error.kind(), VerificationErrorKind::InvalidData);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_verify_malformed_vkey_hashes() {
+    let proof = hex::decode(GROTH16_PROOF).unwrap();
+    let public_inputs = hex::decode(PUBLIC_INPUTS).unwrap();
+    let unprefixed = VKEY_HASH.strip_prefix("0x").unwrap();
+    for vkey_hash in ["", "0", "0x", "é", "0xé", "0x00e608", unprefixed, &format!("{VKEY_HASH}00")]
+    {
+        let error = verify_groth16(&proof, &public_inputs, vkey_hash).unwrap_err();
+        assert_eq!(error.kind(), VerificationErrorKind::InvalidProgramVkeyHash);
+    }
+}
@@ -0,0 +1,108 @@
+//! 64-bit atomic operations, which RV32 cannot perform with the atomic extension.
+//!
+//! LLVM lowers them to these `libatomic` calls instead. The zkVM executes a single hart, so plain
+//! memory accesses are already atomic.
+//!
+//! The symbols are only exported inside the zkVM, so that the host's own `libatomic` is used when
+//! testing them.
+
+use core::ptr::{read_volatile, write_volatile};
+
+#[cfg_attr(target_os = "zkvm", no_mangle)]
+unsafe extern "C" fn __atomic_load_8(ptr: *const u64, _ordering: i32) -> u64 {
+    read_volatile(ptr)
+}
+
+#[cfg_attr(target_os = "zkvm", no_mangle)]
+unsafe extern "C" fn __atomic_store_8(ptr: *mut u64, value: u64, _ordering: i32) {
+    write_volatile(ptr, value);
+}
+
+#[cfg_attr(target_os = "zkvm", no_mangle)]
+unsafe extern "C" fn __atomic_exchange_8(ptr: *mut u64, value: u64, _ordering: i32) -> u64 {
+    let prev = read_volatile(ptr);
+    write_volatile(ptr, value);
+    prev
+}
+
+#[cfg_attr(target_os = "zkvm", no_mangle)]
+unsafe extern "C" fn __atomic_compare_exchange_8(
+    ptr: *mut u64,
+    expected: *mut u64,
+    desired: u64,
+    _success_ordering: i32,
+    _failure_ordering: i32,
+) -> bool {
+    let prev = read_volatile(ptr);
+    if prev == *expected {
+        write_volatile(ptr, desired);
+        true
+    } else {
+        *expected = prev;
+        false
+    }
+}
+
+macro_rules! atomic_fetch_op {
+    ($name:ident, |$prev:ident, $value:ident| $op:expr) => {
+        #[cfg_attr(target_os = "zkvm", no_mangle)]
+        unsafe extern "C" fn $name(ptr: *mut u64, $value: u64, _ordering: i32) -> u64 {
+            let $prev = read_volatile(ptr);
+            write_volatile(ptr, $op);
+            $prev
+        }
+    };
+}
+
+atomic_fetch_op!(__atomic_fetch_add_8, |prev, value| prev.wrapping_add(value));
+atomic_fetch_op!(__atomic_fetch_sub_8, |prev, value| prev.wrapping_sub(value));
+atomic_fetch_op!(__atomic_fetch_and_8, |prev, value| prev & value);
+atomic_fetch_op!(__atomic_fetch_or_8, |prev, value| prev | value);
+atomic_fetch_op!(__atomic_fetch_xor_8, |prev, value| prev ^ value);
+atomic_fetch_op!(__atomic_fetch_nand_8, |prev, value| !(prev & value));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `__ATOMIC_SEQ_CST` memory ordering.
+    const SEQ_CST: i32 = 5;
+
+    #[test]
+    fn test_load_store_exchange() {
+        let mut value = u64::MAX - 1;
+        unsafe {
+            assert_eq!(__atomic_load_8(&value, SEQ_CST), u64::MAX - 1);
+            __atomic_store_8(&mut value, 1 << 40, SEQ_CST);
+            assert_eq!(value, 1 << 40);
+            assert_eq!(__atomic_exchange_8(&mut value, 7, SEQ_CST), 1 << 40);
+        }
+        assert_eq!(value, 7);
+    }
+
+    #[test]
+    fn test_compare_exchange() {
+        let mut value = 7u64;
+        let mut expected = 8u64;
+        unsafe {
+            assert!(!__atomic_compare_exchange_8(&mut value, &mut expected, 9, SEQ_CST, SEQ_CST));
+            assert_eq!((value, expected), (7, 7));
+            assert!(__atomic_compare_exchange_8(&mut value, &mut expected, 9, SEQ_CST, SEQ_CST));
+        }
+        assert_eq!((value, expected), (9, 7));
+    }
+
+    #[test]
+    fn test_fetch_ops() {
+        let mut value = u64::MAX;
+        unsafe {
+            assert_eq!(__atomic_fetch_add_8(&mut value, 2, SEQ_CST), u64::MAX);
+            assert_eq!(__atomic_fetch_sub_8(&mut value, 3, SEQ_CST), 1);
+            assert_eq!(__atomic_fetch_or_8(&mut value, 1 << 33, SEQ_CST), u64::MAX - 1);
+            assert_eq!(__atomic_fetch_and_8(&mut value, 1 << 33 | 1, SEQ_CST), u64::MAX - 1);
+            assert_eq!(__atomic_fetch_xor_8(&mut value, 1 << 33 | 1, SEQ_CST), 1 << 33);
+            assert_eq!(__atomic_fetch_nand_8(&mut value, 1, SEQ_CST), 1);
+        }
+        assert_eq!(value, u64::MAX - 1);
+    }
+}
@@ -0,0 +1,237 @@
+//! A read-only file system, backed by the files the host adds to the stdin of the program.
+//!
+//! Files are opened, read, seeked and closed through the `FS_OPEN`, `FS_READ`, `FS_SEEK` and
+//! `FS_CLOSE` syscalls, which the executor serves from the files of the stdin. Opening a file
+//! returns the hashes of its chunks, as described in [sp1_primitives::fs], and every chunk read from
+//! the host is checked against its hash and kept in memory for the rest of the execution, so that
+//! every later read sees the same contents. When the program halts, it appends a digest of every
+//! path it opened and of the contents of the files to its public values, so that verifiers can
+//! check which files it read.
+
+use std::{
+    collections::{btree_map, BTreeMap},
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+use sha2::{Digest, Sha256};
+use sp1_primitives::fs::FILE_CHUNK_SIZE;
+
+use crate::syscalls::{
+    sys_alloc_aligned, syscall_fs_close, syscall_fs_open, syscall_fs_read, syscall_fs_seek,
+    syscall_hint_len, syscall_hint_read,
+};
+
+/// A file opened by the program, shared by every handle to it.
+struct FileState {
+    /// The length of the file in bytes.
+    len: u64,
+    /// The concatenated sha256 hashes of the chunks of the file.
+    chunk_hashes: &'static [u8],
+    /// The chunks of the file read so far, by index.
+    chunks: BTreeMap<usize, &'static [u8]>,
+}
+
+/// The files opened by the program, by path, or `None` for the paths without a file.
+static FILES: Mutex<BTreeMap<String, Option<FileState>>> = Mutex::new(BTreeMap::new());
+
+/// Reads the next hint into memory which is never freed, so it can be borrowed for the rest of the
+/// execution.
+fn read_hint() -> &'static [u8] {
+    let len = syscall_hint_len();
+    let capacity = (len + 3) / 4 * 4;
+    unsafe {
+        let ptr = sys_alloc_aligned(capacity, 4);
+        syscall_hint_read(ptr, len);
+        core::slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// A file of the read-only file system.
+///
+/// Closing a file, by dropping it, does not discard the chunks read from it: opening the same path
+/// again returns the same contents.
+#[derive(Debug)]
+pub struct File {
+    fd: u32,
+    path: String,
+    len: u64,
+    position: u64,
+}
+
+impl File {
+    /// Opens the file at `path`.
+    ///
+    /// Paths are matched exactly against the paths the host added the files with.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_str().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "path is not valid UTF-8")
+        })?;
+        syscall_fs_open(path.as_ptr(), path.len());
+        let found = match read_hint() {
+            [0] => None,
+            [1, response @ ..] if response.len() >= 12 => {
+                let fd = u32::from_le_bytes(response[..4].try_into().unwrap());
+                let len = u64::from_le_bytes(response[4..12].try_into().unwrap());
+                let chunk_hashes = &response[12..];
+                let num_chunks = len.div_ceil(FILE_CHUNK_SIZE as u64);
+                assert_eq!(
+                    chunk_hashes.len() as u64,
+                    num_chunks * 32,
+                    "malformed file system response"
+                );
+                Some((fd, len, chunk_hashes))
+            }
+            _ => panic!("malformed file system response"),
+        };
+
+        // The first response for a path is committed to, and every later one must match it.
+        let mut files = FILES.lock().unwrap_or_else(PoisonError::into_inner);
+        match files.entry(path.to_string()) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(found.map(|(_, len, chunk_hashes)| FileState {
+                    len,
+                    chunk_hashes,
+                    chunks: BTreeMap::new(),
+                }));
+            }
+            btree_map::Entry::Occupied(entry) => {
+                let unchanged = match (entry.get(), found) {
+                    (Some(state), Some((_, len, chunk_hashes))) => {
+                        state.len == len && state.chunk_hashes == chunk_hashes
+                    }
+                    (None, None) => true,
+                    _ => false,
+                };
+                assert!(unchanged, "the file at {path} changed");
+            }
+        }
+
+        let (fd, len, _) =
+            found.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such file"))?;
+        Ok(Self { fd, path: path.to_string(), len, position: 0 })
+    }
+
+    /// Returns the length of the file in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the chunk of the file at `index`, reading it from the host the first time.
+    fn chunk(&self, index: usize) -> &'static [u8] {
+        let mut files = FILES.lock().unwrap_or_else(PoisonError::into_inner);
+        let state = files.get_mut(&self.path).and_then(Option::as_mut).unwrap();
+        if let Some(chunk) = state.chunks.get(&index) {
+            return chunk;
+        }
+
+        let start = index * FILE_CHUNK_SIZE;
+        let len = (state.len as usize - start).min(FILE_CHUNK_SIZE);
+        syscall_fs_seek(self.fd, start);
+        syscall_fs_read(self.fd, len);
+        let chunk = read_hint();
+        assert!(
+            chunk.len() == len
+                && Sha256::digest(chunk)[..] == state.chunk_hashes[index * 32..(index + 1) * 32],
+            "the chunk of the file at {} does not match its hash",
+            self.path
+        );
+        state.chunks.insert(index, chunk);
+        chunk
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() || self.position >= self.len {
+            return Ok(0);
+        }
+        let chunk = self.chunk((self.position / FILE_CHUNK_SIZE as u64) as usize);
+        let offset = (self.position % FILE_CHUNK_SIZE as u64) as usize;
+        let read = buf.len().min(chunk.len() - offset);
+        buf[..read].copy_from_slice(&chunk[offset..offset + read]);
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for File {
+    /// Moves the position of the file.
+    ///
+    /// The chunk at the new position is only read from the host by the next read.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative position")
+        })?;
+        Ok(self.position)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        syscall_fs_close(self.fd);
+    }
+}
+
+/// Reads the contents of the file at `path`.
+pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut contents = Vec::with_capacity(file.len() as usize);
+    file.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// Reads the contents of the file at `path` as a string.
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Returns whether there is a file at `path`.
+pub fn exists(path: impl AsRef<Path>) -> bool {
+    File::open(path).is_ok()
+}
+
+/// Appends the digest of the files the program opened to the public values, if it opened any.
+///
+/// Called when the program halts, so that verifiers can check which files it read. See
+/// [FD_PUBLIC_VALUES](sp1_primitives::consts::fd::FD_PUBLIC_VALUES) for where the digest goes.
+#[cfg(target_os = "zkvm")]
+pub(crate) fn commit_file_system_digest() {
+    use sp1_primitives::{consts::fd::FD_PUBLIC_VALUES, fs::file_digest_from_chunk_hashes};
+    use std::sync::TryLockError;
+
+    // The lock is held if the program halts while opening or reading a file.
+    let files = match FILES.try_lock() {
+        Ok(files) => files,
+        Err(TryLockError::Poisoned(err)) => err.into_inner(),
+        Err(TryLockError::WouldBlock) => return,
+    };
+    if files.is_empty() {
+        return;
+    }
+    let mut hasher = Sha256::new();
+    for (path, state) in files.iter() {
+        hasher.update((path.len() as u32).to_le_bytes());
+        hasher.update(path.as_bytes());
+        match state {
+            Some(state) => {
+                hasher.update([1]);
+                hasher.update(file_digest_from_chunk_hashes(state.len, state.chunk_hashes));
+            }
+            None => hasher.update([0]),
+        }
+    }
+    let digest: [u8; 32] = hasher.finalize().into();
+    crate::syscalls::syscall_write(FD_PUBLIC_VALUES, digest.as_ptr(), digest.len());
+}
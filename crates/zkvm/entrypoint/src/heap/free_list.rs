@@ -0,0 +1,199 @@
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    ptr,
+};
+
+use crate::syscalls::{bump_alloc, out_of_memory};
+
+/// The log of the size of the smallest size class, large enough to hold a pointer.
+const MIN_CLASS_LOG: usize = 3;
+
+/// The log of the size of a page, which is also the size of the largest size class.
+const PAGE_LOG: usize = 12;
+
+/// The size of a page.
+const PAGE_SIZE: usize = 1 << PAGE_LOG;
+
+/// The number of size classes, from 8 bytes to a page.
+const NUM_CLASSES: usize = PAGE_LOG - MIN_CLASS_LOG + 1;
+
+/// A freed block of a size class, linking to the next freed block of the class.
+struct FreeBlock {
+    next: *mut FreeBlock,
+}
+
+/// A freed run of pages, linking to the next freed run.
+struct FreeChunk {
+    next: *mut FreeChunk,
+    size: usize,
+}
+
+/// The free lists of the allocator.
+struct State {
+    /// The freed blocks of every size class.
+    blocks: [*mut FreeBlock; NUM_CLASSES],
+    /// The part of the last page carved into blocks of every size class that was never allocated,
+    /// as the next block and the end of the page.
+    carved: [(usize, usize); NUM_CLASSES],
+    /// The freed runs of pages of allocations larger than a page.
+    chunks: *mut FreeChunk,
+}
+
+/// A heap allocator reusing freed memory.
+///
+/// Allocations of at most a page are rounded up to a power of two size class, from 8 bytes to 4
+/// KiB. The blocks of a class are carved out of pages taken from the heap, and freed blocks are kept
+/// in a free list per class. Larger allocations are rounded up to whole pages, and freed runs of
+/// pages are kept in a first fit free list, split on reuse but never coalesced.
+///
+/// Compared to [SimpleAlloc](super::SimpleAlloc), programs allocating and freeing many temporary
+/// buffers touch far fewer memory addresses, at the cost of a few more cycles per allocation.
+pub struct FreeListAlloc {
+    state: UnsafeCell<State>,
+}
+
+// SAFETY: The zkVM is single threaded.
+unsafe impl Sync for FreeListAlloc {}
+
+impl FreeListAlloc {
+    /// Creates a new [FreeListAlloc].
+    pub const fn new() -> Self {
+        Self {
+            state: UnsafeCell::new(State {
+                blocks: [ptr::null_mut(); NUM_CLASSES],
+                carved: [(0, 0); NUM_CLASSES],
+                chunks: ptr::null_mut(),
+            }),
+        }
+    }
+}
+
+impl FreeListAlloc {
+    /// Allocates memory for the layout past every previous allocation, taking the whole block or
+    /// run of pages the layout is rounded up to, so that it can be freed like any other allocation.
+    ///
+    /// # Safety
+    ///
+    /// The layout must be valid.
+    pub(crate) unsafe fn alloc_fresh(layout: Layout) -> *mut u8 {
+        let (size, align) = match size_class(&layout) {
+            Some(class) => (1 << (class + MIN_CLASS_LOG), 1 << (class + MIN_CLASS_LOG)),
+            None => (chunk_size(&layout), layout.align().max(PAGE_SIZE)),
+        };
+        bump_alloc(size, align).unwrap_or_else(|| out_of_memory(layout.size()))
+    }
+}
+
+impl Default for FreeListAlloc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the size class of the layout, or `None` if it is larger than a page.
+fn size_class(layout: &Layout) -> Option<usize> {
+    let size = layout.size().max(layout.align()).max(1 << MIN_CLASS_LOG);
+    if size > PAGE_SIZE {
+        return None;
+    }
+    Some(size.next_power_of_two().trailing_zeros() as usize - MIN_CLASS_LOG)
+}
+
+/// Returns the size of the run of pages holding an allocation larger than a page.
+fn chunk_size(layout: &Layout) -> usize {
+    (layout.size().max(1) + PAGE_SIZE - 1) & !(PAGE_SIZE - 1)
+}
+
+impl State {
+    unsafe fn alloc_block(&mut self, class: usize, requested: usize) -> *mut u8 {
+        let block = self.blocks[class];
+        if !block.is_null() {
+            self.blocks[class] = (*block).next;
+            return block as *mut u8;
+        }
+
+        // Carve the block out of the last page of the class, taking a new page if it is full.
+        let block_size = 1 << (class + MIN_CLASS_LOG);
+        let (mut next, mut end) = self.carved[class];
+        if next == end {
+            let page = bump_alloc(PAGE_SIZE, PAGE_SIZE).unwrap_or_else(|| out_of_memory(requested));
+            next = page as usize;
+            end = next + PAGE_SIZE;
+        }
+        self.carved[class] = (next + block_size, end);
+        next as *mut u8
+    }
+
+    unsafe fn dealloc_block(&mut self, ptr: *mut u8, class: usize) {
+        let block = ptr as *mut FreeBlock;
+        (*block).next = self.blocks[class];
+        self.blocks[class] = block;
+    }
+
+    unsafe fn alloc_chunk(&mut self, layout: &Layout) -> *mut u8 {
+        let size = chunk_size(layout);
+
+        // Take the first freed run that is large enough and aligned, splitting off the rest.
+        let mut link = ptr::addr_of_mut!(self.chunks);
+        while !(*link).is_null() {
+            let chunk = *link;
+            let chunk_size = (*chunk).size;
+            if chunk_size >= size && (chunk as usize) & (layout.align() - 1) == 0 {
+                *link = (*chunk).next;
+                if chunk_size > size {
+                    self.dealloc_chunk((chunk as *mut u8).add(size), chunk_size - size);
+                }
+                return chunk as *mut u8;
+            }
+            link = ptr::addr_of_mut!((*chunk).next);
+        }
+
+        bump_alloc(size, layout.align().max(PAGE_SIZE))
+            .unwrap_or_else(|| out_of_memory(layout.size()))
+    }
+
+    unsafe fn dealloc_chunk(&mut self, ptr: *mut u8, size: usize) {
+        let chunk = ptr as *mut FreeChunk;
+        (*chunk).next = self.chunks;
+        (*chunk).size = size;
+        self.chunks = chunk;
+    }
+}
+
+unsafe impl GlobalAlloc for FreeListAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: The zkVM is single threaded, and the allocator does not reenter itself.
+        let state = &mut *self.state.get();
+        match size_class(&layout) {
+            Some(class) => state.alloc_block(class, layout.size()),
+            None => state.alloc_chunk(&layout),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let state = &mut *self.state.get();
+        match size_class(&layout) {
+            Some(class) => state.dealloc_block(ptr, class),
+            None => state.dealloc_chunk(ptr, chunk_size(&layout)),
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // Keep the allocation in place if it still fits in its block or run of pages.
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let in_place = match (size_class(&layout), size_class(&new_layout)) {
+            (Some(class), Some(new_class)) => class == new_class,
+            (None, None) => chunk_size(&layout) == chunk_size(&new_layout),
+            _ => false,
+        };
+        if in_place {
+            return ptr;
+        }
+
+        let new_ptr = self.alloc(new_layout);
+        ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+        self.dealloc(ptr, layout);
+        new_ptr
+    }
+}
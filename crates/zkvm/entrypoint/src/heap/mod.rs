@@ -0,0 +1,65 @@
+use core::alloc::{GlobalAlloc, Layout};
+
+use crate::syscalls::sys_alloc_aligned;
+
+#[cfg(feature = "free-list-alloc")]
+mod free_list;
+
+#[cfg(feature = "free-list-alloc")]
+pub use free_list::FreeListAlloc;
+
+/// A simple heap allocator.
+///
+/// Allocates memory from left to right, without any deallocation.
+pub struct SimpleAlloc;
+
+impl SimpleAlloc {
+    /// Creates a new [SimpleAlloc].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SimpleAlloc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for SimpleAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        sys_alloc_aligned(layout.size(), layout.align())
+    }
+
+    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "free-list-alloc")] {
+        /// The allocator installed by [entrypoint](crate::entrypoint).
+        pub type EntrypointAlloc = FreeListAlloc;
+    } else {
+        /// The allocator installed by [entrypoint](crate::entrypoint).
+        pub type EntrypointAlloc = SimpleAlloc;
+    }
+}
+
+/// Allocates `bytes` aligned to `align` from memory the program never touched, so that a hint can
+/// be read into it, with room for [EntrypointAlloc] to later free or reallocate it.
+///
+/// Allocations of [EntrypointAlloc] may reuse freed memory, which the executor refuses to read
+/// hints into.
+///
+/// # Safety
+///
+/// `align` must be a power of two.
+#[no_mangle]
+pub unsafe extern "C" fn sys_alloc_fresh(bytes: usize, align: usize) -> *mut u8 {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "free-list-alloc")] {
+            FreeListAlloc::alloc_fresh(Layout::from_size_align_unchecked(bytes, align))
+        } else {
+            sys_alloc_aligned(bytes, align)
+        }
+    }
+}
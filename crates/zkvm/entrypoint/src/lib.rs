@@ -1,5 +1,6 @@
 extern crate alloc;
 
+pub mod fs;
 pub mod heap;
 pub mod syscalls;
 
@@ -16,6 +17,9 @@ pub mod lib {
 #[cfg(all(target_os = "zkvm", feature = "libm"))]
 mod libm;
 
+#[cfg(any(target_os = "zkvm", test))]
+mod atomic;
+
 /// The number of 32 bit words that the public values digest is composed of.
 pub const PV_DIGEST_NUM_WORDS: usize = 8;
 pub const POSEIDON_NUM_WORDS: usize = 8;
@@ -42,6 +46,7 @@ mod zkvm {
     unsafe extern "C" fn __start() {
         {
             PUBLIC_VALUES_HASHER = Some(Sha256::new());
+            crate::syscalls::install_panic_hook();
             #[cfg(feature = "verify")]
             {
                 DEFERRED_PROOFS_DIGEST = Some([BabyBear::zero(); 8]);
@@ -93,10 +98,10 @@ macro_rules! entrypoint {
     ($path:path) => {
         const ZKVM_ENTRY: fn() = $path;
 
-        use $crate::heap::SimpleAlloc;
+        use $crate::heap::EntrypointAlloc;
 
         #[global_allocator]
-        static HEAP: SimpleAlloc = SimpleAlloc;
+        static HEAP: EntrypointAlloc = EntrypointAlloc::new();
 
         mod zkvm_generated_main {
 
@@ -1,8 +1,21 @@
-use super::syscall_uint256_mulmod;
+use alloc::{vec, vec::Vec};
+
+use sp1_primitives::consts::fd::FD_BIGINT_DIVREM;
+
+use super::{
+    sys_alloc_aligned, syscall_hint_len, syscall_hint_read, syscall_u256x2048_mul,
+    syscall_uint256_mulmod, syscall_write,
+};
 
 /// The number of limbs in a "uint256".
 const N: usize = 8;
 
+/// The number of limbs in a "uint2048".
+const N_2048: usize = 64;
+
+/// The number of limbs in a "uint4096".
+const N_4096: usize = 128;
+
 /// Sets `result` to be `(x op y) % modulus`.
 ///
 /// Currently only multiplication is supported and `op` is not used. If the modulus is zero, then
@@ -46,3 +59,148 @@ pub extern "C" fn sys_bigint(
         syscall_uint256_mulmod(result_ptr, concat_ptr);
     }
 }
+
+/// Sets `result` to be `(x op y) % modulus` for "uint2048" values.
+///
+/// Currently only multiplication is supported and `op` is not used. If the modulus is zero, then
+/// the modulus applied is 2^2048. The product takes eight calls of `syscall_u256x2048_mul`, and
+/// its reduction is checked against a quotient and remainder hinted by the host.
+///
+/// ### Safety
+///
+/// The caller must ensure that `result`, `x`, `y`, and `modulus` are valid pointers to data that is
+/// aligned along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn sys_bigint2048(
+    result: *mut [u32; N_2048],
+    op: u32,
+    x: *const [u32; N_2048],
+    y: *const [u32; N_2048],
+    modulus: *const [u32; N_2048],
+) {
+    unsafe {
+        let value = mulmod_wide(&*x, &*y, &*modulus);
+        core::ptr::copy(value.as_ptr(), result as *mut u32, N_2048);
+    }
+}
+
+/// Sets `result` to be `(x op y) % modulus` for "uint4096" values.
+///
+/// Currently only multiplication is supported and `op` is not used. If the modulus is zero, then
+/// the modulus applied is 2^4096. The product takes thirty-two calls of `syscall_u256x2048_mul`,
+/// and its reduction is checked against a quotient and remainder hinted by the host.
+///
+/// ### Safety
+///
+/// The caller must ensure that `result`, `x`, `y`, and `modulus` are valid pointers to data that is
+/// aligned along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn sys_bigint4096(
+    result: *mut [u32; N_4096],
+    op: u32,
+    x: *const [u32; N_4096],
+    y: *const [u32; N_4096],
+    modulus: *const [u32; N_4096],
+) {
+    unsafe {
+        let value = mulmod_wide(&*x, &*y, &*modulus);
+        core::ptr::copy(value.as_ptr(), result as *mut u32, N_4096);
+    }
+}
+
+/// Returns `(x * y) % modulus`, where the values have the same number of limbs, a multiple of the
+/// 64 limbs of a "uint2048".
+fn mulmod_wide(x: &[u32], y: &[u32], modulus: &[u32]) -> Vec<u32> {
+    let n = modulus.len();
+    let product = mul_wide(x, y);
+    if modulus.iter().all(|&limb| limb == 0) {
+        return product[..n].to_vec();
+    }
+
+    // Ask the host for the quotient and remainder of the product by the modulus.
+    let mut buf = Vec::with_capacity(3 * n * 4);
+    for limb in product.iter().chain(modulus) {
+        buf.extend_from_slice(&limb.to_le_bytes());
+    }
+    syscall_write(FD_BIGINT_DIVREM, buf.as_ptr(), buf.len());
+    let quotient = read_hint_limbs(2 * n);
+    let remainder = read_hint_limbs(n);
+
+    // Check that `product == quotient * modulus + remainder` and `remainder < modulus`.
+    let mut expected = mul_wide(quotient, modulus);
+    let carry = add_assign(&mut expected, remainder);
+    assert!(
+        !carry
+            && expected[..2 * n] == product[..]
+            && expected[2 * n..].iter().all(|&limb| limb == 0),
+        "bigint quotient and remainder do not match the product"
+    );
+    assert!(less_than(remainder, modulus), "bigint remainder is not reduced");
+
+    remainder.to_vec()
+}
+
+/// Returns the product of `a` by `b`, whose numbers of limbs are multiples of the 8 limbs of a
+/// "uint256" and of the 64 limbs of a "uint2048" respectively.
+fn mul_wide(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    let mut product = [0u32; N + N_2048];
+    for (i, a_limb) in a.chunks_exact(N).enumerate() {
+        if a_limb.iter().all(|&limb| limb == 0) {
+            continue;
+        }
+        for (j, b_limb) in b.chunks_exact(N_2048).enumerate() {
+            // The low 2048 bits of the product are written to its first 64 limbs, and the high 256
+            // bits to its last 8 limbs.
+            let (lo, hi) = product.split_at_mut(N_2048);
+            syscall_u256x2048_mul(
+                a_limb.as_ptr() as *const [u32; N],
+                b_limb.as_ptr() as *const [u32; N_2048],
+                lo.as_mut_ptr() as *mut [u32; N_2048],
+                hi.as_mut_ptr() as *mut [u32; N],
+            );
+            let carry = add_assign(&mut result[N * i + N_2048 * j..], &product);
+            debug_assert!(!carry);
+        }
+    }
+    result
+}
+
+/// Adds `rhs` to `lhs` in place, returning whether the sum overflows `lhs`.
+fn add_assign(lhs: &mut [u32], rhs: &[u32]) -> bool {
+    let mut carry = false;
+    for (i, limb) in lhs.iter_mut().enumerate() {
+        let Some(&rhs_limb) = rhs.get(i) else {
+            if !carry {
+                return false;
+            }
+            (*limb, carry) = limb.overflowing_add(1);
+            continue;
+        };
+        let (sum, carry_1) = limb.overflowing_add(rhs_limb);
+        let (sum, carry_2) = sum.overflowing_add(carry as u32);
+        *limb = sum;
+        carry = carry_1 || carry_2;
+    }
+    carry
+}
+
+/// Returns whether `a < b`, where both values have the same number of limbs.
+fn less_than(a: &[u32], b: &[u32]) -> bool {
+    a.iter().rev().cmp(b.iter().rev()).is_lt()
+}
+
+/// Reads the next element of the hint stream, which holds `len` little-endian limbs.
+fn read_hint_limbs(len: usize) -> &'static [u32] {
+    let nbytes = syscall_hint_len();
+    assert_eq!(nbytes, len * 4, "bigint hint has an unexpected length");
+    unsafe {
+        // The bump allocator never hands out memory twice, so the hint is read into memory that
+        // has not been written yet.
+        let ptr = sys_alloc_aligned(nbytes, 4);
+        syscall_hint_read(ptr, nbytes);
+        core::slice::from_raw_parts(ptr as *const u32, len)
+    }
+}
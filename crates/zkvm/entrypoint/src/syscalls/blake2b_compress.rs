@@ -0,0 +1,30 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the BLAKE2b compression function on the given state, mixing in the given message
+/// block.
+///
+/// The state holds the chaining value `h`, followed by the words which are XORed with the IV:
+/// four zeros, the low and high words of the offset counter `t`, the final block flag `f` and a
+/// zero. After the compression, the state holds the new chaining value in its first eight words.
+///
+/// ### Safety
+///
+/// The caller must ensure that `state` and `msg` are valid pointers to non-overlapping data that
+/// is aligned along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake2b_compress(state: *mut [u64; 16], msg: *const [u64; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BLAKE2B_COMPRESS,
+            in("a0") state,
+            in("a1") msg
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
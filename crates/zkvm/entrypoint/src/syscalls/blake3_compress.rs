@@ -0,0 +1,30 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the BLAKE3 compression function on the given state, mixing in the given message
+/// block.
+///
+/// The state holds the chaining value `cv`, followed by four zeros, the low and high words of the
+/// counter, the block length and the flags. After the compression, the state holds the 16-word
+/// output, the first eight words of which are the new chaining value.
+///
+/// ### Safety
+///
+/// The caller must ensure that `state` and `msg` are valid pointers to non-overlapping data that
+/// is aligned along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake3_compress(state: *mut [u32; 16], msg: *const [u32; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BLAKE3_COMPRESS,
+            in("a0") state,
+            in("a1") msg
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -0,0 +1,125 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+use std::sync::OnceLock;
+
+use sha2::{Digest, Sha256};
+
+use crate::syscalls::{sys_alloc_aligned, syscall_hint_len, syscall_hint_read, syscall_write};
+
+/// The environment of the program, fetched from the host the first time it is read.
+static ENVIRONMENT: OnceLock<Environment> = OnceLock::new();
+
+/// The environment variables of the program, as encoded by the host.
+struct Environment {
+    /// The variables, each one as the little-endian `u32` length of its name, the name, the
+    /// little-endian `u32` length of its value and the value.
+    encoded: &'static [u8],
+    /// The sha256 digest of `encoded`.
+    digest: [u8; 32],
+}
+
+impl Environment {
+    /// Fetches the environment from the host.
+    fn fetch() -> Self {
+        syscall_getenv();
+        let len = syscall_hint_len();
+        let capacity = (len + 3) / 4 * 4;
+
+        // The environment is read into memory which is never freed, so it can be borrowed for the
+        // rest of the execution.
+        let encoded = unsafe {
+            let ptr = sys_alloc_aligned(capacity, 4);
+            syscall_hint_read(ptr, len);
+            core::slice::from_raw_parts(ptr, len)
+        };
+        Self { encoded, digest: Sha256::digest(encoded).into() }
+    }
+
+    /// Returns the value of the variable `name`, if it is set.
+    fn get(&self, name: &[u8]) -> Option<&'static [u8]> {
+        let mut rest = self.encoded;
+        while !rest.is_empty() {
+            let (key, tail) = split_field(rest);
+            let (value, tail) = split_field(tail);
+            if key == name {
+                return Some(value);
+            }
+            rest = tail;
+        }
+        None
+    }
+}
+
+/// Splits a length-prefixed field off the front of `bytes`.
+fn split_field(bytes: &'static [u8]) -> (&'static [u8], &'static [u8]) {
+    let (len, rest) = bytes.split_at(4);
+    rest.split_at(u32::from_le_bytes(len.try_into().unwrap()) as usize)
+}
+
+/// Adds the environment of the program to the beginning of the hint stream.
+#[no_mangle]
+pub extern "C" fn syscall_getenv() {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::GETENV,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Reads the environment variable `varname` into `recv_buf`.
+///
+/// Returns the length of the value in bytes, or `usize::MAX` if the variable is not set. At most
+/// `words` words of the value are written to `recv_buf`, so the caller can pass `0` to query the
+/// length first.
+///
+/// # Safety
+///
+/// `recv_buf` must be valid for writes of `words` words and `varname` must be valid for reads of
+/// `varname_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sys_getenv(
+    recv_buf: *mut u32,
+    words: usize,
+    varname: *const u8,
+    varname_len: usize,
+) -> usize {
+    let name = core::slice::from_raw_parts(varname, varname_len);
+    match ENVIRONMENT.get_or_init(Environment::fetch).get(name) {
+        Some(value) => {
+            // The buffer may be null when only the length is queried.
+            let nbytes = value.len().min(words * 4);
+            if nbytes > 0 {
+                core::ptr::copy_nonoverlapping(value.as_ptr(), recv_buf.cast::<u8>(), nbytes);
+            }
+            value.len()
+        }
+        None => usize::MAX,
+    }
+}
+
+/// Allocates `nwords` words, used by the standard library to receive environment variables.
+///
+/// # Safety
+///
+/// The returned memory is uninitialized.
+#[no_mangle]
+pub unsafe extern "C" fn sys_alloc_words(nwords: usize) -> *mut u32 {
+    sys_alloc_aligned(nwords * 4, 4).cast::<u32>()
+}
+
+/// Appends the digest of the environment to the public values, if the program read it.
+///
+/// Called when the program halts, so that verifiers can check which environment it saw. See
+/// [FD_PUBLIC_VALUES](sp1_primitives::consts::fd::FD_PUBLIC_VALUES) for where the digest goes.
+#[cfg(target_os = "zkvm")]
+pub(crate) fn commit_environment_digest() {
+    use sp1_primitives::consts::fd::FD_PUBLIC_VALUES;
+    if let Some(env) = ENVIRONMENT.get() {
+        syscall_write(FD_PUBLIC_VALUES, env.digest.as_ptr(), env.digest.len());
+    }
+}
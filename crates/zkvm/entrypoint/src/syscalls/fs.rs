@@ -0,0 +1,80 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Opens the file at the path `path_len` bytes long at `path`, and adds the response to the
+/// beginning of the hint stream.
+///
+/// The response is a `1` byte followed by the little-endian `u32` file descriptor of the file, its
+/// little-endian `u64` length and the sha256 hashes of its chunks, or a single `0` byte if there is
+/// no file at the path.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_fs_open(path: *const u8, path_len: usize) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::FS_OPEN,
+            in("a0") path,
+            in("a1") path_len,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Reads up to `len` bytes of the file open at `fd` from its position, and adds them to the
+/// beginning of the hint stream.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_fs_read(fd: u32, len: usize) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::FS_READ,
+            in("a0") fd,
+            in("a1") len,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Moves the position of the file open at `fd` to `position` bytes from its start.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_fs_seek(fd: u32, position: usize) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::FS_SEEK,
+            in("a0") fd,
+            in("a1") position,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Closes the file open at `fd`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_fs_close(fd: u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::FS_CLOSE,
+            in("a0") fd,
+            in("a1") 0,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -20,6 +20,19 @@ cfg_if::cfg_if! {
 pub extern "C" fn syscall_halt(exit_code: u8) -> ! {
     #[cfg(target_os = "zkvm")]
     unsafe {
+        // Append the digests of what the program fetched from the host to its public values, in the
+        // order documented on `FD_PUBLIC_VALUES`.
+
+        // If the program read its environment, commit to the environment it saw.
+        super::env::commit_environment_digest();
+
+        // If the program opened files, commit to the files it read.
+        crate::fs::commit_file_system_digest();
+
+        // If the program used verifiable randomness, commit to the seed it used.
+        #[cfg(feature = "verifiable-rand")]
+        super::rand_seed::commit_rand_seed();
+
         // When we halt, we retrieve the public values finalized digest.  This is the hash of all
         // the bytes written to the public values fd.
         let pv_digest_bytes =
@@ -12,27 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use sp1_primitives::consts::fd::FD_OUT_OF_MEMORY;
+
 // Memory addresses must be lower than BabyBear prime.
 const MAX_MEMORY: usize = 0x78000000;
 
+// Pointer to next heap address to use, or 0 if the heap has not yet been initialized.
+static mut HEAP_POS: usize = 0;
+
+extern "C" {
+    // https://lld.llvm.org/ELF/linker_script.html#sections-command
+    static _end: u8;
+}
+
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
-    extern "C" {
-        // https://lld.llvm.org/ELF/linker_script.html#sections-command
-        static _end: u8;
+    match unsafe { bump_alloc(bytes, align) } {
+        Some(ptr) => ptr,
+        None => out_of_memory(bytes),
     }
+}
 
-    // Pointer to next heap address to use, or 0 if the heap has not yet been
-    // initialized.
-    static mut HEAP_POS: usize = 0;
-
+/// Allocates `bytes` aligned to `align` past every previous allocation, or returns `None` if the
+/// heap cannot fit them.
+///
+/// # Safety
+///
+/// `align` must be a power of two.
+pub(crate) unsafe fn bump_alloc(bytes: usize, align: usize) -> Option<*mut u8> {
     // SAFETY: Single threaded, so nothing else can touch this while we're working.
-    let mut heap_pos = unsafe { HEAP_POS };
-
-    if heap_pos == 0 {
-        heap_pos = unsafe { (&_end) as *const u8 as usize };
-    }
+    let mut heap_pos = heap_high_water_mark();
 
     let offset = heap_pos & (align - 1);
     if offset != 0 {
@@ -43,9 +53,30 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     let (heap_pos, overflowed) = heap_pos.overflowing_add(bytes);
 
     if overflowed || MAX_MEMORY < heap_pos {
-        panic!("Memory limit exceeded (0x78000000)");
+        return None;
     }
 
     unsafe { HEAP_POS = heap_pos };
-    ptr
+    Some(ptr)
+}
+
+/// Returns the address past the highest allocation made so far.
+pub fn heap_high_water_mark() -> usize {
+    match unsafe { HEAP_POS } {
+        0 => unsafe { (&_end) as *const u8 as usize },
+        heap_pos => heap_pos,
+    }
+}
+
+/// Reports to the executor that an allocation of `bytes` does not fit in the heap, and panics.
+///
+/// The executor fails the execution with the size of the allocation and the heap high-water mark.
+pub(crate) fn out_of_memory(bytes: usize) -> ! {
+    let mut report = [0u8; 8];
+    report[..4].copy_from_slice(&(bytes as u32).to_le_bytes());
+    report[4..].copy_from_slice(&(heap_high_water_mark() as u32).to_le_bytes());
+    crate::syscalls::syscall_write(FD_OUT_OF_MEMORY, report.as_ptr(), report.len());
+
+    // The panic message is static, since formatting it could allocate.
+    panic!("Memory limit exceeded (0x78000000)");
 }
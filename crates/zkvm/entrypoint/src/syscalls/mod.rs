@@ -1,34 +1,48 @@
 mod bigint;
+mod blake2b_compress;
+mod blake3_compress;
 mod bls12381;
 mod bn254;
 mod ed25519;
+mod env;
 mod fptower;
+mod fs;
 mod halt;
 mod io;
 mod keccak_permute;
 mod memory;
+mod rand_seed;
 mod secp256k1;
+mod secp256r1;
 mod sha_compress;
 mod sha_extend;
 mod sys;
+mod u256x2048_mul;
 mod uint256_mul;
 mod unconstrained;
 #[cfg(feature = "verify")]
 mod verify;
 
 pub use bigint::*;
+pub use blake2b_compress::*;
+pub use blake3_compress::*;
 pub use bls12381::*;
 pub use bn254::*;
 pub use ed25519::*;
+pub use env::*;
 pub use fptower::*;
+pub use fs::*;
 pub use halt::*;
 pub use io::*;
 pub use keccak_permute::*;
 pub use memory::*;
+pub use rand_seed::*;
 pub use secp256k1::*;
+pub use secp256r1::*;
 pub use sha_compress::*;
 pub use sha_extend::*;
 pub use sys::*;
+pub use u256x2048_mul::*;
 pub use uint256_mul::*;
 pub use unconstrained::*;
 #[cfg(feature = "verify")]
@@ -94,6 +108,24 @@ pub const HINT_LEN: u32 = 0x00_00_00_F0;
 /// Executes `HINT_READ`.
 pub const HINT_READ: u32 = 0x00_00_00_F1;
 
+/// Executes `GETENV`.
+pub const GETENV: u32 = 0x00_00_00_F2;
+
+/// Executes `RAND_SEED`.
+pub const RAND_SEED: u32 = 0x00_00_00_F3;
+
+/// Executes `FS_OPEN`.
+pub const FS_OPEN: u32 = 0x00_00_00_F4;
+
+/// Executes `FS_READ`.
+pub const FS_READ: u32 = 0x00_00_00_F5;
+
+/// Executes `FS_SEEK`.
+pub const FS_SEEK: u32 = 0x00_00_00_F6;
+
+/// Executes `FS_CLOSE`.
+pub const FS_CLOSE: u32 = 0x00_00_00_F7;
+
 /// Executes `BLS12381_DECOMPRESS`.
 pub const BLS12381_DECOMPRESS: u32 = 0x00_00_01_1C;
 
@@ -141,3 +173,21 @@ pub const BN254_FP2_SUB: u32 = 0x00_01_01_2A;
 
 /// Executes the `BN254_FP2_MUL` precompile.
 pub const BN254_FP2_MUL: u32 = 0x00_01_01_2B;
+
+/// Executes `SECP256R1_ADD`.
+pub const SECP256R1_ADD: u32 = 0x00_01_01_2C;
+
+/// Executes `SECP256R1_DOUBLE`.
+pub const SECP256R1_DOUBLE: u32 = 0x00_00_01_2D;
+
+/// Executes `SECP256R1_DECOMPRESS`.
+pub const SECP256R1_DECOMPRESS: u32 = 0x00_00_01_2E;
+
+/// Executes `BLAKE2B_COMPRESS`.
+pub const BLAKE2B_COMPRESS: u32 = 0x00_60_01_2F;
+
+/// Executes `BLAKE3_COMPRESS`.
+pub const BLAKE3_COMPRESS: u32 = 0x00_38_01_30;
+
+/// Executes `U256XU2048_MUL`.
+pub const U256XU2048_MUL: u32 = 0x00_01_01_31;
@@ -0,0 +1,64 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Adds the randomness seed of the program to the beginning of the hint stream.
+#[no_mangle]
+pub extern "C" fn syscall_rand_seed() {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::RAND_SEED,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+#[cfg(feature = "verifiable-rand")]
+mod verifiable {
+    use std::sync::OnceLock;
+
+    use crate::syscalls::{
+        sys_alloc_aligned, syscall_hint_len, syscall_hint_read, syscall_rand_seed,
+    };
+
+    /// The seed of the random number generator, fetched from the host the first time randomness
+    /// is requested.
+    static RAND_SEED: OnceLock<[u8; 32]> = OnceLock::new();
+
+    /// Returns the seed of the random number generator, fetching it from the host on first use.
+    pub(crate) fn rand_seed() -> [u8; 32] {
+        *RAND_SEED.get_or_init(|| {
+            syscall_rand_seed();
+            let len = syscall_hint_len();
+            assert_eq!(len, 32, "the randomness seed must be 32 bytes");
+
+            // The seed is read into fresh memory, since hints can only be written to memory which
+            // was never accessed.
+            let mut seed = [0u8; 32];
+            unsafe {
+                let ptr = sys_alloc_aligned(len, 4);
+                syscall_hint_read(ptr, len);
+                seed.copy_from_slice(core::slice::from_raw_parts(ptr, len));
+            }
+            seed
+        })
+    }
+
+    /// Appends the randomness seed to the public values, if the program used randomness.
+    ///
+    /// Called when the program halts, so that verifiers can check which seed it used. See
+    /// [FD_PUBLIC_VALUES](sp1_primitives::consts::fd::FD_PUBLIC_VALUES) for where the seed goes.
+    #[cfg(target_os = "zkvm")]
+    pub(crate) fn commit_rand_seed() {
+        use sp1_primitives::consts::fd::FD_PUBLIC_VALUES;
+        if let Some(seed) = RAND_SEED.get() {
+            crate::syscalls::syscall_write(FD_PUBLIC_VALUES, seed.as_ptr(), seed.len());
+        }
+    }
+}
+
+#[cfg(feature = "verifiable-rand")]
+pub(crate) use verifiable::*;
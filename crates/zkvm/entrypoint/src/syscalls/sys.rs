@@ -2,20 +2,36 @@ use std::sync::Mutex;
 
 use lazy_static::lazy_static;
 use rand::{rngs::StdRng, Rng, SeedableRng};
+#[cfg(target_os = "zkvm")]
+use sp1_primitives::consts::fd::FD_PANIC;
 
 use crate::syscalls::{syscall_halt, syscall_write};
 
 /// The random number generator seed for the zkVM.
 ///
-/// In the future, we can pass in this seed from the host or have the verifier generate it.
+/// With the `verifiable-rand` feature, the seed is instead provided by the host and committed to
+/// the public values, so that the verifier can choose it.
+#[cfg(not(feature = "verifiable-rand"))]
 const PRNG_SEED: u64 = 0x123456789abcdef0;
 
 lazy_static! {
     /// A lazy static to generate a global random number generator.
-    static ref RNG: Mutex<StdRng> = Mutex::new(StdRng::seed_from_u64(PRNG_SEED));
+    static ref RNG: Mutex<StdRng> = Mutex::new(new_rng());
+}
+
+/// Creates the global random number generator.
+fn new_rng() -> StdRng {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "verifiable-rand")] {
+            StdRng::from_seed(crate::syscalls::rand_seed())
+        } else {
+            StdRng::seed_from_u64(PRNG_SEED)
+        }
+    }
 }
 
 /// A lazy static to print a warning once for using the `sys_rand` system call.
+#[cfg(not(feature = "verifiable-rand"))]
 static SYS_RAND_WARNING: std::sync::Once = std::sync::Once::new();
 
 /// Generates random bytes.
@@ -25,6 +41,7 @@ static SYS_RAND_WARNING: std::sync::Once = std::sync::Once::new();
 /// Make sure that `buf` has at least `nwords` words.
 #[no_mangle]
 pub unsafe extern "C" fn sys_rand(recv_buf: *mut u8, words: usize) {
+    #[cfg(not(feature = "verifiable-rand"))]
     SYS_RAND_WARNING.call_once(|| {
         println!("WARNING: Using insecure random number generator.");
     });
@@ -35,6 +52,34 @@ pub unsafe extern "C" fn sys_rand(recv_buf: *mut u8, words: usize) {
     }
 }
 
+/// Installs a panic hook which reports the location and message of panics to the host, before
+/// printing them with the default hook.
+#[cfg(target_os = "zkvm")]
+pub(crate) fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(location) = info.location() {
+            let payload = info.payload();
+            let message = if let Some(message) = payload.downcast_ref::<&str>() {
+                message
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.as_str()
+            } else {
+                "Box<dyn Any>"
+            };
+            let file = location.file();
+            let mut report = Vec::with_capacity(12 + file.len() + message.len());
+            report.extend_from_slice(&location.line().to_le_bytes());
+            report.extend_from_slice(&location.column().to_le_bytes());
+            report.extend_from_slice(&(file.len() as u32).to_le_bytes());
+            report.extend_from_slice(file.as_bytes());
+            report.extend_from_slice(message.as_bytes());
+            sys_write(FD_PANIC, report.as_ptr(), report.len());
+        }
+        default_hook(info);
+    }));
+}
+
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
@@ -42,23 +87,6 @@ pub unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
     syscall_halt(1);
 }
 
-#[allow(unused_variables)]
-#[no_mangle]
-pub const fn sys_getenv(
-    recv_buf: *mut u32,
-    words: usize,
-    varname: *const u8,
-    varname_len: usize,
-) -> usize {
-    0
-}
-
-#[allow(unused_variables)]
-#[no_mangle]
-pub const fn sys_alloc_words(nwords: usize) -> *mut u32 {
-    core::ptr::null_mut()
-}
-
 #[allow(unused_unsafe)]
 #[no_mangle]
 pub fn sys_write(fd: u32, write_buf: *const u8, nbytes: usize) {
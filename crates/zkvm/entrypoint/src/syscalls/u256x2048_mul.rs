@@ -0,0 +1,34 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Uint256 by uint2048 multiplication operation.
+///
+/// The low 2048 bits of the product are written to `lo` and the high 256 bits to `hi`.
+///
+/// ### Safety
+///
+/// The caller must ensure that `a`, `b`, `lo` and `hi` are valid pointers to data that is aligned
+/// along a four byte boundary, and that `lo` and `hi` do not overlap.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_u256x2048_mul(
+    a: *const [u32; 8],
+    b: *const [u32; 64],
+    lo: *mut [u32; 64],
+    hi: *mut [u32; 8],
+) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::U256XU2048_MUL,
+            in("a0") a,
+            in("a1") b,
+            in("a2") lo,
+            in("a3") hi,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
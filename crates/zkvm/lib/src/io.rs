@@ -1,25 +1,12 @@
 #![allow(unused_unsafe)]
-use crate::{syscall_hint_len, syscall_hint_read, syscall_write};
+use crate::{sys_alloc_fresh, syscall_hint_len, syscall_hint_read, syscall_write};
 use serde::{de::DeserializeOwned, Serialize};
-use std::{
-    alloc::Layout,
-    io::{Result, Write},
-};
-
-/// The file descriptor for public values.
-pub const FD_PUBLIC_VALUES: u32 = 3;
-
-/// The file descriptor for hints.
-pub const FD_HINT: u32 = 4;
+use std::io::{Result, Write};
 
-/// The file descriptor for the `ecreover` hook.
-pub const FD_ECRECOVER_HOOK: u32 = 5;
-
-/// The file descriptor through which to access `hook_ecrecover_2`.
-pub const FD_ECRECOVER_HOOK_2: u32 = 7;
-
-/// The file descriptor through which to access `hook_ed_decompress`.
-pub const FD_EDDECOMPRESS: u32 = 8;
+pub use sp1_primitives::consts::fd::{
+    FD_BIGINT_DIVREM, FD_ECRECOVER_HOOK, FD_ECRECOVER_HOOK_2, FD_EDDECOMPRESS, FD_HINT,
+    FD_PUBLIC_VALUES,
+};
 
 /// A writer that writes to a file descriptor inside the zkVM.
 struct SyscallWriter {
@@ -52,20 +39,20 @@ pub fn read_vec() -> Vec<u8> {
     let len = unsafe { syscall_hint_len() };
     let capacity = (len + 3) / 4 * 4;
 
-    // Allocate a buffer of the required length that is 4 byte aligned
-    let layout = Layout::from_size_align(capacity, 4).expect("vec is too large");
-    let ptr = unsafe { std::alloc::alloc(layout) };
+    // Allocate a buffer of the required length that is 4 byte aligned. The hint read requires
+    // memory that was never touched, which a global allocator freeing memory may not return.
+    let ptr = unsafe { sys_alloc_fresh(capacity, 4) };
 
     // SAFETY:
-    // 1. `ptr` was allocated using alloc
-    // 2. We assuume that the VM global allocator doesn't dealloc
+    // 1. `ptr` was allocated with a layout the global allocator can free
+    // 2. `ptr` is 4 byte aligned, which is at least the alignment of `u8`
     // 3/6. Size is correct from above
     // 4/5. Length is 0
-    // 7. Layout::from_size_align already checks this
+    // 7. `capacity` fits in memory, or the allocation would have failed
     let mut vec = unsafe { Vec::from_raw_parts(ptr, 0, capacity) };
 
     // Read the vec into uninitialized memory. The syscall assumes the memory is uninitialized,
-    // which should be true because the allocator does not dealloc, so a new alloc should be fresh.
+    // which is true because `sys_alloc_fresh` never reuses memory.
     unsafe {
         syscall_hint_read(ptr, len);
         vec.set_len(len);
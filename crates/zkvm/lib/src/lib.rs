@@ -8,6 +8,7 @@ pub mod bn254;
 pub mod ed25519;
 pub mod io;
 pub mod secp256k1;
+pub mod secp256r1;
 pub mod unconstrained;
 pub mod utils;
 #[cfg(feature = "verify")]
@@ -44,6 +45,15 @@ extern "C" {
     /// Executes an Secp256k1 curve decompression on the given point.
     pub fn syscall_secp256k1_decompress(point: &mut [u8; 64], is_odd: bool);
 
+    /// Executes an Secp256r1 curve addition on the given points.
+    pub fn syscall_secp256r1_add(p: *mut [u32; 16], q: *const [u32; 16]);
+
+    /// Executes an Secp256r1 curve doubling on the given point.
+    pub fn syscall_secp256r1_double(p: *mut [u32; 16]);
+
+    /// Executes an Secp256r1 curve decompression on the given point.
+    pub fn syscall_secp256r1_decompress(point: &mut [u8; 64], is_odd: bool);
+
     /// Executes a Bn254 curve addition on the given points.
     pub fn syscall_bn254_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
@@ -59,9 +69,24 @@ extern "C" {
     /// Executes the Keccak-256 permutation on the given state.
     pub fn syscall_keccak_permute(state: *mut [u64; 25]);
 
+    /// Executes the BLAKE2b compression function on the given state and message block.
+    pub fn syscall_blake2b_compress(state: *mut [u64; 16], msg: *const [u64; 16]);
+
+    /// Executes the BLAKE3 compression function on the given state and message block.
+    pub fn syscall_blake3_compress(state: *mut [u32; 16], msg: *const [u32; 16]);
+
     /// Executes an uint256 multiplication on the given inputs.
     pub fn syscall_uint256_mulmod(x: *mut [u32; 8], y: *const [u32; 8]);
 
+    /// Executes a multiplication of an uint256 by an uint2048, writing the low 2048 bits of the
+    /// product to `lo` and the high 256 bits to `hi`.
+    pub fn syscall_u256x2048_mul(
+        a: *const [u32; 8],
+        b: *const [u32; 64],
+        lo: *mut [u32; 64],
+        hi: *mut [u32; 8],
+    );
+
     /// Enters unconstrained mode.
     pub fn syscall_enter_unconstrained() -> bool;
 
@@ -80,6 +105,10 @@ extern "C" {
     /// Allocates a buffer aligned to the given alignment.
     pub fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8;
 
+    /// Allocates a buffer aligned to the given alignment from memory the program never touched,
+    /// which the global allocator of the program can free.
+    pub fn sys_alloc_fresh(bytes: usize, align: usize) -> *mut u8;
+
     /// Decompresses a BLS12-381 point.
     pub fn syscall_bls12381_decompress(point: &mut [u8; 96], is_odd: bool);
 
@@ -92,6 +121,24 @@ extern "C" {
         modulus: *const [u32; 8],
     );
 
+    /// Computes a big integer operation with a 2048-bit modulus.
+    pub fn sys_bigint2048(
+        result: *mut [u32; 64],
+        op: u32,
+        x: *const [u32; 64],
+        y: *const [u32; 64],
+        modulus: *const [u32; 64],
+    );
+
+    /// Computes a big integer operation with a 4096-bit modulus.
+    pub fn sys_bigint4096(
+        result: *mut [u32; 128],
+        op: u32,
+        x: *const [u32; 128],
+        y: *const [u32; 128],
+        modulus: *const [u32; 128],
+    );
+
     /// Executes a BLS12-381 field addition on the given inputs.
     pub fn syscall_bls12381_fp_addmod(p: *mut u32, q: *const u32);
 
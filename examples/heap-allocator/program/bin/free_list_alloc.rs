@@ -0,0 +1,15 @@
+//! Runs the allocation heavy workload, with the global allocator selected by the features of
+//! `sp1-zkvm`.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+pub fn main() {
+    let rounds = sp1_zkvm::io::read::<u32>();
+
+    println!("cycle-tracker-report-start: churn");
+    let checksum = heap_allocator_program::churn(rounds);
+    println!("cycle-tracker-report-end: churn");
+
+    sp1_zkvm::io::commit(&checksum);
+}
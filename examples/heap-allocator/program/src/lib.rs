@@ -0,0 +1,30 @@
+//! An allocation heavy workload, allocating and freeing many short lived buffers of various sizes
+//! as parsers and interpreters do.
+
+use std::collections::BTreeMap;
+
+/// Runs `rounds` rounds of the workload, returning a checksum of the computed values.
+pub fn churn(rounds: u32) -> u64 {
+    let mut checksum = 0u64;
+    let mut live: BTreeMap<u32, Vec<u8>> = BTreeMap::new();
+    for round in 0..rounds {
+        // Small strings, freed at the end of the round.
+        let words: Vec<String> = (0..64).map(|i| format!("word-{round}-{i}")).collect();
+        checksum += words.iter().map(|word| word.len() as u64).sum::<u64>();
+
+        // A growing buffer, reallocated many times.
+        let mut buffer = Vec::new();
+        for i in 0..(4096 + round % 4096) {
+            buffer.push(i as u8);
+        }
+        checksum += buffer.iter().map(|&byte| byte as u64).sum::<u64>();
+
+        // Medium buffers living for a few rounds.
+        live.insert(round, vec![round as u8; 256 + (round as usize % 8) * 128]);
+        if round >= 8 {
+            let buffer = live.remove(&(round - 8)).unwrap();
+            checksum += buffer.len() as u64;
+        }
+    }
+    checksum
+}
@@ -0,0 +1,17 @@
+use sp1_build::{build_program_with_args, BuildArgs};
+
+fn main() {
+    // Build the same workload once with each allocator.
+    build_program_with_args(
+        "../program",
+        BuildArgs { binary: "simple_alloc".to_string(), ..Default::default() },
+    );
+    build_program_with_args(
+        "../program",
+        BuildArgs {
+            binary: "free_list_alloc".to_string(),
+            features: vec!["free-list-alloc".to_string()],
+            ..Default::default()
+        },
+    );
+}
@@ -0,0 +1,47 @@
+//! Compares the cost of an allocation heavy workload with `SimpleAlloc` and `FreeListAlloc`.
+//!
+//! You can run this script using the following command:
+//! ```shell
+//! RUST_LOG=info cargo run --release -- --rounds 100
+//! ```
+
+use sp1_sdk::{include_elf, utils, ExecutionReport, ProverClient, SP1Stdin};
+
+/// The workload with the default bump allocator, which never frees memory.
+const SIMPLE_ALLOC_ELF: &[u8] = include_elf!("simple_alloc");
+
+/// The workload with the freeing allocator of the `free-list-alloc` feature of `sp1-zkvm`.
+const FREE_LIST_ALLOC_ELF: &[u8] = include_elf!("free_list_alloc");
+
+fn main() {
+    utils::setup_logger();
+
+    let rounds = std::env::args()
+        .skip_while(|arg| arg != "--rounds")
+        .nth(1)
+        .map_or(100, |rounds| rounds.parse().expect("invalid number of rounds"));
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&rounds);
+
+    let client = ProverClient::new();
+    let mut checksums = Vec::new();
+    println!("| allocator     | cycles | churn cycles | touched addresses |");
+    println!("|---------------|--------|--------------|-------------------|");
+    for (name, elf) in [("SimpleAlloc", SIMPLE_ALLOC_ELF), ("FreeListAlloc", FREE_LIST_ALLOC_ELF)] {
+        let (mut public_values, report) =
+            client.execute(elf, stdin.clone()).run().expect("execution failed");
+        checksums.push(public_values.read::<u64>());
+        print_row(name, &report);
+    }
+    assert_eq!(checksums[0], checksums[1], "the allocators computed different checksums");
+}
+
+fn print_row(name: &str, report: &ExecutionReport) {
+    println!(
+        "| {:<13} | {} | {} | {} |",
+        name,
+        report.total_instruction_count(),
+        report.cycle_tracker.get("churn").copied().unwrap_or_default(),
+        report.touched_memory_addresses,
+    );
+}
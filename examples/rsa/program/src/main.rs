@@ -1,10 +1,18 @@
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
-use rsa::Pkcs1v15Sign;
-use rsa::{pkcs8::DecodePublicKey, RsaPublicKey};
+use rsa::{pkcs8::DecodePublicKey, traits::PublicKeyParts, RsaPublicKey};
 use sha2::{Digest, Sha256}; // Ensure this is imported for the Digest trait to work
 
+/// The number of bytes in an RSA-2048 modulus.
+const RSA_2048_NUM_BYTES: usize = 256;
+
+/// The DER encoding of the `DigestInfo` prefix of a SHA-256 hash, as defined in PKCS #1 v1.5.
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
 pub fn main() {
     // Read an input to the program.
     //
@@ -19,21 +27,83 @@ pub fn main() {
     hasher.update(message);
     let hashed_msg = hasher.finalize();
 
-    let verification = public_key.verify(Pkcs1v15Sign::new::<Sha256>(), &hashed_msg, &signature);
-
-    let verified = match verification {
-        Ok(_) => {
-            println!("Signature verified successfully.");
-            true
-        }
-        Err(e) => {
-            println!("Failed to verify signature: {:?}", e);
-            false
-        }
-    };
+    let verified = verify_pkcs1v15_sha256(&public_key, &hashed_msg, &signature);
+    if verified {
+        println!("Signature verified successfully.");
+    } else {
+        println!("Failed to verify signature.");
+    }
 
     // Write the output of the program.
     //
     // Behind the scenes, this also compiles down to a system call which handles writing
     sp1_zkvm::io::commit(&verified);
 }
+
+/// Verifies a PKCS #1 v1.5 signature of a SHA-256 hash with an RSA-2048 public key, using the
+/// `sys_bigint2048` modular multiplication for the exponentiation.
+fn verify_pkcs1v15_sha256(public_key: &RsaPublicKey, hashed_msg: &[u8], signature: &[u8]) -> bool {
+    assert_eq!(public_key.size(), RSA_2048_NUM_BYTES, "the public key must be an RSA-2048 key");
+    if signature.len() != RSA_2048_NUM_BYTES {
+        return false;
+    }
+
+    let modulus = limbs_from_bytes_le(&public_key.n().to_bytes_le());
+    let mut signature_le = signature.to_vec();
+    signature_le.reverse();
+    let signature = limbs_from_bytes_le(&signature_le);
+    if !less_than(&signature, &modulus) {
+        return false;
+    }
+
+    // The encoded message is `0x00 || 0x01 || 0xff..0xff || 0x00 || DigestInfo || hash`.
+    let mut expected = [0xffu8; RSA_2048_NUM_BYTES];
+    let digest_info_len = SHA256_DIGEST_INFO_PREFIX.len() + hashed_msg.len();
+    expected[0] = 0x00;
+    expected[1] = 0x01;
+    expected[RSA_2048_NUM_BYTES - digest_info_len - 1] = 0x00;
+    expected[RSA_2048_NUM_BYTES - digest_info_len..RSA_2048_NUM_BYTES - hashed_msg.len()]
+        .copy_from_slice(&SHA256_DIGEST_INFO_PREFIX);
+    expected[RSA_2048_NUM_BYTES - hashed_msg.len()..].copy_from_slice(hashed_msg);
+
+    let encoded = modpow(&signature, &public_key.e().to_bytes_le(), &modulus);
+    let mut encoded_be = encoded.iter().flat_map(|limb| limb.to_le_bytes()).collect::<Vec<_>>();
+    encoded_be.reverse();
+    encoded_be == expected
+}
+
+/// Computes `base^exponent % modulus` by square-and-multiply, where the exponent is little-endian.
+fn modpow(base: &[u32; 64], exponent: &[u8], modulus: &[u32; 64]) -> [u32; 64] {
+    let mut result = [0u32; 64];
+    result[0] = 1;
+    for byte in exponent.iter().rev() {
+        for i in (0..8).rev() {
+            result = mulmod(&result, &result, modulus);
+            if (byte >> i) & 1 == 1 {
+                result = mulmod(&result, base, modulus);
+            }
+        }
+    }
+    result
+}
+
+/// Computes `x * y % modulus` with the `sys_bigint2048` syscall.
+fn mulmod(x: &[u32; 64], y: &[u32; 64], modulus: &[u32; 64]) -> [u32; 64] {
+    let mut result = [0u32; 64];
+    unsafe { sp1_zkvm::syscalls::sys_bigint2048(&mut result, 0, x, y, modulus) };
+    result
+}
+
+/// Converts little-endian bytes into little-endian limbs.
+fn limbs_from_bytes_le(bytes: &[u8]) -> [u32; 64] {
+    let mut limbs = [0u32; 64];
+    for (i, byte) in bytes.iter().enumerate() {
+        limbs[i / 4] |= (*byte as u32) << (8 * (i % 4));
+    }
+    limbs
+}
+
+/// Returns whether `a < b`.
+fn less_than(a: &[u32; 64], b: &[u32; 64]) -> bool {
+    a.iter().rev().cmp(b.iter().rev()).is_lt()
+}
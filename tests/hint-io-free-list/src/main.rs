@@ -0,0 +1,24 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+pub fn main() {
+    // Free blocks and pages of the sizes of the inputs, so that the allocator would reuse them.
+    for len in [100, 108, 10_000, 10_008] {
+        let x = vec![1u8; len];
+        assert_eq!(x[len - 1], 1);
+        drop(x);
+    }
+
+    let a = sp1_zkvm::io::read::<Vec<u8>>();
+    let b = sp1_zkvm::io::read_vec();
+    assert_eq!(a, b);
+    let c = sp1_zkvm::io::read::<Vec<u8>>();
+    let d = sp1_zkvm::io::read_vec();
+    assert_eq!(c, d);
+
+    // Freeing the inputs must leave the allocator usable.
+    drop((a, b, c, d));
+    let y = vec![2u8; 10_000];
+    assert_eq!(y[9_999], 2);
+    println!("success");
+}